@@ -95,6 +95,92 @@ fn bench_async_logging(c: &mut Criterion) {
     group.finish();
 }
 
+/// Formatted messages at sustained high rates, where allocation churn shows
+///
+/// Compare runs with and without `--features entry-pool`.
+fn bench_async_throughput(c: &mut Criterion) {
+    const MESSAGES: u64 = 100_000;
+
+    let mut group = c.benchmark_group("async_throughput");
+    group.throughput(Throughput::Elements(MESSAGES));
+    group.sample_size(20);
+
+    let logger = Logger::builder()
+        .appender(NullAppender)
+        .async_mode(MESSAGES as usize)
+        .build();
+
+    group.bench_function("formatted_100k", |b| {
+        b.iter(|| {
+            for i in 0..MESSAGES {
+                rust_logger_system::info!(logger, "request {} served in {}us", black_box(i), 42);
+            }
+            logger.drain(std::time::Duration::from_secs(10));
+        });
+    });
+
+    group.finish();
+}
+
+/// Crossbeam channel against the lock-free ring, with four producers
+fn bench_async_queues(c: &mut Criterion) {
+    const PRODUCERS: u64 = 4;
+    const MESSAGES: u64 = 100_000;
+
+    let mut group = c.benchmark_group("async_queues");
+    group.throughput(Throughput::Elements(MESSAGES));
+    group.sample_size(20);
+
+    for (name, queue) in [("channel", AsyncQueue::Channel), ("lock_free", AsyncQueue::LockFree)] {
+        let logger = Arc::new(
+            Logger::builder()
+                .appender(NullAppender)
+                .async_mode(65_536)
+                .async_queue(queue)
+                .overflow_policy(OverflowPolicy::Block)
+                .build(),
+        );
+
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let producers: Vec<_> = (0..PRODUCERS)
+                    .map(|_| {
+                        let logger = Arc::clone(&logger);
+                        std::thread::spawn(move || {
+                            for _ in 0..MESSAGES / PRODUCERS {
+                                logger.info(black_box("queued message"));
+                            }
+                        })
+                    })
+                    .collect();
+                for producer in producers {
+                    producer.join().unwrap();
+                }
+                logger.drain(std::time::Duration::from_secs(10));
+            });
+        });
+    }
+
+    group.finish();
+}
+
+struct NullAppender;
+
+impl Appender for NullAppender {
+    fn append(&mut self, entry: &LogEntry) -> rust_logger_system::Result<()> {
+        black_box(entry);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> rust_logger_system::Result<()> {
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "null"
+    }
+}
+
 // ============================================================================
 // Concurrent Logging Benchmarks
 // ============================================================================
@@ -362,6 +448,8 @@ criterion_group!(
     bench_logger_creation,
     bench_sync_logging,
     bench_async_logging,
+    bench_async_throughput,
+    bench_async_queues,
     bench_concurrent_logging,
     bench_log_entry_creation,
     bench_serialization,
@@ -0,0 +1,53 @@
+//! Acknowledging collector example
+//!
+//! Receives entries from `NetworkAppender::with_acks`, prints each one once
+//! and acknowledges it. Frames resent after a reconnection are recognized by
+//! their session and sequence number, acknowledged again but not printed
+//! twice.
+//!
+//! Run with: cargo run --example ack_receiver -- 127.0.0.1:5141
+
+use rust_logger_system::appenders::network_ack::{read_frame, write_ack};
+use std::collections::HashMap;
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Highest stored sequence number of each session
+type Sessions = Arc<Mutex<HashMap<u64, u64>>>;
+
+fn serve(stream: TcpStream, sessions: Sessions) -> std::io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    while let Some(frame) = read_frame(&mut reader)? {
+        {
+            let mut sessions = sessions.lock().unwrap();
+            let stored = sessions.entry(frame.session).or_insert(0);
+            if frame.sequence > *stored {
+                print!("{}", String::from_utf8_lossy(&frame.payload));
+                *stored = frame.sequence;
+            }
+        }
+        write_ack(&mut writer, frame.sequence)?;
+    }
+    Ok(())
+}
+
+fn main() -> std::io::Result<()> {
+    let address = std::env::args().nth(1).unwrap_or_else(|| "127.0.0.1:5141".to_string());
+    let listener = TcpListener::bind(&address)?;
+    eprintln!("Listening on {}", address);
+
+    let sessions = Sessions::default();
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let sessions = Arc::clone(&sessions);
+        thread::spawn(move || {
+            if let Err(e) = serve(stream, sessions) {
+                eprintln!("Connection closed: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
@@ -50,8 +50,8 @@ fn main() -> Result<()> {
 
     println!("   5 threads logged 20 messages each");
 
-    // Give async logger time to flush
-    thread::sleep(Duration::from_millis(100));
+    // Wait for the async logger to write everything
+    logger_clone.drain(Duration::from_secs(5));
 
     println!("\n=== Example completed successfully! ===");
     println!("Check 'async_test.log' for file output");
@@ -0,0 +1,29 @@
+//! Configuration check example
+//!
+//! Validates a logger configuration file without opening its log files or
+//! connections, and prints the pipeline it describes. Exits with status 1
+//! if the file is invalid, so it can run as a CI step.
+//!
+//! Run with: cargo run --example check_config -- logger.json
+
+use rust_logger_system::prelude::*;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: check_config <config file>");
+        return ExitCode::FAILURE;
+    };
+
+    match LoggerConfig::from_file(&path).and_then(|config| config.describe()) {
+        Ok(summary) => {
+            println!("{}: ok\n", path);
+            print!("{}", summary);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            ExitCode::FAILURE
+        }
+    }
+}
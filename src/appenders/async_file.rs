@@ -38,9 +38,12 @@ use std::path::{Path, PathBuf};
 ///     file: None,
 ///     line: None,
 ///     module_path: None,
-///     thread_id: "main".to_string(),
-///     thread_name: Some("main".to_string()),
+///     target: None,
+///     thread_id: "main".into(),
+///     thread_name: Some("main".into()),
 ///     context: None,
+///     tracing: None,
+///     custom_level: None,
 /// };
 ///
 /// appender.append(&entry).await?;
@@ -127,7 +130,7 @@ impl AsyncAppender for AsyncFileAppender {
         let mut message = format!(
             "[{}] [{:5}] [{}] {}",
             entry.timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
-            entry.level.to_str(),
+            entry.level_name(),
             entry.thread_name.as_ref().unwrap_or(&entry.thread_id),
             entry.message
         );
@@ -220,12 +223,15 @@ mod tests {
             level: LogLevel::Info,
             message: "Test message".to_string(),
             timestamp: Utc::now(),
-            file: Some("test.rs".to_string()),
+            file: Some("test.rs".into()),
             line: Some(42),
-            module_path: Some("test".to_string()),
-            thread_id: "main".to_string(),
-            thread_name: Some("main".to_string()),
+            module_path: Some("test".into()),
+            target: None,
+            thread_id: "main".into(),
+            thread_name: Some("main".into()),
             context: None,
+            tracing: None,
+            custom_level: None,
         };
 
         appender.append(&entry).await.expect("Failed to append");
@@ -258,9 +264,12 @@ mod tests {
                 file: None,
                 line: None,
                 module_path: None,
-                thread_id: "main".to_string(),
-                thread_name: Some("main".to_string()),
+                target: None,
+                thread_id: "main".into(),
+                thread_name: Some("main".into()),
                 context: None,
+                tracing: None,
+                custom_level: None,
             };
 
             appender.append(&entry).await.expect("Failed to append");
@@ -1,29 +1,491 @@
 //! Console appender implementation
+//!
+//! Outside of a batch every entry is written with a single locked write.
+//! Inside a batch (see [`Appender::begin_batch`]) entries are collected in
+//! per-stream buffers and written under one stdout/stderr lock when the
+//! batch is flushed, so the async worker pays for locking and syscalls once
+//! per batch instead of once per line. Within a batch, stdout lines are
+//! written before stderr lines.
+//!
+//! Colors follow a [`ColorMode`]. The default, [`ColorMode::Auto`], colors
+//! a stream only if it is a terminal, and honors the `NO_COLOR` and
+//! `CLICOLOR_FORCE` environment variables. On Windows the appender enables
+//! virtual terminal processing so the console understands ANSI escapes, and
+//! writes uncolored output to consoles that cannot.
 
-use crate::core::{Appender, LogEntry, LogLevel, OutputFormat, Result, TimestampFormat};
-use colored::Colorize;
+use crate::core::{
+    Appender, ConsoleTheme, Formatter, FormatterConfig, LogEntry, LogLevel, OutputFormat, Result,
+    TimestampFormat,
+};
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::io::{IsTerminal, Write};
+
+/// Buffered bytes per stream after which a batch is written out early
+const MAX_BUFFERED_BYTES: usize = 64 * 1024;
+
+/// Which stream the console appender writes each entry to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamPolicy {
+    /// Write every entry to stdout
+    AllStdout,
+    /// Write every entry to stderr
+    AllStderr,
+    /// Write entries at or above `threshold` to stderr, the rest to stdout
+    SplitByLevel {
+        /// Lowest level written to stderr
+        threshold: LogLevel,
+    },
+}
+
+impl Default for StreamPolicy {
+    /// Error and Fatal to stderr, everything else to stdout
+    fn default() -> Self {
+        StreamPolicy::SplitByLevel {
+            threshold: LogLevel::Error,
+        }
+    }
+}
+
+impl StreamPolicy {
+    /// Check whether an entry at `level` goes to stderr
+    pub fn uses_stderr(&self, level: LogLevel) -> bool {
+        match self {
+            StreamPolicy::AllStdout => false,
+            StreamPolicy::AllStderr => true,
+            StreamPolicy::SplitByLevel { threshold } => level >= *threshold,
+        }
+    }
+}
+
+/// Column limit for console lines
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineWidth {
+    /// Print lines in full
+    #[default]
+    Unlimited,
+    /// Cut lines at the width of the terminal, checked on every line
+    ///
+    /// Falls back to the `COLUMNS` environment variable, and to no limit
+    /// when neither is available (for example when output is redirected).
+    Terminal,
+    /// Cut lines at a fixed number of columns
+    Fixed(usize),
+}
+
+/// When the console appender emits ANSI colors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Color a stream if it is a terminal
+    ///
+    /// A non-empty `NO_COLOR` disables colors, and `CLICOLOR_FORCE` set to
+    /// anything but `0` enables them even when output is redirected.
+    #[default]
+    Auto,
+    /// Always emit colors
+    Always,
+    /// Never emit colors
+    Never,
+}
+
+impl ColorMode {
+    /// Decide whether to color a stream
+    ///
+    /// `NO_COLOR` and `CLICOLOR_FORCE` are read from the environment in
+    /// [`ColorMode::Auto`].
+    pub fn enabled(self, is_terminal: bool) -> bool {
+        self.resolve(
+            is_terminal,
+            std::env::var_os("NO_COLOR"),
+            std::env::var_os("CLICOLOR_FORCE"),
+        )
+    }
+
+    fn resolve(
+        self,
+        is_terminal: bool,
+        no_color: Option<OsString>,
+        clicolor_force: Option<OsString>,
+    ) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => {
+                if no_color.is_some_and(|value| !value.is_empty()) {
+                    false
+                } else if clicolor_force.is_some_and(|value| value != "0") {
+                    true
+                } else {
+                    is_terminal
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ConsoleStream {
+    Stdout,
+    Stderr,
+}
+
+/// Resolve `mode` for one stream, preparing a Windows console for ANSI
+///
+/// A terminal that cannot process escape sequences (pre-Windows 10
+/// consoles) falls back to uncolored output. Redirected output keeps the
+/// escape sequences `mode` asks for.
+fn stream_colors(mode: ColorMode, stream: ConsoleStream) -> bool {
+    let is_terminal = match stream {
+        ConsoleStream::Stdout => std::io::stdout().is_terminal(),
+        ConsoleStream::Stderr => std::io::stderr().is_terminal(),
+    };
+    mode.enabled(is_terminal) && (!is_terminal || enable_virtual_terminal(stream))
+}
+
+/// Columns of the terminal behind `stream`
+fn terminal_width(stream: ConsoleStream) -> Option<usize> {
+    query_terminal_width(stream).or_else(|| {
+        std::env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.trim().parse().ok())
+            .filter(|&columns| columns > 0)
+    })
+}
+
+#[cfg(unix)]
+fn query_terminal_width(stream: ConsoleStream) -> Option<usize> {
+    let fd = match stream {
+        ConsoleStream::Stdout => libc::STDOUT_FILENO,
+        ConsoleStream::Stderr => libc::STDERR_FILENO,
+    };
+
+    // SAFETY: TIOCGWINSZ only writes a `winsize` into `size`, and fails on
+    // descriptors that are not terminals.
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    let ok = unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) } == 0;
+    (ok && size.ws_col > 0).then_some(size.ws_col as usize)
+}
+
+#[cfg(windows)]
+fn query_terminal_width(stream: ConsoleStream) -> Option<usize> {
+    use windows_sys::Win32::System::Console::{
+        GetConsoleScreenBufferInfo, GetStdHandle, CONSOLE_SCREEN_BUFFER_INFO, STD_ERROR_HANDLE,
+        STD_OUTPUT_HANDLE,
+    };
+
+    let id = match stream {
+        ConsoleStream::Stdout => STD_OUTPUT_HANDLE,
+        ConsoleStream::Stderr => STD_ERROR_HANDLE,
+    };
+
+    // SAFETY: GetConsoleScreenBufferInfo only writes into `info`, and fails
+    // on handles that are not consoles.
+    unsafe {
+        let mut info: CONSOLE_SCREEN_BUFFER_INFO = std::mem::zeroed();
+        if GetConsoleScreenBufferInfo(GetStdHandle(id), &mut info) == 0 {
+            return None;
+        }
+        let columns = info.srWindow.Right - info.srWindow.Left + 1;
+        (columns > 0).then_some(columns as usize)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn query_terminal_width(_stream: ConsoleStream) -> Option<usize> {
+    None
+}
+
+/// Cut `line` to `width` visible columns
+///
+/// A cut line ends in an ellipsis and the number of characters dropped,
+/// for example `… [+1048576 chars]`. ANSI escape sequences do not count
+/// toward the width and are never split.
+fn truncate_line(line: &str, width: usize) -> Cow<'_, str> {
+    let visible = visible_chars(line);
+    if visible <= width {
+        return Cow::Borrowed(line);
+    }
+
+    let marker = |dropped: usize| format!("\u{2026} [+{} chars]", dropped);
+    let mut keep = width;
+    while keep > 0 && keep + marker(visible - keep).chars().count() > width {
+        keep -= 1;
+    }
+
+    let mut output = String::with_capacity(width * 4);
+    let mut kept = 0;
+    let mut styled = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            styled = true;
+            output.push(c);
+            for c in chars.by_ref() {
+                output.push(c);
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else if kept < keep {
+            output.push(c);
+            kept += 1;
+        } else {
+            break;
+        }
+    }
+    if styled {
+        output.push_str("\x1b[0m");
+    }
+    output.push_str(&marker(visible - keep));
+    Cow::Owned(output)
+}
+
+/// Count characters outside of ANSI escape sequences
+fn visible_chars(line: &str) -> usize {
+    let mut count = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Turn on virtual terminal processing for a Windows console
+///
+/// Returns false if the console does not support it.
+#[cfg(windows)]
+fn enable_virtual_terminal(stream: ConsoleStream) -> bool {
+    use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+    use windows_sys::Win32::System::Console::{
+        GetConsoleMode, GetStdHandle, SetConsoleMode, ENABLE_VIRTUAL_TERMINAL_PROCESSING,
+        STD_ERROR_HANDLE, STD_OUTPUT_HANDLE,
+    };
+
+    let id = match stream {
+        ConsoleStream::Stdout => STD_OUTPUT_HANDLE,
+        ConsoleStream::Stderr => STD_ERROR_HANDLE,
+    };
+
+    // SAFETY: the handle comes from GetStdHandle and is only passed back to
+    // the console mode functions, which fail cleanly on non-console handles.
+    unsafe {
+        let handle = GetStdHandle(id);
+        if handle.is_null() || handle == INVALID_HANDLE_VALUE {
+            return false;
+        }
+
+        let mut mode = 0;
+        if GetConsoleMode(handle, &mut mode) == 0 {
+            return false;
+        }
+        if mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0 {
+            return true;
+        }
+        SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+    }
+}
+
+/// ANSI escapes work on every non-Windows terminal
+#[cfg(not(windows))]
+fn enable_virtual_terminal(_stream: ConsoleStream) -> bool {
+    true
+}
+
+impl std::str::FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Ok(ColorMode::Auto),
+            "always" => Ok(ColorMode::Always),
+            "never" => Ok(ColorMode::Never),
+            other => Err(format!("unknown color mode '{}'", other)),
+        }
+    }
+}
 
 pub struct ConsoleAppender {
-    use_colors: bool,
-    timestamp_format: TimestampFormat,
+    color_mode: ColorMode,
+    /// Resolved from `color_mode` for each stream
+    stdout_colors: bool,
+    stderr_colors: bool,
+    theme: ConsoleTheme,
+    stream_policy: StreamPolicy,
+    config: FormatterConfig,
     output_format: OutputFormat,
+    formatter: Option<Box<dyn Formatter>>,
+    /// Set between `begin_batch` and `end_batch` or `flush`
+    batching: bool,
+    stdout_buffer: Vec<u8>,
+    stderr_buffer: Vec<u8>,
+    line_width: LineWidth,
+    collapse_repeats: bool,
+    /// Level and message of the last entry written while collapsing
+    last_message: Option<(LogLevel, String)>,
+    /// Repeats of `last_message` not yet summarized
+    repeats: u64,
 }
 
 impl ConsoleAppender {
     pub fn new() -> Self {
         Self {
-            use_colors: true,
-            timestamp_format: TimestampFormat::default(),
+            color_mode: ColorMode::Auto,
+            stdout_colors: false,
+            stderr_colors: false,
+            theme: ConsoleTheme::default(),
+            stream_policy: StreamPolicy::default(),
+            config: FormatterConfig::default(),
             output_format: OutputFormat::default(),
+            formatter: None,
+            batching: false,
+            stdout_buffer: Vec::new(),
+            stderr_buffer: Vec::new(),
+            line_width: LineWidth::default(),
+            collapse_repeats: false,
+            last_message: None,
+            repeats: 0,
         }
+        .with_color_mode(ColorMode::Auto)
+    }
+
+    /// Create an appender for containers that writes one JSON object per line
+    /// to stdout
+    ///
+    /// Every level goes to stdout and colors are off, so Docker and
+    /// Kubernetes log drivers see only machine-parseable lines. The timestamp
+    /// format and JSON profile can still be changed through
+    /// [`with_formatter_config`](Self::with_formatter_config).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::appenders::ConsoleAppender;
+    /// use rust_logger_system::TimestampFormat;
+    ///
+    /// let appender = ConsoleAppender::json().with_timestamp_format(TimestampFormat::UnixMillis);
+    /// ```
+    pub fn json() -> Self {
+        Self::new()
+            .with_color_mode(ColorMode::Never)
+            .with_stream_policy(StreamPolicy::AllStdout)
+            .with_output_format(OutputFormat::Json)
     }
 
+    /// Create an appender that always (`true`) or never (`false`) colors
     pub fn with_colors(use_colors: bool) -> Self {
-        Self {
-            use_colors,
-            timestamp_format: TimestampFormat::default(),
-            output_format: OutputFormat::default(),
-        }
+        Self::new().with_color_mode(if use_colors {
+            ColorMode::Always
+        } else {
+            ColorMode::Never
+        })
+    }
+
+    /// Set when to emit ANSI colors
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::appenders::{ColorMode, ConsoleAppender};
+    ///
+    /// let appender = ConsoleAppender::new().with_color_mode(ColorMode::Never);
+    /// ```
+    #[must_use]
+    pub fn with_color_mode(mut self, mode: ColorMode) -> Self {
+        self.color_mode = mode;
+        self.stdout_colors = stream_colors(mode, ConsoleStream::Stdout);
+        self.stderr_colors = stream_colors(mode, ConsoleStream::Stderr);
+        self
+    }
+
+    /// Get the color mode
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Choose which stream each entry is written to
+    ///
+    /// The default sends Error and Fatal entries to stderr and everything
+    /// else to stdout. Use [`StreamPolicy::AllStdout`] when an orchestrator
+    /// treats any stderr output as a failure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::appenders::{ConsoleAppender, StreamPolicy};
+    /// use rust_logger_system::LogLevel;
+    ///
+    /// let appender = ConsoleAppender::new().with_stream_policy(StreamPolicy::SplitByLevel {
+    ///     threshold: LogLevel::Warn,
+    /// });
+    /// ```
+    #[must_use]
+    pub fn with_stream_policy(mut self, policy: StreamPolicy) -> Self {
+        self.stream_policy = policy;
+        self
+    }
+
+    /// Limit the width of printed lines
+    ///
+    /// Longer lines are cut and end in an ellipsis with the number of
+    /// characters dropped. Multi-line output is cut line by line. Only the
+    /// console output is shortened; other appenders receive the full
+    /// message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::appenders::{ConsoleAppender, LineWidth};
+    ///
+    /// let appender = ConsoleAppender::new().with_line_width(LineWidth::Terminal);
+    /// ```
+    #[must_use]
+    pub fn with_line_width(mut self, width: LineWidth) -> Self {
+        self.line_width = width;
+        self
+    }
+
+    /// Collapse identical consecutive messages into one summary line
+    ///
+    /// An entry with the same level and message as the previous one is not
+    /// printed. Instead, `Last message repeated N times` is printed when a
+    /// different message arrives or the appender is flushed. Only this
+    /// appender collapses; other appenders still receive every entry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::appenders::ConsoleAppender;
+    ///
+    /// let appender = ConsoleAppender::new().with_collapse_repeats(true);
+    /// ```
+    #[must_use]
+    pub fn with_collapse_repeats(mut self, collapse: bool) -> Self {
+        self.collapse_repeats = collapse;
+        self
+    }
+
+    /// Set the styles used when colors are enabled
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::appenders::ConsoleAppender;
+    /// use rust_logger_system::core::ConsoleTheme;
+    ///
+    /// let appender = ConsoleAppender::new().with_theme(ConsoleTheme::light());
+    /// ```
+    #[must_use]
+    pub fn with_theme(mut self, theme: ConsoleTheme) -> Self {
+        self.theme = theme;
+        self
     }
 
     /// Set the output format for this appender
@@ -43,6 +505,46 @@ impl ConsoleAppender {
         self
     }
 
+    /// Replace the built-in layout with a custom [`Formatter`]
+    ///
+    /// The formatter's output is written followed by a newline. Error and Fatal
+    /// entries still go to stderr.
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Box<dyn Formatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    /// Set the formatting options for this appender
+    ///
+    /// The timestamp format and JSON profile apply to every output format.
+    /// Level casing, the source location and the `include_level` and
+    /// `include_thread_id` flags apply to [`OutputFormat::Text`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::appenders::ConsoleAppender;
+    /// use rust_logger_system::{FormatterConfig, TimestampFormat};
+    ///
+    /// let appender = ConsoleAppender::new().with_formatter_config(
+    ///     FormatterConfig::new()
+    ///         .with_timestamp_format(TimestampFormat::Rfc3339)
+    ///         .with_level_uppercase(false)
+    ///         .with_include_thread_id(false),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_formatter_config(mut self, config: FormatterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Get the formatting options
+    pub fn formatter_config(&self) -> &FormatterConfig {
+        &self.config
+    }
+
     /// Set the timestamp format for this appender
     ///
     /// # Examples
@@ -56,7 +558,7 @@ impl ConsoleAppender {
     /// ```
     #[must_use]
     pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
-        self.timestamp_format = format;
+        self.config.timestamp_format = format;
         self
     }
 
@@ -72,7 +574,7 @@ impl ConsoleAppender {
     /// ```
     #[must_use]
     pub fn with_custom_timestamp(mut self, format_str: &str) -> Self {
-        self.timestamp_format = TimestampFormat::Custom(format_str.to_string());
+        self.config.timestamp_format = TimestampFormat::Custom(format_str.to_string());
         self
     }
 }
@@ -85,23 +587,27 @@ impl Default for ConsoleAppender {
 
 impl Appender for ConsoleAppender {
     fn append(&mut self, entry: &LogEntry) -> Result<()> {
-        let output = match self.output_format {
-            OutputFormat::Text => self.format_text(entry),
-            OutputFormat::Json | OutputFormat::Logfmt => {
-                self.output_format.format(entry, &self.timestamp_format)
+        if self.collapse_repeats {
+            let repeated = self
+                .last_message
+                .as_ref()
+                .is_some_and(|(level, message)| *level == entry.level && *message == entry.message);
+            if repeated {
+                self.repeats += 1;
+                return Ok(());
             }
-        };
 
-        // Route Error and Fatal levels to stderr, others to stdout
-        match entry.level {
-            LogLevel::Error | LogLevel::Fatal => eprintln!("{}", output),
-            _ => println!("{}", output),
+            self.write_repeat_summary()?;
+            self.last_message = Some((entry.level, entry.message.clone()));
         }
-        Ok(())
+
+        self.write_entry(entry)
     }
 
     fn flush(&mut self) -> Result<()> {
-        use std::io::Write;
+        self.write_repeat_summary()?;
+        self.batching = false;
+        self.write_buffers()?;
         // Flush both stdout and stderr since we write to both
         std::io::stdout().flush()?;
         std::io::stderr().flush()?;
@@ -111,36 +617,355 @@ impl Appender for ConsoleAppender {
     fn name(&self) -> &str {
         "console"
     }
+
+    fn begin_batch(&mut self) {
+        self.batching = true;
+    }
+
+    fn end_batch(&mut self) -> Result<()> {
+        self.batching = false;
+        self.write_buffers()
+    }
+
+    fn set_formatter_config(&mut self, config: &FormatterConfig) -> bool {
+        self.config = config.clone();
+        true
+    }
 }
 
 impl ConsoleAppender {
-    /// Format as text with optional colors
-    fn format_text(&self, entry: &LogEntry) -> String {
-        let level_str = if self.use_colors {
-            format!("{:5}", entry.level.to_str())
-                .color(entry.level.color_code())
-                .to_string()
+    /// Format `entry` and write or buffer it on its stream
+    fn write_entry(&mut self, entry: &LogEntry) -> Result<()> {
+        let to_stderr = self.stream_policy.uses_stderr(entry.level);
+        let colors = if to_stderr {
+            self.stderr_colors
+        } else {
+            self.stdout_colors
+        };
+
+        let output = if let Some(ref formatter) = self.formatter {
+            let mut output = String::new();
+            formatter.format(entry, &mut output);
+            output
         } else {
-            format!("{:5}", entry.level.to_str())
+            match self.output_format {
+                OutputFormat::Text => self.format_text(entry, colors),
+                OutputFormat::Pretty => {
+                    let theme = colors.then_some(&self.theme);
+                    let timestamp_format = &self.config.timestamp_format;
+                    self.output_format.format_pretty(entry, timestamp_format, theme)
+                }
+                OutputFormat::Json
+                | OutputFormat::Logfmt
+                | OutputFormat::Pattern(_)
+                | OutputFormat::OtelJson => {
+                    self.output_format.format_with_config(entry, &self.config)
+                }
+            }
         };
 
-        let timestamp_str = self.timestamp_format.format(&entry.timestamp);
+        let width = match self.line_width {
+            LineWidth::Unlimited => None,
+            LineWidth::Fixed(width) => Some(width),
+            LineWidth::Terminal => terminal_width(if to_stderr {
+                ConsoleStream::Stderr
+            } else {
+                ConsoleStream::Stdout
+            }),
+        };
 
-        let base = format!(
-            "[{}] [{}] {} - {}",
-            timestamp_str,
-            level_str,
-            entry.thread_name.as_ref().unwrap_or(&entry.thread_id),
-            entry.message
-        );
+        let buffer = if to_stderr {
+            &mut self.stderr_buffer
+        } else {
+            &mut self.stdout_buffer
+        };
+        match width {
+            Some(width) => {
+                for line in output.split('\n') {
+                    buffer.extend_from_slice(truncate_line(line, width).as_bytes());
+                    buffer.push(b'\n');
+                }
+            }
+            None => {
+                buffer.extend_from_slice(output.as_bytes());
+                buffer.push(b'\n');
+            }
+        }
+
+        if !self.batching || buffer.len() >= MAX_BUFFERED_BYTES {
+            self.write_buffers()?;
+        }
+        Ok(())
+    }
+
+    /// Write the summary for collapsed repeats, if there are any
+    ///
+    /// The summary is formatted like a regular entry at the repeated
+    /// message's level, so it stays valid in JSON and other formats.
+    fn write_repeat_summary(&mut self) -> Result<()> {
+        if self.repeats == 0 {
+            return Ok(());
+        }
+        let Some((level, _)) = self.last_message else {
+            return Ok(());
+        };
+
+        let message = match self.repeats {
+            1 => "Last message repeated 1 time".to_string(),
+            n => format!("Last message repeated {} times", n),
+        };
+        self.repeats = 0;
+        self.write_entry(&LogEntry::new(level, message))
+    }
+
+    /// Write buffered output, taking each stream's lock once
+    fn write_buffers(&mut self) -> Result<()> {
+        if !self.stdout_buffer.is_empty() {
+            let mut stdout = std::io::stdout().lock();
+            let result = stdout.write_all(&self.stdout_buffer);
+            self.stdout_buffer.clear();
+            result?;
+        }
+        if !self.stderr_buffer.is_empty() {
+            let mut stderr = std::io::stderr().lock();
+            let result = stderr.write_all(&self.stderr_buffer);
+            self.stderr_buffer.clear();
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Format as text, styled with the theme if `colors` is set
+    fn format_text(&self, entry: &LogEntry, colors: bool) -> String {
+        use crate::core::TextStyle;
+
+        let paint = |style: TextStyle, text: &str| {
+            if colors {
+                style.paint(text)
+            } else {
+                text.to_string()
+            }
+        };
+
+        let timestamp_str = self.config.timestamp_format.format(&entry.timestamp);
+        let mut output = format!("[{}]", paint(self.theme.timestamp(), &timestamp_str));
+        if self.config.include_level {
+            let level_str = format!("{:5}", self.config.entry_level_str(entry));
+            output.push_str(&format!(" [{}]", paint(self.theme.level(entry.level), &level_str)));
+        }
+        let thread = entry.thread_name.as_ref().unwrap_or(&entry.thread_id);
+        let thread = self.config.include_thread_id.then(|| paint(self.theme.thread(), thread));
+        let source: Vec<String> = thread.into_iter().chain(self.config.location(entry)).collect();
+        if !source.is_empty() {
+            output.push(' ');
+            output.push_str(&source.join(" "));
+            output.push_str(" -");
+        }
+        output.push(' ');
+        output.push_str(&entry.message);
 
-        // Append context fields if present
         if let Some(ref context) = entry.context {
-            if !context.is_empty() {
-                return format!("{} {}", base, context.format_fields());
+            for (key, value) in context.field_map() {
+                output.push(' ');
+                output.push_str(&paint(self.theme.context_key(), key));
+                output.push('=');
+                output.push_str(&value.to_string());
             }
         }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_mode_resolution() {
+        let set = |value: &str| Some(OsString::from(value));
+
+        assert!(ColorMode::Always.resolve(false, set("1"), None));
+        assert!(!ColorMode::Never.resolve(true, None, set("1")));
+
+        assert!(ColorMode::Auto.resolve(true, None, None));
+        assert!(!ColorMode::Auto.resolve(false, None, None));
+        assert!(!ColorMode::Auto.resolve(true, set("1"), None));
+        assert!(ColorMode::Auto.resolve(true, set(""), None));
+        assert!(ColorMode::Auto.resolve(false, None, set("1")));
+        assert!(!ColorMode::Auto.resolve(false, None, set("0")));
+        assert!(!ColorMode::Auto.resolve(true, set("1"), set("1")));
+
+        assert_eq!("Never".parse::<ColorMode>(), Ok(ColorMode::Never));
+        assert!("sometimes".parse::<ColorMode>().is_err());
+    }
+
+    #[test]
+    fn test_stream_policy() {
+        let default = StreamPolicy::default();
+        assert!(!default.uses_stderr(LogLevel::Warn));
+        assert!(default.uses_stderr(LogLevel::Error));
+        assert!(default.uses_stderr(LogLevel::Fatal));
+
+        let warn = StreamPolicy::SplitByLevel {
+            threshold: LogLevel::Warn,
+        };
+        assert!(warn.uses_stderr(LogLevel::Warn));
+        assert!(!StreamPolicy::AllStdout.uses_stderr(LogLevel::Fatal));
+        assert!(StreamPolicy::AllStderr.uses_stderr(LogLevel::Trace));
+
+        let mut appender = ConsoleAppender::new().with_stream_policy(StreamPolicy::AllStdout);
+        appender.begin_batch();
+        appender.append(&LogEntry::new(LogLevel::Fatal, "boom".to_string())).unwrap();
+        assert!(appender.stderr_buffer.is_empty());
+        assert!(!appender.stdout_buffer.is_empty());
+        appender.flush().unwrap();
+    }
+
+    #[test]
+    fn test_themed_text() {
+        use crate::core::{LogContext, TextStyle};
+        use colored::Color;
+
+        let theme = ConsoleTheme::plain()
+            .with_level(LogLevel::Warn, TextStyle::new().color(Color::Magenta))
+            .with_context_key(TextStyle::new().bold());
+        let appender = ConsoleAppender::new().with_theme(theme);
+        let entry = LogEntry::new(LogLevel::Warn, "slow".to_string())
+            .with_context(LogContext::new().with_field("ms", 900));
+
+        let output = appender.format_text(&entry, true);
+        assert!(output.contains("[\x1b[35mWARN \x1b[0m]"));
+        assert!(output.ends_with("slow \x1b[1mms\x1b[0m=900"));
+        assert!(!appender.format_text(&entry, false).contains('\x1b'));
+    }
+
+    #[test]
+    fn test_formatter_config_text() {
+        let entry = LogEntry::new(LogLevel::Warn, "slow".to_string());
+
+        let default = ConsoleAppender::new().format_text(&entry, false);
+        assert!(default.contains("[WARN ] ") && default.contains(" - slow"));
+
+        let appender = ConsoleAppender::new().with_formatter_config(
+            FormatterConfig::new()
+                .with_timestamp_format(TimestampFormat::Unix)
+                .with_level_uppercase(false)
+                .with_include_thread_id(false),
+        );
+        let output = appender.format_text(&entry, false);
+        assert_eq!(output, format!("[{}] [warn ] slow", entry.timestamp.timestamp()));
+
+        let appender = appender
+            .with_formatter_config(FormatterConfig::new().with_include_level(false));
+        assert!(!appender.format_text(&entry, false).contains("WARN"));
+
+        let entry = entry.with_location("src/db.rs", 42, "app::db");
+        let appender = appender.with_formatter_config(
+            FormatterConfig::new()
+                .with_include_file_location(true)
+                .with_include_module_path(true),
+        );
+        assert!(appender
+            .format_text(&entry, false)
+            .ends_with(" app::db src/db.rs:42 - slow"));
+    }
+
+    #[test]
+    fn test_json_mode() {
+        let mut appender = ConsoleAppender::json();
+        let entry = LogEntry::new(LogLevel::Error, "failed".to_string())
+            .with_context(crate::core::LogContext::new().with_field("attempt", 3));
+
+        appender.begin_batch();
+        appender.append(&entry).unwrap();
+        assert!(appender.stderr_buffer.is_empty());
+
+        let line = String::from_utf8(appender.stdout_buffer.clone()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(line.trim_end()).unwrap();
+        assert_eq!(value["level"], "ERROR");
+        assert_eq!(value["message"], "failed");
+        assert_eq!(value["attempt"], 3);
+        assert!(value["timestamp"].is_string());
+        appender.flush().unwrap();
+    }
+
+    #[test]
+    fn test_collapse_repeats() {
+        let mut appender = ConsoleAppender::with_colors(false)
+            .with_stream_policy(StreamPolicy::AllStdout)
+            .with_collapse_repeats(true);
+        let retry = LogEntry::new(LogLevel::Warn, "retrying".to_string());
+
+        appender.begin_batch();
+        for _ in 0..4 {
+            appender.append(&retry).unwrap();
+        }
+        appender.append(&LogEntry::new(LogLevel::Info, "connected".to_string())).unwrap();
+        appender.append(&LogEntry::new(LogLevel::Info, "connected".to_string())).unwrap();
+
+        let output = String::from_utf8(appender.stdout_buffer.clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("retrying"));
+        assert!(lines[1].contains("WARN") && lines[1].ends_with("Last message repeated 3 times"));
+        assert!(lines[2].ends_with("connected"));
+
+        // Pending repeats are summarized on flush
+        appender.stdout_buffer.clear();
+        appender.write_repeat_summary().unwrap();
+        let output = String::from_utf8(appender.stdout_buffer.clone()).unwrap();
+        assert!(output.trim_end().ends_with("Last message repeated 1 time"));
+        appender.flush().unwrap();
+    }
+
+    #[test]
+    fn test_truncate_line() {
+        assert_eq!(truncate_line("short", 10), "short");
+
+        let long = "x".repeat(100);
+        let cut = truncate_line(&long, 30);
+        assert_eq!(cut.chars().count(), 30);
+        assert!(cut.ends_with("\u{2026} [+83 chars]"));
+
+        // Escape sequences are kept whole and styling is reset
+        let styled = format!("\x1b[31m{}\x1b[0m", long);
+        let cut = truncate_line(&styled, 30);
+        assert!(cut.starts_with("\x1b[31mxxx"));
+        assert!(cut.contains("\x1b[0m\u{2026}"));
+        assert_eq!(visible_chars(&cut), 30);
+    }
+
+    #[test]
+    fn test_line_width() {
+        let mut appender = ConsoleAppender::with_colors(false)
+            .with_stream_policy(StreamPolicy::AllStdout)
+            .with_line_width(LineWidth::Fixed(60));
+        let entry = LogEntry::new(LogLevel::Info, "y".repeat(1 << 20));
+
+        appender.begin_batch();
+        appender.append(&entry).unwrap();
+        let output = String::from_utf8(appender.stdout_buffer.clone()).unwrap();
+        assert_eq!(output.trim_end().chars().count(), 60);
+        assert!(output.trim_end().ends_with(" chars]"));
+        appender.flush().unwrap();
+        assert_eq!(entry.message.len(), 1 << 20);
+    }
+
+    #[test]
+    fn test_batch_buffers_until_flush() {
+        let mut appender = ConsoleAppender::with_colors(false);
+
+        appender.begin_batch();
+        appender.append(&LogEntry::new(LogLevel::Info, "first".to_string())).unwrap();
+        appender.append(&LogEntry::new(LogLevel::Error, "second".to_string())).unwrap();
+        assert!(String::from_utf8_lossy(&appender.stdout_buffer).ends_with("first\n"));
+        assert!(String::from_utf8_lossy(&appender.stderr_buffer).ends_with("second\n"));
+
+        appender.flush().unwrap();
+        assert!(appender.stdout_buffer.is_empty() && appender.stderr_buffer.is_empty());
 
-        base
+        // Outside a batch entries are written immediately
+        appender.append(&LogEntry::new(LogLevel::Info, "third".to_string())).unwrap();
+        assert!(appender.stdout_buffer.is_empty());
     }
 }
@@ -1,13 +1,123 @@
 //! File appender implementation
 
-use crate::core::{Appender, LogEntry, LoggerError, Result, TimestampFormat};
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+use crate::core::{BinaryFormat, StructuredLogEntry};
+use crate::core::{
+    Appender, Formatter, FormatterConfig, LogEntry, LoggerError, Result, TimestampFormat,
+};
 use std::fs::{File, OpenOptions};
+use std::fmt::Write as _;
 use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 
+/// Largest amount of batched output held before it is written
+const MAX_PENDING_BYTES: usize = 1024 * 1024;
+
+/// Lines of a batch collected for a single write
+///
+/// Between [`Appender::begin_batch`] and [`Appender::end_batch`], lines
+/// are appended to one buffer that is written with a single `write_all`,
+/// instead of one write per entry.
+pub(crate) struct LineBatch {
+    enabled: bool,
+    active: bool,
+    pending: Vec<u8>,
+}
+
+impl LineBatch {
+    pub(crate) fn new() -> Self {
+        Self {
+            enabled: true,
+            active: false,
+            pending: Vec::new(),
+        }
+    }
+
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub(crate) fn begin(&mut self) {
+        self.active = self.enabled;
+    }
+
+    /// Write `line`, or hold it until the batch ends
+    pub(crate) fn write(&mut self, line: &[u8], writer: &mut impl Write) -> std::io::Result<()> {
+        if !self.active {
+            return writer.write_all(line);
+        }
+        self.pending.extend_from_slice(line);
+        if self.pending.len() >= MAX_PENDING_BYTES {
+            self.write_pending(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Write the held lines
+    pub(crate) fn write_pending(&mut self, writer: &mut impl Write) -> std::io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let result = writer.write_all(&self.pending);
+        self.pending.clear();
+        if self.pending.capacity() > MAX_PENDING_BYTES {
+            self.pending.shrink_to(MAX_PENDING_BYTES);
+        }
+        result
+    }
+
+    /// Write the held lines and end the batch
+    pub(crate) fn finish(&mut self, writer: &mut impl Write) -> std::io::Result<()> {
+        self.active = false;
+        self.write_pending(writer)
+    }
+}
+
+/// Format `entry` as a line of a text log file, newline included
+///
+/// Uses `formatter` if there is one, the built-in layout otherwise.
+pub(crate) fn format_line(
+    entry: &LogEntry,
+    config: &FormatterConfig,
+    formatter: Option<&dyn Formatter>,
+    output: &mut String,
+) {
+    if let Some(formatter) = formatter {
+        formatter.format(entry, output);
+    } else {
+        output.push('[');
+        config.timestamp_format.format_into(&entry.timestamp, output);
+        let _ = write!(
+            output,
+            "] [{:5}] [{}] ",
+            entry.level_name(),
+            entry.thread_name.as_ref().unwrap_or(&entry.thread_id),
+        );
+        // Bracket the location, or drop the bracket if there is none
+        output.push('[');
+        if config.push_location(entry, output) {
+            output.push_str("] ");
+        } else {
+            output.pop();
+        }
+        output.push_str(&entry.message);
+
+        // Append context fields if present
+        if let Some(ref context) = entry.context {
+            output.push_str(" | ");
+            let _ = write!(output, "{}", context);
+        }
+    }
+    output.push('\n');
+}
+
 pub struct FileAppender {
     writer: Option<BufWriter<File>>,
-    timestamp_format: TimestampFormat,
+    config: FormatterConfig,
+    formatter: Option<Box<dyn Formatter>>,
+    batch: LineBatch,
+    #[cfg(any(feature = "msgpack", feature = "cbor"))]
+    binary_format: Option<BinaryFormat>,
 }
 
 impl FileAppender {
@@ -18,10 +128,36 @@ impl FileAppender {
 
         Ok(Self {
             writer,
-            timestamp_format: TimestampFormat::default(),
+            config: FormatterConfig::default(),
+            formatter: None,
+            batch: LineBatch::new(),
+            #[cfg(any(feature = "msgpack", feature = "cbor"))]
+            binary_format: None,
         })
     }
 
+    /// Set the formatting options for this appender
+    ///
+    /// The built-in layout uses the timestamp format and, when
+    /// `include_file_location` is set, writes the source location after the
+    /// thread name.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rust_logger_system::appenders::FileAppender;
+    /// use rust_logger_system::FormatterConfig;
+    ///
+    /// let appender = FileAppender::new("/var/log/app.log")
+    ///     .unwrap()
+    ///     .with_formatter_config(FormatterConfig::new().with_include_file_location(true));
+    /// ```
+    #[must_use]
+    pub fn with_formatter_config(mut self, config: FormatterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     /// Set the timestamp format for this appender
     ///
     /// # Examples
@@ -36,49 +172,74 @@ impl FileAppender {
     /// ```
     #[must_use]
     pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
-        self.timestamp_format = format;
+        self.config.timestamp_format = format;
         self
     }
 
     /// Set a custom timestamp format using a strftime-compatible format string
     #[must_use]
     pub fn with_custom_timestamp(mut self, format_str: &str) -> Self {
-        self.timestamp_format = TimestampFormat::Custom(format_str.to_string());
+        self.config.timestamp_format = TimestampFormat::Custom(format_str.to_string());
+        self
+    }
+
+    /// Replace the built-in layout with a custom [`Formatter`]
+    ///
+    /// The formatter's output is written followed by a newline.
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Box<dyn Formatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    /// Collect the lines of each batch of the async worker into one write
+    ///
+    /// Enabled by default. The lines are written when the batch ends;
+    /// disable to write every entry
+    /// as soon as it is appended.
+    #[must_use]
+    pub fn with_coalesced_writes(mut self, enabled: bool) -> Self {
+        self.batch.set_enabled(enabled);
+        self
+    }
+
+    /// Write entries as length-prefixed binary frames instead of text lines
+    ///
+    /// Any custom formatter is ignored while a binary format is set. Read
+    /// the file back with [`BinaryFormat::read_frame`].
+    #[cfg(any(feature = "msgpack", feature = "cbor"))]
+    #[must_use]
+    pub fn with_binary_format(mut self, format: BinaryFormat) -> Self {
+        self.binary_format = Some(format);
         self
     }
 }
 
 impl Appender for FileAppender {
     fn append(&mut self, entry: &LogEntry) -> Result<()> {
+        self.append_formatted(entry, &mut String::new())
+    }
+
+    fn append_formatted(&mut self, entry: &LogEntry, scratch: &mut String) -> Result<()> {
         let writer = self
             .writer
             .as_mut()
             .ok_or_else(|| LoggerError::writer("File writer not initialized"))?;
 
-        let timestamp_str = self.timestamp_format.format(&entry.timestamp);
-
-        let mut output = format!(
-            "[{}] [{:5}] [{}] {}",
-            timestamp_str,
-            entry.level.to_str(),
-            entry.thread_name.as_ref().unwrap_or(&entry.thread_id),
-            entry.message
-        );
-
-        // Append context fields if present
-        if let Some(ref context) = entry.context {
-            output.push_str(" | ");
-            output.push_str(&context.to_string());
+        #[cfg(any(feature = "msgpack", feature = "cbor"))]
+        if let Some(format) = self.binary_format {
+            format.write_frame(&StructuredLogEntry::from(entry), writer)?;
+            return Ok(());
         }
 
-        output.push('\n');
-
-        writer.write_all(output.as_bytes())?;
+        format_line(entry, &self.config, self.formatter.as_deref(), scratch);
+        self.batch.write(scratch.as_bytes(), writer)?;
         Ok(())
     }
 
     fn flush(&mut self) -> Result<()> {
         if let Some(ref mut writer) = self.writer {
+            self.batch.finish(writer)?;
             writer.flush()?;
         }
         Ok(())
@@ -87,6 +248,22 @@ impl Appender for FileAppender {
     fn name(&self) -> &str {
         "file"
     }
+
+    fn begin_batch(&mut self) {
+        self.batch.begin();
+    }
+
+    fn end_batch(&mut self) -> Result<()> {
+        if let Some(ref mut writer) = self.writer {
+            self.batch.finish(writer)?;
+        }
+        Ok(())
+    }
+
+    fn set_formatter_config(&mut self, config: &FormatterConfig) -> bool {
+        self.config = config.clone();
+        true
+    }
 }
 
 impl Drop for FileAppender {
@@ -0,0 +1,277 @@
+//! Failover groups of appenders
+//!
+//! An [`AppenderGroup`] writes each entry to the first of its members that
+//! accepts it: "ship to the collector, else the local file, else stderr". A
+//! member that fails is skipped for `retry_interval` and then tried again,
+//! so the group returns to its preferred member once it recovers.
+//!
+//! Added with [`LoggerBuilder::appender_group`], the group is registered
+//! under its own name. Its health is degraded while it writes to a fallback
+//! and unhealthy while every member fails, and each switch between members
+//! is delivered as [`LoggerEvent::FailoverChanged`].
+//!
+//! [`LoggerBuilder::appender_group`]: crate::core::LoggerBuilder::appender_group
+//! [`LoggerEvent::FailoverChanged`]: crate::core::LoggerEvent::FailoverChanged
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rust_logger_system::appenders::{AppenderGroup, NetworkAppender};
+//! use rust_logger_system::prelude::*;
+//!
+//! let logger = Logger::builder()
+//!     .appender(ConsoleAppender::new())
+//!     .appender_group(
+//!         AppenderGroup::new("shipping")
+//!             .member(NetworkAppender::new("logs.example.com:5140").unwrap())
+//!             .member(FileAppender::new("shipping-fallback.log").unwrap())
+//!             .min_level(LogLevel::Warn),
+//!     )
+//!     .build();
+//! ```
+
+use crate::core::{
+    Appender, AppenderEvent, FormatterConfig, HealthStatus, LogEntry, LogLevel, LoggerError,
+    Result,
+};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Appenders tried in order until one accepts the entry
+pub struct AppenderGroup {
+    name: String,
+    members: Vec<Member>,
+    min_level: LogLevel,
+    retry_interval: Duration,
+    /// Member that accepted the last entry
+    active: usize,
+    /// Last error if every member failed the last entry
+    exhausted: Option<String>,
+    events: VecDeque<AppenderEvent>,
+}
+
+struct Member {
+    appender: Box<dyn Appender>,
+    failed_at: Option<Instant>,
+    last_error: Option<String>,
+}
+
+impl AppenderGroup {
+    /// Create an empty group registered as `name`
+    ///
+    /// Writes entries of every level and retries failed members after 30
+    /// seconds.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            members: Vec::new(),
+            min_level: LogLevel::Trace,
+            retry_interval: Duration::from_secs(30),
+            active: 0,
+            exhausted: None,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Add the next member in order of preference
+    #[must_use]
+    pub fn member<A: Appender + 'static>(self, appender: A) -> Self {
+        self.boxed_member(Box::new(appender))
+    }
+
+    /// Add an already boxed member
+    #[must_use]
+    pub fn boxed_member(mut self, appender: Box<dyn Appender>) -> Self {
+        self.members.push(Member {
+            appender,
+            failed_at: None,
+            last_error: None,
+        });
+        self
+    }
+
+    /// Only write entries at `level` or above
+    #[must_use]
+    pub fn min_level(mut self, level: LogLevel) -> Self {
+        self.min_level = level;
+        self
+    }
+
+    /// Set how long a failed member is skipped
+    #[must_use]
+    pub fn retry_interval(mut self, interval: Duration) -> Self {
+        self.retry_interval = interval;
+        self
+    }
+
+    /// Names of the members, in order of preference
+    pub fn member_names(&self) -> Vec<&str> {
+        self.members.iter().map(|member| member.appender.name()).collect()
+    }
+
+    /// Name of the member that accepted the last entry
+    pub fn active_member(&self) -> Option<&str> {
+        self.members.get(self.active).map(|member| member.appender.name())
+    }
+
+    fn switch_to(&mut self, index: usize) {
+        if index != self.active {
+            self.events.push_back(AppenderEvent::FailedOver {
+                from: self.members[self.active].appender.name().to_string(),
+                to: self.members[index].appender.name().to_string(),
+            });
+            self.active = index;
+        }
+    }
+}
+
+impl Appender for AppenderGroup {
+    fn append(&mut self, entry: &LogEntry) -> Result<()> {
+        if entry.level < self.min_level {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let mut last_error = None;
+        for index in 0..self.members.len() {
+            let member = &mut self.members[index];
+            let waiting = member
+                .failed_at
+                .is_some_and(|failed| now.saturating_duration_since(failed) < self.retry_interval);
+            if waiting {
+                continue;
+            }
+            match member.appender.append(entry) {
+                Ok(()) => {
+                    member.failed_at = None;
+                    member.last_error = None;
+                    self.events.extend(std::iter::from_fn(|| member.appender.take_event()));
+                    self.exhausted = None;
+                    self.switch_to(index);
+                    return Ok(());
+                }
+                Err(e) => {
+                    member.failed_at = Some(now);
+                    member.last_error = Some(e.to_string());
+                    last_error = Some(e);
+                }
+            }
+        }
+        let error = last_error.unwrap_or_else(|| {
+            LoggerError::other(format!(
+                "every member of appender group '{}' is failing",
+                self.name
+            ))
+        });
+        self.exhausted = Some(error.to_string());
+        Err(error)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let mut result = Ok(());
+        for member in self.members.iter_mut().filter(|member| member.failed_at.is_none()) {
+            if let Err(e) = member.appender.flush() {
+                result = result.and(Err(e));
+            }
+        }
+        result
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn begin_batch(&mut self) {
+        for member in self.members.iter_mut().filter(|member| member.failed_at.is_none()) {
+            member.appender.begin_batch();
+        }
+    }
+
+    fn end_batch(&mut self) -> Result<()> {
+        let mut result = Ok(());
+        for member in self.members.iter_mut().filter(|member| member.failed_at.is_none()) {
+            if let Err(e) = member.appender.end_batch() {
+                result = result.and(Err(e));
+            }
+        }
+        result
+    }
+
+    fn set_formatter_config(&mut self, config: &FormatterConfig) -> bool {
+        let mut applied = false;
+        for member in &mut self.members {
+            applied |= member.appender.set_formatter_config(config);
+        }
+        applied
+    }
+
+    fn health(&self) -> HealthStatus {
+        let Some(active) = self.members.get(self.active) else {
+            return HealthStatus::Unhealthy("group has no members".to_string());
+        };
+        if let Some(ref error) = self.exhausted {
+            return HealthStatus::Unhealthy(format!("every member is failing: {}", error));
+        }
+        if self.active == 0 {
+            return active.appender.health();
+        }
+        let primary = &self.members[0];
+        HealthStatus::Degraded(format!(
+            "failed over from {} to {}: {}",
+            primary.appender.name(),
+            active.appender.name(),
+            primary.last_error.as_deref().unwrap_or("unknown error")
+        ))
+    }
+
+    fn take_event(&mut self) -> Option<AppenderEvent> {
+        self.events.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{FailingAppender, FaultHandle};
+
+    #[test]
+    fn test_fails_over_and_recovers() {
+        let primary = FailingAppender::on_trigger().with_name("primary");
+        let fallback = FailingAppender::on_trigger().with_name("fallback");
+        let (primary_faults, fallback_faults) = (primary.handle(), fallback.handle());
+        let mut group = AppenderGroup::new("shipping")
+            .member(primary)
+            .member(fallback)
+            .min_level(LogLevel::Info)
+            .retry_interval(Duration::ZERO);
+        let entry = |message: &str| LogEntry::new(LogLevel::Info, message.to_string());
+        let written = || {
+            let written = |faults: &FaultHandle| faults.calls() - faults.faults();
+            (written(&primary_faults), written(&fallback_faults))
+        };
+
+        group.append(&LogEntry::new(LogLevel::Debug, "skipped".to_string())).unwrap();
+        group.append(&entry("one")).unwrap();
+        assert_eq!(written(), (1, 0));
+        primary_faults.trigger();
+        group.append(&entry("two")).unwrap();
+        assert_eq!(written(), (1, 1));
+        assert_eq!(group.active_member(), Some("fallback"));
+        assert!(matches!(group.health(), HealthStatus::Degraded(_)));
+        assert_eq!(
+            group.take_event(),
+            Some(AppenderEvent::FailedOver {
+                from: "primary".to_string(),
+                to: "fallback".to_string(),
+            })
+        );
+
+        fallback_faults.trigger();
+        assert!(group.append(&entry("lost")).is_err());
+        assert!(matches!(group.health(), HealthStatus::Unhealthy(_)));
+
+        primary_faults.recover();
+        group.append(&entry("three")).unwrap();
+        assert_eq!(group.health(), HealthStatus::Healthy);
+        assert_eq!(written(), (2, 1));
+    }
+}
@@ -99,7 +99,7 @@ impl Appender for JsonAppender {
         // Add level
         json_obj.insert(
             "level".to_string(),
-            serde_json::Value::String(entry.level.to_str().to_string()),
+            serde_json::Value::String(entry.level_name().to_string()),
         );
 
         // Add message
@@ -111,18 +111,23 @@ impl Appender for JsonAppender {
         // Add thread info
         json_obj.insert(
             "thread_id".to_string(),
-            serde_json::Value::String(entry.thread_id.clone()),
+            serde_json::Value::String(entry.thread_id.to_string()),
         );
         if let Some(name) = &entry.thread_name {
             json_obj.insert(
                 "thread_name".to_string(),
-                serde_json::Value::String(name.clone()),
+                serde_json::Value::String(name.to_string()),
             );
         }
 
+        // Add tracing identifiers if present
+        if let Some(tracing) = &entry.tracing {
+            tracing.insert_json(&mut json_obj);
+        }
+
         // Add context fields if present
         if let Some(context) = &entry.context {
-            for (key, value) in context.fields() {
+            for (key, value) in context.field_map() {
                 json_obj.insert(key.clone(), value.to_json_value());
             }
         }
@@ -0,0 +1,454 @@
+//! Memory-mapped file appender
+//!
+//! [`MmapFileAppender`] copies each line into a preallocated, memory-mapped
+//! region of the file instead of calling `write()`, so appending an entry is
+//! a memory copy with no system call. The kernel writes the pages back on
+//! its own schedule; the appender starts writeback with `msync` at most once
+//! per sync interval, and [`sync`](MmapFileAppender::sync) waits for it.
+//! Lines are visible to other readers of the file as soon as they are
+//! appended.
+//!
+//! The file is extended a region at a time, so while it is open it ends in
+//! zero bytes past the last line. They are cut off when the appender rotates
+//! or is dropped, and skipped when a file left behind by a crash is opened
+//! again.
+//!
+//! Another process truncating the file while it is mapped makes the next
+//! write fault (`SIGBUS` on Unix), so log cleanup tools must not touch the
+//! file of a running appender. Use [`with_max_size`] to rotate instead.
+//!
+//! Requires the `mmap` feature.
+//!
+//! [`with_max_size`]: MmapFileAppender::with_max_size
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rust_logger_system::appenders::MmapFileAppender;
+//! use rust_logger_system::prelude::*;
+//! use std::time::Duration;
+//!
+//! let appender = MmapFileAppender::new("/var/log/app.log")?
+//!     .with_region_size(64 * 1024 * 1024)
+//!     .with_sync_interval(Duration::from_millis(500))
+//!     .with_max_size(1024 * 1024 * 1024);
+//!
+//! let logger = Logger::builder().appender(appender).async_mode(10_000).build();
+//! # Ok::<(), LoggerError>(())
+//! ```
+
+use super::file::format_line;
+use crate::core::{
+    Appender, AppenderEvent, Formatter, FormatterConfig, HealthStatus, LogEntry, LoggerError,
+    Result, TimestampFormat,
+};
+use memmap2::{MmapMut, MmapOptions};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Default size of the region mapped at a time (16 MiB)
+pub const DEFAULT_REGION_SIZE: usize = 16 * 1024 * 1024;
+
+/// Default time between `msync` calls
+pub const DEFAULT_SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Appender writing lines into a memory-mapped region of a file
+pub struct MmapFileAppender {
+    path: PathBuf,
+    /// `None` after the file could not be reopened
+    file: Option<File>,
+    map: Option<MmapMut>,
+    /// Offset in the file the map starts at
+    map_start: u64,
+    /// Bytes of the map holding lines
+    map_used: usize,
+    /// Bytes of the map already handed to `msync`
+    map_synced: usize,
+    region_size: usize,
+    sync_interval: Duration,
+    last_sync: Instant,
+    max_size: Option<u64>,
+    max_backups: usize,
+    config: FormatterConfig,
+    formatter: Option<Box<dyn Formatter>>,
+    rotated: Option<AppenderEvent>,
+}
+
+impl MmapFileAppender {
+    /// Open `path` for appending, creating it if needed
+    ///
+    /// Nothing is mapped until the first entry, so the builder methods
+    /// apply from the start.
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let (file, len) = Self::open(&path)?;
+        Ok(Self {
+            path,
+            file: Some(file),
+            map: None,
+            map_start: len,
+            map_used: 0,
+            map_synced: 0,
+            region_size: DEFAULT_REGION_SIZE,
+            sync_interval: DEFAULT_SYNC_INTERVAL,
+            last_sync: Instant::now(),
+            max_size: None,
+            max_backups: 5,
+            config: FormatterConfig::default(),
+            formatter: None,
+            rotated: None,
+        })
+    }
+
+    /// Set how much of the file is mapped, and preallocated, at a time
+    ///
+    /// A larger region remaps less often. A line longer than the region is
+    /// given a region of its own.
+    #[must_use]
+    pub fn with_region_size(mut self, bytes: usize) -> Self {
+        self.region_size = bytes.max(1);
+        self
+    }
+
+    /// Set the least time between two `msync` calls
+    ///
+    /// `Duration::ZERO` starts writeback on every append and flush.
+    #[must_use]
+    pub fn with_sync_interval(mut self, interval: Duration) -> Self {
+        self.sync_interval = interval;
+        self
+    }
+
+    /// Rotate the file before it grows past `bytes`
+    ///
+    /// The file is moved to `<name>.1`, older backups to `<name>.2` and so
+    /// on, up to [`with_max_backups`](Self::with_max_backups).
+    #[must_use]
+    pub fn with_max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Set how many rotated files are kept (default: 5)
+    #[must_use]
+    pub fn with_max_backups(mut self, count: usize) -> Self {
+        self.max_backups = count;
+        self
+    }
+
+    /// Set the formatting options for this appender
+    ///
+    /// The built-in layout is the same as [`FileAppender`]'s.
+    ///
+    /// [`FileAppender`]: super::FileAppender
+    #[must_use]
+    pub fn with_formatter_config(mut self, config: FormatterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the timestamp format for this appender
+    #[must_use]
+    pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.config.timestamp_format = format;
+        self
+    }
+
+    /// Replace the built-in layout with a custom [`Formatter`]
+    ///
+    /// The formatter's output is written followed by a newline.
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Box<dyn Formatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    /// Path of the file written to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Bytes of lines in the current file, excluding the preallocated rest
+    pub fn current_size(&self) -> u64 {
+        self.map_start + self.map_used as u64
+    }
+
+    /// Write the mapped lines back to the file and wait until they are
+    pub fn sync(&mut self) -> Result<()> {
+        if let Some(ref map) = self.map {
+            map.flush_range(0, self.map_used)?;
+            self.map_synced = self.map_used;
+        }
+        self.last_sync = Instant::now();
+        Ok(())
+    }
+
+    /// Open `path` and find where its lines end
+    fn open(path: &Path) -> Result<(File, u64)> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let len = Self::data_len(&mut file)?;
+        Ok((file, len))
+    }
+
+    /// Length of `file` without the zero bytes preallocated at its end
+    ///
+    /// Lines never contain zero bytes, so they end at the last other byte.
+    fn data_len(file: &mut File) -> io::Result<u64> {
+        let mut end = file.metadata()?.len();
+        let mut chunk = vec![0u8; 64 * 1024];
+        while end > 0 {
+            let start = end.saturating_sub(chunk.len() as u64);
+            let buffer = &mut chunk[..(end - start) as usize];
+            file.seek(SeekFrom::Start(start))?;
+            file.read_exact(buffer)?;
+            if let Some(last) = buffer.iter().rposition(|&byte| byte != 0) {
+                return Ok(start + last as u64 + 1);
+            }
+            end = start;
+        }
+        Ok(0)
+    }
+
+    /// Map a new region starting where the lines end, with room for at
+    /// least `needed` bytes
+    fn remap(&mut self, needed: usize) -> Result<()> {
+        self.sync_due(true)?;
+        self.map = None;
+
+        let start = self.current_size();
+        let len = self.region_size.max(needed);
+        let file = self
+            .file
+            .as_ref()
+            .ok_or_else(|| LoggerError::writer("Memory-mapped file is not open"))?;
+        file.set_len(start + len as u64)?;
+        // SAFETY: the file stays open and is only resized by this appender,
+        // after the map is dropped; see the module docs about other processes
+        let map = unsafe { MmapOptions::new().offset(start).len(len).map_mut(file)? };
+
+        self.map = Some(map);
+        self.map_start = start;
+        self.map_used = 0;
+        self.map_synced = 0;
+        Ok(())
+    }
+
+    /// Start writeback of the lines not yet synced, if the interval passed
+    fn sync_due(&mut self, force: bool) -> Result<()> {
+        if !force && self.last_sync.elapsed() < self.sync_interval {
+            return Ok(());
+        }
+        if let Some(ref map) = self.map {
+            if self.map_used > self.map_synced {
+                map.flush_async_range(self.map_synced, self.map_used - self.map_synced)?;
+                self.map_synced = self.map_used;
+            }
+        }
+        self.last_sync = Instant::now();
+        Ok(())
+    }
+
+    /// Unmap and cut the file down to its lines
+    fn close(&mut self) -> Result<()> {
+        let len = self.current_size();
+        if let Some(map) = self.map.take() {
+            map.flush()?;
+        }
+        self.map_start = len;
+        self.map_used = 0;
+        self.map_synced = 0;
+        if let Some(file) = self.file.take() {
+            file.set_len(len)?;
+        }
+        Ok(())
+    }
+
+    /// Reopen the file after it was closed or could not be rotated
+    fn reopen(&mut self) -> Result<()> {
+        let (file, len) = Self::open(&self.path)?;
+        self.file = Some(file);
+        self.map_start = len;
+        Ok(())
+    }
+
+    fn backup_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone();
+        let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("app.log");
+        path.set_file_name(format!("{}.{}", filename, index));
+        path
+    }
+
+    /// Move the file to the first backup and start a new one
+    fn rotate(&mut self) -> Result<()> {
+        let path = self.path.display().to_string();
+        let failed = |e: io::Error| LoggerError::file_rotation(path.clone(), e.to_string());
+        self.close()?;
+
+        if self.max_backups == 0 {
+            fs::remove_file(&self.path).map_err(failed)?;
+        } else {
+            match fs::remove_file(self.backup_path(self.max_backups)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(failed(e)),
+                _ => {}
+            }
+            for index in (1..self.max_backups).rev() {
+                let from = self.backup_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.backup_path(index + 1)).map_err(failed)?;
+                }
+            }
+            fs::rename(&self.path, self.backup_path(1)).map_err(failed)?;
+            self.rotated = Some(AppenderEvent::Rotated {
+                path: self.path.clone(),
+                backup: self.backup_path(1),
+            });
+        }
+
+        self.reopen()
+    }
+
+    /// Copy `line` into the map, rotating or remapping first as needed
+    fn write_line(&mut self, line: &[u8]) -> Result<()> {
+        if self.file.is_none() {
+            self.reopen()?;
+        }
+        let size = self.current_size();
+        if self.max_size.is_some_and(|max| size > 0 && size + line.len() as u64 > max) {
+            self.rotate()?;
+        }
+        let room = self.map.as_ref().map_or(0, |map| map.len() - self.map_used);
+        if room < line.len() {
+            self.remap(line.len())?;
+        }
+
+        let Some(ref mut map) = self.map else {
+            return Err(LoggerError::writer("Memory-mapped file is not open"));
+        };
+        map[self.map_used..self.map_used + line.len()].copy_from_slice(line);
+        self.map_used += line.len();
+        self.sync_due(false)
+    }
+}
+
+impl Appender for MmapFileAppender {
+    fn append(&mut self, entry: &LogEntry) -> Result<()> {
+        self.append_formatted(entry, &mut String::new())
+    }
+
+    fn append_formatted(&mut self, entry: &LogEntry, scratch: &mut String) -> Result<()> {
+        format_line(entry, &self.config, self.formatter.as_deref(), scratch);
+        self.write_line(scratch.as_bytes())
+    }
+
+    /// Lines are in the page cache once appended; this only starts
+    /// writeback if the sync interval has passed
+    fn flush(&mut self) -> Result<()> {
+        self.sync_due(false)
+    }
+
+    fn name(&self) -> &str {
+        "mmap_file"
+    }
+
+    fn set_formatter_config(&mut self, config: &FormatterConfig) -> bool {
+        self.config = config.clone();
+        true
+    }
+
+    fn take_event(&mut self) -> Option<AppenderEvent> {
+        self.rotated.take()
+    }
+
+    fn health(&self) -> HealthStatus {
+        if self.file.is_none() {
+            HealthStatus::Unhealthy(format!("{} is not open", self.path.display()))
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+}
+
+impl Drop for MmapFileAppender {
+    fn drop(&mut self) {
+        // Best effort: leave the file without its preallocated tail
+        let _ = self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{LogLevel, PatternLayout};
+    use tempfile::tempdir;
+
+    fn appender(path: &Path) -> MmapFileAppender {
+        MmapFileAppender::new(path)
+            .unwrap()
+            .with_formatter(Box::new(PatternLayout::parse("%m").unwrap()))
+    }
+
+    fn log(appender: &mut MmapFileAppender, message: &str) {
+        appender.append(&LogEntry::new(LogLevel::Info, message.to_string())).unwrap();
+    }
+
+    #[test]
+    fn test_remaps_and_trims_on_drop() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("mapped.log");
+        let mut mapped = appender(&path).with_region_size(16);
+
+        log(&mut mapped, "first");
+        // Preallocated while open
+        assert_eq!(fs::metadata(&path).unwrap().len(), 16);
+        log(&mut mapped, "second entry, longer than a region");
+        log(&mut mapped, "third");
+        mapped.sync().unwrap();
+        assert_eq!(mapped.current_size(), 47);
+        drop(mapped);
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "first\nsecond entry, longer than a region\nthird\n");
+    }
+
+    #[test]
+    fn test_reopens_after_crash() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("crashed.log");
+        let mut left_behind = b"before\n".to_vec();
+        left_behind.resize(1000, 0);
+        fs::write(&path, left_behind).unwrap();
+
+        let mut mapped = appender(&path);
+        assert_eq!(mapped.current_size(), 7);
+        log(&mut mapped, "after");
+        drop(mapped);
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "before\nafter\n");
+    }
+
+    #[test]
+    fn test_rotates_at_max_size() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("rotated.log");
+        let mut mapped = appender(&path).with_max_size(20).with_max_backups(2);
+
+        for i in 0..6 {
+            log(&mut mapped, &format!("message {}", i));
+        }
+        assert!(matches!(mapped.take_event(), Some(AppenderEvent::Rotated { .. })));
+        drop(mapped);
+
+        let read = |name: &str| fs::read_to_string(dir.path().join(name)).unwrap();
+        assert_eq!(read("rotated.log.2"), "message 0\nmessage 1\n");
+        assert_eq!(read("rotated.log.1"), "message 2\nmessage 3\n");
+        assert_eq!(read("rotated.log"), "message 4\nmessage 5\n");
+        assert!(!dir.path().join("rotated.log.3").exists());
+    }
+}
@@ -2,22 +2,38 @@
 
 pub mod console;
 pub mod file;
+pub mod group;
 pub mod json;
 pub mod network;
+pub mod network_ack;
+pub mod network_auth;
+pub mod network_retry;
+pub mod network_spool;
 pub mod rotating_file;
 
 #[cfg(feature = "async-appenders")]
 pub mod async_file;
 
-pub use console::ConsoleAppender;
+#[cfg(feature = "mmap")]
+pub mod mmap_file;
+
+pub use console::{ColorMode, ConsoleAppender, LineWidth, StreamPolicy};
 pub use file::FileAppender;
+pub use group::AppenderGroup;
 pub use json::JsonAppender;
 pub use network::NetworkAppender;
+pub use network_ack::{AckConfig, AckFrame};
+pub use network_auth::{CredentialProvider, NetworkAuth, Secret};
+pub use network_retry::{GiveUp, RetryPolicy};
+pub use network_spool::SpoolConfig;
 pub use rotating_file::{RotatingFileAppender, RotationPolicy, RotationStrategy};
 
 #[cfg(feature = "async-appenders")]
 pub use async_file::AsyncFileAppender;
 
+#[cfg(feature = "mmap")]
+pub use mmap_file::MmapFileAppender;
+
 // Re-export traits for backward compatibility
 pub use crate::core::Appender;
 #[cfg(feature = "async-appenders")]
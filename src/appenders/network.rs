@@ -3,10 +3,19 @@
 //! Sends log messages to a remote server over TCP.
 //! Useful for centralized logging in distributed systems.
 
-use crate::core::{Appender, LogEntry, LoggerError, Result};
-use std::io::Write;
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+use crate::core::{BinaryFormat, StructuredLogEntry};
+use super::network_ack::{AckConfig, AckState};
+use super::network_auth::NetworkAuth;
+use super::network_retry::{GiveUp, RetryPolicy};
+use super::network_spool::{DiskSpool, SpoolConfig};
+use crate::core::{Appender, Formatter, HealthStatus, LogEntry, LoggerError, Result};
+use std::io::{self, Read, Write};
 use std::net::{TcpStream, ToSocketAddrs};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// Upper bound of a single connection attempt
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Network appender that sends logs to a remote TCP server
 ///
@@ -27,6 +36,15 @@ pub struct NetworkAppender {
     stream: Option<TcpStream>,
     address: String,
     reconnect_on_error: bool,
+    retry: RetryPolicy,
+    formatter: Option<Box<dyn Formatter>>,
+    #[cfg(any(feature = "msgpack", feature = "cbor"))]
+    binary_format: Option<BinaryFormat>,
+    auth: Option<NetworkAuth>,
+    /// Whether the credentials were sent on the current connection
+    authenticated: bool,
+    spool: Option<DiskSpool>,
+    acks: Option<AckState>,
 }
 
 impl NetworkAppender {
@@ -54,89 +72,403 @@ impl NetworkAppender {
             stream: Some(stream),
             address,
             reconnect_on_error: true,
+            retry: RetryPolicy::default(),
+            formatter: None,
+            #[cfg(any(feature = "msgpack", feature = "cbor"))]
+            binary_format: None,
+            auth: None,
+            authenticated: false,
+            spool: None,
+            acks: None,
         })
     }
 
     /// Enable or disable automatic reconnection on errors
     ///
-    /// Default: enabled
+    /// Default: enabled, following [`RetryPolicy::default`]
     #[must_use]
     pub fn with_reconnect(mut self, enable: bool) -> Self {
         self.reconnect_on_error = enable;
         self
     }
 
-    /// Attempt to reconnect to the server
-    fn reconnect(&mut self) -> Result<()> {
-        let stream = TcpStream::connect(&self.address)?;
+    /// Reconnect following `policy` when a write fails
+    ///
+    /// Also re-enables reconnection if it was disabled with
+    /// [`with_reconnect`](Self::with_reconnect). See
+    /// [`super::network_retry`].
+    #[must_use]
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self.reconnect_on_error = true;
+        self
+    }
+
+    /// Replace the built-in layout with a custom [`Formatter`]
+    ///
+    /// The formatter's output is written followed by a newline.
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Box<dyn Formatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    /// Send entries as length-prefixed binary frames instead of text lines
+    ///
+    /// Any custom formatter is ignored while a binary format is set. See
+    /// [`crate::core::binary_format`] for the framing.
+    #[cfg(any(feature = "msgpack", feature = "cbor"))]
+    #[must_use]
+    pub fn with_binary_format(mut self, format: BinaryFormat) -> Self {
+        self.binary_format = Some(format);
+        self
+    }
+
+    /// Authenticate every connection with `auth`
+    ///
+    /// The credentials are sent before the first entry of each connection,
+    /// including reconnections; see [`super::network_auth`].
+    #[must_use]
+    pub fn with_auth(mut self, auth: NetworkAuth) -> Self {
+        self.auth = Some(auth);
+        self.authenticated = false;
+        self
+    }
+
+    /// Keep entries that cannot be delivered in a file and replay them later
+    ///
+    /// Entries left in the spool by a previous run are replayed too. With a
+    /// spool, undeliverable entries are spooled instead of following the
+    /// retry policy's [`GiveUp`]; see [`super::network_spool`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the spool file cannot be opened
+    pub fn with_spool(mut self, config: SpoolConfig) -> Result<Self> {
+        self.spool = Some(DiskSpool::open(config)?);
+        Ok(self)
+    }
+
+    /// Number of entries waiting in the spool
+    pub fn spooled(&self) -> u64 {
+        self.spool.as_ref().map_or(0, DiskSpool::len)
+    }
+
+    /// Send entries as sequenced frames and keep them until acknowledged
+    ///
+    /// Unacknowledged entries are resent after reconnecting. Once `window`
+    /// entries are unacknowledged, appends wait up to the ack timeout, and
+    /// [`flush`](Appender::flush) waits for every entry to be acknowledged.
+    /// The collector must speak the protocol described in
+    /// [`super::network_ack`].
+    #[must_use]
+    pub fn with_acks(mut self, config: AckConfig) -> Self {
+        self.acks = Some(AckState::new(config));
+        self
+    }
+
+    /// Number of entries sent but not yet acknowledged
+    pub fn unacknowledged(&self) -> usize {
+        self.acks.as_ref().map_or(0, AckState::len)
+    }
+
+    /// Write bytes, preceded by the credentials on a new connection
+    fn write_authenticated(&mut self, bytes: &[u8]) -> Result<std::io::Result<()>> {
+        let handshake = match self.auth {
+            Some(ref auth) if !self.authenticated => Some(auth.header_line()?),
+            _ => None,
+        };
+        let Some(ref mut stream) = self.stream else {
+            return Ok(Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "Network stream not connected",
+            )));
+        };
+        if let Some(handshake) = handshake {
+            if let Err(e) = stream.write_all(handshake.as_bytes()) {
+                return Ok(Err(e));
+            }
+            self.authenticated = true;
+        }
+        Ok(stream.write_all(bytes))
+    }
+
+    /// Send bytes, spooling them if they cannot be delivered
+    fn send(&mut self, bytes: &[u8]) -> Result<()> {
+        if self.spool.is_none() {
+            return match self.deliver(bytes) {
+                Err(_) if self.retry.give_up == GiveUp::Discard => Ok(()),
+                result => result,
+            };
+        }
+
+        // Entries already spooled go first
+        if self.replay_spool()? {
+            if self.deliver(bytes).is_ok() {
+                return Ok(());
+            }
+            if let Some(ref mut spool) = self.spool {
+                spool.record_failure();
+            }
+        }
+        let spooled = match self.spool {
+            Some(ref mut spool) => spool.push(bytes)?,
+            None => false,
+        };
+        if spooled {
+            Ok(())
+        } else {
+            Err(LoggerError::writer("Failed to send log: remote unreachable and spool full"))
+        }
+    }
+
+    /// Replay the spool if it is due, returning whether it is empty
+    fn replay_spool(&mut self) -> Result<bool> {
+        let Some(mut spool) = self.spool.take() else {
+            return Ok(true);
+        };
+        let result = if !spool.is_empty() && spool.replay_due() {
+            spool.replay(|bytes| self.deliver(bytes).is_ok())
+        } else {
+            Ok(())
+        };
+        let empty = spool.is_empty();
+        self.spool = Some(spool);
+        result?;
+        Ok(empty)
+    }
+
+    /// Deliver bytes, directly or through the acknowledgement window
+    fn deliver(&mut self, bytes: &[u8]) -> Result<()> {
+        if self.acks.is_none() {
+            return self.write_with_retry(|this| this.write_authenticated(bytes));
+        }
+
+        let window = self.acks.as_ref().map_or(1, |acks| acks.config.window);
+        let full = self.acks.as_ref().is_some_and(AckState::is_full);
+        if full && self.await_acks(window - 1).is_err() {
+            // The collector went quiet: resend over a new connection
+            self.stream = None;
+            self.transmit()?;
+            self.await_acks(window - 1)?;
+        }
+        if let Some(ref mut acks) = self.acks {
+            acks.push(bytes)?;
+        }
+        // If sending fails the entry stays queued and is resent after reconnecting
+        if self.transmit().is_ok() {
+            self.poll_acks();
+        }
+        Ok(())
+    }
+
+    /// Write the frames not yet sent on the current connection
+    fn transmit(&mut self) -> Result<()> {
+        self.write_with_retry(|this| {
+            let Some(ref acks) = this.acks else {
+                return Ok(Ok(()));
+            };
+            let frames = acks.unsent();
+            let result = this.write_authenticated(&frames)?;
+            if let (Ok(()), Some(ref mut acks)) = (&result, &mut this.acks) {
+                acks.mark_sent();
+            }
+            Ok(result)
+        })
+    }
+
+    /// Read acknowledgements until at most `until` entries are unacknowledged
+    fn await_acks(&mut self, until: usize) -> Result<()> {
+        let Some(timeout) = self.acks.as_ref().map(|acks| acks.config.ack_timeout) else {
+            return Ok(());
+        };
+        let deadline = Instant::now() + timeout;
+        let mut buf = [0u8; 512];
+        while self.unacknowledged() > until {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let (Some(ref mut stream), Some(ref mut acks)) = (&mut self.stream, &mut self.acks)
+            else {
+                return Err(LoggerError::writer("Network stream not connected"));
+            };
+            if remaining.is_zero() {
+                return Err(LoggerError::writer(format!(
+                    "{} entries not acknowledged within {:?}",
+                    acks.len(),
+                    timeout
+                )));
+            }
+            stream.set_read_timeout(Some(remaining))?;
+            let read = stream.read(&mut buf);
+            stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+            match read {
+                Ok(0) => {
+                    self.stream = None;
+                    return Err(LoggerError::writer("Collector closed the connection"));
+                }
+                Ok(n) => acks.receive(&buf[..n]),
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::WouldBlock
+                            | io::ErrorKind::TimedOut
+                            | io::ErrorKind::Interrupted
+                    ) => {}
+                Err(e) => {
+                    self.stream = None;
+                    return Err(e.into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Process acknowledgements that already arrived, without waiting
+    fn poll_acks(&mut self) {
+        let (Some(ref mut stream), Some(ref mut acks)) = (&mut self.stream, &mut self.acks) else {
+            return;
+        };
+        if stream.set_nonblocking(true).is_err() {
+            return;
+        }
+        let mut buf = [0u8; 512];
+        let connected = loop {
+            match stream.read(&mut buf) {
+                Ok(0) => break false,
+                Ok(n) => acks.receive(&buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => break e.kind() == io::ErrorKind::WouldBlock,
+            }
+        };
+        if !connected || stream.set_nonblocking(false).is_err() {
+            self.stream = None;
+        }
+    }
+
+    /// Run `write`, reconnecting and running it again on failure
+    fn write_with_retry(
+        &mut self,
+        mut write: impl FnMut(&mut Self) -> Result<io::Result<()>>,
+    ) -> Result<()> {
+        // Try to send log message
+        let error = match write(self)? {
+            Ok(()) => return Ok(()),
+            Err(e) => e,
+        };
+
+        // Connection lost
+        self.stream = None;
+        if !self.reconnect_on_error {
+            return Err(error.into());
+        }
+
+        let policy = self.retry;
+        let started = Instant::now();
+        let mut attempts = 0;
+        let mut last_error = None;
+        while attempts < policy.max_attempts {
+            let delay = policy.backoff(attempts);
+            let remaining = policy.budget.saturating_sub(started.elapsed());
+            if delay >= remaining {
+                break;
+            }
+            std::thread::sleep(delay);
+            attempts += 1;
+
+            let result = self
+                .reconnect(remaining - delay)
+                .and_then(|()| Ok(write(self)??));
+            match result {
+                // Resent the log message
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    self.stream = None;
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(LoggerError::writer(match last_error {
+            Some(last) => format!(
+                "Failed to send log: {} (reconnect attempts: {}, last error: {})",
+                error, attempts, last
+            ),
+            None => format!("Failed to send log: {} (retry budget exhausted)", error),
+        }))
+    }
+
+    /// Attempt to reconnect to the server within `timeout`
+    fn reconnect(&mut self, timeout: Duration) -> Result<()> {
+        let timeout = timeout.min(CONNECT_TIMEOUT);
+        let mut last_error = None;
+        let mut connected = None;
+        for addr in self.address.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&addr, timeout) {
+                Ok(stream) => {
+                    connected = Some(stream);
+                    break;
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        let stream = match (connected, last_error) {
+            (Some(stream), _) => stream,
+            (None, Some(e)) => return Err(e.into()),
+            (None, None) => {
+                return Err(LoggerError::writer(format!("No address for {}", self.address)))
+            }
+        };
         stream.set_write_timeout(Some(Duration::from_secs(5)))?;
         stream.set_read_timeout(Some(Duration::from_secs(5)))?;
         stream.set_nodelay(true)?;
 
         self.stream = Some(stream);
+        self.authenticated = false;
+        if let Some(ref mut acks) = self.acks {
+            acks.reset_connection();
+        }
         Ok(())
     }
 }
 
 impl Appender for NetworkAppender {
     fn append(&mut self, entry: &LogEntry) -> Result<()> {
-        // Format log entry
-        let mut message = format!(
-            "[{}] [{:5}] [{}] {}",
-            entry.timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
-            entry.level.to_str(),
-            entry.thread_name.as_ref().unwrap_or(&entry.thread_id),
-            entry.message
-        );
-
-        // Append context fields if present
-        if let Some(ref context) = entry.context {
-            message.push_str(" | ");
-            message.push_str(&context.to_string());
+        #[cfg(any(feature = "msgpack", feature = "cbor"))]
+        if let Some(format) = self.binary_format {
+            let frame = format.encode_frame(&StructuredLogEntry::from(entry))?;
+            return self.send(&frame);
         }
 
-        message.push('\n');
-
-        // Try to send log message
-        let result = if let Some(ref mut stream) = self.stream {
-            stream.write_all(message.as_bytes())
+        // Format log entry
+        let mut message = String::new();
+        if let Some(ref formatter) = self.formatter {
+            formatter.format(entry, &mut message);
         } else {
-            return Err(LoggerError::writer("Network stream not connected"));
-        };
+            message = format!(
+                "[{}] [{:5}] [{}] {}",
+                entry.timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
+                entry.level_name(),
+                entry.thread_name.as_ref().unwrap_or(&entry.thread_id),
+                entry.message
+            );
 
-        // Handle errors with optional reconnection
-        match result {
-            Ok(()) => Ok(()),
-            Err(e) => {
-                // Connection lost
-                self.stream = None;
-
-                if self.reconnect_on_error {
-                    // Try to reconnect and resend
-                    match self.reconnect() {
-                        Ok(()) => {
-                            // Resend the log message
-                            if let Some(ref mut stream) = self.stream {
-                                stream.write_all(message.as_bytes())?;
-                            }
-                            Ok(())
-                        }
-                        Err(reconnect_err) => {
-                            // Reconnection failed, return original error
-                            Err(LoggerError::writer(format!(
-                                "Failed to send log and reconnect: {} (reconnect: {})",
-                                e, reconnect_err
-                            )))
-                        }
-                    }
-                } else {
-                    Err(e.into())
-                }
+            // Append context fields if present
+            if let Some(ref context) = entry.context {
+                message.push_str(" | ");
+                message.push_str(&context.to_string());
             }
         }
+
+        message.push('\n');
+
+        self.send(message.as_bytes())
     }
 
     fn flush(&mut self) -> Result<()> {
+        self.replay_spool()?;
+        if self.unacknowledged() > 0 {
+            self.transmit()?;
+            self.await_acks(0)?;
+        }
         if let Some(ref mut stream) = self.stream {
             stream.flush()?;
         }
@@ -146,6 +478,17 @@ impl Appender for NetworkAppender {
     fn name(&self) -> &str {
         "network"
     }
+
+    fn health(&self) -> HealthStatus {
+        if self.stream.is_some() {
+            match self.spooled() {
+                0 => HealthStatus::Healthy,
+                spooled => HealthStatus::Degraded(format!("{} entries spooled", spooled)),
+            }
+        } else {
+            HealthStatus::Unhealthy(format!("not connected to {}", self.address))
+        }
+    }
 }
 
 impl Drop for NetworkAppender {
@@ -184,21 +527,178 @@ mod tests {
             stream: None,
             address: "127.0.0.1:9999".to_string(),
             reconnect_on_error: false,
+            retry: RetryPolicy::default(),
+            formatter: None,
+            #[cfg(any(feature = "msgpack", feature = "cbor"))]
+            binary_format: None,
+            auth: None,
+            authenticated: false,
+            spool: None,
+            acks: None,
         };
 
         let entry = LogEntry {
             level: LogLevel::Info,
             message: "test".to_string(),
             timestamp: Utc::now(),
-            file: Some("test.rs".to_string()),
+            file: Some("test.rs".into()),
             line: Some(42),
-            module_path: Some("test".to_string()),
-            thread_id: "main".to_string(),
-            thread_name: Some("main".to_string()),
+            module_path: Some("test".into()),
+            target: None,
+            thread_id: "main".into(),
+            thread_name: Some("main".into()),
             context: None,
+            tracing: None,
+            custom_level: None,
         };
 
         let result = appender.append(&entry);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_auth_sent_once_per_connection() {
+        use std::io::Read;
+        use std::net::TcpListener;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut received = String::new();
+            stream.read_to_string(&mut received).unwrap();
+            received
+        });
+
+        let refreshes = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&refreshes);
+        let mut appender = NetworkAppender::new(address)
+            .unwrap()
+            .with_auth(NetworkAuth::refreshing(move || {
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                Ok(NetworkAuth::bearer(format!("token-{}", n)))
+            }));
+        let entry = LogEntry::new(LogLevel::Info, "first".to_string());
+        appender.append(&entry).unwrap();
+        appender.append(&entry).unwrap();
+        drop(appender);
+
+        let received = server.join().unwrap();
+        assert!(received.starts_with("Authorization: Bearer token-0\n"));
+        assert_eq!(received.matches("Authorization").count(), 1);
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_retry_within_budget() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut appender = NetworkAppender::new(listener.local_addr().unwrap()).unwrap();
+        drop(listener);
+
+        let entry = LogEntry::new(LogLevel::Info, "lost".to_string());
+        let policy = RetryPolicy::new(1000)
+            .with_backoff(Duration::from_millis(10), Duration::from_millis(10))
+            .with_budget(Duration::from_millis(200));
+        appender.stream = None;
+        appender = appender.with_retry(policy);
+        let started = Instant::now();
+        assert!(appender.append(&entry).is_err());
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        appender = appender.with_retry(policy.with_give_up(GiveUp::Discard));
+        assert!(appender.append(&entry).is_ok());
+        assert!(!appender.health().is_healthy());
+    }
+
+    #[test]
+    fn test_acks_resend_unacknowledged() {
+        use super::super::network_ack::{read_frame, write_ack};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            // Acknowledge only the first of two entries, then hang up
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = stream.try_clone().unwrap();
+            let first = read_frame(&mut reader).unwrap().unwrap();
+            read_frame(&mut reader).unwrap().unwrap();
+            write_ack(&mut stream, first.sequence).unwrap();
+            drop((stream, reader));
+
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = stream.try_clone().unwrap();
+            let mut received = Vec::new();
+            while let Some(frame) = read_frame(&mut reader).unwrap() {
+                received.push(frame.sequence);
+                if frame.sequence == 3 {
+                    write_ack(&mut stream, 3).unwrap();
+                }
+            }
+            received
+        });
+
+        let mut appender = NetworkAppender::new(address)
+            .unwrap()
+            .with_retry(RetryPolicy::new(1))
+            .with_acks(AckConfig::new(10).with_ack_timeout(Duration::from_secs(1)));
+        for message in ["a", "b"] {
+            appender.append(&LogEntry::new(LogLevel::Info, message.to_string())).unwrap();
+        }
+        assert!(appender.flush().is_err());
+        assert_eq!(appender.unacknowledged(), 1);
+
+        appender.append(&LogEntry::new(LogLevel::Info, "c".to_string())).unwrap();
+        appender.flush().unwrap();
+        assert_eq!(appender.unacknowledged(), 0);
+        drop(appender);
+
+        assert_eq!(server.join().unwrap(), [2, 3]);
+    }
+
+    #[test]
+    fn test_spool_replayed_in_order() {
+        use std::io::Read;
+        use std::net::TcpListener;
+
+        let dir = tempfile::tempdir().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let mut appender = NetworkAppender::new(address)
+            .unwrap()
+            .with_retry(RetryPolicy::new(1).with_budget(Duration::from_millis(200)))
+            .with_spool(
+                SpoolConfig::new(dir.path().join("net.spool"))
+                    .with_replay_interval(Duration::ZERO),
+            )
+            .unwrap();
+        drop(listener);
+        appender.stream = None;
+
+        for message in ["one", "two"] {
+            let entry = LogEntry::new(LogLevel::Info, message.to_string());
+            appender.append(&entry).unwrap();
+        }
+        assert_eq!(appender.spooled(), 2);
+
+        let listener = TcpListener::bind(address).unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut received = String::new();
+            stream.read_to_string(&mut received).unwrap();
+            received
+        });
+        appender.append(&LogEntry::new(LogLevel::Info, "three".to_string())).unwrap();
+        assert_eq!(appender.spooled(), 0);
+        drop(appender);
+
+        let received = server.join().unwrap();
+        let lines: Vec<&str> = received.lines().collect();
+        assert_eq!(lines.len(), 3);
+        for (line, message) in lines.iter().zip(["one", "two", "three"]) {
+            assert!(line.ends_with(message));
+        }
+    }
 }
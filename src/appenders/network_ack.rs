@@ -0,0 +1,256 @@
+//! Acknowledged delivery for network log shipping
+//!
+//! With [`NetworkAppender::with_acks`](super::NetworkAppender::with_acks),
+//! entries are sent as frames carrying a sequence number, and the collector
+//! confirms them. An entry is only discarded once it is acknowledged; until
+//! then it is kept in memory and sent again after a reconnection, giving
+//! at-least-once delivery.
+//!
+//! # Protocol
+//!
+//! All integers are big-endian. The appender sends one frame per entry:
+//!
+//! | Field | Size | |
+//! |-------|------|-|
+//! | length | 4 | length of the payload |
+//! | session | 8 | random, fixed for the lifetime of the appender |
+//! | sequence | 8 | 1, 2, 3, ... within the session |
+//! | payload | length | the formatted entry |
+//!
+//! The collector answers with the 8-byte sequence number of the last frame
+//! it has stored; an acknowledgement covers every earlier frame of the
+//! session. Frames may arrive more than once after a reconnection, so the
+//! collector keeps the highest sequence number of each session and ignores
+//! (but still acknowledges) frames at or below it. If authentication is
+//! configured, its header line precedes the first frame of a connection.
+//!
+//! [`read_frame`] and [`write_ack`] implement the collector side; see the
+//! `ack_receiver` example for a complete collector.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rust_logger_system::appenders::{AckConfig, NetworkAppender};
+//! use std::time::Duration;
+//!
+//! let appender = NetworkAppender::new("audit.example.com:5141")
+//!     .unwrap()
+//!     .with_acks(AckConfig::new(512).with_ack_timeout(Duration::from_secs(2)));
+//! ```
+
+use std::collections::VecDeque;
+use std::io::{self, ErrorKind, Read, Write};
+use std::time::Duration;
+
+/// Largest payload [`read_frame`] accepts
+pub const MAX_PAYLOAD_LEN: usize = 16 * 1024 * 1024;
+
+/// Length, session and sequence number
+const FRAME_HEADER_LEN: usize = 20;
+
+/// Settings of acknowledged delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AckConfig {
+    /// Unacknowledged entries kept before appends wait for acknowledgements
+    pub window: usize,
+
+    /// Time to wait for an acknowledgement when the window is full or on
+    /// flush
+    pub ack_timeout: Duration,
+}
+
+impl AckConfig {
+    /// Keep up to `window` unacknowledged entries
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Set the time to wait for an acknowledgement
+    #[must_use]
+    pub fn with_ack_timeout(mut self, timeout: Duration) -> Self {
+        self.ack_timeout = timeout;
+        self
+    }
+}
+
+impl Default for AckConfig {
+    /// A window of 1024 entries and a 5 second timeout
+    fn default() -> Self {
+        Self {
+            window: 1024,
+            ack_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Frame received by a collector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AckFrame {
+    /// Session of the sending appender
+    pub session: u64,
+    /// Sequence number within the session
+    pub sequence: u64,
+    /// Formatted entry
+    pub payload: Vec<u8>,
+}
+
+/// Read the next frame, or `None` at the end of the stream
+///
+/// # Errors
+///
+/// Returns an error for truncated frames and payloads larger than
+/// [`MAX_PAYLOAD_LEN`].
+pub fn read_frame(reader: &mut impl Read) -> io::Result<Option<AckFrame>> {
+    let mut header = [0u8; FRAME_HEADER_LEN];
+    match reader.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let (len, rest) = header.split_at(4);
+    let (session, sequence) = rest.split_at(8);
+    let len = u32::from_be_bytes(len.try_into().unwrap_or_default()) as usize;
+    if len > MAX_PAYLOAD_LEN {
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            format!("frame of {} bytes exceeds {} bytes", len, MAX_PAYLOAD_LEN),
+        ));
+    }
+    let mut payload = vec![0u8; len];
+    reader.read_exact(&mut payload)?;
+    Ok(Some(AckFrame {
+        session: u64::from_be_bytes(session.try_into().unwrap_or_default()),
+        sequence: u64::from_be_bytes(sequence.try_into().unwrap_or_default()),
+        payload,
+    }))
+}
+
+/// Acknowledge every frame up to and including `sequence`
+pub fn write_ack(writer: &mut impl Write, sequence: u64) -> io::Result<()> {
+    writer.write_all(&sequence.to_be_bytes())?;
+    writer.flush()
+}
+
+/// Sent but unacknowledged frames of an appender
+#[derive(Debug)]
+pub(crate) struct AckState {
+    pub(crate) config: AckConfig,
+    session: u64,
+    next_sequence: u64,
+    /// Frames by sequence number, oldest first
+    unacked: VecDeque<(u64, Vec<u8>)>,
+    /// Leading frames of `unacked` written on the current connection
+    sent: usize,
+    /// Bytes of a partially received acknowledgement
+    partial: Vec<u8>,
+}
+
+impl AckState {
+    pub(crate) fn new(config: AckConfig) -> Self {
+        Self {
+            config,
+            session: rand::random(),
+            next_sequence: 1,
+            unacked: VecDeque::new(),
+            sent: 0,
+            partial: Vec::with_capacity(8),
+        }
+    }
+
+    /// Unacknowledged entries
+    pub(crate) fn len(&self) -> usize {
+        self.unacked.len()
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.unacked.len() >= self.config.window
+    }
+
+    /// Frame a payload and queue it for sending
+    pub(crate) fn push(&mut self, payload: &[u8]) -> io::Result<()> {
+        let len = u32::try_from(payload.len())
+            .ok()
+            .filter(|&len| len as usize <= MAX_PAYLOAD_LEN)
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, "entry too large for frame"))?;
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        let mut frame = Vec::with_capacity(FRAME_HEADER_LEN + payload.len());
+        frame.extend_from_slice(&len.to_be_bytes());
+        frame.extend_from_slice(&self.session.to_be_bytes());
+        frame.extend_from_slice(&sequence.to_be_bytes());
+        frame.extend_from_slice(payload);
+        self.unacked.push_back((sequence, frame));
+        Ok(())
+    }
+
+    /// Frames not yet written on the current connection
+    pub(crate) fn unsent(&self) -> Vec<u8> {
+        self.unacked.iter().skip(self.sent).flat_map(|(_, frame)| frame.iter().copied()).collect()
+    }
+
+    pub(crate) fn mark_sent(&mut self) {
+        self.sent = self.unacked.len();
+    }
+
+    /// Resend every unacknowledged frame on the next connection
+    pub(crate) fn reset_connection(&mut self) {
+        self.sent = 0;
+        self.partial.clear();
+    }
+
+    /// Process received acknowledgement bytes
+    pub(crate) fn receive(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            let take = (8 - self.partial.len()).min(bytes.len());
+            self.partial.extend_from_slice(&bytes[..take]);
+            bytes = &bytes[take..];
+            if self.partial.len() == 8 {
+                let acked = u64::from_be_bytes(self.partial[..].try_into().unwrap_or_default());
+                self.partial.clear();
+                while self.unacked.front().is_some_and(|(sequence, _)| *sequence <= acked) {
+                    self.unacked.pop_front();
+                    self.sent = self.sent.saturating_sub(1);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_and_acks() {
+        let mut state = AckState::new(AckConfig::new(2));
+        state.push(b"one").unwrap();
+        state.push(b"two").unwrap();
+        assert!(state.is_full());
+
+        let bytes = state.unsent();
+        state.mark_sent();
+        assert!(state.unsent().is_empty());
+        let mut reader = &bytes[..];
+        let first = read_frame(&mut reader).unwrap().unwrap();
+        let second = read_frame(&mut reader).unwrap().unwrap();
+        assert!(read_frame(&mut reader).unwrap().is_none());
+        assert_eq!((first.sequence, first.payload.as_slice()), (1, &b"one"[..]));
+        assert_eq!((second.sequence, second.payload.as_slice()), (2, &b"two"[..]));
+        assert_eq!(first.session, second.session);
+
+        // An acknowledgement split across reads
+        let mut ack = Vec::new();
+        write_ack(&mut ack, 1).unwrap();
+        state.receive(&ack[..3]);
+        assert_eq!(state.len(), 2);
+        state.receive(&ack[3..]);
+        assert_eq!(state.len(), 1);
+
+        state.reset_connection();
+        let mut reader = &state.unsent()[..];
+        assert_eq!(read_frame(&mut reader).unwrap().unwrap().sequence, 2);
+    }
+}
@@ -0,0 +1,173 @@
+//! Reconnection policy for network log shipping
+//!
+//! When a write fails, [`NetworkAppender`](super::NetworkAppender) drops the
+//! connection and follows its [`RetryPolicy`]: it reconnects up to
+//! `max_attempts` times, waiting an exponentially growing, jittered delay
+//! between attempts, and resends the entry once connected. The first attempt
+//! is made immediately.
+//!
+//! Appends run on the logger's batch loop, so all attempts for one entry,
+//! connection timeouts and delays included, stop once `budget` is spent. What
+//! happens to the entry then is decided by [`GiveUp`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rust_logger_system::appenders::{GiveUp, NetworkAppender, RetryPolicy};
+//! use std::time::Duration;
+//!
+//! let appender = NetworkAppender::new("logs.example.com:5140")
+//!     .unwrap()
+//!     .with_retry(
+//!         RetryPolicy::new(5)
+//!             .with_backoff(Duration::from_millis(20), Duration::from_millis(500))
+//!             .with_budget(Duration::from_secs(1))
+//!             .with_give_up(GiveUp::Discard),
+//!     );
+//! ```
+
+use rand::Rng;
+use std::time::Duration;
+
+/// What to do with an entry that could not be sent within the retry policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GiveUp {
+    /// Return the error, counting the entry as failed (default)
+    #[default]
+    Error,
+
+    /// Discard the entry and report success
+    ///
+    /// Keeps an unreachable server from being reported as failing appends on
+    /// every entry; [`Appender::health`](crate::core::Appender::health)
+    /// still reports the appender as unhealthy while disconnected.
+    Discard,
+}
+
+/// Reconnection policy of a [`NetworkAppender`](super::NetworkAppender)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Reconnection attempts per failed entry
+    pub max_attempts: u32,
+
+    /// Delay before the second attempt
+    pub initial_backoff: Duration,
+
+    /// Upper bound of the delay between attempts
+    pub max_backoff: Duration,
+
+    /// Factor applied to the delay after every attempt
+    pub multiplier: f64,
+
+    /// Random variation of each delay, as a fraction of it (0.0 - 1.0)
+    pub jitter: f64,
+
+    /// Total time the attempts for one entry may take
+    pub budget: Duration,
+
+    /// What to do with the entry when all attempts failed
+    pub give_up: GiveUp,
+}
+
+impl RetryPolicy {
+    /// Policy making up to `max_attempts` attempts with the default timing
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Self::default()
+        }
+    }
+
+    /// Policy that never reconnects
+    pub fn none() -> Self {
+        Self::new(0)
+    }
+
+    /// Set the first delay and the upper bound of delays
+    #[must_use]
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Set the factor applied to the delay after every attempt
+    #[must_use]
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Set the random variation of delays, as a fraction of them
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the total time the attempts for one entry may take
+    #[must_use]
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Set what happens to an entry when all attempts failed
+    #[must_use]
+    pub fn with_give_up(mut self, give_up: GiveUp) -> Self {
+        self.give_up = give_up;
+        self
+    }
+
+    /// Delay before the attempt with index `attempt` (0-based), jitter included
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        if attempt == 0 {
+            return Duration::ZERO;
+        }
+        let exponent = i32::try_from(attempt - 1).unwrap_or(i32::MAX);
+        let base = (self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent))
+            .min(self.max_backoff.as_secs_f64());
+        let factor = if self.jitter > 0.0 {
+            rand::thread_rng().gen_range(1.0 - self.jitter..=1.0 + self.jitter)
+        } else {
+            1.0
+        };
+        Duration::from_secs_f64((base * factor).max(0.0))
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts 50 ms apart, doubling up to 1 s with 20% jitter,
+    /// within 500 ms per entry
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_secs(1),
+            multiplier: 2.0,
+            jitter: 0.2,
+            budget: Duration::from_millis(500),
+            give_up: GiveUp::Error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new(10)
+            .with_backoff(Duration::from_millis(10), Duration::from_millis(50))
+            .with_jitter(0.0);
+        let delays: Vec<u128> = (0..5).map(|attempt| policy.backoff(attempt).as_millis()).collect();
+        assert_eq!(delays, [0, 10, 20, 40, 50]);
+
+        let jittered = RetryPolicy::default().with_jitter(0.5);
+        for _ in 0..100 {
+            let delay = jittered.backoff(1);
+            assert!(delay >= Duration::from_millis(25) && delay <= Duration::from_millis(75));
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Disk-backed spool for network log shipping
+//!
+//! With [`NetworkAppender::with_spool`](super::NetworkAppender::with_spool),
+//! entries that cannot be delivered are appended to a file instead of being
+//! lost. While the spool holds entries, new entries are spooled behind them
+//! so order is kept; every `replay_interval` the appender tries to deliver
+//! the spooled entries, oldest first, and resumes direct sending once the
+//! spool is empty.
+//!
+//! Each record is framed as a 4-byte marker, the payload length and a CRC-32
+//! of the payload. A torn write or a damaged region only loses the affected
+//! records: reading skips ahead to the next valid marker and reports the
+//! skipped bytes as [`DiagnosticEvent::SpoolCorrupted`].
+//!
+//! When the spool reaches `max_bytes`, further entries fail as if there were
+//! no spool. Delivery is at least once: entries replayed just before a crash
+//! may be sent again after a restart.
+//!
+//! [`DiagnosticEvent::SpoolCorrupted`]: crate::core::DiagnosticEvent::SpoolCorrupted
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rust_logger_system::appenders::{NetworkAppender, SpoolConfig};
+//!
+//! let appender = NetworkAppender::new("logs.example.com:5140")
+//!     .unwrap()
+//!     .with_spool(SpoolConfig::new("/var/spool/myapp/logs.spool").with_max_bytes(64 << 20))
+//!     .expect("Failed to open spool");
+//! ```
+
+use crate::core::diagnostics::{self, DiagnosticEvent};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Marker starting every record
+const MAGIC: &[u8; 4] = b"LSP1";
+
+/// Marker, payload length and CRC-32
+const HEADER_LEN: usize = 12;
+
+/// Settings of a network spool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpoolConfig {
+    /// File the entries are spooled to
+    pub path: PathBuf,
+
+    /// Largest size of the spool file, framing included
+    pub max_bytes: u64,
+
+    /// Time between attempts to replay the spool
+    pub replay_interval: Duration,
+}
+
+impl SpoolConfig {
+    /// Spool to `path`, holding up to 16 MiB and replaying every second
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_bytes: 16 << 20,
+            replay_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Set the largest size of the spool file
+    #[must_use]
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Set the time between attempts to replay the spool
+    #[must_use]
+    pub fn with_replay_interval(mut self, interval: Duration) -> Self {
+        self.replay_interval = interval;
+        self
+    }
+}
+
+/// On-disk queue of undelivered payloads
+#[derive(Debug)]
+pub(crate) struct DiskSpool {
+    config: SpoolConfig,
+    file: File,
+    /// Size of the file
+    size: u64,
+    /// Records in the file
+    entries: u64,
+    /// When delivery last failed
+    last_failure: Option<Instant>,
+}
+
+impl DiskSpool {
+    /// Open the spool, keeping records left by a previous run
+    pub(crate) fn open(config: SpoolConfig) -> io::Result<Self> {
+        if let Some(parent) = config.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&config.path)?;
+        let data = fs::read(&config.path)?;
+        let entries = Frames::new(&data, &config.path).count() as u64;
+        Ok(Self {
+            size: data.len() as u64,
+            config,
+            file,
+            entries,
+            last_failure: None,
+        })
+    }
+
+    /// Records waiting to be delivered
+    pub(crate) fn len(&self) -> u64 {
+        self.entries
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries == 0
+    }
+
+    /// Append a payload, returning `false` if the spool is full
+    pub(crate) fn push(&mut self, payload: &[u8]) -> io::Result<bool> {
+        let size = (HEADER_LEN + payload.len()) as u64;
+        let Ok(payload_len) = u32::try_from(payload.len()) else {
+            return Ok(false);
+        };
+        if self.size + size > self.config.max_bytes {
+            return Ok(false);
+        }
+        let mut record = Vec::with_capacity(size as usize);
+        record.extend_from_slice(MAGIC);
+        record.extend_from_slice(&payload_len.to_le_bytes());
+        record.extend_from_slice(&crc32(payload).to_le_bytes());
+        record.extend_from_slice(payload);
+        self.file.write_all(&record)?;
+        self.size += size;
+        self.entries += 1;
+        Ok(true)
+    }
+
+    /// Remember that delivery failed, delaying the next replay
+    pub(crate) fn record_failure(&mut self) {
+        self.last_failure = Some(Instant::now());
+    }
+
+    /// Whether the replay interval passed since delivery last failed
+    pub(crate) fn replay_due(&self) -> bool {
+        self.last_failure
+            .is_none_or(|failed| failed.elapsed() >= self.config.replay_interval)
+    }
+
+    /// Deliver records oldest first until `deliver` fails or none are left
+    ///
+    /// Delivered records are removed from the file.
+    pub(crate) fn replay(&mut self, mut deliver: impl FnMut(&[u8]) -> bool) -> io::Result<()> {
+        let data = fs::read(&self.config.path)?;
+        let mut frames = Frames::new(&data, &self.config.path);
+        let mut delivered_to = 0;
+        let mut failed = false;
+        while let Some(payload) = frames.next() {
+            if !deliver(payload) {
+                failed = true;
+                break;
+            }
+            delivered_to = frames.pos;
+            self.entries -= 1;
+        }
+        if failed {
+            self.record_failure();
+        } else {
+            // Nothing left but corrupted bytes, if anything
+            delivered_to = data.len();
+            self.entries = 0;
+        }
+        self.compact(&data[delivered_to..])
+    }
+
+    /// Replace the file contents with the undelivered `rest`
+    fn compact(&mut self, rest: &[u8]) -> io::Result<()> {
+        if rest.len() as u64 == self.size {
+            return Ok(());
+        }
+        if rest.is_empty() {
+            self.file.set_len(0)?;
+        } else {
+            let temp = self.config.path.with_extension("spool.tmp");
+            fs::write(&temp, rest)?;
+            fs::rename(&temp, &self.config.path)?;
+            self.file = OpenOptions::new().append(true).open(&self.config.path)?;
+        }
+        self.size = rest.len() as u64;
+        Ok(())
+    }
+}
+
+/// Valid payloads of spool data, skipping corrupted regions
+struct Frames<'a> {
+    data: &'a [u8],
+    path: &'a Path,
+    /// Offset after the last record returned
+    pos: usize,
+}
+
+impl<'a> Frames<'a> {
+    fn new(data: &'a [u8], path: &'a Path) -> Self {
+        Self { data, path, pos: 0 }
+    }
+
+    /// Payload of a valid record starting at `at`
+    fn record_at(&self, at: usize) -> Option<&'a [u8]> {
+        let header = self.data.get(at..at + HEADER_LEN)?;
+        if &header[..4] != MAGIC {
+            return None;
+        }
+        let len = u32::from_le_bytes(header[4..8].try_into().ok()?) as usize;
+        let crc = u32::from_le_bytes(header[8..12].try_into().ok()?);
+        let payload = self.data.get(at + HEADER_LEN..at + HEADER_LEN + len)?;
+        (crc32(payload) == crc).then_some(payload)
+    }
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let mut at = self.pos;
+        while at < self.data.len() {
+            if let Some(payload) = self.record_at(at) {
+                if at > self.pos {
+                    report_corruption(self.path, at - self.pos);
+                }
+                self.pos = at + HEADER_LEN + payload.len();
+                return Some(payload);
+            }
+            // Resynchronize on the next marker
+            at = self.data[at + 1..]
+                .windows(MAGIC.len())
+                .position(|window| window == MAGIC)
+                .map_or(self.data.len(), |offset| at + 1 + offset);
+        }
+        if at > self.pos {
+            report_corruption(self.path, at - self.pos);
+            self.pos = at;
+        }
+        None
+    }
+}
+
+fn report_corruption(path: &Path, bytes: usize) {
+    diagnostics::report(DiagnosticEvent::SpoolCorrupted {
+        path: path.to_path_buf(),
+        bytes: bytes as u64,
+    });
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(bytes);
+    crc.sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_in_order_across_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SpoolConfig::new(dir.path().join("net.spool"));
+        let mut spool = DiskSpool::open(config.clone()).unwrap();
+        for payload in ["one", "two", "three"] {
+            assert!(spool.push(payload.as_bytes()).unwrap());
+        }
+        drop(spool);
+
+        let mut spool = DiskSpool::open(config).unwrap();
+        assert_eq!(spool.len(), 3);
+        let mut delivered = Vec::new();
+        spool
+            .replay(|payload| {
+                if payload == b"three" {
+                    return false;
+                }
+                delivered.push(payload.to_vec());
+                true
+            })
+            .unwrap();
+        assert_eq!(delivered, [b"one".to_vec(), b"two".to_vec()]);
+        assert_eq!(spool.len(), 1);
+        assert!(!spool.replay_due());
+
+        spool.replay(|payload| payload == b"three").unwrap();
+        assert!(spool.is_empty());
+        assert_eq!(fs::metadata(dir.path().join("net.spool")).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_skips_corruption_and_caps_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("net.spool");
+        let mut spool = DiskSpool::open(SpoolConfig::new(&path).with_max_bytes(60)).unwrap();
+        assert!(spool.push(b"first").unwrap());
+        assert!(spool.push(b"second").unwrap());
+        assert!(spool.push(b"third").unwrap());
+        assert!(!spool.push(b"too much").unwrap());
+        drop(spool);
+
+        // Damage the second record and tear the end of the file
+        let mut data = fs::read(&path).unwrap();
+        data[HEADER_LEN + 5 + HEADER_LEN] ^= 0xff;
+        data.extend_from_slice(&MAGIC[..]);
+        fs::write(&path, &data).unwrap();
+
+        let mut spool = DiskSpool::open(SpoolConfig::new(&path)).unwrap();
+        assert_eq!(spool.len(), 2);
+        let mut delivered = Vec::new();
+        spool
+            .replay(|payload| {
+                delivered.push(payload.to_vec());
+                true
+            })
+            .unwrap();
+        assert_eq!(delivered, [b"first".to_vec(), b"third".to_vec()]);
+        assert!(spool.is_empty());
+    }
+}
@@ -4,13 +4,21 @@
 //! based on various strategies including size, time, daily, hourly, or hybrid.
 
 use crate::core::appender::Appender;
+use crate::core::clock::{self, Clock};
+use crate::core::diagnostics::{self, DiagnosticEvent};
 use crate::core::error::{LoggerError, Result};
+use crate::core::events::AppenderEvent;
+use crate::core::formatter::Formatter;
+use crate::core::health::HealthStatus;
 use crate::core::log_entry::LogEntry;
 use crate::core::timestamp::TimestampFormat;
+use super::file::LineBatch;
 use chrono::{DateTime, Local, Timelike};
 use std::fs::{self, File, OpenOptions};
+use std::fmt::Write as _;
 use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 /// Rotation strategy defining when to rotate log files
@@ -233,6 +241,14 @@ pub struct RotatingFileAppender {
     deletion_failure_count: usize,
     /// Timestamp format for log entries
     timestamp_format: TimestampFormat,
+    /// Custom formatter replacing the built-in layout
+    formatter: Option<Box<dyn Formatter>>,
+    /// Rotation not yet passed on to the logger
+    rotated: Option<AppenderEvent>,
+    /// Lines of the current batch, counted in `current_size` already
+    batch: LineBatch,
+    /// Time source for time-based strategies
+    clock: Arc<dyn Clock>,
 }
 
 impl RotatingFileAppender {
@@ -294,9 +310,27 @@ impl RotatingFileAppender {
             last_rotation,
             deletion_failure_count: 0,
             timestamp_format: TimestampFormat::default(),
+            formatter: None,
+            rotated: None,
+            batch: LineBatch::new(),
+            clock: clock::system_clock(),
         })
     }
 
+    /// Read the time for time-based strategies from `clock`
+    ///
+    /// An empty file counts as rotated at the clock's current time, so a
+    /// [`ManualClock`](crate::core::ManualClock) starting at any date can be
+    /// used from the first entry.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        if self.current_size == 0 {
+            self.last_rotation = clock.system_time();
+        }
+        self.clock = clock;
+        self
+    }
+
     /// Set the timestamp format for this appender
     ///
     /// # Examples
@@ -322,6 +356,25 @@ impl RotatingFileAppender {
         self
     }
 
+    /// Replace the built-in layout with a custom [`Formatter`]
+    ///
+    /// The formatter's output is written followed by a newline.
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Box<dyn Formatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    /// Collect the lines of each batch of the async worker into one write
+    ///
+    /// Enabled by default. Held lines are written before the file rotates,
+    /// so they land in the file their size was counted against.
+    #[must_use]
+    pub fn with_coalesced_writes(mut self, enabled: bool) -> Self {
+        self.batch.set_enabled(enabled);
+        self
+    }
+
     /// Check if rotation is needed based on the configured strategy
     fn should_rotate(&self) -> bool {
         match &self.policy.strategy {
@@ -330,14 +383,16 @@ impl RotatingFileAppender {
             RotationStrategy::Size { max_bytes } => self.current_size >= *max_bytes,
 
             RotationStrategy::Time { interval } => {
-                let elapsed = SystemTime::now()
+                let elapsed = self
+                    .clock
+                    .system_time()
                     .duration_since(self.last_rotation)
                     .unwrap_or(Duration::ZERO);
                 elapsed >= *interval
             }
 
             RotationStrategy::Daily { hour } => {
-                let now: DateTime<Local> = SystemTime::now().into();
+                let now: DateTime<Local> = self.clock.system_time().into();
                 let last: DateTime<Local> = self.last_rotation.into();
 
                 // Rotate if we're on a different day and past the target hour
@@ -345,7 +400,9 @@ impl RotatingFileAppender {
             }
 
             RotationStrategy::Hourly => {
-                let elapsed = SystemTime::now()
+                let elapsed = self
+                    .clock
+                    .system_time()
                     .duration_since(self.last_rotation)
                     .unwrap_or(Duration::ZERO);
                 elapsed >= Duration::from_secs(3600)
@@ -353,7 +410,9 @@ impl RotatingFileAppender {
 
             RotationStrategy::Hybrid { max_bytes, interval } => {
                 let size_exceeded = self.current_size >= *max_bytes;
-                let time_exceeded = SystemTime::now()
+                let time_exceeded = self
+                    .clock
+                    .system_time()
                     .duration_since(self.last_rotation)
                     .unwrap_or(Duration::ZERO)
                     >= *interval;
@@ -367,7 +426,7 @@ impl RotatingFileAppender {
         // Flush and close current file
         // Explicitly drop writer to release file handle immediately
         if let Some(mut writer) = self.writer.take() {
-            writer.flush().map_err(|e| {
+            self.batch.write_pending(&mut writer).and_then(|()| writer.flush()).map_err(|e| {
                 LoggerError::file_rotation(
                     self.base_path.display().to_string(),
                     format!("Failed to flush before rotation: {}", e),
@@ -388,25 +447,29 @@ impl RotatingFileAppender {
         if oldest_compressed.exists() {
             if let Err(e) = fs::remove_file(&oldest_compressed) {
                 deletion_failed = true;
-                eprintln!(
-                    "[WARN] Failed to remove oldest compressed backup {}: {} (failure #{}/{})",
-                    oldest_compressed.display(),
-                    e,
-                    self.deletion_failure_count + 1,
-                    MAX_DELETION_FAILURES
-                );
+                diagnostics::report(DiagnosticEvent::RotationFailed {
+                    path: oldest_compressed.clone(),
+                    error: format!(
+                        "cannot remove oldest compressed backup: {} (failure #{}/{})",
+                        e,
+                        self.deletion_failure_count + 1,
+                        MAX_DELETION_FAILURES
+                    ),
+                });
             }
         }
         if oldest_backup.exists() {
             if let Err(e) = fs::remove_file(&oldest_backup) {
                 deletion_failed = true;
-                eprintln!(
-                    "[WARN] Failed to remove oldest backup {}: {} (failure #{}/{})",
-                    oldest_backup.display(),
-                    e,
-                    self.deletion_failure_count + 1,
-                    MAX_DELETION_FAILURES
-                );
+                diagnostics::report(DiagnosticEvent::RotationFailed {
+                    path: oldest_backup.clone(),
+                    error: format!(
+                        "cannot remove oldest backup: {} (failure #{}/{})",
+                        e,
+                        self.deletion_failure_count + 1,
+                        MAX_DELETION_FAILURES
+                    ),
+                });
             }
         }
 
@@ -485,9 +548,16 @@ impl RotatingFileAppender {
             })?;
 
             // Compress if enabled
-            if self.policy.compress {
+            let backup_path = if self.policy.compress {
                 self.compress_file(&backup_path)?;
-            }
+                backup_path.with_extension("log.gz")
+            } else {
+                backup_path
+            };
+            self.rotated = Some(AppenderEvent::Rotated {
+                path: self.base_path.clone(),
+                backup: backup_path,
+            });
         }
 
         // Open new file
@@ -504,7 +574,7 @@ impl RotatingFileAppender {
 
         self.writer = Some(BufWriter::new(file));
         self.current_size = 0;
-        self.last_rotation = SystemTime::now();
+        self.last_rotation = self.clock.system_time();
 
         Ok(())
     }
@@ -612,12 +682,14 @@ impl RotatingFileAppender {
         // Only remove original file after compression is fully successful
         // This ensures we never lose data due to compression failures
         if let Err(e) = fs::remove_file(path) {
-            eprintln!(
-                "[WARN] Compression succeeded but failed to remove original file {}: {}. \
-                Both compressed and uncompressed versions exist.",
-                path.display(),
-                e
-            );
+            diagnostics::report(DiagnosticEvent::RotationFailed {
+                path: path.to_path_buf(),
+                error: format!(
+                    "compression succeeded but the original file cannot be removed: {}. \
+                     Both compressed and uncompressed versions exist.",
+                    e
+                ),
+            });
             // Don't return error - compression succeeded, original file remaining is not critical
             // The file will be cleaned up on next rotation
         }
@@ -685,15 +757,19 @@ impl Appender for RotatingFileAppender {
     }
 
     fn append(&mut self, entry: &LogEntry) -> Result<()> {
+        self.append_formatted(entry, &mut String::new())
+    }
+
+    fn append_formatted(&mut self, entry: &LogEntry, scratch: &mut String) -> Result<()> {
         // Check if rotation is needed
         if self.should_rotate() {
             if let Err(e) = self.rotate() {
                 // Log rotation failed - try to recover by continuing with current file
                 // This prevents losing log messages due to rotation failures
-                eprintln!(
-                    "[WARN] Log rotation failed: {}. Continuing with current file.",
-                    e
-                );
+                diagnostics::report(DiagnosticEvent::RotationFailed {
+                    path: self.base_path.clone(),
+                    error: format!("{}. Continuing with current file.", e),
+                });
 
                 // Try to reopen the file if writer is missing
                 if self.writer.is_none() {
@@ -704,10 +780,10 @@ impl Appender for RotatingFileAppender {
                             self.last_rotation = last_rotation;
                         }
                         Err(reopen_err) => {
-                            eprintln!(
-                                "[ERROR] Failed to reopen log file after rotation failure: {}",
-                                reopen_err
-                            );
+                            diagnostics::report(DiagnosticEvent::RotationFailed {
+                                path: self.base_path.clone(),
+                                error: format!("cannot reopen the log file: {}", reopen_err),
+                            });
                             return Err(e); // Original rotation error
                         }
                     }
@@ -720,13 +796,19 @@ impl Appender for RotatingFileAppender {
         }
 
         // Format and write entry
-        let timestamp_str = self.timestamp_format.format(&entry.timestamp);
-        let formatted = format!("[{}] [{}] {}\n", timestamp_str, entry.level, entry.message);
+        if let Some(ref formatter) = self.formatter {
+            formatter.format(entry, scratch);
+        } else {
+            scratch.push('[');
+            self.timestamp_format.format_into(&entry.timestamp, scratch);
+            let _ = write!(scratch, "] [{}] {}", entry.level, entry.message);
+        }
+        scratch.push('\n');
 
-        let bytes_written = formatted.len() as u64;
+        let bytes_written = scratch.len() as u64;
 
         if let Some(ref mut writer) = self.writer {
-            writer.write_all(formatted.as_bytes()).map_err(|e| {
+            self.batch.write(scratch.as_bytes(), writer).map_err(|e| {
                 LoggerError::file_appender(
                     self.base_path.display().to_string(),
                     format!("Failed to write log entry: {}", e),
@@ -741,7 +823,7 @@ impl Appender for RotatingFileAppender {
 
     fn flush(&mut self) -> Result<()> {
         if let Some(ref mut writer) = self.writer {
-            writer.flush().map_err(|e| {
+            self.batch.finish(writer).and_then(|()| writer.flush()).map_err(|e| {
                 LoggerError::file_appender(
                     self.base_path.display().to_string(),
                     format!("Failed to flush: {}", e),
@@ -750,6 +832,39 @@ impl Appender for RotatingFileAppender {
         }
         Ok(())
     }
+
+    fn begin_batch(&mut self) {
+        self.batch.begin();
+    }
+
+    fn end_batch(&mut self) -> Result<()> {
+        if let Some(ref mut writer) = self.writer {
+            self.batch.finish(writer).map_err(|e| {
+                LoggerError::file_appender(
+                    self.base_path.display().to_string(),
+                    format!("Failed to write log entries: {}", e),
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    fn take_event(&mut self) -> Option<AppenderEvent> {
+        self.rotated.take()
+    }
+
+    fn health(&self) -> HealthStatus {
+        if self.writer.is_none() {
+            HealthStatus::Unhealthy(format!("{} is not open", self.base_path.display()))
+        } else if self.deletion_failure_count > 0 {
+            HealthStatus::Degraded(format!(
+                "{} consecutive failures to remove old backups",
+                self.deletion_failure_count
+            ))
+        } else {
+            HealthStatus::Healthy
+        }
+    }
 }
 
 impl Drop for RotatingFileAppender {
@@ -758,6 +873,7 @@ impl Drop for RotatingFileAppender {
         // This prevents resource leaks when the appender is dropped
         if let Some(mut writer) = self.writer.take() {
             // Best effort flush - ignore errors during drop
+            let _ = self.batch.write_pending(&mut writer);
             let _ = writer.flush();
             // Writer is explicitly dropped here, releasing file handle immediately
         }
@@ -871,6 +987,52 @@ mod tests {
         assert_eq!(appender.current_size(), 0);
     }
 
+    #[test]
+    fn test_custom_formatter() {
+        let dir = tempdir().unwrap();
+        let log_path = dir.path().join("formatted.log");
+
+        let layout = crate::core::PatternLayout::parse("%p %m %X{user}").unwrap();
+        let mut appender = RotatingFileAppender::new(&log_path)
+            .unwrap()
+            .with_formatter(Box::new(layout));
+
+        let context = crate::core::LogContext::new().with_field("user", "alice");
+        let entry = LogEntry::new(LogLevel::Warn, "login".to_string()).with_context(context);
+        appender.append(&entry).unwrap();
+        appender.flush().unwrap();
+
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "WARN login alice\n");
+        assert_eq!(appender.current_size(), 17);
+    }
+
+    #[test]
+    fn test_coalesced_batch_rotates_at_counted_size() {
+        let dir = tempdir().unwrap();
+        let log_path = dir.path().join("batched.log");
+        let policy = RotationPolicy::new()
+            .with_max_size(20)
+            .with_max_backups(3)
+            .with_compression(false);
+        let layout = crate::core::PatternLayout::parse("%m").unwrap();
+        let mut appender = RotatingFileAppender::with_policy(&log_path, policy)
+            .unwrap()
+            .with_formatter(Box::new(layout));
+
+        appender.begin_batch();
+        for i in 0..4 {
+            let entry = LogEntry::new(LogLevel::Info, format!("message {}", i));
+            appender.append(&entry).unwrap();
+        }
+        // The batch is held until the flush that ends it
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "");
+        appender.flush().unwrap();
+
+        let backup = log_path.with_file_name("batched.log.1");
+        assert_eq!(fs::read_to_string(backup).unwrap(), "message 0\nmessage 1\n");
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "message 2\nmessage 3\n");
+    }
+
     #[test]
     fn test_log_rotation_size_based() {
         let dir = tempdir().unwrap();
@@ -928,6 +1090,36 @@ mod tests {
         assert!(backup1.exists() || log_path.with_file_name("time_rotation.log.1.gz").exists());
     }
 
+    #[test]
+    fn test_daily_rotation_on_virtual_time() {
+        let dir = tempdir().unwrap();
+        let log_path = dir.path().join("daily.log");
+        let noon = chrono::TimeZone::with_ymd_and_hms(&Local, 2024, 6, 15, 12, 0, 0).unwrap();
+        let clock = Arc::new(crate::core::ManualClock::starting_at(noon.into()));
+        let policy = RotationPolicy::new().with_strategy(RotationStrategy::daily(0));
+        let layout = crate::core::PatternLayout::parse("%m").unwrap();
+        let mut appender = RotatingFileAppender::with_policy(&log_path, policy)
+            .unwrap()
+            .with_formatter(Box::new(layout))
+            .with_clock(clock.clone());
+
+        appender.append(&LogEntry::new(LogLevel::Info, "today".to_string())).unwrap();
+        clock.advance(Duration::from_secs(3600));
+        appender.append(&LogEntry::new(LogLevel::Info, "same day".to_string())).unwrap();
+        assert!(appender.take_event().is_none());
+
+        // Past midnight, whatever the time zone
+        clock.advance(Duration::from_secs(25 * 3600));
+        appender.append(&LogEntry::new(LogLevel::Info, "tomorrow".to_string())).unwrap();
+        appender.flush().unwrap();
+
+        assert!(matches!(appender.take_event(), Some(AppenderEvent::Rotated { .. })));
+        let backup = log_path.with_file_name("daily.log.1");
+        assert_eq!(fs::read_to_string(backup).unwrap(), "today\nsame day\n");
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "tomorrow\n");
+        assert_eq!(appender.last_rotation(), clock.system_time());
+    }
+
     #[test]
     fn test_log_rotation_hybrid() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,171 @@
+//! `log` crate facade backend
+
+use crate::core::{intern, LogEntry, LogLevel, Logger, LoggerError, Result};
+use std::sync::Arc;
+
+/// [`log::Log`] implementation that forwards records to a [`Logger`]
+///
+/// Each record becomes a [`LogEntry`] with the record's source location and
+/// a `target` context field holding the `log` target (usually the module
+/// path of the crate that logged it).
+///
+/// Level mapping: `Error`, `Warn`, `Info`, `Debug` and `Trace` map to the
+/// levels of the same name. `Fatal` has no `log` equivalent.
+pub struct LogBridge {
+    logger: Arc<Logger>,
+}
+
+impl LogBridge {
+    /// Create a bridge forwarding to the given logger
+    pub fn new(logger: impl Into<Arc<Logger>>) -> Self {
+        Self {
+            logger: logger.into(),
+        }
+    }
+
+    /// Get the logger records are forwarded to
+    pub fn logger(&self) -> &Arc<Logger> {
+        &self.logger
+    }
+
+    fn entry_for(record: &log::Record<'_>) -> LogEntry {
+        let mut entry = LogEntry::new(map_level(record.level()), record.args().to_string())
+            .with_target(record.target());
+        entry.file = record.file().map(intern);
+        entry.line = record.line();
+        entry.module_path = record.module_path().map(intern);
+        entry
+    }
+}
+
+impl log::Log for LogBridge {
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        self.logger.is_enabled() && map_level(metadata.level()) >= self.logger.effective_min_level()
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        if self.enabled(record.metadata()) {
+            self.logger.log_entry(Self::entry_for(record));
+        }
+    }
+
+    fn flush(&self) {
+        let _ = self.logger.flush();
+    }
+}
+
+/// Map a `log` level to a [`LogLevel`]
+fn map_level(level: log::Level) -> LogLevel {
+    match level {
+        log::Level::Error => LogLevel::Error,
+        log::Level::Warn => LogLevel::Warn,
+        log::Level::Info => LogLevel::Info,
+        log::Level::Debug => LogLevel::Debug,
+        log::Level::Trace => LogLevel::Trace,
+    }
+}
+
+/// Install a [`LogBridge`] as the global `log` backend
+///
+/// The `log` max level is set to `Trace` so that level changes made later on
+/// the logger (e.g. [`Logger::set_min_level`] or a level schedule) take
+/// effect; records below the logger's level are discarded by the bridge.
+/// Call [`log::set_max_level`] afterwards to filter earlier at the call site.
+///
+/// # Errors
+///
+/// Returns an error if a global `log` backend is already installed.
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::bridge::init_log_bridge;
+/// use rust_logger_system::prelude::*;
+///
+/// let logger = Logger::builder()
+///     .appender(ConsoleAppender::new())
+///     .build();
+///
+/// init_log_bridge(logger).expect("no other log backend installed");
+///
+/// // Records from dependencies using the `log` crate now reach the logger
+/// log::info!(target: "hyper::client", "connection established");
+/// ```
+pub fn init_log_bridge(logger: impl Into<Arc<Logger>>) -> Result<()> {
+    log::set_boxed_logger(Box::new(LogBridge::new(logger)))
+        .map_err(|e| LoggerError::config("log bridge", e.to_string()))?;
+    log::set_max_level(log::LevelFilter::Trace);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CaptureAppender;
+    use log::Log;
+    use parking_lot::Mutex;
+
+    fn bridge(min_level: LogLevel) -> (LogBridge, Arc<Mutex<Vec<LogEntry>>>) {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .min_level(min_level)
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .build();
+        (LogBridge::new(logger), entries)
+    }
+
+    #[test]
+    fn test_record_is_forwarded_with_metadata() {
+        let (bridge, entries) = bridge(LogLevel::Debug);
+
+        bridge.log(
+            &log::Record::builder()
+                .level(log::Level::Warn)
+                .target("hyper::proto")
+                .args(format_args!("connection reset by {}", "peer"))
+                .file(Some("src/proto.rs"))
+                .line(Some(120))
+                .module_path(Some("hyper::proto"))
+                .build(),
+        );
+
+        let entries = entries.lock();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.level, LogLevel::Warn);
+        assert_eq!(entry.message, "connection reset by peer");
+        assert_eq!(entry.file.as_deref(), Some("src/proto.rs"));
+        assert_eq!(entry.line, Some(120));
+        assert_eq!(entry.target.as_deref(), Some("hyper::proto"));
+    }
+
+    #[test]
+    fn test_level_filtering() {
+        let (bridge, entries) = bridge(LogLevel::Info);
+
+        let debug = log::Metadata::builder().level(log::Level::Debug).build();
+        let error = log::Metadata::builder().level(log::Level::Error).build();
+        assert!(!bridge.enabled(&debug));
+        assert!(bridge.enabled(&error));
+
+        bridge.log(
+            &log::Record::builder()
+                .level(log::Level::Debug)
+                .args(format_args!("hidden"))
+                .build(),
+        );
+        assert!(entries.lock().is_empty());
+
+        bridge.logger().set_enabled(false);
+        assert!(!bridge.enabled(&error));
+    }
+
+    #[test]
+    fn test_level_mapping() {
+        assert_eq!(map_level(log::Level::Error), LogLevel::Error);
+        assert_eq!(map_level(log::Level::Warn), LogLevel::Warn);
+        assert_eq!(map_level(log::Level::Info), LogLevel::Info);
+        assert_eq!(map_level(log::Level::Debug), LogLevel::Debug);
+        assert_eq!(map_level(log::Level::Trace), LogLevel::Trace);
+    }
+}
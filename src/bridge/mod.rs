@@ -0,0 +1,38 @@
+//! Bridges from other logging frameworks
+//!
+//! Libraries commonly log through a facade such as the [`log`] crate or
+//! instrument with `tracing`. The bridges in this module route those records
+//! into a [`Logger`] so that dependency logs end up in the same appenders as
+//! application logs.
+//!
+//! - `log-bridge` feature: [`init_log_bridge`] / [`LogBridge`]
+//! - `tracing-bridge` feature: `LoggerLayer`
+//! - `slog-bridge` feature: `SlogDrain`
+//! - `otel` feature: `current_otel_context`, used by the logger to tag
+//!   entries with the active OpenTelemetry trace and span IDs
+//! - `otel-metrics` feature: `OtelMetricsSink`, publishing logger metrics to
+//!   OpenTelemetry meters
+//!
+//! [`Logger`]: crate::Logger
+
+#[cfg(feature = "log-bridge")]
+mod log_bridge;
+#[cfg(feature = "otel")]
+mod otel;
+#[cfg(feature = "otel-metrics")]
+mod otel_metrics;
+#[cfg(feature = "slog-bridge")]
+mod slog_drain;
+#[cfg(feature = "tracing-bridge")]
+mod tracing_layer;
+
+#[cfg(feature = "log-bridge")]
+pub use log_bridge::{init_log_bridge, LogBridge};
+#[cfg(feature = "otel")]
+pub use otel::current_otel_context;
+#[cfg(feature = "otel-metrics")]
+pub use otel_metrics::OtelMetricsSink;
+#[cfg(feature = "slog-bridge")]
+pub use slog_drain::SlogDrain;
+#[cfg(feature = "tracing-bridge")]
+pub use tracing_layer::LoggerLayer;
@@ -0,0 +1,110 @@
+//! Ambient OpenTelemetry span context
+
+use crate::core::TracingContext;
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry::Context;
+
+/// Tracing context of the currently active OpenTelemetry span
+///
+/// Reads [`opentelemetry::Context::current`], which follows the context
+/// attached on the current thread or propagated into the current task with
+/// `FutureExt::with_context`. Returns `None` when no valid span is active.
+///
+/// With the `otel` feature enabled, the logger calls this for every entry
+/// that has no tracing context of its own, so log lines correlate with
+/// traces without passing IDs around. The parent span ID is not exposed by
+/// the OpenTelemetry API and is left unset.
+///
+/// # Example
+///
+/// ```
+/// use opentelemetry::trace::{
+///     SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+/// };
+/// use opentelemetry::Context;
+/// use rust_logger_system::bridge::current_otel_context;
+///
+/// let span = SpanContext::new(
+///     TraceId::from(1_u128),
+///     SpanId::from(2_u64),
+///     TraceFlags::SAMPLED,
+///     false,
+///     TraceState::default(),
+/// );
+/// let _attached = Context::current().with_remote_span_context(span).attach();
+///
+/// let ids = current_otel_context().unwrap();
+/// assert_eq!(ids.trace_id, "00000000000000000000000000000001");
+/// assert_eq!(ids.span_id, "0000000000000002");
+/// ```
+pub fn current_otel_context() -> Option<TracingContext> {
+    let context = Context::current();
+    let span = context.span();
+    let span_context = span.span_context();
+    if !span_context.is_valid() {
+        return None;
+    }
+
+    Some(TracingContext::new(
+        span_context.trace_id().to_string(),
+        span_context.span_id().to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{LogEntry, Logger};
+    use crate::testing::CaptureAppender;
+    use opentelemetry::trace::{SpanContext, SpanId, TraceFlags, TraceId, TraceState};
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    fn remote_context(trace_id: u128, span_id: u64) -> Context {
+        Context::current().with_remote_span_context(SpanContext::new(
+            TraceId::from(trace_id),
+            SpanId::from(span_id),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        ))
+    }
+
+    #[test]
+    fn test_no_active_span() {
+        assert!(current_otel_context().is_none());
+    }
+
+    #[test]
+    fn test_entries_pick_up_active_span() {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .build();
+
+        {
+            let _attached = remote_context(0xabc, 0x42).attach();
+            logger.info("inside");
+
+            // Explicit tracing context on the entry wins
+            let explicit = TracingContext::new("t".to_string(), "s".to_string());
+            logger.log_entry(
+                LogEntry::new(crate::core::LogLevel::Info, "explicit".to_string())
+                    .with_tracing(explicit),
+            );
+
+            // Crate spans opened inside an OpenTelemetry span join its trace
+            let span = logger.span("child");
+            assert_eq!(span.trace_id(), format!("{:032x}", 0xabc));
+            assert_eq!(span.context().parent_span_id.as_deref(), Some("0000000000000042"));
+        }
+        logger.info("outside");
+
+        let entries = entries.lock();
+        let ids = entries[0].tracing.as_ref().expect("otel context");
+        assert_eq!(ids.trace_id, format!("{:032x}", 0xabc));
+        assert_eq!(ids.span_id, "0000000000000042");
+        assert_eq!(entries[1].tracing.as_ref().unwrap().trace_id, "t");
+        assert!(entries.last().unwrap().tracing.is_none());
+    }
+}
@@ -0,0 +1,233 @@
+//! Logger metrics as OpenTelemetry instruments
+
+use crate::core::{LoggerMetrics, MetricsSink};
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+use opentelemetry::KeyValue;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Attribute naming the appender of per-appender instruments
+const APPENDER_ATTRIBUTE: &str = "appender";
+
+/// [`MetricsSink`] publishing to OpenTelemetry meters
+///
+/// The logger's cumulative counters are added to OpenTelemetry counters as
+/// deltas at every publication:
+///
+/// | Instrument | Source |
+/// |------------|--------|
+/// | `logger.entries.logged` | [`LoggerMetrics::total_logged`] |
+/// | `logger.entries.dropped` | [`LoggerMetrics::dropped_count`] |
+/// | `logger.entries.critical_preserved` | [`LoggerMetrics::critical_logs_preserved`] |
+/// | `logger.entries.sync_fallbacks` | [`LoggerMetrics::sync_fallbacks`] |
+/// | `logger.queue.full_events` | [`LoggerMetrics::queue_full_events`] |
+/// | `logger.queue.block_events` | [`LoggerMetrics::block_events`] |
+/// | `logger.appender.written` | [`AppenderMetrics::written`], per `appender` |
+/// | `logger.appender.errors` | failed and panicked appends, per `appender` |
+/// | `logger.appender.flush_errors` | [`AppenderMetrics::flush_failures`], per `appender` |
+///
+/// Append durations are recorded as they happen in the
+/// `logger.appender.duration` histogram, in seconds.
+///
+/// [`AppenderMetrics::written`]: crate::core::AppenderMetrics::written
+/// [`AppenderMetrics::flush_failures`]: crate::core::AppenderMetrics::flush_failures
+///
+/// # Example
+///
+/// ```
+/// use opentelemetry::global;
+/// use rust_logger_system::bridge::OtelMetricsSink;
+/// use rust_logger_system::prelude::*;
+/// use rust_logger_system::core::DEFAULT_METRICS_INTERVAL;
+///
+/// let sink = OtelMetricsSink::new(&global::meter("my_service"));
+/// let logger = Logger::builder()
+///     .appender(ConsoleAppender::new())
+///     .async_mode(1000)
+///     .metrics_sink(sink, DEFAULT_METRICS_INTERVAL)
+///     .build();
+/// ```
+pub struct OtelMetricsSink {
+    logged: Counter<u64>,
+    dropped: Counter<u64>,
+    critical_preserved: Counter<u64>,
+    sync_fallbacks: Counter<u64>,
+    queue_full: Counter<u64>,
+    blocked: Counter<u64>,
+    appender_written: Counter<u64>,
+    appender_errors: Counter<u64>,
+    appender_flush_errors: Counter<u64>,
+    append_duration: Histogram<f64>,
+    /// Values at the last publication, by instrument and appender
+    published: Mutex<HashMap<(&'static str, String), u64>>,
+}
+
+impl OtelMetricsSink {
+    /// Create the instruments on `meter`
+    pub fn new(meter: &Meter) -> Self {
+        let counter = |name: &'static str, description: &'static str| {
+            meter.u64_counter(name).with_description(description).build()
+        };
+        Self {
+            logged: counter("logger.entries.logged", "Entries written to every appender"),
+            dropped: counter("logger.entries.dropped", "Entries lost to overflow or failures"),
+            critical_preserved: counter(
+                "logger.entries.critical_preserved",
+                "High-priority entries kept although the queue was full",
+            ),
+            sync_fallbacks: counter(
+                "logger.entries.sync_fallbacks",
+                "Entries written synchronously because the queue was full",
+            ),
+            queue_full: counter("logger.queue.full_events", "Times the async queue was full"),
+            blocked: counter("logger.queue.block_events", "Waits for queue space"),
+            appender_written: counter("logger.appender.written", "Entries written"),
+            appender_errors: counter("logger.appender.errors", "Failed or panicked appends"),
+            appender_flush_errors: counter("logger.appender.flush_errors", "Failed flushes"),
+            append_duration: meter
+                .f64_histogram("logger.appender.duration")
+                .with_description("Time taken to write one entry")
+                .with_unit("s")
+                .build(),
+            published: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl MetricsSink for OtelMetricsSink {
+    fn publish(&self, metrics: &LoggerMetrics) {
+        let mut published = self.published.lock();
+        let mut add = |counter: &Counter<u64>, name: &'static str, appender: &str, value: u64| {
+            let last = published.entry((name, appender.to_string())).or_insert(0);
+            // Counters only go down when the metrics are reset
+            let delta = value.saturating_sub(*last);
+            *last = value;
+            if delta > 0 {
+                if appender.is_empty() {
+                    counter.add(delta, &[]);
+                } else {
+                    counter.add(delta, &[KeyValue::new(APPENDER_ATTRIBUTE, appender.to_string())]);
+                }
+            }
+        };
+
+        add(&self.logged, "logged", "", metrics.total_logged());
+        add(&self.dropped, "dropped", "", metrics.dropped_count());
+        add(&self.critical_preserved, "critical_preserved", "", metrics.critical_logs_preserved());
+        add(&self.sync_fallbacks, "sync_fallbacks", "", metrics.sync_fallbacks());
+        add(&self.queue_full, "queue_full", "", metrics.queue_full_events());
+        add(&self.blocked, "blocked", "", metrics.block_events());
+        for (name, appender) in metrics.appenders() {
+            add(&self.appender_written, "written", &name, appender.written());
+            add(&self.appender_errors, "errors", &name, appender.failed() + appender.panicked());
+            add(&self.appender_flush_errors, "flush_errors", &name, appender.flush_failures());
+        }
+    }
+
+    fn record_append(&self, appender: &str, duration: Duration) {
+        self.append_duration.record(
+            duration.as_secs_f64(),
+            &[KeyValue::new(APPENDER_ATTRIBUTE, appender.to_string())],
+        );
+    }
+}
+
+impl std::fmt::Debug for OtelMetricsSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OtelMetricsSink").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Logger;
+    use opentelemetry::metrics::{
+        HistogramBuilder, InstrumentBuilder, InstrumentProvider, SyncInstrument,
+    };
+    use std::sync::Arc;
+
+    type Recorded = Arc<Mutex<Vec<(String, f64, Vec<KeyValue>)>>>;
+
+    struct Recorder {
+        name: String,
+        recorded: Recorded,
+    }
+
+    impl Recorder {
+        fn record(&self, value: f64, attributes: &[KeyValue]) {
+            self.recorded.lock().push((self.name.clone(), value, attributes.to_vec()));
+        }
+    }
+
+    impl SyncInstrument<u64> for Recorder {
+        fn measure(&self, measurement: u64, attributes: &[KeyValue]) {
+            self.record(measurement as f64, attributes);
+        }
+    }
+
+    impl SyncInstrument<f64> for Recorder {
+        fn measure(&self, measurement: f64, attributes: &[KeyValue]) {
+            self.record(measurement, attributes);
+        }
+    }
+
+    struct RecordingProvider(Recorded);
+
+    impl RecordingProvider {
+        fn recorder(&self, name: &str) -> Arc<Recorder> {
+            Arc::new(Recorder {
+                name: name.to_string(),
+                recorded: Arc::clone(&self.0),
+            })
+        }
+    }
+
+    impl InstrumentProvider for RecordingProvider {
+        fn u64_counter(&self, builder: InstrumentBuilder<'_, Counter<u64>>) -> Counter<u64> {
+            Counter::new(self.recorder(&builder.name))
+        }
+
+        fn f64_histogram(&self, builder: HistogramBuilder<'_, Histogram<f64>>) -> Histogram<f64> {
+            Histogram::new(self.recorder(&builder.name))
+        }
+    }
+
+    #[test]
+    fn test_publishes_deltas() {
+        let recorded = Recorded::default();
+        let meter = Meter::new(Arc::new(RecordingProvider(Arc::clone(&recorded))));
+        let sink = OtelMetricsSink::new(&meter);
+
+        let metrics = LoggerMetrics::new();
+        metrics.record_logged();
+        metrics.record_logged();
+        metrics.register_appender("file").record_written();
+        sink.publish(&metrics);
+        metrics.record_logged();
+        sink.publish(&metrics);
+        sink.record_append("file", Duration::from_millis(2));
+
+        let recorded = recorded.lock();
+        let logged: Vec<f64> = recorded
+            .iter()
+            .filter(|(name, ..)| name == "logger.entries.logged")
+            .map(|(_, value, _)| *value)
+            .collect();
+        assert_eq!(logged, [2.0, 1.0]);
+        let file = KeyValue::new(APPENDER_ATTRIBUTE, "file");
+        assert!(recorded.contains(&("logger.appender.written".into(), 1.0, vec![file.clone()])));
+        assert!(recorded.contains(&("logger.appender.duration".into(), 0.002, vec![file])));
+        assert!(!recorded.iter().any(|(name, ..)| name == "logger.entries.dropped"));
+    }
+
+    #[test]
+    fn test_installs_on_logger() {
+        let meter = opentelemetry::global::meter("test");
+        let logger = Logger::builder()
+            .metrics_sink(OtelMetricsSink::new(&meter), Duration::from_secs(1))
+            .try_build();
+        assert!(logger.is_ok());
+    }
+}
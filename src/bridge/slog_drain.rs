@@ -0,0 +1,245 @@
+//! `slog` drain adapter
+
+use crate::core::{FieldValue, LogContext, LogEntry, LogLevel, Logger};
+use slog::{Key, OwnedKVList, Record, Serializer, KV};
+use std::fmt;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::sync::Arc;
+
+/// [`slog::Drain`] that forwards records to a [`Logger`]
+///
+/// Lets slog-based applications write through this crate's appenders while
+/// migrating. Each record becomes a [`LogEntry`] with the record's source
+/// location; key-value pairs of the record and of the `slog::Logger` chain
+/// become context fields, with record pairs and values of child loggers
+/// winning on conflicts. A non-empty record tag is stored in a `tag` field.
+///
+/// Level mapping: `Critical` maps to [`LogLevel::Fatal`], `Warning` to
+/// [`LogLevel::Warn`]; the other levels map to the level of the same name.
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::bridge::SlogDrain;
+/// use rust_logger_system::prelude::*;
+/// use slog::{info, o, Drain};
+///
+/// let logger = Logger::builder()
+///     .appender(ConsoleAppender::new())
+///     .build();
+///
+/// let root = slog::Logger::root(SlogDrain::new(logger).fuse(), o!("service" => "api"));
+/// info!(root, "request handled"; "status" => 200, "path" => "/users");
+/// ```
+pub struct SlogDrain {
+    logger: Arc<Logger>,
+}
+
+impl SlogDrain {
+    /// Create a drain forwarding to the given logger
+    pub fn new(logger: impl Into<Arc<Logger>>) -> Self {
+        Self {
+            logger: logger.into(),
+        }
+    }
+
+    /// Get the logger records are forwarded to
+    pub fn logger(&self) -> &Arc<Logger> {
+        &self.logger
+    }
+
+    fn entry_for(record: &Record<'_>, values: &OwnedKVList) -> LogEntry {
+        let mut collector = FieldCollector(LogContext::new());
+        // Earlier values win, so serialize from the most specific source:
+        // the record itself, then the logger chain (child before parent).
+        let _ = record.kv().serialize(record, &mut collector);
+        let _ = values.serialize(record, &mut collector);
+
+        let mut context = collector.0;
+        if !record.tag().is_empty() {
+            context.add_field("tag", record.tag());
+        }
+
+        let mut entry = LogEntry::new(map_level(record.level()), record.msg().to_string())
+            .with_location(record.file(), record.line(), record.module());
+        if !context.is_empty() {
+            entry = entry.with_context(context);
+        }
+        entry
+    }
+}
+
+// `slog::Logger::root` requires unwind-safe drains. The logger's shared
+// state sits behind locks that do not poison, so a panic while logging
+// cannot leave it observably broken.
+impl UnwindSafe for SlogDrain {}
+impl RefUnwindSafe for SlogDrain {}
+
+impl slog::Drain for SlogDrain {
+    type Ok = ();
+    type Err = slog::Never;
+
+    fn log(&self, record: &Record<'_>, values: &OwnedKVList) -> Result<(), slog::Never> {
+        if self.is_enabled(record.level()) {
+            self.logger.log_entry(Self::entry_for(record, values));
+        }
+        Ok(())
+    }
+
+    fn is_enabled(&self, level: slog::Level) -> bool {
+        self.logger.is_enabled() && map_level(level) >= self.logger.effective_min_level()
+    }
+}
+
+/// Map a `slog` level to a [`LogLevel`]
+fn map_level(level: slog::Level) -> LogLevel {
+    match level {
+        slog::Level::Critical => LogLevel::Fatal,
+        slog::Level::Error => LogLevel::Error,
+        slog::Level::Warning => LogLevel::Warn,
+        slog::Level::Info => LogLevel::Info,
+        slog::Level::Debug => LogLevel::Debug,
+        slog::Level::Trace => LogLevel::Trace,
+    }
+}
+
+/// Collects `slog` key-value pairs into a [`LogContext`], keeping the
+/// first value seen for each key
+struct FieldCollector(LogContext);
+
+impl FieldCollector {
+    fn insert(&mut self, key: Key, value: impl Into<FieldValue>) -> slog::Result {
+        if !self.0.field_map().contains_key(key) {
+            self.0.add_field(key, value);
+        }
+        Ok(())
+    }
+}
+
+impl Serializer for FieldCollector {
+    fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments<'_>) -> slog::Result {
+        self.insert(key, val.to_string())
+    }
+
+    fn emit_unit(&mut self, key: Key) -> slog::Result {
+        self.insert(key, FieldValue::Null)
+    }
+
+    fn emit_none(&mut self, key: Key) -> slog::Result {
+        self.insert(key, FieldValue::Null)
+    }
+
+    fn emit_bool(&mut self, key: Key, val: bool) -> slog::Result {
+        self.insert(key, val)
+    }
+
+    fn emit_str(&mut self, key: Key, val: &str) -> slog::Result {
+        self.insert(key, val)
+    }
+
+    fn emit_char(&mut self, key: Key, val: char) -> slog::Result {
+        self.insert(key, val.to_string())
+    }
+
+    fn emit_i8(&mut self, key: Key, val: i8) -> slog::Result {
+        self.insert(key, i64::from(val))
+    }
+
+    fn emit_i16(&mut self, key: Key, val: i16) -> slog::Result {
+        self.insert(key, i64::from(val))
+    }
+
+    fn emit_i32(&mut self, key: Key, val: i32) -> slog::Result {
+        self.insert(key, val)
+    }
+
+    fn emit_i64(&mut self, key: Key, val: i64) -> slog::Result {
+        self.insert(key, val)
+    }
+
+    fn emit_isize(&mut self, key: Key, val: isize) -> slog::Result {
+        self.insert(key, val as i64)
+    }
+
+    fn emit_u8(&mut self, key: Key, val: u8) -> slog::Result {
+        self.insert(key, i64::from(val))
+    }
+
+    fn emit_u16(&mut self, key: Key, val: u16) -> slog::Result {
+        self.insert(key, i64::from(val))
+    }
+
+    fn emit_u32(&mut self, key: Key, val: u32) -> slog::Result {
+        self.insert(key, val)
+    }
+
+    fn emit_u64(&mut self, key: Key, val: u64) -> slog::Result {
+        self.insert(key, val)
+    }
+
+    fn emit_usize(&mut self, key: Key, val: usize) -> slog::Result {
+        self.insert(key, val)
+    }
+
+    fn emit_f32(&mut self, key: Key, val: f32) -> slog::Result {
+        self.insert(key, f64::from(val))
+    }
+
+    fn emit_f64(&mut self, key: Key, val: f64) -> slog::Result {
+        self.insert(key, val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CaptureAppender;
+    use parking_lot::Mutex;
+    use slog::{o, Drain};
+
+    fn capture(min_level: LogLevel) -> (slog::Logger, Arc<Mutex<Vec<LogEntry>>>) {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .min_level(min_level)
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .build();
+        let root = slog::Logger::root(SlogDrain::new(logger).fuse(), o!("service" => "api"));
+        (root, entries)
+    }
+
+    #[test]
+    fn test_record_fields_and_location() {
+        let (root, entries) = capture(LogLevel::Trace);
+        let child = root.new(o!("request_id" => "abc", "service" => "billing"));
+        slog::warn!(child, "retrying {}", "query"; "attempt" => 3, "ok" => false, "ratio" => 0.5);
+
+        let entries = entries.lock();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.level, LogLevel::Warn);
+        assert_eq!(entry.message, "retrying query");
+        assert_eq!(entry.file.as_deref(), Some(file!()));
+        assert!(entry.line.is_some());
+
+        let fields = entry.context.as_ref().unwrap().fields();
+        assert_eq!(fields["attempt"], 3.into());
+        assert_eq!(fields["ok"], false.into());
+        assert_eq!(fields["ratio"], 0.5.into());
+        assert_eq!(fields["request_id"], "abc".into());
+        // Child logger values win over the root's
+        assert_eq!(fields["service"], "billing".into());
+    }
+
+    #[test]
+    fn test_level_mapping_and_filtering() {
+        let (root, entries) = capture(LogLevel::Info);
+        slog::debug!(root, "hidden");
+        slog::crit!(root, "down");
+        slog::info!(root, #"audit", "tagged");
+
+        let entries = entries.lock();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].level, LogLevel::Fatal);
+        assert_eq!(entries[1].context.as_ref().unwrap().fields()["tag"], "audit".into());
+    }
+}
@@ -0,0 +1,286 @@
+//! `tracing` subscriber layer
+
+use crate::core::{intern, LogContext, LogEntry, LogLevel, Logger, TracingContext};
+use std::fmt;
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// [`tracing_subscriber::Layer`] that writes events through a [`Logger`]
+///
+/// Each event becomes a [`LogEntry`]:
+///
+/// - the `message` field becomes the entry message
+/// - the event target is stored in a `target` context field
+/// - fields of all enclosing spans (outermost first) and of the event itself
+///   become context fields; event fields win on conflicts
+/// - the enclosing spans populate [`LogEntry::tracing`]: the root span ID is
+///   used as trace ID, the innermost span as span ID and its parent as
+///   parent span ID
+///
+/// Span IDs come from the `tracing` registry and are only unique within the
+/// process while the span is open.
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::bridge::LoggerLayer;
+/// use rust_logger_system::prelude::*;
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let logger = Logger::builder()
+///     .appender(ConsoleAppender::new())
+///     .build();
+///
+/// let subscriber = tracing_subscriber::registry().with(LoggerLayer::new(logger));
+/// tracing::subscriber::with_default(subscriber, || {
+///     let span = tracing::info_span!("request", request_id = "abc-123");
+///     let _enter = span.enter();
+///     tracing::info!(latency_ms = 42, "request processed");
+/// });
+/// ```
+pub struct LoggerLayer {
+    logger: Arc<Logger>,
+}
+
+impl LoggerLayer {
+    /// Create a layer writing to the given logger
+    pub fn new(logger: impl Into<Arc<Logger>>) -> Self {
+        Self {
+            logger: logger.into(),
+        }
+    }
+
+    /// Get the logger events are written to
+    pub fn logger(&self) -> &Arc<Logger> {
+        &self.logger
+    }
+}
+
+/// Fields recorded on a span, kept in the span's extensions
+struct SpanFields(LogContext);
+
+impl<S> Layer<S> for LoggerLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut fields = LogContext::new();
+        attrs.record(&mut FieldVisitor::new(&mut fields));
+        span.extensions_mut().insert(SpanFields(fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut extensions = span.extensions_mut();
+        if let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() {
+            values.record(&mut FieldVisitor::new(fields));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = map_level(metadata.level());
+        if !self.logger.is_enabled() || level < self.logger.effective_min_level() {
+            return;
+        }
+
+        let mut context = LogContext::new();
+        let mut tracing = None;
+
+        if let Some(scope) = ctx.event_scope(event) {
+            let spans: Vec<_> = scope.from_root().collect();
+            for span in &spans {
+                if let Some(SpanFields(fields)) = span.extensions().get::<SpanFields>() {
+                    for (key, value) in fields.field_map() {
+                        context.add_field(key.clone(), value.clone());
+                    }
+                }
+            }
+
+            if let (Some(root), Some(leaf)) = (spans.first(), spans.last()) {
+                let mut ids = TracingContext::new(
+                    format!("{:032x}", root.id().into_u64()),
+                    format!("{:016x}", leaf.id().into_u64()),
+                );
+                if let Some(parent) = leaf.parent() {
+                    ids = ids.with_parent(format!("{:016x}", parent.id().into_u64()));
+                }
+                tracing = Some(ids);
+            }
+        }
+
+        let mut visitor = FieldVisitor::new(&mut context).capture_message();
+        event.record(&mut visitor);
+        let message = visitor.message.unwrap_or_default();
+
+        let mut entry = LogEntry::new(level, message).with_target(metadata.target());
+        if !context.is_empty() {
+            entry.context = Some(context);
+        }
+        entry.file = metadata.file().map(intern);
+        entry.line = metadata.line();
+        entry.module_path = metadata.module_path().map(intern);
+        entry.tracing = tracing;
+
+        self.logger.log_entry(entry);
+    }
+}
+
+/// Map a `tracing` level to a [`LogLevel`]
+fn map_level(level: &Level) -> LogLevel {
+    match *level {
+        Level::ERROR => LogLevel::Error,
+        Level::WARN => LogLevel::Warn,
+        Level::INFO => LogLevel::Info,
+        Level::DEBUG => LogLevel::Debug,
+        Level::TRACE => LogLevel::Trace,
+    }
+}
+
+/// Records `tracing` fields into a [`LogContext`]
+struct FieldVisitor<'a> {
+    context: &'a mut LogContext,
+    capture_message: bool,
+    message: Option<String>,
+}
+
+impl<'a> FieldVisitor<'a> {
+    fn new(context: &'a mut LogContext) -> Self {
+        Self {
+            context,
+            capture_message: false,
+            message: None,
+        }
+    }
+
+    /// Treat the `message` field as the entry message instead of a field
+    fn capture_message(mut self) -> Self {
+        self.capture_message = true;
+        self
+    }
+}
+
+impl Visit for FieldVisitor<'_> {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.context.add_field(field.name(), value);
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.context.add_field(field.name(), value);
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.context.add_field(field.name(), value);
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.context.add_field(field.name(), value);
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if self.capture_message && field.name() == "message" {
+            self.message = Some(value.to_string());
+        } else {
+            self.context.add_field(field.name(), value);
+        }
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.context.add_field(field.name(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if self.capture_message && field.name() == "message" {
+            self.message = Some(format!("{:?}", value));
+        } else {
+            self.context.add_field(field.name(), format!("{:?}", value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CaptureAppender;
+    use parking_lot::Mutex;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn capture(min_level: LogLevel, f: impl FnOnce()) -> Vec<LogEntry> {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .min_level(min_level)
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .build();
+
+        let subscriber = tracing_subscriber::registry().with(LoggerLayer::new(logger));
+        tracing::subscriber::with_default(subscriber, f);
+
+        let entries = entries.lock();
+        entries.clone()
+    }
+
+    #[test]
+    fn test_event_fields_and_message() {
+        let entries = capture(LogLevel::Trace, || {
+            tracing::warn!(target: "db", attempt = 3_i64, ok = false, "retrying {}", "query");
+        });
+
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.level, LogLevel::Warn);
+        assert_eq!(entry.message, "retrying query");
+        assert!(entry.line.is_some());
+        assert!(entry.tracing.is_none());
+        assert_eq!(entry.target.as_deref(), Some("db"));
+
+        let fields = entry.context.as_ref().unwrap().fields();
+        assert_eq!(fields["attempt"], 3.into());
+        assert_eq!(fields["ok"], false.into());
+    }
+
+    #[test]
+    fn test_span_fields_and_ids() {
+        let entries = capture(LogLevel::Trace, || {
+            let outer = tracing::info_span!("request", request_id = "abc", stage = "outer");
+            let _outer = outer.enter();
+            let inner = tracing::debug_span!("query", stage = tracing::field::Empty);
+            inner.record("stage", "inner");
+            let _inner = inner.enter();
+            tracing::info!(rows = 10_u64, "done");
+        });
+
+        assert_eq!(entries.len(), 1);
+        let fields = entries[0].context.as_ref().unwrap().fields();
+        assert_eq!(fields["request_id"], "abc".into());
+        assert_eq!(fields["stage"], "inner".into());
+        assert_eq!(fields["rows"], 10_u64.into());
+
+        let ids = entries[0].tracing.as_ref().expect("span context");
+        assert_eq!(ids.trace_id.len(), 32);
+        assert_eq!(ids.span_id.len(), 16);
+        assert_eq!(ids.parent_span_id.as_deref(), Some(&ids.trace_id[16..]));
+    }
+
+    #[test]
+    fn test_min_level_is_respected() {
+        let entries = capture(LogLevel::Info, || {
+            tracing::debug!("hidden");
+            tracing::error!("shown");
+        });
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message, "shown");
+    }
+}
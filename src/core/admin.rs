@@ -0,0 +1,444 @@
+//! Control channel for a running process
+//!
+//! An [`AdminServer`] listens on a localhost TCP port or, on Unix, a socket
+//! file, and accepts one command per line. Operators can read metrics and
+//! change levels and sampling without a restart; changes go through
+//! [`Logger::reconfigure`] like any other runtime change.
+//!
+//! | Command | Effect |
+//! |---------|--------|
+//! | `STATS` | logger and sampler counters |
+//! | `CONFIG` | current level, sampling rate, policies and appenders |
+//! | `LEVEL [logger]` | level in effect for the root or a child logger |
+//! | `SETLEVEL <level> [logger]` | set the root level or a child logger's level |
+//! | `CLEARLEVEL <logger>` | make a child logger inherit its level again |
+//! | `SAMPLING <rate>` | set the base sample rate |
+//! | `FLUSH` | flush all appenders |
+//! | `HELP` | list the commands |
+//! | `QUIT` | close the connection |
+//!
+//! Commands are case-insensitive. Each response is zero or more
+//! `key value` lines followed by `OK`, or a single `ERR <reason>` line.
+//!
+//! There is no authentication: anyone who can connect can change the
+//! logger, so TCP servers only bind loopback addresses and Unix sockets
+//! should be placed in a directory only the service user can access.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rust_logger_system::core::AdminServer;
+//! use rust_logger_system::Logger;
+//! use std::sync::Arc;
+//!
+//! let logger = Arc::new(Logger::new());
+//! let admin = AdminServer::tcp(Arc::clone(&logger), "127.0.0.1:7070")?;
+//! // $ echo "SETLEVEL debug" | nc 127.0.0.1 7070
+//! // OK
+//! # Ok::<(), rust_logger_system::LoggerError>(())
+//! ```
+
+use super::error::{LoggerError, Result};
+use super::log_level::LogLevel;
+use super::logger::Logger;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// How often idle listeners and connections check for shutdown
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Longest accepted command line, in bytes
+const MAX_LINE_LENGTH: usize = 1024;
+
+const HELP: &str = "\
+STATS                       logger and sampler counters
+CONFIG                      current settings
+LEVEL [logger]              level in effect
+SETLEVEL <level> [logger]   set the root or a child logger's level
+CLEARLEVEL <logger>         make a child logger inherit its level
+SAMPLING <rate>             set the base sample rate
+FLUSH                       flush all appenders
+QUIT                        close the connection
+";
+
+/// Background thread serving the admin line protocol
+///
+/// See the [module docs](self) for the commands. Stops when dropped.
+pub struct AdminServer {
+    endpoint: Endpoint,
+    stop: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+#[derive(Debug)]
+enum Endpoint {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl AdminServer {
+    /// Listen on a loopback TCP address
+    ///
+    /// Use port 0 to have the system pick a free port, then read it with
+    /// [`local_addr`](Self::local_addr).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address is not a loopback address or cannot
+    /// be bound.
+    pub fn tcp(logger: Arc<Logger>, addr: impl ToSocketAddrs) -> Result<Self> {
+        let addrs: Vec<SocketAddr> = addr
+            .to_socket_addrs()
+            .map_err(|e| LoggerError::io_operation("resolving admin address", "", e))?
+            .collect();
+        if addrs.is_empty() || addrs.iter().any(|a| !a.ip().is_loopback()) {
+            return Err(LoggerError::config(
+                "admin",
+                "the admin channel only listens on loopback addresses",
+            ));
+        }
+        let (listener, local) = TcpListener::bind(&addrs[..])
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                let local = listener.local_addr()?;
+                Ok((listener, local))
+            })
+            .map_err(|e| {
+                LoggerError::io_operation("binding admin channel", format!("{:?}", addrs), e)
+            })?;
+        Ok(Self::start(logger, Endpoint::Tcp(local), listener))
+    }
+
+    /// Listen on a Unix domain socket at `path`
+    ///
+    /// The socket file is removed when the server stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket cannot be created, for example
+    /// because `path` already exists.
+    #[cfg(unix)]
+    pub fn unix(logger: Arc<Logger>, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let listener = std::os::unix::net::UnixListener::bind(&path)
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                Ok(listener)
+            })
+            .map_err(|e| {
+                LoggerError::io_operation("binding admin channel", path.display().to_string(), e)
+            })?;
+        Ok(Self::start(logger, Endpoint::Unix(path), listener))
+    }
+
+    fn start<L: Listener>(logger: Arc<Logger>, endpoint: Endpoint, listener: L) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopping = Arc::clone(&stop);
+
+        let handle = thread::spawn(move || {
+            let mut connections = Vec::new();
+            while !stopping.load(Ordering::Relaxed) {
+                match listener.accept_stream() {
+                    Ok(stream) => {
+                        let logger = Arc::clone(&logger);
+                        let stopping = Arc::clone(&stopping);
+                        connections.push(thread::spawn(move || {
+                            serve(&logger, stream, &stopping);
+                        }));
+                        connections.retain(|c: &thread::JoinHandle<()>| !c.is_finished());
+                    }
+                    // WouldBlock, or a connection that failed before it was accepted
+                    Err(_) => thread::sleep(POLL_INTERVAL),
+                }
+            }
+            for connection in connections {
+                let _ = connection.join();
+            }
+        });
+
+        Self {
+            endpoint,
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Get the TCP address the server listens on
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        match self.endpoint {
+            Endpoint::Tcp(addr) => Some(addr),
+            #[cfg(unix)]
+            Endpoint::Unix(_) => None,
+        }
+    }
+
+    /// Get the path of the Unix socket the server listens on
+    #[cfg(unix)]
+    pub fn socket_path(&self) -> Option<&Path> {
+        match self.endpoint {
+            Endpoint::Unix(ref path) => Some(path),
+            Endpoint::Tcp(_) => None,
+        }
+    }
+
+    /// Stop listening, close open connections and wait for the threads
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+            #[cfg(unix)]
+            if let Endpoint::Unix(ref path) = self.endpoint {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
+impl Drop for AdminServer {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl std::fmt::Debug for AdminServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminServer")
+            .field("endpoint", &self.endpoint)
+            .finish()
+    }
+}
+
+/// Non-blocking listener handing out blocking streams with a read timeout
+trait Listener: Send + 'static {
+    type Stream: Read + Write + Send + 'static;
+
+    fn accept_stream(&self) -> io::Result<Self::Stream>;
+}
+
+impl Listener for TcpListener {
+    type Stream = TcpStream;
+
+    fn accept_stream(&self) -> io::Result<TcpStream> {
+        let (stream, _) = self.accept()?;
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(POLL_INTERVAL))?;
+        Ok(stream)
+    }
+}
+
+#[cfg(unix)]
+impl Listener for std::os::unix::net::UnixListener {
+    type Stream = std::os::unix::net::UnixStream;
+
+    fn accept_stream(&self) -> io::Result<Self::Stream> {
+        let (stream, _) = self.accept()?;
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(POLL_INTERVAL))?;
+        Ok(stream)
+    }
+}
+
+/// Answer commands on one connection until it closes or the server stops
+fn serve<S: Read + Write>(logger: &Logger, stream: S, stop: &AtomicBool) {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+
+    while !stop.load(Ordering::Relaxed) {
+        let limit = (MAX_LINE_LENGTH + 1).saturating_sub(line.len()) as u64;
+        match reader.by_ref().take(limit).read_line(&mut line) {
+            Ok(0) => return,
+            Ok(_) if line.len() > MAX_LINE_LENGTH => {
+                let _ = reader.get_mut().write_all(b"ERR line too long\n");
+                return;
+            }
+            Ok(_) => {
+                let response = execute(logger, line.trim());
+                line.clear();
+                if reader.get_mut().write_all(response.render().as_bytes()).is_err()
+                    || response == Response::Quit
+                {
+                    return;
+                }
+            }
+            Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {}
+            Err(_) => return,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Response {
+    /// `key value` lines, then `OK`
+    Ok(String),
+    Err(String),
+    Quit,
+}
+
+impl Response {
+    fn render(&self) -> String {
+        match self {
+            Response::Ok(body) => format!("{}OK\n", body),
+            // Keep the reply on one line
+            Response::Err(reason) => format!("ERR {}\n", reason.replace('\n', " ")),
+            Response::Quit => "OK\n".to_string(),
+        }
+    }
+}
+
+/// Run one command line against `logger`
+fn execute(logger: &Logger, line: &str) -> Response {
+    let mut words = line.split_whitespace();
+    let command = words.next().unwrap_or_default().to_uppercase();
+    let args: Vec<&str> = words.collect();
+    let lower = |level: LogLevel| level.to_str().to_lowercase();
+    let mut out = String::new();
+
+    match (command.as_str(), args.as_slice()) {
+        ("STATS", []) => {
+            let metrics = logger.metrics();
+            let _ = writeln!(out, "total_logged {}", metrics.total_logged());
+            let _ = writeln!(out, "dropped {}", metrics.dropped_count());
+            let _ = writeln!(out, "queue_full_events {}", metrics.queue_full_events());
+            let _ = writeln!(out, "block_events {}", metrics.block_events());
+            let _ = writeln!(out, "critical_logs_preserved {}", metrics.critical_logs_preserved());
+            let _ = writeln!(out, "sync_fallbacks {}", metrics.sync_fallbacks());
+            let _ = writeln!(out, "appender_errors {}", metrics.appender_errors());
+            let _ = writeln!(out, "flush_errors {}", metrics.flush_errors());
+            let _ = writeln!(out, "drop_rate {:.2}", metrics.drop_rate());
+            if let Some(sampler) = logger.sampler() {
+                let rate = sampler.effective_sample_rate();
+                let _ = writeln!(out, "sampled {}", sampler.metrics().sampled_count());
+                let _ = writeln!(out, "sampling_dropped {}", sampler.metrics().dropped_count());
+                let _ = writeln!(out, "effective_sample_rate {:.3}", rate);
+            }
+        }
+        ("CONFIG", []) => {
+            let config = logger.runtime_config();
+            let appenders = logger.list_appenders();
+            let _ = writeln!(out, "level {}", lower(logger.effective_min_level()));
+            let _ = writeln!(out, "sampling_rate {}", config.sampling.rate);
+            let _ = writeln!(out, "overflow_policy {}", config.overflow_policy);
+            let _ = writeln!(out, "flush_policy {}", config.flush_policy);
+            let _ = writeln!(out, "appenders {}", appenders.join(","));
+        }
+        ("LEVEL", [name]) => {
+            let _ = writeln!(out, "level {}", lower(logger.logger_level(name)));
+        }
+        ("LEVEL", []) => {
+            let _ = writeln!(out, "level {}", lower(logger.effective_min_level()));
+        }
+        ("SETLEVEL", [level, rest @ ..]) if rest.len() <= 1 => {
+            let level: LogLevel = match level.parse() {
+                Ok(level) => level,
+                Err(e) => return Response::Err(e),
+            };
+            match rest.first() {
+                Some(name) => logger.set_logger_level(*name, level),
+                None => {
+                    if let Err(e) = logger.reconfigure(|config| config.min_level = level) {
+                        return Response::Err(e.to_string());
+                    }
+                }
+            }
+        }
+        ("CLEARLEVEL", [name]) => logger.clear_logger_level(name),
+        ("SAMPLING", [rate]) => {
+            let Ok(rate) = rate.parse::<f64>() else {
+                return Response::Err(format!("invalid sample rate '{}'", rate));
+            };
+            if let Err(e) = logger.reconfigure(|config| config.sampling.rate = rate) {
+                return Response::Err(e.to_string());
+            }
+        }
+        ("FLUSH", []) => {
+            if let Err(e) = logger.flush() {
+                return Response::Err(e.to_string());
+            }
+        }
+        ("HELP", []) => out.push_str(HELP),
+        ("QUIT", []) => return Response::Quit,
+        ("STATS" | "CONFIG" | "LEVEL" | "SETLEVEL" | "CLEARLEVEL" | "SAMPLING" | "FLUSH", _)
+        | ("HELP" | "QUIT", _) => {
+            return Response::Err(format!("wrong arguments for {}; try HELP", command));
+        }
+        _ => return Response::Err(format!("unknown command '{}'; try HELP", command)),
+    }
+    Response::Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(response: Response) -> String {
+        match response {
+            Response::Ok(body) => body,
+            other => panic!("expected OK, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_commands() {
+        let logger = Logger::new();
+
+        assert_eq!(body(execute(&logger, "setlevel debug")), "");
+        assert_eq!(logger.effective_min_level(), LogLevel::Debug);
+        assert_eq!(body(execute(&logger, "SETLEVEL error db")), "");
+        assert_eq!(body(execute(&logger, "LEVEL db.pool")), "level error\n");
+        assert_eq!(body(execute(&logger, "CLEARLEVEL db")), "");
+        assert_eq!(body(execute(&logger, "LEVEL db")), "level debug\n");
+
+        assert_eq!(body(execute(&logger, "SAMPLING 0.5")), "");
+        assert_eq!(logger.sampler().unwrap().config().rate, 0.5);
+        assert!(body(execute(&logger, "CONFIG")).contains("sampling_rate 0.5\n"));
+        assert!(body(execute(&logger, "STATS")).contains("dropped 0\n"));
+
+        assert!(matches!(execute(&logger, "SAMPLING 2"), Response::Err(_)));
+        assert!(matches!(execute(&logger, "SETLEVEL loud"), Response::Err(_)));
+        assert!(matches!(execute(&logger, "SETLEVEL"), Response::Err(_)));
+        assert!(matches!(execute(&logger, "REBOOT"), Response::Err(_)));
+        assert_eq!(execute(&logger, "quit"), Response::Quit);
+    }
+
+    #[test]
+    fn test_tcp_session() {
+        let logger = Arc::new(Logger::new());
+        assert!(AdminServer::tcp(Arc::clone(&logger), "0.0.0.0:0").is_err());
+
+        let server = AdminServer::tcp(Arc::clone(&logger), "127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(server.local_addr().unwrap()).unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut reply = String::new();
+
+        writer.write_all(b"SETLEVEL warn\nLEVEL\nQUIT\n").unwrap();
+        reader.read_to_string(&mut reply).unwrap();
+        assert_eq!(reply, "OK\nlevel warn\nOK\nOK\n");
+        assert_eq!(logger.effective_min_level(), LogLevel::Warn);
+        server.stop();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_socket_is_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("admin.sock");
+        let server = AdminServer::unix(Arc::new(Logger::new()), &path).unwrap();
+        assert_eq!(server.socket_path(), Some(path.as_path()));
+        assert!(path.exists());
+        drop(server);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,35 @@
+//! ANSI escape sequences for console styling
+//!
+//! The console appender decides per stream whether to emit colors (see
+//! [`ColorMode`](crate::appenders::ColorMode)), so styles are written
+//! directly instead of through `colored`, whose global detection only
+//! looks at stdout.
+
+use colored::Color;
+
+/// Bold text
+pub(crate) const BOLD: &str = "1";
+
+/// Dimmed text
+pub(crate) const DIM: &str = "2";
+
+/// SGR parameters for a foreground color
+pub(crate) fn fg(color: Color) -> String {
+    color.to_fg_str().into_owned()
+}
+
+/// Wrap `text` in the given SGR parameters (e.g. `"1;31"`) and a reset
+pub(crate) fn paint(text: &str, style: &str) -> String {
+    format!("\x1b[{}m{}\x1b[0m", style, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paint() {
+        assert_eq!(paint("WARN", &fg(Color::Yellow)), "\x1b[33mWARN\x1b[0m");
+        assert_eq!(paint("key", BOLD), "\x1b[1mkey\x1b[0m");
+    }
+}
@@ -1,9 +1,68 @@
 //! Appender trait for log output destinations
 
-use super::{error::Result, log_entry::LogEntry};
+use super::{
+    error::Result, events::AppenderEvent, health::HealthStatus, log_entry::LogEntry,
+    timestamp::FormatterConfig,
+};
 
 pub trait Appender: Send + Sync {
     fn append(&mut self, entry: &LogEntry) -> Result<()>;
     fn flush(&mut self) -> Result<()>;
     fn name(&self) -> &str;
+
+    /// Append an entry, formatting it into a caller-owned scratch buffer
+    ///
+    /// The logger passes each appender the same `scratch` for every entry,
+    /// emptied before each call, so appenders that format text can reuse
+    /// its allocation instead of building a new `String` per entry. The
+    /// default ignores `scratch` and calls [`append`](Appender::append).
+    fn append_formatted(&mut self, entry: &LogEntry, scratch: &mut String) -> Result<()> {
+        let _ = scratch;
+        self.append(entry)
+    }
+
+    /// Called by the async worker before appending a batch of entries
+    ///
+    /// The batch ends with a call to [`end_batch`](Appender::end_batch).
+    /// Appenders can use this to buffer output for the whole batch instead
+    /// of writing every entry separately. The default does nothing.
+    fn begin_batch(&mut self) {}
+
+    /// Called by the async worker after appending a batch of entries
+    ///
+    /// Writes any output held since [`begin_batch`](Appender::begin_batch).
+    /// Whether the appender is flushed as well depends on the logger's
+    /// [`FlushPolicy`](super::FlushPolicy). The default does nothing.
+    fn end_batch(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Replace the formatting options while the logger is running
+    ///
+    /// Called by [`Logger::reconfigure`](super::Logger::reconfigure).
+    /// Returns `false` if the appender has no such options; the default
+    /// ignores the call.
+    fn set_formatter_config(&mut self, _config: &FormatterConfig) -> bool {
+        false
+    }
+
+    /// Report whether the appender can currently write entries
+    ///
+    /// Returned by [`Logger::appender_health`](super::Logger::appender_health)
+    /// together with the logger's record of recent writes, so only state the
+    /// logger cannot observe needs reporting. The default is
+    /// [`HealthStatus::Healthy`].
+    fn health(&self) -> HealthStatus {
+        HealthStatus::Healthy
+    }
+
+    /// Take an event caused by the last successful append, if any
+    ///
+    /// Called by the logger after every successful append until it returns
+    /// `None`; the events are delivered to
+    /// [`Logger::subscribe`](super::Logger::subscribe) callbacks. The
+    /// default returns `None`.
+    fn take_event(&mut self) -> Option<AppenderEvent> {
+        None
+    }
 }
@@ -0,0 +1,258 @@
+//! Binary encodings for structured log entries
+//!
+//! High-volume pipelines can write [`StructuredLogEntry`] values in a
+//! compact binary encoding instead of text. Each encoded entry is written
+//! as a frame: a 4-byte big-endian payload length followed by the payload,
+//! so a reader can split a file or TCP stream back into entries.
+//!
+//! File and network appenders switch to framed output via
+//! `with_binary_format`.
+//!
+//! Each encoding has its own feature: `msgpack` for
+//! [`BinaryFormat::MessagePack`] and `cbor` for [`BinaryFormat::Cbor`].
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "msgpack")]
+//! # {
+//! use rust_logger_system::core::{BinaryFormat, LogLevel, StructuredLogEntry};
+//!
+//! let entry = StructuredLogEntry::new(LogLevel::Info, "ready");
+//!
+//! let mut frames = Vec::new();
+//! BinaryFormat::MessagePack.write_frame(&entry, &mut frames).unwrap();
+//!
+//! let decoded = BinaryFormat::MessagePack
+//!     .read_frame(&mut frames.as_slice())
+//!     .unwrap()
+//!     .unwrap();
+//! assert_eq!(decoded.message, "ready");
+//! # }
+//! ```
+
+use super::error::{LoggerError, Result};
+use super::structured_entry::StructuredLogEntry;
+use std::io::{ErrorKind, Read, Write};
+
+/// Largest payload accepted by [`BinaryFormat::read_frame`] (16 MiB)
+pub const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Binary encoding for structured log entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BinaryFormat {
+    /// MessagePack, with field names kept as map keys
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+
+    /// CBOR (RFC 8949), with field names kept as map keys
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+impl BinaryFormat {
+    /// Name used in error messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "msgpack")]
+            BinaryFormat::MessagePack => "msgpack",
+            #[cfg(feature = "cbor")]
+            BinaryFormat::Cbor => "cbor",
+        }
+    }
+
+    /// Encode an entry without framing
+    ///
+    /// # Errors
+    ///
+    /// Returns a formatter error if the entry cannot be serialized.
+    pub fn encode(&self, entry: &StructuredLogEntry) -> Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "msgpack")]
+            BinaryFormat::MessagePack => rmp_serde::to_vec_named(entry)
+                .map_err(|e| LoggerError::formatter(self.name(), e.to_string())),
+            #[cfg(feature = "cbor")]
+            BinaryFormat::Cbor => {
+                let mut payload = Vec::new();
+                ciborium::into_writer(entry, &mut payload)
+                    .map_err(|e| LoggerError::formatter(self.name(), e.to_string()))?;
+                Ok(payload)
+            }
+        }
+    }
+
+    /// Decode an unframed payload
+    ///
+    /// # Errors
+    ///
+    /// Returns a formatter error if the payload is not a valid entry.
+    pub fn decode(&self, payload: &[u8]) -> Result<StructuredLogEntry> {
+        match self {
+            #[cfg(feature = "msgpack")]
+            BinaryFormat::MessagePack => rmp_serde::from_slice(payload)
+                .map_err(|e| LoggerError::formatter(self.name(), e.to_string())),
+            #[cfg(feature = "cbor")]
+            BinaryFormat::Cbor => ciborium::from_reader(payload)
+                .map_err(|e| LoggerError::formatter(self.name(), e.to_string())),
+        }
+    }
+
+    /// Encode an entry as a length-prefixed frame
+    ///
+    /// # Errors
+    ///
+    /// Returns a formatter error if the entry cannot be serialized or the
+    /// payload exceeds [`MAX_FRAME_LEN`].
+    pub fn encode_frame(&self, entry: &StructuredLogEntry) -> Result<Vec<u8>> {
+        let payload = self.encode(entry)?;
+        if payload.len() > MAX_FRAME_LEN {
+            return Err(LoggerError::formatter(
+                self.name(),
+                format!("frame of {} bytes exceeds {} bytes", payload.len(), MAX_FRAME_LEN),
+            ));
+        }
+
+        let mut frame = Vec::with_capacity(4 + payload.len());
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+        Ok(frame)
+    }
+
+    /// Write an entry as a length-prefixed frame, returning the bytes written
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if encoding or writing fails.
+    pub fn write_frame(
+        &self,
+        entry: &StructuredLogEntry,
+        writer: &mut impl Write,
+    ) -> Result<usize> {
+        let frame = self.encode_frame(entry)?;
+        writer.write_all(&frame)?;
+        Ok(frame.len())
+    }
+
+    /// Read the next length-prefixed frame
+    ///
+    /// Returns `Ok(None)` at a clean end of input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for truncated frames, frames larger than
+    /// [`MAX_FRAME_LEN`] and payloads that fail to decode.
+    pub fn read_frame(&self, reader: &mut impl Read) -> Result<Option<StructuredLogEntry>> {
+        let mut len = [0u8; 4];
+        match reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+
+        let len = u32::from_be_bytes(len) as usize;
+        if len > MAX_FRAME_LEN {
+            return Err(LoggerError::formatter(
+                self.name(),
+                format!("frame of {} bytes exceeds {} bytes", len, MAX_FRAME_LEN),
+            ));
+        }
+
+        let mut payload = vec![0u8; len];
+        reader.read_exact(&mut payload)?;
+        self.decode(&payload).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{LogContext, LogLevel, TracingContext};
+
+    fn formats() -> Vec<BinaryFormat> {
+        vec![
+            #[cfg(feature = "msgpack")]
+            BinaryFormat::MessagePack,
+            #[cfg(feature = "cbor")]
+            BinaryFormat::Cbor,
+        ]
+    }
+
+    fn entry() -> StructuredLogEntry {
+        let context = LogContext::new()
+            .with_field("user_id", 42)
+            .with_field("path", "/api/orders")
+            .with_field("latency_ms", 12.5)
+            .with_field("cached", false);
+        StructuredLogEntry::from_context(LogLevel::Warn, "slow request", context)
+            .with_tracing(TracingContext::new("trace-1".to_string(), "span-1".to_string()))
+    }
+
+    #[test]
+    fn test_round_trip_and_size() {
+        let entry = entry();
+        for format in formats() {
+            let payload = format.encode(&entry).unwrap();
+            let decoded = format.decode(&payload).unwrap();
+
+            assert_eq!(decoded.timestamp, entry.timestamp, "{}", format.name());
+            assert_eq!(decoded.level, LogLevel::Warn);
+            assert_eq!(decoded.message, "slow request");
+            assert_eq!(decoded.context.fields(), entry.context.fields());
+            assert_eq!(decoded.tracing.unwrap().span_id, "span-1");
+
+            assert!(payload.len() < entry.to_json().unwrap().len());
+        }
+    }
+
+    #[test]
+    fn test_frames() {
+        for format in formats() {
+            let mut stream = Vec::new();
+            let written = format.write_frame(&entry(), &mut stream).unwrap();
+            format
+                .write_frame(&StructuredLogEntry::new(LogLevel::Info, "second"), &mut stream)
+                .unwrap();
+            let len = u32::from_be_bytes(stream[..4].try_into().unwrap()) as usize;
+            assert_eq!(len, written - 4);
+
+            let mut reader = stream.as_slice();
+            assert_eq!(format.read_frame(&mut reader).unwrap().unwrap().message, "slow request");
+            assert_eq!(format.read_frame(&mut reader).unwrap().unwrap().message, "second");
+            assert!(format.read_frame(&mut reader).unwrap().is_none());
+
+            // Truncated payload
+            assert!(format.read_frame(&mut &stream[..written - 1]).is_err());
+            // Oversized length prefix
+            let oversized = (MAX_FRAME_LEN as u32 + 1).to_be_bytes();
+            assert!(format.read_frame(&mut oversized.as_slice()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_file_appender_frames() {
+        use crate::appenders::FileAppender;
+        use crate::core::{Appender, LogEntry};
+
+        let dir = tempfile::tempdir().unwrap();
+        for format in formats() {
+            let path = dir.path().join(format!("app.{}", format.name()));
+            let mut appender = FileAppender::new(&path).unwrap().with_binary_format(format);
+            for i in 0..3 {
+                let entry = LogEntry::new(LogLevel::Info, format!("entry {}", i))
+                    .with_context(LogContext::new().with_field("i", i));
+                appender.append(&entry).unwrap();
+            }
+            appender.flush().unwrap();
+
+            let bytes = std::fs::read(&path).unwrap();
+            let mut reader = bytes.as_slice();
+            for i in 0..3 {
+                let entry = format.read_frame(&mut reader).unwrap().unwrap();
+                assert_eq!(entry.message, format!("entry {}", i));
+                assert_eq!(entry.context.fields()["i"], i.into());
+            }
+            assert!(format.read_frame(&mut reader).unwrap().is_none());
+        }
+    }
+}
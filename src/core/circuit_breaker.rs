@@ -0,0 +1,194 @@
+//! Circuit breaker for failing appenders
+//!
+//! Without a breaker, an appender that is permanently broken (a full disk, a
+//! server that is gone) is called, fails and is reported for every entry.
+//! With [`LoggerBuilder::circuit_breaker`], each appender gets its own
+//! breaker:
+//!
+//! - **Closed**: entries are written normally. After `failure_threshold`
+//!   consecutive failed or panicked appends the circuit opens.
+//! - **Open**: the appender is skipped, neither appended to nor flushed;
+//!   entries count as dropped for it. After `probe_interval` the circuit
+//!   becomes half-open.
+//! - **HalfOpen**: the next entry is written as a probe. Success closes the
+//!   circuit, failure opens it for another interval.
+//!
+//! Every transition is delivered as [`LoggerEvent::CircuitChanged`] and
+//! [`DiagnosticEvent::CircuitChanged`], and the current state and number of
+//! skipped entries are in the appender's
+//! [`AppenderMetrics`](super::AppenderMetrics).
+//!
+//! [`LoggerBuilder::circuit_breaker`]: super::LoggerBuilder::circuit_breaker
+//! [`LoggerEvent::CircuitChanged`]: super::LoggerEvent::CircuitChanged
+//! [`DiagnosticEvent::CircuitChanged`]: super::DiagnosticEvent::CircuitChanged
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::core::{CircuitBreakerConfig, CircuitState};
+//! use rust_logger_system::prelude::*;
+//! use std::time::Duration;
+//!
+//! let logger = Logger::builder()
+//!     .appender(ConsoleAppender::new())
+//!     .circuit_breaker(CircuitBreakerConfig::new(5, Duration::from_secs(30)))
+//!     .build();
+//!
+//! let console = logger.metrics().appender("console").unwrap();
+//! assert_eq!(console.circuit_state(), CircuitState::Closed);
+//! ```
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Settings of the per-appender circuit breakers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit (at least 1)
+    pub failure_threshold: u32,
+
+    /// Time an open circuit waits before probing the appender again
+    pub probe_interval: Duration,
+}
+
+impl CircuitBreakerConfig {
+    /// Open after `failure_threshold` failures and probe every `probe_interval`
+    pub fn new(failure_threshold: u32, probe_interval: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            probe_interval,
+        }
+    }
+}
+
+impl Default for CircuitBreakerConfig {
+    /// Open after 5 consecutive failures, probe every 30 seconds
+    fn default() -> Self {
+        Self::new(5, Duration::from_secs(30))
+    }
+}
+
+/// State of an appender's circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CircuitState {
+    /// Entries are written normally
+    #[default]
+    Closed,
+
+    /// The appender is skipped
+    Open,
+
+    /// The next entry is written to probe the appender
+    HalfOpen,
+}
+
+impl CircuitState {
+    /// Stable lowercase name (`closed`, `open`, `half_open`)
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+impl fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Breaker of one appender, owned by the logger
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: CircuitState,
+    failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: CircuitState::Closed,
+            failures: 0,
+            opened_at: None,
+        }
+    }
+
+    pub(crate) fn state(&self) -> CircuitState {
+        self.state
+    }
+
+    /// Consecutive failures so far
+    pub(crate) fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// Whether the appender is skipped, without changing the state
+    pub(crate) fn is_open(&self) -> bool {
+        self.state == CircuitState::Open
+    }
+
+    /// Whether the next entry should be written
+    ///
+    /// Moves an open circuit to half-open once the probe interval passed.
+    pub(crate) fn allow(&mut self, now: Instant) -> bool {
+        match (self.state, self.opened_at) {
+            (CircuitState::Open, Some(opened)) => {
+                if now.saturating_duration_since(opened) < self.config.probe_interval {
+                    return false;
+                }
+                self.state = CircuitState::HalfOpen;
+                true
+            }
+            _ => true,
+        }
+    }
+
+    pub(crate) fn record_success(&mut self) {
+        self.state = CircuitState::Closed;
+        self.failures = 0;
+        self.opened_at = None;
+    }
+
+    pub(crate) fn record_failure(&mut self, now: Instant) {
+        self.failures = self.failures.saturating_add(1);
+        if self.state == CircuitState::HalfOpen || self.failures >= self.config.failure_threshold {
+            self.state = CircuitState::Open;
+            self.opened_at = Some(now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transitions() {
+        let interval = Duration::from_secs(10);
+        let mut breaker = CircuitBreaker::new(CircuitBreakerConfig::new(2, interval));
+        let start = Instant::now();
+
+        breaker.record_failure(start);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        breaker.record_failure(start);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow(start + Duration::from_secs(1)));
+
+        // A failed probe opens the circuit for another interval
+        assert!(breaker.allow(start + interval));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        breaker.record_failure(start + interval);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow(start + interval + Duration::from_secs(1)));
+
+        assert!(breaker.allow(start + interval * 2));
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.failures(), 0);
+    }
+}
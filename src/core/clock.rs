@@ -0,0 +1,132 @@
+//! Time source for time-based logic
+//!
+//! Rotation intervals, the adaptive sampling rate, burst suppression
+//! windows, circuit breaker probes and [`throttle!`](crate::throttle) read
+//! the time through a [`Clock`]. By default that is the
+//! [`SystemClock`]; tests pass a [`ManualClock`] and advance it instead of
+//! sleeping.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::core::suppression::SuppressionDecision;
+//! use rust_logger_system::core::{BurstSuppressor, ManualClock, SuppressionConfig};
+//! use rust_logger_system::prelude::*;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let clock = Arc::new(ManualClock::new());
+//! let config = SuppressionConfig::new(1, Duration::from_secs(60));
+//! let suppressor = BurstSuppressor::with_clock(config, clock.clone());
+//! let entry = LogEntry::new(LogLevel::Warn, "retrying".to_string());
+//!
+//! assert_eq!(suppressor.check(&entry), SuppressionDecision::Allow);
+//! assert_eq!(suppressor.check(&entry), SuppressionDecision::Suppress);
+//! clock.advance(Duration::from_secs(60));
+//! assert_eq!(suppressor.check(&entry), SuppressionDecision::AllowWithSummary(1));
+//! ```
+
+use parking_lot::Mutex;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Source of the current time
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// Current monotonic time, for measuring intervals
+    fn now(&self) -> Instant;
+
+    /// Current wall-clock time, for calendar-based decisions
+    fn system_time(&self) -> SystemTime;
+}
+
+/// The real time of the operating system
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// The shared [`SystemClock`]
+pub(crate) fn system_clock() -> Arc<dyn Clock> {
+    static SYSTEM: OnceLock<Arc<dyn Clock>> = OnceLock::new();
+    Arc::clone(SYSTEM.get_or_init(|| Arc::new(SystemClock)))
+}
+
+/// Virtual time that only moves when advanced
+///
+/// Both the monotonic and the wall-clock time start at the moment the clock
+/// is created (or at the given wall-clock time) and move together.
+#[derive(Debug)]
+pub struct ManualClock {
+    start: Instant,
+    start_time: SystemTime,
+    elapsed: Mutex<Duration>,
+}
+
+impl ManualClock {
+    /// Create a clock standing at the current time
+    pub fn new() -> Self {
+        Self::starting_at(SystemTime::now())
+    }
+
+    /// Create a clock whose wall-clock time starts at `time`
+    pub fn starting_at(time: SystemTime) -> Self {
+        Self {
+            start: Instant::now(),
+            start_time: time,
+            elapsed: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Move the clock forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock() += duration;
+    }
+
+    /// Time the clock has been advanced by in total
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock()
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        self.start_time + self.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_moves_only_when_advanced() {
+        let clock = ManualClock::starting_at(SystemTime::UNIX_EPOCH);
+        let before = clock.now();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(clock.now(), before);
+        assert_eq!(clock.system_time(), SystemTime::UNIX_EPOCH);
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(clock.now() - before, Duration::from_secs(90));
+        assert_eq!(clock.system_time(), SystemTime::UNIX_EPOCH + Duration::from_secs(90));
+    }
+}
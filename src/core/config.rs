@@ -0,0 +1,1359 @@
+//! Declarative logger configuration
+//!
+//! [`LoggerConfig`] describes a logger (levels, appenders, rotation, formats,
+//! sampling and overflow handling) as data, so it can be loaded from a file
+//! managed outside the application. JSON is always supported; TOML and YAML
+//! need the `config-toml` and `config-yaml` features.
+//!
+//! Level, format and color names are case-insensitive and use the same
+//! spelling as the [`env_config`](super::env_config) directives.
+//!
+//! # Example
+//!
+//! ```toml
+//! level = "info"
+//! async_buffer = 10000
+//! overflow_policy = "drop_newest"
+//!
+//! [modules]
+//! "my_app::db" = "debug"
+//!
+//! [loggers]
+//! "db.pool" = "trace"
+//!
+//! [fields]
+//! service = "checkout"
+//!
+//! [sampling]
+//! rate = 0.25
+//! always_sample = ["warn", "error", "fatal"]
+//!
+//! [[appenders]]
+//! type = "console"
+//! format = "pretty"
+//!
+//! [[appenders]]
+//! type = "rotating_file"
+//! path = "logs/app.log"
+//! max_size = 52428800
+//! max_backups = 7
+//! compress = true
+//! ```
+//!
+//! ```no_run
+//! use rust_logger_system::Logger;
+//!
+//! let logger = Logger::from_config_file("logger.toml")?;
+//! # Ok::<(), rust_logger_system::LoggerError>(())
+//! ```
+
+use super::appender::Appender;
+use super::env_config::EnvConfig;
+use super::error::{LoggerError, Result};
+use super::log_context::FieldValue;
+use super::log_level::LogLevel;
+use super::logger::{Logger, LoggerBuilder};
+use super::output_format::OutputFormat;
+use super::overflow_policy::{OverflowPolicy, PriorityConfig};
+use super::sampling::{SamplingConfig, SamplingMode};
+use crate::appenders::{
+    ColorMode, ConsoleAppender, FileAppender, JsonAppender, NetworkAppender,
+    RotatingFileAppender, RotationPolicy, RotationStrategy,
+};
+use indexmap::IndexMap;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Logger settings loaded from a configuration file
+///
+/// Every field is optional; an empty configuration builds the same logger
+/// as [`Logger::builder`] without appenders.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggerConfig {
+    /// Minimum level (Info if unset)
+    #[serde(deserialize_with = "parse_optional")]
+    pub level: Option<LogLevel>,
+
+    /// Minimum levels for modules and their submodules
+    #[serde(deserialize_with = "parse_level_map")]
+    pub modules: IndexMap<String, LogLevel>,
+
+    /// Minimum levels for child loggers and their descendants
+    ///
+    /// See [`Logger::set_logger_level`].
+    #[serde(deserialize_with = "parse_level_map")]
+    pub loggers: IndexMap<String, LogLevel>,
+
+    /// Fields added to every entry
+    pub fields: IndexMap<String, FieldValue>,
+
+    /// Async queue size; the logger is synchronous if unset
+    pub async_buffer: Option<usize>,
+
+    /// Queue overflow handling: `drop_newest`, `drop_oldest`, `block` or
+    /// `alert_and_drop`
+    pub overflow_policy: Option<String>,
+
+    /// Maximum wait for the `block` policy, in milliseconds
+    pub overflow_timeout_ms: Option<u64>,
+
+    /// Preservation of high-priority entries when the queue is full
+    pub priority: Option<PriorityConfig>,
+
+    /// Log sampling
+    pub sampling: Option<SamplingSettings>,
+
+    /// Output destinations, in order
+    pub appenders: Vec<AppenderConfig>,
+
+    /// Names of appenders to disable, e.g. `["console"]`
+    ///
+    /// See [`Logger::set_appender_enabled`].
+    pub disabled_appenders: Vec<String>,
+}
+
+/// Sampling section of a [`LoggerConfig`]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SamplingSettings {
+    /// Sample rate between 0.0 and 1.0
+    pub rate: f64,
+
+    /// Levels that are never sampled (Error and Fatal if unset)
+    #[serde(deserialize_with = "parse_optional_levels")]
+    pub always_sample: Option<Vec<LogLevel>>,
+
+    /// Per-category sample rates
+    pub categories: HashMap<String, f64>,
+
+    /// Context field used for consistent sampling
+    pub key_field: Option<String>,
+
+    /// Messages per second above which the rate is reduced adaptively
+    pub adaptive_threshold: Option<usize>,
+
+    /// Lowest rate used by adaptive sampling
+    pub adaptive_min_rate: Option<f64>,
+
+    /// `random`, `counter` or `seeded:<seed>` (default: `random`)
+    #[serde(deserialize_with = "parse_optional")]
+    pub mode: Option<SamplingMode>,
+}
+
+impl Default for SamplingSettings {
+    fn default() -> Self {
+        Self {
+            rate: 1.0,
+            always_sample: None,
+            categories: HashMap::new(),
+            key_field: None,
+            adaptive_threshold: None,
+            adaptive_min_rate: None,
+            mode: None,
+        }
+    }
+}
+
+impl SamplingSettings {
+    /// Convert to the sampler configuration
+    pub fn to_sampling_config(&self) -> SamplingConfig {
+        let mut config = SamplingConfig::new(self.rate);
+        if let Some(ref levels) = self.always_sample {
+            config = config.with_always_sample(levels.clone());
+        }
+        for (category, rate) in &self.categories {
+            config = config.with_category_rate(category.clone(), *rate);
+        }
+        if let Some(ref field) = self.key_field {
+            config = config.with_key_field(field.clone());
+        }
+        if let Some(threshold) = self.adaptive_threshold {
+            let min_rate = self.adaptive_min_rate.unwrap_or(config.adaptive_min_rate);
+            config = config.with_adaptive(threshold, min_rate);
+        }
+        if let Some(mode) = self.mode {
+            config = config.with_mode(mode);
+        }
+        config
+    }
+}
+
+/// One output destination of a [`LoggerConfig`], selected by `type`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum AppenderConfig {
+    /// Console output
+    Console {
+        /// `text`, `json`, `logfmt`, `otel` or `pretty`
+        #[serde(default, deserialize_with = "parse_optional")]
+        format: Option<OutputFormat>,
+        /// `auto`, `always` or `never`
+        #[serde(default, deserialize_with = "parse_optional")]
+        color: Option<ColorMode>,
+    },
+
+    /// Plain text file
+    File {
+        /// File path
+        path: PathBuf,
+    },
+
+    /// Text file rotated by size and/or time
+    ///
+    /// With neither `max_size` nor `interval_secs` nor `daily_at` set, the
+    /// default size limit of [`RotationStrategy`] applies.
+    RotatingFile {
+        /// File path
+        path: PathBuf,
+        /// Rotate when the file exceeds this many bytes
+        #[serde(default)]
+        max_size: Option<u64>,
+        /// Rotate after this many seconds
+        #[serde(default)]
+        interval_secs: Option<u64>,
+        /// Rotate daily at this hour (0-23); cannot be combined with the above
+        #[serde(default)]
+        daily_at: Option<u8>,
+        /// Number of rotated files to keep
+        #[serde(default)]
+        max_backups: Option<usize>,
+        /// Compress rotated files
+        #[serde(default)]
+        compress: bool,
+    },
+
+    /// JSON lines file
+    Json {
+        /// File path
+        path: PathBuf,
+        /// Pretty-print each entry
+        #[serde(default)]
+        pretty: bool,
+    },
+
+    /// TCP stream
+    Network {
+        /// Socket address, e.g. `logs.internal:5140`
+        address: String,
+    },
+}
+
+impl AppenderConfig {
+    /// Rotation policy of a `rotating_file` appender
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `daily_at` is not a valid hour or is combined
+    /// with `max_size` or `interval_secs`.
+    pub fn rotation_policy(&self) -> Result<Option<RotationPolicy>> {
+        let AppenderConfig::RotatingFile {
+            max_size,
+            interval_secs,
+            daily_at,
+            max_backups,
+            compress,
+            ..
+        } = *self
+        else {
+            return Ok(None);
+        };
+
+        let interval = interval_secs.map(Duration::from_secs);
+        let strategy = match (max_size, interval, daily_at) {
+            (_, _, Some(hour)) if hour > 23 => {
+                return Err(LoggerError::config(
+                    "rotation",
+                    format!("daily_at must be between 0 and 23, got {}", hour),
+                ));
+            }
+            (None, None, Some(hour)) => RotationStrategy::daily(hour),
+            (_, _, Some(_)) => {
+                return Err(LoggerError::config(
+                    "rotation",
+                    "daily_at cannot be combined with max_size or interval_secs",
+                ));
+            }
+            (Some(max_bytes), Some(interval), None) => {
+                RotationStrategy::hybrid(max_bytes, interval)
+            }
+            (Some(max_bytes), None, None) => RotationStrategy::size(max_bytes),
+            (None, Some(interval), None) => RotationStrategy::time(interval),
+            (None, None, None) => RotationStrategy::default(),
+        };
+
+        let mut policy = RotationPolicy::new()
+            .with_strategy(strategy)
+            .with_compression(compress);
+        if let Some(count) = max_backups {
+            policy = policy.with_max_backups(count);
+        }
+        Ok(Some(policy))
+    }
+
+    /// Check the settings without opening the file or connection
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for an empty path, a network address that is not
+    /// `host:port`, or invalid rotation settings.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            AppenderConfig::Console { .. } => Ok(()),
+            AppenderConfig::File { path } | AppenderConfig::Json { path, .. } => {
+                check_path(path)
+            }
+            AppenderConfig::RotatingFile { path, .. } => {
+                check_path(path)?;
+                self.rotation_policy().map(drop)
+            }
+            AppenderConfig::Network { address } => match address.rsplit_once(':') {
+                Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => Ok(()),
+                _ => Err(LoggerError::config(
+                    "address",
+                    format!("expected host:port, got '{}'", address),
+                )),
+            },
+        }
+    }
+
+    /// Create the appender, opening its file or connection
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the settings are invalid (see
+    /// [`validate`](Self::validate)) or the file or connection cannot be
+    /// opened.
+    pub fn open(&self) -> Result<Box<dyn Appender>> {
+        self.validate()?;
+        Ok(match self {
+            AppenderConfig::Console { format, color } => Box::new(
+                ConsoleAppender::new()
+                    .with_color_mode(color.unwrap_or_default())
+                    .with_output_format(format.clone().unwrap_or_default()),
+            ),
+            AppenderConfig::File { path } => Box::new(FileAppender::new(path)?),
+            AppenderConfig::RotatingFile { path, .. } => {
+                let policy = self.rotation_policy()?.unwrap_or_default();
+                Box::new(RotatingFileAppender::with_policy(path, policy)?)
+            }
+            AppenderConfig::Json { path, pretty: false } => Box::new(JsonAppender::new(path)?),
+            AppenderConfig::Json { path, pretty: true } => {
+                Box::new(JsonAppender::new_pretty(path)?)
+            }
+            AppenderConfig::Network { address } => {
+                Box::new(NetworkAppender::new(address.as_str())?)
+            }
+        })
+    }
+}
+
+impl fmt::Display for AppenderConfig {
+    /// One-line summary used by [`LoggerConfig::describe`]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppenderConfig::Console { format, color } => {
+                f.write_str("console, ")?;
+                match format.as_ref().unwrap_or(&OutputFormat::default()) {
+                    OutputFormat::Text => f.write_str("text")?,
+                    OutputFormat::Json => f.write_str("json")?,
+                    OutputFormat::Logfmt => f.write_str("logfmt")?,
+                    OutputFormat::Pattern(layout) => write!(f, "pattern '{}'", layout.as_str())?,
+                    OutputFormat::OtelJson => f.write_str("otel")?,
+                    OutputFormat::Pretty => f.write_str("pretty")?,
+                }
+                let color = match color.unwrap_or_default() {
+                    ColorMode::Auto => "auto",
+                    ColorMode::Always => "always",
+                    ColorMode::Never => "never",
+                };
+                write!(f, " format, color {}", color)
+            }
+            AppenderConfig::File { path } => write!(f, "file {}", path.display()),
+            AppenderConfig::RotatingFile { path, .. } => {
+                write!(f, "rotating_file {}", path.display())?;
+                let Ok(Some(policy)) = self.rotation_policy() else {
+                    return Ok(());
+                };
+                match policy.strategy {
+                    RotationStrategy::Size { max_bytes } => {
+                        write!(f, ", rotated at {} bytes", max_bytes)?
+                    }
+                    RotationStrategy::Time { interval } => {
+                        write!(f, ", rotated every {}s", interval.as_secs())?
+                    }
+                    RotationStrategy::Daily { hour } => {
+                        write!(f, ", rotated daily at {:02}:00", hour)?
+                    }
+                    RotationStrategy::Hourly => write!(f, ", rotated hourly")?,
+                    RotationStrategy::Hybrid { max_bytes, interval } => write!(
+                        f,
+                        ", rotated at {} bytes or every {}s",
+                        max_bytes,
+                        interval.as_secs()
+                    )?,
+                    RotationStrategy::Never => write!(f, ", never rotated")?,
+                }
+                write!(f, ", {} backups kept", policy.max_backup_files)?;
+                if policy.compress {
+                    write!(f, ", compressed")?;
+                }
+                Ok(())
+            }
+            AppenderConfig::Json { path, pretty } => {
+                write!(f, "json {}", path.display())?;
+                if *pretty {
+                    write!(f, ", pretty-printed")?;
+                }
+                Ok(())
+            }
+            AppenderConfig::Network { address } => write!(f, "network {}", address),
+        }
+    }
+}
+
+impl LoggerConfig {
+    /// Parse a JSON configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document is malformed or has unknown keys or
+    /// invalid names.
+    pub fn from_json(contents: &str) -> Result<Self> {
+        serde_json::from_str(contents).map_err(|e| LoggerError::config("config", e.to_string()))
+    }
+
+    /// Parse a TOML configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document is malformed or has unknown keys or
+    /// invalid names.
+    #[cfg(feature = "config-toml")]
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        toml::from_str(contents).map_err(|e| LoggerError::config("config", e.to_string()))
+    }
+
+    /// Parse a YAML configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document is malformed or has unknown keys or
+    /// invalid names.
+    #[cfg(feature = "config-yaml")]
+    pub fn from_yaml(contents: &str) -> Result<Self> {
+        serde_yaml::from_str(contents).map_err(|e| LoggerError::config("config", e.to_string()))
+    }
+
+    /// Load a configuration file, choosing the format by extension
+    ///
+    /// `.json` is always supported, `.toml` with the `config-toml` feature
+    /// and `.yaml`/`.yml` with the `config-yaml` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, its extension is not
+    /// supported, or it does not parse.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            LoggerError::io_operation("reading configuration", path.display().to_string(), e)
+        })?;
+        Self::from_file_contents(path, &contents)
+    }
+
+    /// Parse the contents of a configuration file, choosing the format by
+    /// the extension of `path`
+    pub(crate) fn from_file_contents(path: &Path, contents: &str) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase);
+
+        let parsed = match extension.as_deref() {
+            Some("json") => Self::from_json(contents),
+            #[cfg(feature = "config-toml")]
+            Some("toml") => Self::from_toml(contents),
+            #[cfg(feature = "config-yaml")]
+            Some("yaml" | "yml") => Self::from_yaml(contents),
+            _ => Err(LoggerError::config(
+                "config",
+                "unsupported file extension (enable the config-toml or config-yaml feature \
+                 for TOML or YAML)",
+            )),
+        };
+        parsed.map_err(|e| LoggerError::config("config", format!("{}: {}", path.display(), e)))
+    }
+
+    /// Overflow policy selected by `overflow_policy` and `overflow_timeout_ms`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for an unknown policy name, or a timeout without the
+    /// `block` policy.
+    pub fn overflow_policy(&self) -> Result<Option<OverflowPolicy>> {
+        let timeout = self.overflow_timeout_ms.map(Duration::from_millis);
+        match self.overflow_policy {
+            Some(ref name) => parse_overflow_policy(name, timeout).map(Some),
+            None if timeout.is_some() => Err(LoggerError::config(
+                "overflow_policy",
+                "overflow_timeout_ms requires overflow_policy = \"block\"",
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Create a builder with these settings, opening all appenders
+    ///
+    /// Further settings and appenders can be added to the builder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a setting is invalid or an appender cannot be
+    /// opened.
+    pub fn builder(&self) -> Result<LoggerBuilder> {
+        let mut builder = self.settings()?;
+        for appender in &self.appenders {
+            builder = builder.boxed_appender(appender.open()?);
+        }
+        Ok(builder)
+    }
+
+    /// Builder with every setting except the appenders
+    fn settings(&self) -> Result<LoggerBuilder> {
+        let base_level = self.level.unwrap_or_default();
+        let levels = self.levels();
+
+        let mut builder = Logger::builder().min_level(levels.min_level().unwrap_or(base_level));
+        if let Some(filter) = levels.module_filter(base_level) {
+            builder = builder.filter(filter);
+        }
+        for (key, value) in &self.fields {
+            builder = builder.global_field(key.clone(), value.clone());
+        }
+        if let Some(size) = self.async_buffer {
+            builder = builder.async_mode(size);
+        }
+        if let Some(policy) = self.overflow_policy()? {
+            builder = builder.overflow_policy(policy);
+        }
+        if let Some(ref priority) = self.priority {
+            builder = builder.priority_config(priority.clone());
+        }
+        if let Some(ref sampling) = self.sampling {
+            builder = builder.with_sampling(sampling.to_sampling_config());
+        }
+        for name in &self.disabled_appenders {
+            builder = builder.disable_appender(name.clone());
+        }
+        for (name, level) in &self.loggers {
+            builder = builder.logger_level(name.clone(), *level);
+        }
+        Ok(builder)
+    }
+
+    /// Check the configuration without opening files or connections
+    ///
+    /// Runs the checks of [`LoggerBuilder::validate`] and
+    /// [`AppenderConfig::validate`], so a configuration file can be verified
+    /// in CI on a machine that cannot reach its log paths or servers.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found. Problems with an appender name its
+    /// position in the list, e.g. `appenders[1].rotation`.
+    pub fn validate(&self) -> Result<()> {
+        self.settings()?.validate()?;
+        for (index, appender) in self.appenders.iter().enumerate() {
+            appender.validate().map_err(|e| match e {
+                LoggerError::InvalidConfiguration { component, message } => {
+                    LoggerError::config(format!("appenders[{}].{}", index, component), message)
+                }
+                other => other,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Summarize the logger this configuration builds
+    ///
+    /// The configuration is validated first; nothing is opened.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`validate`](Self::validate).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::LoggerConfig;
+    ///
+    /// let config = LoggerConfig::from_json(
+    ///     r#"{ "level": "debug", "appenders": [{ "type": "file", "path": "app.log" }] }"#,
+    /// )?;
+    /// let summary = config.describe()?;
+    /// assert!(summary.contains("level: debug"));
+    /// assert!(summary.contains("1. file app.log"));
+    /// # Ok::<(), rust_logger_system::LoggerError>(())
+    /// ```
+    pub fn describe(&self) -> Result<String> {
+        self.validate()?;
+        let mut out = String::new();
+        let pairs = |map: &IndexMap<String, LogLevel>| {
+            map.iter()
+                .map(|(name, level)| format!("{}={}", name, level.to_str().to_lowercase()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let _ = writeln!(out, "level: {}", self.level.unwrap_or_default().to_str().to_lowercase());
+        if !self.modules.is_empty() {
+            let _ = writeln!(out, "module levels: {}", pairs(&self.modules));
+        }
+        if !self.loggers.is_empty() {
+            let _ = writeln!(out, "logger levels: {}", pairs(&self.loggers));
+        }
+        if !self.fields.is_empty() {
+            let fields: Vec<_> =
+                self.fields.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+            let _ = writeln!(out, "fields: {}", fields.join(", "));
+        }
+
+        match self.async_buffer {
+            Some(size) => {
+                let priority = self.priority.clone().unwrap_or_default();
+                let _ = writeln!(
+                    out,
+                    "mode: async, queue of {} entries, overflow {}",
+                    size,
+                    self.overflow_policy()?.unwrap_or_default()
+                );
+                let _ = writeln!(
+                    out,
+                    "priority: preserve critical {}, preserve warn {} ({} retries), \
+                     block on critical {}",
+                    priority.preserve_critical,
+                    priority.preserve_high,
+                    priority.high_priority_retry_count,
+                    priority.block_on_critical
+                );
+            }
+            None => {
+                let _ = writeln!(out, "mode: synchronous");
+            }
+        }
+
+        if let Some(ref sampling) = self.sampling {
+            let config = sampling.to_sampling_config();
+            let always: Vec<_> =
+                config.always_sample.iter().map(|l| l.to_str().to_lowercase()).collect();
+            let _ = write!(
+                out,
+                "sampling: rate {}, always sampled: {}",
+                config.rate,
+                always.join("/")
+            );
+            let mut categories: Vec<_> = config.category_rates.iter().collect();
+            categories.sort_by(|a, b| a.0.cmp(b.0));
+            for (category, rate) in categories {
+                let _ = write!(out, ", {} at {}", category, rate);
+            }
+            if config.adaptive {
+                let _ = write!(
+                    out,
+                    ", adaptive above {} msg/s down to {}",
+                    config.adaptive_threshold, config.adaptive_min_rate
+                );
+            }
+            if let Some(ref key) = config.key_field {
+                let _ = write!(out, ", keyed by {}", key);
+            }
+            if config.mode != SamplingMode::Random {
+                let _ = write!(out, ", {} mode", config.mode);
+            }
+            out.push('\n');
+        }
+
+        if self.appenders.is_empty() {
+            let _ = writeln!(out, "appenders: none (entries are discarded)");
+        } else {
+            let _ = writeln!(out, "appenders:");
+            for (index, appender) in self.appenders.iter().enumerate() {
+                let _ = writeln!(out, "  {}. {}", index + 1, appender);
+            }
+        }
+        if !self.disabled_appenders.is_empty() {
+            let _ = writeln!(out, "disabled appenders: {}", self.disabled_appenders.join(", "));
+        }
+        Ok(out)
+    }
+
+    /// Minimum level and module levels, in the form used by the builder
+    fn levels(&self) -> EnvConfig {
+        EnvConfig {
+            default_level: Some(self.level.unwrap_or_default()),
+            module_levels: self
+                .modules
+                .iter()
+                .map(|(module, level)| (module.clone(), *level))
+                .collect(),
+            ..EnvConfig::default()
+        }
+    }
+
+    /// Apply the changes from `previous` to a running logger
+    ///
+    /// `previous` is the configuration the logger was built from or last
+    /// updated with. The minimum level, child logger levels, sampling rates
+    /// and disabled appenders are updated, and appenders added to the end of the list
+    /// are opened and attached. Everything is validated, and new appenders
+    /// are opened, before the logger is changed, so an error leaves it
+    /// untouched.
+    ///
+    /// Other changes need a new logger; their setting names (`modules`,
+    /// `appenders`, ...) are returned so they can be reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a setting is invalid or a new appender cannot be
+    /// opened.
+    pub fn apply_to(
+        &self,
+        logger: &Logger,
+        previous: &LoggerConfig,
+    ) -> Result<Vec<&'static str>> {
+        self.validate()?;
+        let mut not_applied = Vec::new();
+        let added = match self.appenders.strip_prefix(previous.appenders.as_slice()) {
+            Some(added) => added.iter().map(AppenderConfig::open).collect::<Result<Vec<_>>>()?,
+            None => {
+                not_applied.push("appenders");
+                Vec::new()
+            }
+        };
+
+        let levels = self.levels();
+        let base_level = self.level.unwrap_or_default();
+        logger.store_min_level(levels.min_level().unwrap_or(base_level));
+        if self.modules != previous.modules {
+            not_applied.push("modules");
+        }
+        for name in previous.loggers.keys() {
+            if !self.loggers.contains_key(name) {
+                logger.clear_logger_level(name);
+            }
+        }
+        for (name, level) in &self.loggers {
+            logger.set_logger_level(name.clone(), *level);
+        }
+
+        let sampling = self.sampling.clone().unwrap_or_default();
+        let previous_sampling = previous.sampling.clone().unwrap_or_default();
+        if self.sampling.is_some() || logger.sampler().is_some() {
+            logger.set_sampling_rate(sampling.rate);
+        }
+        if let Some(sampler) = logger.sampler() {
+            for category in previous_sampling.categories.keys() {
+                if !sampling.categories.contains_key(category) {
+                    sampler.clear_category_rate(category);
+                }
+            }
+            for (category, rate) in &sampling.categories {
+                sampler.set_category_rate(category.clone(), *rate);
+            }
+        }
+        let rates_only = |settings: &SamplingSettings| SamplingSettings {
+            rate: 1.0,
+            categories: HashMap::new(),
+            ..settings.clone()
+        };
+        if rates_only(&sampling) != rates_only(&previous_sampling) {
+            not_applied.push("sampling");
+        }
+
+        for name in &previous.disabled_appenders {
+            logger.set_appender_enabled(name, true);
+        }
+        for name in &self.disabled_appenders {
+            logger.set_appender_enabled(name, false);
+        }
+        for appender in added {
+            logger.attach_appender(appender);
+        }
+
+        if self.fields != previous.fields {
+            not_applied.push("fields");
+        }
+        if self.async_buffer != previous.async_buffer {
+            not_applied.push("async_buffer");
+        }
+        if self.overflow_policy != previous.overflow_policy
+            || self.overflow_timeout_ms != previous.overflow_timeout_ms
+        {
+            not_applied.push("overflow_policy");
+        }
+        if self.priority != previous.priority {
+            not_applied.push("priority");
+        }
+        Ok(not_applied)
+    }
+
+    /// Build a logger with these settings
+    ///
+    /// The builder settings are checked with [`LoggerBuilder::try_build`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a setting is invalid or an appender cannot be
+    /// opened.
+    pub fn build(&self) -> Result<Logger> {
+        self.builder()?.try_build()
+    }
+}
+
+impl Logger {
+    /// Build a logger from a configuration file
+    ///
+    /// See [`LoggerConfig::from_file`] for the supported formats.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be loaded or describes an invalid
+    /// logger.
+    ///
+    /// `LOGGER_*` environment variables override the file; see
+    /// [`EnvOverrides`].
+    pub fn from_config_file(path: impl AsRef<Path>) -> Result<Logger> {
+        let mut config = LoggerConfig::from_file(path)?;
+        EnvOverrides::from_env()?.apply_to_config(&mut config);
+        config.build()
+    }
+}
+
+/// Prefix of the variables read by [`EnvOverrides::from_env`]
+pub const ENV_OVERRIDE_PREFIX: &str = "LOGGER";
+
+/// Settings overridden by `LOGGER_*` environment variables
+///
+/// Lets containers adjust logging without changing the image or its
+/// configuration file:
+///
+/// | Variable                 | Overrides                                     |
+/// |--------------------------|-----------------------------------------------|
+/// | `LOGGER_LEVEL`           | minimum level                                 |
+/// | `LOGGER_FORMAT`          | format of the console appenders               |
+/// | `LOGGER_COLOR`           | color mode of the console appenders           |
+/// | `LOGGER_FILE_PATH`       | path of the first file, rotating or JSON file |
+/// | `LOGGER_SAMPLING_RATE`   | sample rate (0.0 to 1.0)                      |
+/// | `LOGGER_ASYNC_BUFFER`    | async queue size                              |
+/// | `LOGGER_OVERFLOW_POLICY` | overflow policy                               |
+///
+/// Precedence, from lowest to highest: settings made in code or in a
+/// configuration file, then these variables. [`Logger::from_config_file`]
+/// applies them automatically; builders opt in with
+/// [`LoggerBuilder::env_overrides`], which cannot change appenders already
+/// added and therefore ignores `LOGGER_FORMAT` and `LOGGER_COLOR`, and adds a
+/// file appender for `LOGGER_FILE_PATH`.
+///
+/// Unset or empty variables leave the setting unchanged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnvOverrides {
+    /// `LOGGER_LEVEL`
+    pub level: Option<LogLevel>,
+    /// `LOGGER_FORMAT`
+    pub format: Option<OutputFormat>,
+    /// `LOGGER_COLOR`
+    pub color: Option<ColorMode>,
+    /// `LOGGER_FILE_PATH`
+    pub file_path: Option<PathBuf>,
+    /// `LOGGER_SAMPLING_RATE`
+    pub sampling_rate: Option<f64>,
+    /// `LOGGER_ASYNC_BUFFER`
+    pub async_buffer: Option<usize>,
+    /// `LOGGER_OVERFLOW_POLICY`, validated but kept as its configuration name
+    pub overflow_policy: Option<String>,
+}
+
+impl EnvOverrides {
+    /// Read the `LOGGER_*` variables
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first variable with an invalid value.
+    pub fn from_env() -> Result<Self> {
+        Self::from_env_with_prefix(ENV_OVERRIDE_PREFIX)
+    }
+
+    /// Read the variables named `<prefix>_LEVEL`, `<prefix>_FORMAT`, ...
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first variable with an invalid value.
+    pub fn from_env_with_prefix(prefix: &str) -> Result<Self> {
+        Self::from_lookup(prefix, |var| std::env::var(var).ok())
+    }
+
+    /// Read the variables through `lookup` instead of the process environment
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first variable with an invalid value.
+    pub fn from_lookup(prefix: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let get = |name: &str| {
+            let var = format!("{}_{}", prefix, name);
+            let value = lookup(&var).map(|value| value.trim().to_string());
+            value.filter(|value| !value.is_empty()).map(|value| (var, value))
+        };
+        fn parse<T: FromStr>(var: &str, value: &str) -> Result<T>
+        where
+            T::Err: std::fmt::Display,
+        {
+            value
+                .parse()
+                .map_err(|e| LoggerError::config("env", format!("{}: {}", var, e)))
+        }
+
+        let mut overrides = Self::default();
+        if let Some((var, value)) = get("LEVEL") {
+            overrides.level = Some(parse(&var, &value)?);
+        }
+        if let Some((var, value)) = get("FORMAT") {
+            overrides.format = Some(parse(&var, &value)?);
+        }
+        if let Some((var, value)) = get("COLOR") {
+            overrides.color = Some(parse(&var, &value)?);
+        }
+        if let Some((_, value)) = get("FILE_PATH") {
+            overrides.file_path = Some(PathBuf::from(value));
+        }
+        if let Some((var, value)) = get("SAMPLING_RATE") {
+            let rate: f64 = parse(&var, &value)?;
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(LoggerError::config(
+                    "env",
+                    format!("{}: sample rate must be between 0.0 and 1.0", var),
+                ));
+            }
+            overrides.sampling_rate = Some(rate);
+        }
+        if let Some((var, value)) = get("ASYNC_BUFFER") {
+            overrides.async_buffer = Some(parse(&var, &value)?);
+        }
+        if let Some((var, value)) = get("OVERFLOW_POLICY") {
+            parse_overflow_policy(&value, None)
+                .map_err(|e| LoggerError::config("env", format!("{}: {}", var, e)))?;
+            overrides.overflow_policy = Some(value);
+        }
+        Ok(overrides)
+    }
+
+    /// Whether no setting is overridden
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Apply the overrides to a configuration
+    ///
+    /// Format and color apply to every console appender, adding one if
+    /// there is none. The file path replaces the path of the first file,
+    /// rotating file or JSON appender, adding a file appender if there is
+    /// none.
+    pub fn apply_to_config(&self, config: &mut LoggerConfig) {
+        if let Some(level) = self.level {
+            config.level = Some(level);
+        }
+
+        if self.format.is_some() || self.color.is_some() {
+            let mut found = false;
+            for appender in &mut config.appenders {
+                if let AppenderConfig::Console { format, color } = appender {
+                    found = true;
+                    if self.format.is_some() {
+                        format.clone_from(&self.format);
+                    }
+                    if self.color.is_some() {
+                        *color = self.color;
+                    }
+                }
+            }
+            if !found {
+                config.appenders.push(AppenderConfig::Console {
+                    format: self.format.clone(),
+                    color: self.color,
+                });
+            }
+        }
+
+        if let Some(ref new_path) = self.file_path {
+            let path = config.appenders.iter_mut().find_map(|appender| match appender {
+                AppenderConfig::File { path }
+                | AppenderConfig::RotatingFile { path, .. }
+                | AppenderConfig::Json { path, .. } => Some(path),
+                _ => None,
+            });
+            match path {
+                Some(path) => path.clone_from(new_path),
+                None => config.appenders.push(AppenderConfig::File {
+                    path: new_path.clone(),
+                }),
+            }
+        }
+
+        if let Some(rate) = self.sampling_rate {
+            config.sampling.get_or_insert_with(SamplingSettings::default).rate = rate;
+        }
+        if let Some(size) = self.async_buffer {
+            config.async_buffer = Some(size);
+        }
+        if let Some(ref policy) = self.overflow_policy {
+            if !policy.eq_ignore_ascii_case("block") {
+                config.overflow_timeout_ms = None;
+            }
+            config.overflow_policy = Some(policy.clone());
+        }
+    }
+}
+
+/// Parse an overflow policy name, with the timeout of the `block` policy
+fn parse_overflow_policy(name: &str, timeout: Option<Duration>) -> Result<OverflowPolicy> {
+    match (name.trim().to_lowercase().as_str(), timeout) {
+        ("block", Some(timeout)) => Ok(OverflowPolicy::BlockWithTimeout(timeout)),
+        ("block", None) => Ok(OverflowPolicy::Block),
+        (_, Some(_)) => Err(LoggerError::config(
+            "overflow_policy",
+            "overflow_timeout_ms requires overflow_policy = \"block\"",
+        )),
+        ("drop_newest", None) => Ok(OverflowPolicy::DropNewest),
+        ("drop_oldest", None) => Ok(OverflowPolicy::DropOldest),
+        ("alert_and_drop", None) => Ok(OverflowPolicy::AlertAndDrop),
+        (other, None) => Err(LoggerError::config(
+            "overflow_policy",
+            format!("unknown policy '{}'", other),
+        )),
+    }
+}
+
+/// Overflow policy named by an already validated [`EnvOverrides`] value
+pub(crate) fn overridden_overflow_policy(name: &str) -> Option<OverflowPolicy> {
+    parse_overflow_policy(name, None).ok()
+}
+
+/// Reject an empty appender path
+fn check_path(path: &Path) -> Result<()> {
+    if path.as_os_str().is_empty() {
+        return Err(LoggerError::config("path", "must not be empty"));
+    }
+    Ok(())
+}
+
+/// Deserialize an optional value through its `FromStr` implementation
+fn parse_optional<'de, D, T>(deserializer: D) -> std::result::Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr<Err = String>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| value.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+fn parse_optional_levels<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<Vec<LogLevel>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<Vec<String>>::deserialize(deserializer)?
+        .map(|levels| {
+            levels
+                .iter()
+                .map(|level| level.parse().map_err(serde::de::Error::custom))
+                .collect()
+        })
+        .transpose()
+}
+
+fn parse_level_map<'de, D>(
+    deserializer: D,
+) -> std::result::Result<IndexMap<String, LogLevel>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    IndexMap::<String, String>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(module, level)| Ok((module, level.parse().map_err(serde::de::Error::custom)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{
+        "level": "warn",
+        "modules": { "my_app::db": "DEBUG" },
+        "fields": { "service": "checkout", "replica": 2 },
+        "async_buffer": 64,
+        "overflow_policy": "block",
+        "overflow_timeout_ms": 50,
+        "priority": { "preserve_high": false },
+        "sampling": { "rate": 0.5, "always_sample": ["error", "fatal"] },
+        "appenders": [
+            { "type": "console", "format": "json", "color": "never" },
+            { "type": "rotating_file", "path": "app.log", "max_size": 1024, "max_backups": 3 }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_json() {
+        let config = LoggerConfig::from_json(JSON).unwrap();
+
+        assert_eq!(config.level, Some(LogLevel::Warn));
+        assert_eq!(config.modules["my_app::db"], LogLevel::Debug);
+        assert_eq!(config.fields["replica"], FieldValue::Int(2));
+        assert_eq!(
+            config.overflow_policy().unwrap(),
+            Some(OverflowPolicy::BlockWithTimeout(Duration::from_millis(50)))
+        );
+        assert!(!config.priority.as_ref().unwrap().preserve_high);
+        assert!(config.priority.as_ref().unwrap().preserve_critical);
+        assert_eq!(
+            config.sampling.as_ref().unwrap().always_sample,
+            Some(vec![LogLevel::Error, LogLevel::Fatal])
+        );
+        assert_eq!(
+            config.appenders[0],
+            AppenderConfig::Console {
+                format: Some(OutputFormat::Json),
+                color: Some(ColorMode::Never),
+            }
+        );
+        let policy = config.appenders[1].rotation_policy().unwrap().unwrap();
+        assert_eq!(policy.strategy, RotationStrategy::size(1024));
+        assert_eq!(policy.max_backup_files, 3);
+    }
+
+    #[test]
+    fn test_invalid_configs() {
+        assert!(LoggerConfig::from_json(r#"{ "level": "loud" }"#).is_err());
+        assert!(LoggerConfig::from_json(r#"{ "levle": "info" }"#).is_err());
+        assert!(LoggerConfig::from_json(r#"{ "appenders": [{ "type": "syslog" }] }"#).is_err());
+        assert!(LoggerConfig::from_json(r#"{ "appenders": [{ "type": "file" }] }"#).is_err());
+
+        let config = LoggerConfig::from_json(r#"{ "overflow_policy": "drop_all" }"#).unwrap();
+        assert!(config.overflow_policy().is_err());
+        assert!(config.build().is_err());
+
+        let rotating = AppenderConfig::RotatingFile {
+            path: "app.log".into(),
+            max_size: Some(1024),
+            interval_secs: None,
+            daily_at: Some(2),
+            max_backups: None,
+            compress: false,
+        };
+        assert!(rotating.rotation_policy().is_err());
+    }
+
+    #[test]
+    fn test_validate_and_describe() {
+        let config = LoggerConfig::from_json(JSON).unwrap();
+        assert!(config.validate().is_ok());
+        assert!(!Path::new("app.log").exists());
+
+        let summary = config.describe().unwrap();
+        assert!(summary.contains("level: warn\n"));
+        assert!(summary.contains("module levels: my_app::db=debug\n"));
+        assert!(summary.contains("mode: async, queue of 64 entries, overflow BlockWithTimeout"));
+        assert!(summary.contains("sampling: rate 0.5, always sampled: error/fatal\n"));
+        assert!(summary.contains("  1. console, json format, color never\n"));
+        assert!(summary
+            .contains("  2. rotating_file app.log, rotated at 1024 bytes, 3 backups kept\n"));
+
+        let config = LoggerConfig::from_json(
+            r#"{ "appenders": [
+                { "type": "console" },
+                { "type": "rotating_file", "path": "app.log", "max_size": 1, "daily_at": 2 }
+            ] }"#,
+        )
+        .unwrap();
+        match config.validate() {
+            Err(LoggerError::InvalidConfiguration { component, .. }) => {
+                assert_eq!(component, "appenders[1].rotation");
+            }
+            other => panic!("expected a rotation error, got {:?}", other),
+        }
+        assert!(config.describe().is_err());
+
+        let console = AppenderConfig::Console {
+            format: Some(OutputFormat::pattern("%p %m").unwrap()),
+            color: None,
+        };
+        assert_eq!(console.to_string(), "console, pattern '%p %m' format, color auto");
+
+        let network = r#"{ "appenders": [{ "type": "network", "address": "localhost" }] }"#;
+        assert!(LoggerConfig::from_json(network).unwrap().validate().is_err());
+        let network = r#"{ "appenders": [{ "type": "network", "address": "localhost:5140" }] }"#;
+        assert!(LoggerConfig::from_json(network).unwrap().validate().is_ok());
+    }
+
+    #[test]
+    fn test_from_file_builds_logger() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("app.log");
+        let config_path = dir.path().join("logger.json");
+        std::fs::write(
+            &config_path,
+            serde_json::json!({
+                "level": "debug",
+                "appenders": [{ "type": "file", "path": log_path }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let logger = Logger::from_config_file(&config_path).unwrap();
+        logger.debug("configured from file");
+        logger.flush().unwrap();
+        drop(logger);
+
+        let written = std::fs::read_to_string(&log_path).unwrap();
+        assert!(written.contains("configured from file"));
+        assert!(Logger::from_config_file(dir.path().join("logger.ini")).is_err());
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let vars: HashMap<&str, &str> = [
+            ("APP_LEVEL", "debug"),
+            ("APP_FORMAT", "json"),
+            ("APP_FILE_PATH", "/var/log/app.log"),
+            ("APP_SAMPLING_RATE", "0.1"),
+            ("APP_OVERFLOW_POLICY", "drop_newest"),
+            ("APP_COLOR", " "),
+        ]
+        .into_iter()
+        .collect();
+        let overrides =
+            EnvOverrides::from_lookup("APP", |var| vars.get(var).map(|v| v.to_string())).unwrap();
+        assert_eq!(overrides.color, None);
+
+        let mut config = LoggerConfig::from_json(JSON).unwrap();
+        overrides.apply_to_config(&mut config);
+
+        assert_eq!(config.level, Some(LogLevel::Debug));
+        assert_eq!(config.overflow_policy().unwrap(), Some(OverflowPolicy::DropNewest));
+        assert_eq!(config.sampling.as_ref().unwrap().rate, 0.1);
+        assert_eq!(
+            config.appenders[0],
+            AppenderConfig::Console {
+                format: Some(OutputFormat::Json),
+                color: Some(ColorMode::Never),
+            }
+        );
+        assert!(matches!(
+            config.appenders[1],
+            AppenderConfig::RotatingFile { ref path, .. } if path == Path::new("/var/log/app.log")
+        ));
+
+        // Missing appenders are added
+        let mut config = LoggerConfig::default();
+        overrides.apply_to_config(&mut config);
+        assert_eq!(config.appenders.len(), 2);
+        assert!(EnvOverrides::default().is_empty());
+    }
+
+    #[test]
+    fn test_env_override_errors() {
+        for (var, value) in [
+            ("LOGGER_LEVEL", "loud"),
+            ("LOGGER_SAMPLING_RATE", "1.5"),
+            ("LOGGER_ASYNC_BUFFER", "-1"),
+            ("LOGGER_OVERFLOW_POLICY", "drop_all"),
+        ] {
+            let result =
+                EnvOverrides::from_lookup("LOGGER", |v| (v == var).then(|| value.to_string()));
+            let message = result.unwrap_err().to_string();
+            assert!(message.contains(var), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_apply_to_running_logger() {
+        let previous = LoggerConfig::from_json(r#"{ "level": "warn" }"#).unwrap();
+        let logger = previous.build().unwrap();
+
+        let updated = LoggerConfig::from_json(
+            r#"{
+                "level": "debug",
+                "modules": { "my_app": "trace" },
+                "loggers": { "db": "error" },
+                "sampling": { "rate": 0.5, "categories": { "audit": 1.0 } },
+                "appenders": [{ "type": "console" }],
+                "disabled_appenders": ["console"]
+            }"#,
+        )
+        .unwrap();
+        let not_applied = updated.apply_to(&logger, &previous).unwrap();
+
+        assert_eq!(not_applied, ["modules"]);
+        assert_eq!(logger.effective_min_level(), LogLevel::Trace);
+        assert_eq!(logger.logger_level("db.pool"), LogLevel::Error);
+        let sampling = logger.sampler().unwrap().config();
+        assert_eq!(sampling.rate, 0.5);
+        assert_eq!(sampling.category_rates["audit"], 1.0);
+        assert!(!logger.is_appender_enabled("console"));
+
+        // Removing an appender needs a new logger
+        let not_applied = previous.apply_to(&logger, &updated).unwrap();
+        assert!(not_applied.contains(&"appenders"));
+        assert!(logger.sampler().unwrap().config().category_rates.is_empty());
+        assert!(logger.is_appender_enabled("console"));
+        assert_eq!(logger.logger_level("db.pool"), LogLevel::Warn);
+    }
+
+    #[cfg(feature = "config-toml")]
+    #[test]
+    fn test_parse_toml() {
+        let config = LoggerConfig::from_toml(
+            r#"
+            level = "info"
+
+            [modules]
+            "my_app::db" = "trace"
+
+            [[appenders]]
+            type = "rotating_file"
+            path = "app.log"
+            daily_at = 2
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.modules["my_app::db"], LogLevel::Trace);
+        let policy = config.appenders[0].rotation_policy().unwrap().unwrap();
+        assert_eq!(policy.strategy, RotationStrategy::daily(2));
+    }
+
+    #[cfg(feature = "config-yaml")]
+    #[test]
+    fn test_parse_yaml() {
+        let config = LoggerConfig::from_yaml(
+            "level: error\nappenders:\n  - type: json\n    path: app.jsonl\n    pretty: true\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.level, Some(LogLevel::Error));
+        assert_eq!(
+            config.appenders,
+            vec![AppenderConfig::Json {
+                path: "app.jsonl".into(),
+                pretty: true,
+            }]
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Configuration file hot reload
+//!
+//! A [`ConfigWatcher`] polls a [`LoggerConfig`] file and applies changes to
+//! a running logger with [`LoggerConfig::apply_to`], so log levels,
+//! sampling rates and disabled appenders can be changed without a restart.
+//! `LOGGER_*` environment overrides are applied on top of every reload, as
+//! with [`Logger::from_config_file`].
+//!
+//! An invalid file is reported and ignored; the logger keeps its current
+//! settings until the file is fixed.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rust_logger_system::core::ConfigWatcher;
+//! use rust_logger_system::Logger;
+//! use std::sync::Arc;
+//!
+//! let logger = Arc::new(Logger::from_config_file("logger.toml")?);
+//! let _watcher = ConfigWatcher::start(Arc::clone(&logger), "logger.toml")?;
+//! // Edits to logger.toml now take effect within two seconds
+//! # Ok::<(), rust_logger_system::LoggerError>(())
+//! ```
+
+use super::config::{EnvOverrides, LoggerConfig};
+use super::diagnostics::{self, DiagnosticEvent};
+use super::error::{LoggerError, Result};
+use super::logger::Logger;
+use crossbeam_channel::{bounded, RecvTimeoutError, Sender};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Default interval between checks of the configuration file
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Callback receiving errors from reloading a configuration file
+pub type ReloadErrorCallback = Arc<dyn Fn(&LoggerError) + Send + Sync>;
+
+/// Builder for a [`ConfigWatcher`]
+pub struct ConfigWatcherBuilder {
+    path: PathBuf,
+    interval: Duration,
+    on_error: Option<ReloadErrorCallback>,
+}
+
+impl ConfigWatcherBuilder {
+    /// Set how often the file is checked (default: [`DEFAULT_WATCH_INTERVAL`])
+    #[must_use = "builder methods return a new value"]
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Receive reload errors instead of the [diagnostics handler](super::diagnostics)
+    ///
+    /// Called for unreadable or invalid files and for appenders that cannot
+    /// be opened.
+    #[must_use = "builder methods return a new value"]
+    pub fn on_error<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&LoggerError) + Send + Sync + 'static,
+    {
+        self.on_error = Some(Arc::new(callback));
+        self
+    }
+
+    /// Load the file and start watching it
+    ///
+    /// The file as loaded now is taken to be the configuration `logger`
+    /// currently runs with; later changes are applied relative to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be loaded.
+    pub fn start(self, logger: Arc<Logger>) -> Result<ConfigWatcher> {
+        let mut contents = read(&self.path)?;
+        let mut current = load(&self.path, &contents)?;
+        let (stop, stopped) = bounded::<()>(0);
+        let path = self.path.clone();
+
+        let handle = thread::spawn(move || {
+            let report = |e: &LoggerError| match self.on_error {
+                Some(ref callback) => callback(e),
+                None => diagnostics::report(DiagnosticEvent::ConfigIgnored {
+                    source: self.path.display().to_string(),
+                    error: e.to_string(),
+                }),
+            };
+            let mut read_failed = false;
+
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(self.interval) {
+                let latest = match read(&self.path) {
+                    Ok(latest) => latest,
+                    Err(e) => {
+                        // Report once; the file may be replaced at any moment
+                        if !read_failed {
+                            report(&e);
+                        }
+                        read_failed = true;
+                        continue;
+                    }
+                };
+                read_failed = false;
+                if latest == contents {
+                    continue;
+                }
+                contents = latest;
+
+                let applied = load(&self.path, &contents).and_then(|config| {
+                    let not_applied = config.apply_to(&logger, &current)?;
+                    Ok((config, not_applied))
+                });
+                match applied {
+                    Ok((config, not_applied)) => {
+                        if !not_applied.is_empty() {
+                            diagnostics::report(DiagnosticEvent::ConfigNotApplied {
+                                source: self.path.display().to_string(),
+                                settings: not_applied,
+                            });
+                        }
+                        current = config;
+                    }
+                    Err(e) => report(&e),
+                }
+            }
+        });
+
+        Ok(ConfigWatcher {
+            path,
+            stop: Some(stop),
+            handle: Some(handle),
+        })
+    }
+}
+
+/// Background thread applying changes of a configuration file to a logger
+///
+/// Stops when dropped.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    stop: Option<Sender<()>>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl ConfigWatcher {
+    /// Create a builder watching `path`
+    #[must_use]
+    pub fn builder(path: impl Into<PathBuf>) -> ConfigWatcherBuilder {
+        ConfigWatcherBuilder {
+            path: path.into(),
+            interval: DEFAULT_WATCH_INTERVAL,
+            on_error: None,
+        }
+    }
+
+    /// Watch `path` with the default settings
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be loaded.
+    pub fn start(logger: Arc<Logger>, path: impl Into<PathBuf>) -> Result<Self> {
+        Self::builder(path).start(logger)
+    }
+
+    /// Get the watched path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Stop watching and wait for the thread to exit
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        drop(self.stop.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl std::fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigWatcher")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|e| {
+        LoggerError::io_operation("reading configuration", path.display().to_string(), e)
+    })
+}
+
+fn load(path: &Path, contents: &str) -> Result<LoggerConfig> {
+    let mut config = LoggerConfig::from_file_contents(path, contents)?;
+    EnvOverrides::from_env()?.apply_to_config(&mut config);
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::LogLevel;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Instant;
+
+    fn wait_for(condition: impl Fn() -> bool) -> bool {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(5) {
+            if condition() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        false
+    }
+
+    #[test]
+    fn test_applies_changes_and_reports_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logger.json");
+        std::fs::write(&path, r#"{ "level": "warn", "sampling": { "rate": 0.5 } }"#).unwrap();
+
+        let logger = Arc::new(LoggerConfig::from_file(&path).unwrap().build().unwrap());
+        let errors = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&errors);
+        let watcher = ConfigWatcher::builder(&path)
+            .interval(Duration::from_millis(10))
+            .on_error(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            })
+            .start(Arc::clone(&logger))
+            .unwrap();
+        assert_eq!(watcher.path(), path);
+
+        std::fs::write(
+            &path,
+            r#"{ "level": "debug", "sampling": { "rate": 1.0 }, "disabled_appenders": ["file"] }"#,
+        )
+        .unwrap();
+        assert!(wait_for(|| logger.effective_min_level() == LogLevel::Debug));
+        assert_eq!(logger.sampler().unwrap().config().rate, 1.0);
+        assert!(!logger.is_appender_enabled("file"));
+
+        std::fs::write(&path, r#"{ "level": "loud" }"#).unwrap();
+        assert!(wait_for(|| errors.load(Ordering::SeqCst) == 1));
+        assert_eq!(logger.effective_min_level(), LogLevel::Debug);
+
+        watcher.stop();
+    }
+}
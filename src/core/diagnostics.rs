@@ -0,0 +1,671 @@
+//! Reporting of the logger's own problems
+//!
+//! Failures that cannot be returned to a caller - an appender erroring on the
+//! worker thread, a full queue, a failed rotation, a shutdown timeout - are
+//! reported as [`DiagnosticEvent`]s to a process-wide [`DiagnosticsHandler`].
+//! The default handler prints them on stderr, throttled per event kind (see
+//! [`Throttled`]) so that a failing sink under load does not flood the
+//! terminal or slow the process down further.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::core::diagnostics::{self, DiagnosticEvent};
+//!
+//! diagnostics::set_diagnostics_handler(|event: &DiagnosticEvent| {
+//!     // Route into the application's own telemetry
+//!     let _ = (event.kind(), event.to_string());
+//! });
+//! # diagnostics::reset_diagnostics_handler();
+//! ```
+
+use super::circuit_breaker::CircuitState;
+use super::clock::{self, Clock};
+use parking_lot::{Mutex, RwLock};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Internal event of the logger
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum DiagnosticEvent {
+    /// The async queue was full and entries were dropped
+    ///
+    /// Reported on the first drop and every 1000 drops after it.
+    BufferFull {
+        /// Entries dropped so far
+        dropped: u64,
+        /// Whether `DropOldest` fell back to dropping the new entry
+        drop_oldest_fallback: bool,
+    },
+
+    /// An appender returned an error from `append`
+    AppenderFailed {
+        /// Registered appender name
+        appender: String,
+        /// Error message
+        error: String,
+    },
+
+    /// An appender panicked; other appenders keep working
+    AppenderPanicked {
+        /// Registered appender name
+        appender: String,
+        /// Panic message
+        message: String,
+    },
+
+    /// An enricher panicked; the entry is written without its fields
+    EnricherPanicked {
+        /// Name of the enricher
+        enricher: String,
+        /// Panic message
+        message: String,
+    },
+
+    /// An appender kept panicking and is no longer called
+    ///
+    /// See [`LoggerBuilder::quarantine_after`](super::LoggerBuilder::quarantine_after).
+    AppenderQuarantined {
+        /// Registered appender name
+        appender: String,
+        /// Consecutive panics that caused the quarantine
+        panics: u32,
+    },
+
+    /// An appender failed or panicked while flushing
+    FlushFailed {
+        /// Registered appender name
+        appender: String,
+        /// Error or panic message
+        error: String,
+    },
+
+    /// Rotating a log file, or cleaning up after it, failed
+    RotationFailed {
+        /// File being rotated or removed
+        path: PathBuf,
+        /// What went wrong
+        error: String,
+    },
+
+    /// The async worker did not drain the queue within the shutdown timeout
+    ShutdownTimeout {
+        /// Timeout that elapsed
+        timeout: Duration,
+    },
+
+    /// Flushing the appenders at shutdown failed
+    ShutdownFlushFailed {
+        /// Error message
+        error: String,
+    },
+
+    /// The async worker thread panicked
+    WorkerPanicked {
+        /// Panic message
+        message: String,
+    },
+
+    /// The logger was dropped after losing entries
+    DroppedAtShutdown {
+        /// Entries dropped over the logger's lifetime
+        dropped: u64,
+        /// Percentage of entries dropped
+        drop_rate: f64,
+    },
+
+    /// Invalid configuration was ignored
+    ConfigIgnored {
+        /// Environment variable or file holding the configuration
+        source: String,
+        /// Why it was ignored
+        error: String,
+    },
+
+    /// A reloaded configuration changed settings that need a restart
+    ConfigNotApplied {
+        /// Configuration file
+        source: String,
+        /// Settings left unchanged
+        settings: Vec<&'static str>,
+    },
+
+    /// A critical entry was written on the logging thread because the
+    /// async queue was full
+    SyncFallback {
+        /// Entries written synchronously so far
+        total: u64,
+    },
+
+    /// The circuit breaker of an appender changed state
+    CircuitChanged {
+        /// Registered appender name
+        appender: String,
+        /// New state
+        state: CircuitState,
+        /// Consecutive failures of the appender
+        failures: u32,
+    },
+
+    /// Damaged records were skipped while reading a network spool
+    SpoolCorrupted {
+        /// Spool file
+        path: PathBuf,
+        /// Bytes skipped
+        bytes: u64,
+    },
+
+    /// Entries left in a write-ahead log by a previous process were found
+    WalRecovered {
+        /// Write-ahead log file
+        path: PathBuf,
+        /// Entries written again
+        entries: u64,
+        /// Bytes of a damaged or torn tail that were skipped
+        corrupted_bytes: u64,
+    },
+
+    /// Writing the write-ahead log failed; the entry is only queued in memory
+    WalFailed {
+        /// Write-ahead log file
+        path: PathBuf,
+        /// Error message
+        error: String,
+    },
+
+    /// A logger was built without appenders
+    NoAppenders,
+
+    /// Events of one kind were throttled; see [`Throttled`]
+    Suppressed {
+        /// Kind of the suppressed events
+        kind: &'static str,
+        /// Events suppressed since the last summary
+        count: u64,
+    },
+}
+
+impl DiagnosticEvent {
+    /// Short, stable name of the event kind (e.g. `"appender_failed"`)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::BufferFull { .. } => "buffer_full",
+            Self::AppenderFailed { .. } => "appender_failed",
+            Self::AppenderPanicked { .. } => "appender_panicked",
+            Self::EnricherPanicked { .. } => "enricher_panicked",
+            Self::AppenderQuarantined { .. } => "appender_quarantined",
+            Self::FlushFailed { .. } => "flush_failed",
+            Self::RotationFailed { .. } => "rotation_failed",
+            Self::ShutdownTimeout { .. } => "shutdown_timeout",
+            Self::ShutdownFlushFailed { .. } => "shutdown_flush_failed",
+            Self::WorkerPanicked { .. } => "worker_panicked",
+            Self::DroppedAtShutdown { .. } => "dropped_at_shutdown",
+            Self::ConfigIgnored { .. } => "config_ignored",
+            Self::ConfigNotApplied { .. } => "config_not_applied",
+            Self::SyncFallback { .. } => "sync_fallback",
+            Self::CircuitChanged { .. } => "circuit_changed",
+            Self::SpoolCorrupted { .. } => "spool_corrupted",
+            Self::WalRecovered { .. } => "wal_recovered",
+            Self::WalFailed { .. } => "wal_failed",
+            Self::NoAppenders => "no_appenders",
+            Self::Suppressed { .. } => "suppressed",
+        }
+    }
+
+    /// Whether entries may have been lost, as opposed to a warning
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            Self::AppenderFailed { .. }
+                | Self::AppenderPanicked { .. }
+                | Self::AppenderQuarantined { .. }
+                | Self::FlushFailed { .. }
+                | Self::ShutdownFlushFailed { .. }
+                | Self::WorkerPanicked { .. }
+                | Self::SpoolCorrupted { .. }
+                | Self::WalFailed { .. }
+                | Self::CircuitChanged {
+                    state: CircuitState::Open,
+                    ..
+                }
+        )
+    }
+}
+
+impl fmt::Display for DiagnosticEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferFull {
+                dropped,
+                drop_oldest_fallback: true,
+            } => write!(
+                f,
+                "Queue full, {} logs dropped. \
+                 Note: DropOldest policy not fully supported, using AlertAndDrop.",
+                dropped
+            ),
+            Self::BufferFull { dropped, .. } => write!(
+                f,
+                "Queue full, {} logs dropped. \
+                 Consider increasing buffer size or using a different overflow policy.",
+                dropped
+            ),
+            Self::AppenderFailed { appender, error } => {
+                write!(f, "Appender '{}' failed: {}", appender, error)
+            }
+            Self::AppenderPanicked { appender, message } => write!(
+                f,
+                "Appender '{}' panicked: {}. Other appenders continue to function.",
+                appender, message
+            ),
+            Self::EnricherPanicked { enricher, message } => {
+                write!(f, "Enricher '{}' panicked: {}", enricher, message)
+            }
+            Self::AppenderQuarantined { appender, panics } => write!(
+                f,
+                "Appender '{}' quarantined after {} consecutive panics",
+                appender, panics
+            ),
+            Self::FlushFailed { appender, error } => {
+                write!(f, "Appender '{}' flush failed: {}", appender, error)
+            }
+            Self::RotationFailed { path, error } => {
+                write!(f, "Log rotation of {} failed: {}", path.display(), error)
+            }
+            Self::ShutdownTimeout { timeout } => write!(
+                f,
+                "Async worker thread did not finish within {:?} timeout. Some logs may be lost.",
+                timeout
+            ),
+            Self::ShutdownFlushFailed { error } => {
+                write!(f, "Failed to flush during shutdown: {}", error)
+            }
+            Self::WorkerPanicked { message } => {
+                write!(f, "Async worker thread panicked: {}", message)
+            }
+            Self::DroppedAtShutdown { dropped, drop_rate } => write!(
+                f,
+                "Logger shutting down with {} dropped logs (drop rate: {:.2}%)",
+                dropped, drop_rate
+            ),
+            Self::ConfigIgnored { source, error } => write!(f, "Ignoring {}: {}", source, error),
+            Self::ConfigNotApplied { source, settings } => write!(
+                f,
+                "Changes to {} in {} take effect after a restart",
+                settings.join(", "),
+                source
+            ),
+            Self::SyncFallback { total } => write!(
+                f,
+                "Queue full, critical log written synchronously ({} so far)",
+                total
+            ),
+            Self::CircuitChanged {
+                appender,
+                state,
+                failures,
+            } => match state {
+                CircuitState::Open => write!(
+                    f,
+                    "Appender '{}' skipped after {} consecutive failures (circuit open)",
+                    appender, failures
+                ),
+                CircuitState::HalfOpen => {
+                    write!(f, "Appender '{}' circuit half-open, probing", appender)
+                }
+                CircuitState::Closed => {
+                    write!(f, "Appender '{}' recovered (circuit closed)", appender)
+                }
+            },
+            Self::SpoolCorrupted { path, bytes } => write!(
+                f,
+                "Skipped {} corrupted bytes in spool {}",
+                bytes,
+                path.display()
+            ),
+            Self::WalRecovered {
+                path,
+                entries,
+                corrupted_bytes,
+            } => write!(
+                f,
+                "Recovered {} entries from write-ahead log {} ({} corrupted bytes skipped)",
+                entries,
+                path.display(),
+                corrupted_bytes
+            ),
+            Self::WalFailed { path, error } => {
+                write!(f, "Write-ahead log {} failed: {}", path.display(), error)
+            }
+            Self::NoAppenders => write!(f, "Logger has no appenders; entries are discarded"),
+            Self::Suppressed { kind, count } => write!(f, "{} {} events suppressed", count, kind),
+        }
+    }
+}
+
+/// Receiver of [`DiagnosticEvent`]s
+///
+/// Implemented for closures taking `&DiagnosticEvent`. The handler runs on
+/// the thread that hit the problem, often the async worker, so it should
+/// return quickly. Events raised while a handler is running on the same
+/// thread, for instance because it logs through a failing logger, go to the
+/// default stderr handler instead.
+pub trait DiagnosticsHandler: Send + Sync {
+    /// Handle one event
+    fn handle(&self, event: &DiagnosticEvent);
+
+    /// Pass on anything held back, such as counts of throttled events
+    ///
+    /// The default does nothing.
+    fn flush(&self) {}
+}
+
+impl<F> DiagnosticsHandler for F
+where
+    F: Fn(&DiagnosticEvent) + Send + Sync,
+{
+    fn handle(&self, event: &DiagnosticEvent) {
+        self(event)
+    }
+}
+
+/// Token-bucket limits of a [`Throttled`] handler, applied per event kind
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThrottleConfig {
+    /// Events passed on back to back before throttling starts
+    pub burst: u32,
+
+    /// Events passed on per second once the burst is used up
+    pub per_second: f64,
+
+    /// Minimum time between two summaries of suppressed events
+    pub summary_interval: Duration,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            burst: 10,
+            per_second: 1.0,
+            summary_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+impl ThrottleConfig {
+    /// Allow bursts of `burst` events, refilled at `per_second`
+    pub fn new(burst: u32, per_second: f64) -> Self {
+        Self {
+            burst,
+            per_second,
+            ..Default::default()
+        }
+    }
+
+    /// Set the minimum time between summaries
+    #[must_use]
+    pub fn with_summary_interval(mut self, interval: Duration) -> Self {
+        self.summary_interval = interval;
+        self
+    }
+}
+
+/// Per-kind throttling state
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled: Instant,
+    suppressed: u64,
+    summarized: Instant,
+}
+
+/// Handler wrapper limiting how many events of each kind get through
+///
+/// Each event kind has a token bucket holding up to
+/// [`burst`](ThrottleConfig::burst) tokens. Events that find the bucket empty
+/// are counted instead of passed on, and the count is passed on as a
+/// [`DiagnosticEvent::Suppressed`] summary with the next event of that kind
+/// once the summary interval has passed, or on
+/// [`flush`](DiagnosticsHandler::flush).
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::core::diagnostics::{self, StderrDiagnostics, ThrottleConfig, Throttled};
+///
+/// // At most 2 lines per kind per second after a burst of 5
+/// diagnostics::set_diagnostics_handler(Throttled::new(
+///     StderrDiagnostics,
+///     ThrottleConfig::new(5, 2.0),
+/// ));
+///
+/// // Print every event
+/// diagnostics::set_diagnostics_handler(StderrDiagnostics);
+/// # diagnostics::reset_diagnostics_handler();
+/// ```
+#[derive(Debug)]
+pub struct Throttled<H> {
+    handler: H,
+    config: ThrottleConfig,
+    buckets: Mutex<HashMap<&'static str, Bucket>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<H: DiagnosticsHandler> Throttled<H> {
+    /// Throttle the events passed to `handler`
+    pub fn new(handler: H, config: ThrottleConfig) -> Self {
+        Self {
+            handler,
+            config,
+            buckets: Mutex::new(HashMap::new()),
+            clock: clock::system_clock(),
+        }
+    }
+
+    /// Refill the buckets by the time of `clock`
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Decide whether to pass on an event of `kind`
+    ///
+    /// Returns whether to pass it on and the number of suppressed events to
+    /// summarize first.
+    fn admit(&self, kind: &'static str, now: Instant) -> (bool, Option<u64>) {
+        let burst = f64::from(self.config.burst);
+        let mut buckets = self.buckets.lock();
+        let bucket = buckets.entry(kind).or_insert(Bucket {
+            tokens: burst,
+            refilled: now,
+            suppressed: 0,
+            summarized: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.refilled).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.config.per_second).min(burst);
+        bucket.refilled = now;
+
+        let summary = if bucket.suppressed > 0
+            && now.saturating_duration_since(bucket.summarized) >= self.config.summary_interval
+        {
+            bucket.summarized = now;
+            Some(std::mem::take(&mut bucket.suppressed))
+        } else {
+            None
+        };
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            (true, summary)
+        } else {
+            bucket.suppressed += 1;
+            (false, summary)
+        }
+    }
+}
+
+impl<H: DiagnosticsHandler> DiagnosticsHandler for Throttled<H> {
+    fn handle(&self, event: &DiagnosticEvent) {
+        let kind = event.kind();
+        let (pass, summary) = self.admit(kind, self.clock.now());
+        if let Some(count) = summary {
+            self.handler.handle(&DiagnosticEvent::Suppressed { kind, count });
+        }
+        if pass {
+            self.handler.handle(event);
+        }
+    }
+
+    fn flush(&self) {
+        let now = self.clock.now();
+        let pending: Vec<_> = self
+            .buckets
+            .lock()
+            .iter_mut()
+            .filter(|(_, bucket)| bucket.suppressed > 0)
+            .map(|(&kind, bucket)| {
+                bucket.summarized = now;
+                (kind, std::mem::take(&mut bucket.suppressed))
+            })
+            .collect();
+        for (kind, count) in pending {
+            self.handler.handle(&DiagnosticEvent::Suppressed { kind, count });
+        }
+        self.handler.flush();
+    }
+}
+
+/// Handler printing every event on stderr
+///
+/// The default handler is this one wrapped in [`Throttled`] with the
+/// default [`ThrottleConfig`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StderrDiagnostics;
+
+impl DiagnosticsHandler for StderrDiagnostics {
+    fn handle(&self, event: &DiagnosticEvent) {
+        let label = if event.is_error() { "ERROR" } else { "WARNING" };
+        eprintln!("[LOGGER {}] {}", label, event);
+    }
+}
+
+static HANDLER: RwLock<Option<Arc<dyn DiagnosticsHandler>>> = RwLock::new(None);
+
+thread_local! {
+    static HANDLING: Cell<bool> = const { Cell::new(false) };
+}
+
+fn default_handler() -> &'static Throttled<StderrDiagnostics> {
+    static DEFAULT: OnceLock<Throttled<StderrDiagnostics>> = OnceLock::new();
+    DEFAULT.get_or_init(|| Throttled::new(StderrDiagnostics, ThrottleConfig::default()))
+}
+
+/// Install the process-wide diagnostics handler
+///
+/// Replaces the default handler, a throttled [`StderrDiagnostics`], or a
+/// previously set handler. Wrap the handler in [`Throttled`] to limit the
+/// events it receives.
+pub fn set_diagnostics_handler<H: DiagnosticsHandler + 'static>(handler: H) {
+    *HANDLER.write() = Some(Arc::new(handler));
+}
+
+/// Restore the default handler
+pub fn reset_diagnostics_handler() {
+    *HANDLER.write() = None;
+}
+
+/// Report an event to the current handler
+///
+/// Custom appenders can use this for failures they recover from.
+pub fn report(event: DiagnosticEvent) {
+    /// Clears the re-entrancy flag, also if the handler panics
+    struct Handling;
+
+    impl Drop for Handling {
+        fn drop(&mut self) {
+            HANDLING.with(|handling| handling.set(false));
+        }
+    }
+
+    let handler = HANDLER.read().clone();
+    match handler {
+        Some(handler) if !HANDLING.with(|handling| handling.replace(true)) => {
+            let _handling = Handling;
+            handler.handle(&event);
+        }
+        _ => default_handler().handle(&event),
+    }
+}
+
+/// Pass pending summaries of throttled events to the handler
+///
+/// Called when a logger is dropped.
+pub fn flush_diagnostics() {
+    let handler = HANDLER.read().clone();
+    match handler {
+        Some(handler) => handler.flush(),
+        None => default_handler().flush(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle() {
+        let config = ThrottleConfig::new(2, 1.0).with_summary_interval(Duration::from_secs(5));
+        let throttled = Throttled::new(StderrDiagnostics, config);
+        let start = Instant::now();
+        assert_eq!(throttled.admit("buffer_full", start), (true, None));
+        assert_eq!(throttled.admit("buffer_full", start), (true, None));
+        assert_eq!(throttled.admit("buffer_full", start), (false, None));
+        assert_eq!(throttled.admit("buffer_full", start), (false, None));
+        // Kinds are throttled separately
+        assert_eq!(throttled.admit("flush_failed", start), (true, None));
+
+        // One token per second, summaries at most every 5 seconds
+        let later = start + Duration::from_secs(1);
+        assert_eq!(throttled.admit("buffer_full", later), (true, None));
+        assert_eq!(throttled.admit("buffer_full", later), (false, None));
+        let later = start + Duration::from_secs(5);
+        assert_eq!(throttled.admit("buffer_full", later), (true, Some(3)));
+        assert_eq!(throttled.admit("buffer_full", later), (true, None));
+    }
+
+    #[test]
+    fn test_custom_handler_receives_events() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&events);
+        set_diagnostics_handler(move |event: &DiagnosticEvent| {
+            if let DiagnosticEvent::RotationFailed { .. } = event {
+                received.lock().push(event.clone());
+                // Re-entrant reports go to stderr instead of recursing
+                report(event.clone());
+            }
+        });
+
+        let event = DiagnosticEvent::RotationFailed {
+            path: PathBuf::from("diagnostics_test.log"),
+            error: "disk full".to_string(),
+        };
+        report(event.clone());
+        reset_diagnostics_handler();
+        report(event.clone());
+
+        assert_eq!(events.lock().len(), 1);
+        assert_eq!(events.lock()[0], event);
+        assert_eq!(event.kind(), "rotation_failed");
+        assert_eq!(
+            event.to_string(),
+            "Log rotation of diagnostics_test.log failed: disk full"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Entry enrichment extension point
+//!
+//! Enrichers add information to an entry after it has passed the filters,
+//! such as Kubernetes pod metadata, the tenant of a request or geo data.
+//! They run in the order they were added to the builder, before redaction
+//! and the appenders, so every appender sees the same fields without a
+//! custom appender doing the lookup. In async mode they run on the worker
+//! thread, keeping slow lookups off the logging threads.
+//!
+//! Filters run before enrichment and do not see the added fields.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//!
+//! struct Tenant;
+//!
+//! impl Enricher for Tenant {
+//!     fn enrich(&self, entry: &mut LogEntry) {
+//!         let user = entry.context.as_ref().and_then(|context| context.field_map().get("user"));
+//!         if let Some(FieldValue::String(user)) = user {
+//!             let tenant = user.split('@').nth(1).unwrap_or("unknown").to_string();
+//!             entry.add_field("tenant", tenant);
+//!         }
+//!     }
+//!
+//!     fn name(&self) -> &str {
+//!         "tenant"
+//!     }
+//! }
+//!
+//! let logger = Logger::builder()
+//!     .enricher(Tenant)
+//!     .enricher(|entry: &mut LogEntry| entry.add_field("region", "eu-west-1"))
+//!     .build();
+//! ```
+
+use super::diagnostics::{self, DiagnosticEvent};
+use super::log_entry::LogEntry;
+use super::logger::panic_message;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Trait for adding information to log entries
+///
+/// A panicking enricher is reported to the
+/// [diagnostics handler](super::diagnostics); the entry is still written
+/// with whatever the other enrichers added.
+pub trait Enricher: Send + Sync {
+    /// Add fields to, or otherwise complete, the entry
+    fn enrich(&self, entry: &mut LogEntry);
+
+    /// Name used in diagnostics
+    fn name(&self) -> &str {
+        "enricher"
+    }
+}
+
+impl<F> Enricher for F
+where
+    F: Fn(&mut LogEntry) + Send + Sync,
+{
+    fn enrich(&self, entry: &mut LogEntry) {
+        self(entry)
+    }
+}
+
+/// Ordered enrichers of a logger
+#[derive(Default)]
+pub(crate) struct EnricherChain {
+    enrichers: Vec<Box<dyn Enricher>>,
+}
+
+impl EnricherChain {
+    pub(crate) fn new(enrichers: Vec<Box<dyn Enricher>>) -> Self {
+        Self { enrichers }
+    }
+
+    /// Run every enricher on `entry`, isolating panics
+    pub(crate) fn enrich(&self, entry: &mut LogEntry) {
+        for enricher in &self.enrichers {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| enricher.enrich(entry)));
+            if let Err(panic_info) = result {
+                diagnostics::report(DiagnosticEvent::EnricherPanicked {
+                    enricher: enricher.name().to_string(),
+                    message: panic_message(&*panic_info),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::log_level::LogLevel;
+
+    #[test]
+    fn test_chain_runs_in_order_and_survives_panics() {
+        let chain = EnricherChain::new(vec![
+            Box::new(|entry: &mut LogEntry| entry.add_field("step", 1)),
+            Box::new(|_: &mut LogEntry| panic!("lookup failed")),
+            Box::new(|entry: &mut LogEntry| entry.add_field("step", 3)),
+        ]);
+        let mut entry = LogEntry::new(LogLevel::Info, "request".to_string());
+        chain.enrich(&mut entry);
+
+        let context = entry.context.unwrap();
+        assert_eq!(context.fields().get("step"), Some(&3.into()));
+    }
+}
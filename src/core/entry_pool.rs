@@ -0,0 +1,133 @@
+//! Recycling of message buffers in async mode
+//!
+//! At high rates the allocator becomes a noticeable share of the cost of a
+//! log call: every entry allocates its message on the producing thread and
+//! frees it on the async worker. With the `entry-pool` feature the worker
+//! returns message buffers to a shared pool after writing a batch, and
+//! messages formatted by the logging macros or escaped by
+//! [`LogEntry::new`](crate::core::LogEntry::new) are written into buffers
+//! taken from it. Without the feature these functions allocate as usual.
+//!
+//! The pool holds at most [`POOL_CAPACITY`] buffers and only keeps buffers
+//! of up to [`MAX_RETAINED_CAPACITY`] bytes, so an occasional large message
+//! does not stay allocated.
+
+use super::log_entry::LogEntry;
+use std::fmt::{self, Write};
+
+/// Most buffers kept in the pool
+pub const POOL_CAPACITY: usize = 4096;
+
+/// Largest buffer, in bytes, returned to the pool
+pub const MAX_RETAINED_CAPACITY: usize = 4096;
+
+#[cfg(feature = "entry-pool")]
+mod pool {
+    use crossbeam_channel::{Receiver, Sender};
+    use std::sync::OnceLock;
+
+    static POOL: OnceLock<(Sender<String>, Receiver<String>)> = OnceLock::new();
+
+    fn pool() -> &'static (Sender<String>, Receiver<String>) {
+        POOL.get_or_init(|| crossbeam_channel::bounded(super::POOL_CAPACITY))
+    }
+
+    pub(super) fn take() -> String {
+        pool().1.try_recv().unwrap_or_default()
+    }
+
+    pub(super) fn give(mut buffer: String) {
+        if buffer.capacity() > 0 && buffer.capacity() <= super::MAX_RETAINED_CAPACITY {
+            buffer.clear();
+            // A full pool drops the buffer
+            let _ = pool().0.try_send(buffer);
+        }
+    }
+}
+
+#[cfg(not(feature = "entry-pool"))]
+mod pool {
+    pub(super) fn take() -> String {
+        String::new()
+    }
+
+    pub(super) fn give(_buffer: String) {}
+}
+
+/// Take an empty buffer with room for at least `capacity` bytes
+pub(crate) fn take(capacity: usize) -> String {
+    let mut buffer = pool::take();
+    buffer.reserve(capacity);
+    buffer
+}
+
+/// Format `args` into a pooled buffer
+///
+/// Used by the logging macros; a message that is a plain string literal is
+/// copied without going through the formatting machinery.
+pub fn format_message(args: fmt::Arguments<'_>) -> String {
+    match args.as_str() {
+        Some(message) => {
+            let mut buffer = take(message.len());
+            buffer.push_str(message);
+            buffer
+        }
+        None => {
+            let mut buffer = take(0);
+            // Writing to a String only fails if a Display impl does
+            let _ = buffer.write_fmt(args);
+            buffer
+        }
+    }
+}
+
+/// Return a buffer that is no longer needed to the pool
+pub(crate) fn recycle(buffer: String) {
+    pool::give(buffer);
+}
+
+/// Return the message buffers of a written batch to the pool, leaving it
+/// empty
+pub(crate) fn recycle_batch(batch: &mut Vec<LogEntry>) {
+    for entry in batch.drain(..) {
+        recycle(entry.message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::LogLevel;
+
+    #[test]
+    fn test_format_message() {
+        assert_eq!(format_message(format_args!("plain")), "plain");
+        assert_eq!(format_message(format_args!("{} + {}", 1, 2)), "1 + 2");
+    }
+
+    #[test]
+    fn test_recycled_buffers_come_back_empty() {
+        let mut batch = vec![
+            LogEntry::new(LogLevel::Info, format_message(format_args!("entry {}", 1))),
+            LogEntry::new(LogLevel::Info, "x".repeat(MAX_RETAINED_CAPACITY + 1)),
+        ];
+        recycle_batch(&mut batch);
+        assert!(batch.is_empty());
+
+        let buffer = take(16);
+        assert!(buffer.is_empty());
+        assert!(buffer.capacity() >= 16);
+    }
+
+    #[cfg(feature = "entry-pool")]
+    #[test]
+    fn test_pool_reuses_buffers() {
+        // Other tests share the pool, so look for the buffer among a few
+        let mut batch = vec![LogEntry::new(LogLevel::Info, String::with_capacity(1000))];
+        let recycled = batch[0].message.as_ptr();
+        recycle_batch(&mut batch);
+
+        let taken: Vec<String> = (0..POOL_CAPACITY).map(|_| take(0)).collect();
+        assert!(taken.iter().any(|buffer| buffer.as_ptr() == recycled));
+    }
+}
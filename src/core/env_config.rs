@@ -0,0 +1,185 @@
+//! `RUST_LOG`-style configuration from environment variables
+//!
+//! A directive string is a comma-separated list of:
+//!
+//! - `level` – default minimum level (e.g. `info`)
+//! - `module=level` – minimum level for a module and its submodules
+//!   (e.g. `my_app::db=trace`); a bare module name enables all levels for it
+//! - `format=text|json|logfmt|otel|pretty` – console output format
+//! - `color=auto|always|never` – console colors
+//!
+//! For example `RUST_LOG="warn,my_app=debug,hyper=error,format=json"`.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//!
+//! // Reads RUST_LOG; logs to the console
+//! let logger = Logger::from_env();
+//!
+//! // Reads APP_LOG and adds a file appender next to the console
+//! let logger = Logger::builder()
+//!     .parse_env("APP_LOG")
+//!     .appender(FileAppender::new("app.log").unwrap())
+//!     .build();
+//! # let _ = std::fs::remove_file("app.log");
+//! ```
+
+use super::error::{LoggerError, Result};
+use crate::appenders::ColorMode;
+use super::filter::ModuleLevelFilter;
+use super::log_level::LogLevel;
+use super::output_format::OutputFormat;
+
+/// Environment variable read by [`Logger::from_env`](super::Logger::from_env)
+pub const DEFAULT_ENV_VAR: &str = "RUST_LOG";
+
+/// Settings parsed from a directive string
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnvConfig {
+    /// Default minimum level
+    pub default_level: Option<LogLevel>,
+
+    /// Per-module minimum levels, in the order given
+    pub module_levels: Vec<(String, LogLevel)>,
+
+    /// Console output format
+    pub format: Option<OutputFormat>,
+
+    /// Console color mode; `None` keeps the appender default (`auto`)
+    pub colors: Option<ColorMode>,
+}
+
+impl EnvConfig {
+    /// Parse a directive string
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first invalid directive.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut config = Self::default();
+
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let invalid = |reason: String| {
+                LoggerError::config("env", format!("invalid directive '{}': {}", directive, reason))
+            };
+
+            match directive.split_once('=') {
+                Some(("format", value)) => {
+                    config.format = Some(value.parse::<OutputFormat>().map_err(invalid)?);
+                }
+                Some(("color", value)) => {
+                    config.colors = Some(value.parse::<ColorMode>().map_err(invalid)?);
+                }
+                Some((module, level)) => {
+                    let level = level.trim().parse::<LogLevel>().map_err(invalid)?;
+                    config.module_levels.push((module.trim().to_string(), level));
+                }
+                None => match directive.parse::<LogLevel>() {
+                    Ok(level) => config.default_level = Some(level),
+                    Err(_) => config
+                        .module_levels
+                        .push((directive.to_string(), LogLevel::Trace)),
+                },
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Read and parse an environment variable
+    ///
+    /// Returns `Ok(None)` if the variable is unset or empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the variable is not valid Unicode or contains an
+    /// invalid directive.
+    pub fn from_env(var: &str) -> Result<Option<Self>> {
+        match std::env::var(var) {
+            Ok(spec) if spec.trim().is_empty() => Ok(None),
+            Ok(spec) => Self::parse(&spec).map(Some),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(e) => Err(LoggerError::config("env", format!("{}: {}", var, e))),
+        }
+    }
+
+    /// Most verbose level enabled by any directive
+    pub fn min_level(&self) -> Option<LogLevel> {
+        self.default_level
+            .into_iter()
+            .chain(self.module_levels.iter().map(|(_, level)| *level))
+            .min()
+    }
+
+    /// Filter enforcing the module levels, if any are configured
+    ///
+    /// Modules without a directive use `default_level`, or `fallback` if
+    /// no default level was given.
+    pub fn module_filter(&self, fallback: LogLevel) -> Option<ModuleLevelFilter> {
+        if self.module_levels.is_empty() {
+            return None;
+        }
+
+        let filter = ModuleLevelFilter::new(self.default_level.unwrap_or(fallback));
+        Some(
+            self.module_levels
+                .iter()
+                .fold(filter, |filter, (module, level)| filter.module(module.clone(), *level)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_directives() {
+        let config =
+            EnvConfig::parse("warn, my_app=debug,my_app::db=TRACE,hyper,format=json,color=never")
+                .unwrap();
+
+        assert_eq!(config.default_level, Some(LogLevel::Warn));
+        assert_eq!(
+            config.module_levels,
+            vec![
+                ("my_app".to_string(), LogLevel::Debug),
+                ("my_app::db".to_string(), LogLevel::Trace),
+                ("hyper".to_string(), LogLevel::Trace),
+            ]
+        );
+        assert_eq!(config.format, Some(OutputFormat::Json));
+        assert_eq!(config.colors, Some(ColorMode::Never));
+        assert_eq!(config.min_level(), Some(LogLevel::Trace));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(EnvConfig::parse("my_app=loud").is_err());
+        assert!(EnvConfig::parse("format=yaml").is_err());
+        assert!(EnvConfig::parse("color=sometimes").is_err());
+        assert_eq!(EnvConfig::parse(" , ").unwrap(), EnvConfig::default());
+    }
+
+    #[test]
+    fn test_module_filter_fallback() {
+        let config = EnvConfig::parse("my_app=debug").unwrap();
+        let filter = config.module_filter(LogLevel::Error).unwrap();
+        assert_eq!(filter.default_level(), LogLevel::Error);
+        assert_eq!(filter.level_for("my_app::api"), LogLevel::Debug);
+
+        assert!(EnvConfig::parse("info").unwrap().module_filter(LogLevel::Info).is_none());
+    }
+
+    #[test]
+    fn test_from_env() {
+        std::env::set_var("RUST_LOGGER_SYSTEM_TEST_ENV", "debug,format=logfmt");
+        let config = EnvConfig::from_env("RUST_LOGGER_SYSTEM_TEST_ENV").unwrap().unwrap();
+        assert_eq!(config.default_level, Some(LogLevel::Debug));
+        assert_eq!(config.format, Some(OutputFormat::Logfmt));
+
+        assert!(EnvConfig::from_env("RUST_LOGGER_SYSTEM_TEST_UNSET").unwrap().is_none());
+    }
+}
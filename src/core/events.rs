@@ -0,0 +1,203 @@
+//! Logger lifecycle events
+//!
+//! [`Logger::subscribe`] registers a callback receiving [`LoggerEvent`]s:
+//! file rotations, failing appenders, dropped entries and shutdown. It
+//! generalizes the [`OverflowCallback`](super::OverflowCallback) of
+//! [`LoggerBuilder::on_overflow`](super::LoggerBuilder::on_overflow), which
+//! is called for [`LoggerEvent::EntriesDropped`] only.
+//!
+//! Callbacks run on the thread that caused the event, often the async
+//! worker while it holds the appender lock. They should return quickly and
+//! must not log synchronously through the same logger.
+//!
+//! [`Logger::subscribe`]: super::Logger::subscribe
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::core::LoggerEvent;
+//! use rust_logger_system::prelude::*;
+//!
+//! let logger = Logger::builder().appender(ConsoleAppender::new()).build();
+//! logger.subscribe(|event: &LoggerEvent| {
+//!     if let LoggerEvent::AppenderFailed { appender, error } = event {
+//!         // Page someone
+//!         let _ = (appender, error);
+//!     }
+//! });
+//! ```
+
+use super::circuit_breaker::CircuitState;
+use parking_lot::RwLock;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Event delivered to [`Logger::subscribe`](super::Logger::subscribe) callbacks
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum LoggerEvent {
+    /// An appender rotated its file
+    RotationCompleted {
+        /// Registered appender name
+        appender: String,
+        /// File that is written to
+        path: PathBuf,
+        /// Where the previous contents were moved
+        backup: PathBuf,
+    },
+
+    /// An appender failed or panicked writing an entry
+    AppenderFailed {
+        /// Registered appender name
+        appender: String,
+        /// Error or panic message
+        error: String,
+    },
+
+    /// An appender kept panicking and is no longer called
+    ///
+    /// It stays registered and can be brought back with
+    /// [`Logger::reinstate_appender`](super::Logger::reinstate_appender).
+    AppenderQuarantined {
+        /// Registered appender name
+        appender: String,
+        /// Consecutive panics that caused the quarantine
+        panics: u32,
+    },
+
+    /// An appender group started writing to another member
+    ///
+    /// `to` is an earlier member again once it has recovered. See
+    /// [`crate::appenders::group`].
+    FailoverChanged {
+        /// Registered name of the group
+        appender: String,
+        /// Member that accepted entries before
+        from: String,
+        /// Member that accepts entries now
+        to: String,
+    },
+
+    /// The circuit breaker of an appender changed state
+    ///
+    /// See [`crate::core::circuit_breaker`].
+    CircuitChanged {
+        /// Registered appender name
+        appender: String,
+        /// State before the change
+        from: CircuitState,
+        /// New state
+        to: CircuitState,
+    },
+
+    /// The async queue was full and entries were dropped
+    ///
+    /// Delivered on the first drop and every 1000 drops after it, like the
+    /// overflow callback.
+    EntriesDropped {
+        /// Entries dropped so far
+        count: u64,
+    },
+
+    /// [`Logger::shutdown`](super::Logger::shutdown) was called or the
+    /// logger is being dropped
+    ShutdownStarted,
+
+    /// The logger finished shutting down
+    ShutdownFinished {
+        /// Whether the queue was drained and flushed within the timeout
+        clean: bool,
+    },
+}
+
+/// Something an appender did that the logger passes on as a [`LoggerEvent`]
+///
+/// Returned by [`Appender::take_event`](super::Appender::take_event).
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum AppenderEvent {
+    /// The appender rotated its file
+    Rotated {
+        /// File that is written to
+        path: PathBuf,
+        /// Where the previous contents were moved
+        backup: PathBuf,
+    },
+
+    /// An appender group started writing to another member
+    FailedOver {
+        /// Member that accepted entries before
+        from: String,
+        /// Member that accepts entries now
+        to: String,
+    },
+}
+
+impl AppenderEvent {
+    /// Convert to the logger event for the appender registered as `appender`
+    pub(crate) fn into_logger_event(self, appender: &str) -> LoggerEvent {
+        match self {
+            Self::Rotated { path, backup } => LoggerEvent::RotationCompleted {
+                appender: appender.to_string(),
+                path,
+                backup,
+            },
+            Self::FailedOver { from, to } => LoggerEvent::FailoverChanged {
+                appender: appender.to_string(),
+                from,
+                to,
+            },
+        }
+    }
+}
+
+/// Callback registered with [`Logger::subscribe`](super::Logger::subscribe)
+pub type EventCallback = Arc<dyn Fn(&LoggerEvent) + Send + Sync>;
+
+/// Handle for removing a subscription with
+/// [`Logger::unsubscribe`](super::Logger::unsubscribe)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+/// Subscribers of one logger, shared with its appenders and worker
+#[derive(Default)]
+pub(crate) struct EventBus {
+    subscribers: RwLock<Vec<(SubscriptionId, EventCallback)>>,
+    next_id: AtomicU64,
+}
+
+impl EventBus {
+    pub(crate) fn subscribe(&self, callback: EventCallback) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.subscribers.write().push((id, callback));
+        id
+    }
+
+    pub(crate) fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let mut subscribers = self.subscribers.write();
+        let before = subscribers.len();
+        subscribers.retain(|(subscribed, _)| *subscribed != id);
+        subscribers.len() != before
+    }
+
+    /// Deliver the event built by `event`, built only if anyone subscribed
+    pub(crate) fn emit(&self, event: impl FnOnce() -> LoggerEvent) {
+        let subscribers = self.subscribers.read();
+        if subscribers.is_empty() {
+            return;
+        }
+        let event = event();
+        for (_, callback) in subscribers.iter() {
+            callback(&event);
+        }
+    }
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("subscribers", &self.subscribers.read().len())
+            .finish()
+    }
+}
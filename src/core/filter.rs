@@ -0,0 +1,449 @@
+//! Log filtering extension point
+//!
+//! Filters decide whether a fully-constructed log entry should be passed on
+//! to the appenders. They run after the minimum level check and sampling,
+//! but before the entry is enqueued (async mode) or written (sync mode).
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//!
+//! struct NoHealthChecks;
+//!
+//! impl Filter for NoHealthChecks {
+//!     fn is_enabled(&self, entry: &LogEntry) -> bool {
+//!         !entry.message.contains("/healthz")
+//!     }
+//! }
+//!
+//! let logger = Logger::builder()
+//!     .filter(NoHealthChecks)
+//!     .filter(|entry: &LogEntry| entry.message.len() < 4096)
+//!     .build();
+//! ```
+
+use super::log_context::FieldValue;
+use super::log_entry::LogEntry;
+use super::log_level::LogLevel;
+use super::severity::Severity;
+
+/// Trait for custom log entry filters
+///
+/// Filters are evaluated in the order they were added. An entry is only
+/// logged if every filter in the chain returns `true`.
+pub trait Filter: Send + Sync {
+    /// Return `true` if the entry should be logged, `false` to suppress it
+    fn is_enabled(&self, entry: &LogEntry) -> bool;
+}
+
+impl<F> Filter for F
+where
+    F: Fn(&LogEntry) -> bool + Send + Sync,
+{
+    fn is_enabled(&self, entry: &LogEntry) -> bool {
+        self(entry)
+    }
+}
+
+/// Condition applied to a single context field by [`FieldFilter`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldCondition {
+    /// The field is present, regardless of its value
+    Exists,
+    /// The field equals the given value
+    ///
+    /// Integer and float values are compared numerically.
+    Equals(FieldValue),
+    /// The field is a string starting with the given prefix
+    Prefix(String),
+    /// The field is numeric and greater than the given value
+    GreaterThan(f64),
+    /// The field is numeric and greater than or equal to the given value
+    GreaterOrEqual(f64),
+    /// The field is numeric and less than the given value
+    LessThan(f64),
+    /// The field is numeric and less than or equal to the given value
+    LessOrEqual(f64),
+}
+
+impl FieldCondition {
+    /// Check whether a field value satisfies this condition
+    pub fn matches(&self, value: &FieldValue) -> bool {
+        match self {
+            FieldCondition::Exists => true,
+            FieldCondition::Equals(expected) => match (as_number(expected), as_number(value)) {
+                (Some(a), Some(b)) => a == b,
+                _ => expected == value,
+            },
+            FieldCondition::Prefix(prefix) => {
+                matches!(value, FieldValue::String(s) if s.starts_with(prefix.as_str()))
+            }
+            FieldCondition::GreaterThan(n) => as_number(value).is_some_and(|v| v > *n),
+            FieldCondition::GreaterOrEqual(n) => as_number(value).is_some_and(|v| v >= *n),
+            FieldCondition::LessThan(n) => as_number(value).is_some_and(|v| v < *n),
+            FieldCondition::LessOrEqual(n) => as_number(value).is_some_and(|v| v <= *n),
+        }
+    }
+}
+
+fn as_number(value: &FieldValue) -> Option<f64> {
+    match value {
+        FieldValue::Int(i) => Some(*i as f64),
+        FieldValue::U64(u) => Some(*u as f64),
+        FieldValue::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// Filter that matches entries on structured context fields
+///
+/// Entries whose context contains a field satisfying the condition are
+/// logged at or above `min_level`. Entries that do not match are rejected,
+/// unless a fallback level is configured with [`FieldFilter::otherwise`].
+/// Levels are compared by [`Severity`], so a threshold can be a built-in
+/// level, a [`CustomLevel`](super::CustomLevel) or any severity between.
+///
+/// Note that the logger's own minimum level is checked first, so it must be
+/// at least as verbose as the most verbose level used here.
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::prelude::*;
+///
+/// // Debug logs for the canary tenant, Info and above for everyone else
+/// let logger = Logger::builder()
+///     .min_level(LogLevel::Debug)
+///     .filter(
+///         FieldFilter::equals("tenant", "canary")
+///             .min_level(LogLevel::Debug)
+///             .otherwise(LogLevel::Info),
+///     )
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FieldFilter {
+    key: String,
+    condition: FieldCondition,
+    min_level: Severity,
+    otherwise: Option<Severity>,
+}
+
+impl FieldFilter {
+    /// Create a filter for the given field and condition
+    pub fn new(key: impl Into<String>, condition: FieldCondition) -> Self {
+        Self {
+            key: key.into(),
+            condition,
+            min_level: Severity::TRACE,
+            otherwise: None,
+        }
+    }
+
+    /// Match entries where the field is present
+    pub fn exists(key: impl Into<String>) -> Self {
+        Self::new(key, FieldCondition::Exists)
+    }
+
+    /// Match entries where the field equals `value`
+    pub fn equals(key: impl Into<String>, value: impl Into<FieldValue>) -> Self {
+        Self::new(key, FieldCondition::Equals(value.into()))
+    }
+
+    /// Match entries where the field is a string starting with `prefix`
+    pub fn prefix(key: impl Into<String>, prefix: impl Into<String>) -> Self {
+        Self::new(key, FieldCondition::Prefix(prefix.into()))
+    }
+
+    /// Match entries where the field is numeric and greater than `value`
+    pub fn greater_than(key: impl Into<String>, value: f64) -> Self {
+        Self::new(key, FieldCondition::GreaterThan(value))
+    }
+
+    /// Match entries where the field is numeric and less than `value`
+    pub fn less_than(key: impl Into<String>, value: f64) -> Self {
+        Self::new(key, FieldCondition::LessThan(value))
+    }
+
+    /// Set the minimum severity for entries that match the condition
+    ///
+    /// Default: `Trace` (all matching entries pass)
+    #[must_use]
+    pub fn min_level(mut self, level: impl Into<Severity>) -> Self {
+        self.min_level = level.into();
+        self
+    }
+
+    /// Allow non-matching entries at or above `level`
+    ///
+    /// Without a fallback level, non-matching entries are rejected.
+    #[must_use]
+    pub fn otherwise(mut self, level: impl Into<Severity>) -> Self {
+        self.otherwise = Some(level.into());
+        self
+    }
+
+    fn field_matches(&self, entry: &LogEntry) -> bool {
+        entry
+            .context
+            .as_ref()
+            .and_then(|ctx| ctx.field_map().get(&self.key))
+            .is_some_and(|value| self.condition.matches(value))
+    }
+}
+
+impl Filter for FieldFilter {
+    fn is_enabled(&self, entry: &LogEntry) -> bool {
+        let severity = entry.severity();
+        if self.field_matches(entry) {
+            severity >= self.min_level
+        } else {
+            self.otherwise.is_some_and(|level| severity >= level)
+        }
+    }
+}
+
+/// Filter applying per-module or per-target minimum levels
+///
+/// The module of an entry is its [`target`](LogEntry::target), which
+/// defaults to the module path as in the `log` and `tracing` crates, so a
+/// library can be quietened by its crate name and a component logging with
+/// an explicit target by that target. The longest configured prefix
+/// matching on a `::` boundary decides the level; entries from other
+/// modules (or without a module) use the default level. Like
+/// [`FieldFilter`], levels are compared by [`Severity`].
+///
+/// As with [`FieldFilter`], the logger's own minimum level is checked
+/// first and must be at least as verbose as the levels used here.
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::prelude::*;
+///
+/// let logger = Logger::builder()
+///     .min_level(LogLevel::Trace)
+///     .filter(
+///         ModuleLevelFilter::new(LogLevel::Info)
+///             .module("my_app::db", LogLevel::Trace)
+///             .module("hyper", LogLevel::Warn)
+///             .module("audit", LogLevel::Info),
+///     )
+///     .build();
+///
+/// // Kept: the "audit" target overrides the module path
+/// rust_logger_system::debug!(logger, target: "audit", "login from {}", "10.0.0.1");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ModuleLevelFilter {
+    default_level: Severity,
+    modules: Vec<(String, Severity)>,
+}
+
+impl ModuleLevelFilter {
+    /// Create a filter with the level used for unlisted modules
+    pub fn new(default_level: impl Into<Severity>) -> Self {
+        Self {
+            default_level: default_level.into(),
+            modules: Vec::new(),
+        }
+    }
+
+    /// Set the minimum level for a module and its submodules
+    #[must_use]
+    pub fn module(mut self, module: impl Into<String>, level: impl Into<Severity>) -> Self {
+        self.modules.push((module.into(), level.into()));
+        self
+    }
+
+    /// Get the level used for unlisted modules
+    ///
+    /// A severity between two built-in levels reports the one below it; see
+    /// [`default_severity`](Self::default_severity).
+    pub fn default_level(&self) -> LogLevel {
+        self.default_level.level()
+    }
+
+    /// Get the severity used for unlisted modules
+    pub fn default_severity(&self) -> Severity {
+        self.default_level
+    }
+
+    /// Get the configured module severities
+    pub fn modules(&self) -> &[(String, Severity)] {
+        &self.modules
+    }
+
+    /// Minimum level applying to the given module path
+    ///
+    /// A severity between two built-in levels reports the one below it; see
+    /// [`severity_for`](Self::severity_for).
+    pub fn level_for(&self, module: &str) -> LogLevel {
+        self.severity_for(module).level()
+    }
+
+    /// Minimum severity applying to the given module path
+    pub fn severity_for(&self, module: &str) -> Severity {
+        self.modules
+            .iter()
+            .filter(|(prefix, _)| {
+                module
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.default_level, |(_, level)| *level)
+    }
+}
+
+impl Filter for ModuleLevelFilter {
+    fn is_enabled(&self, entry: &LogEntry) -> bool {
+        let level = match entry.target.as_deref().or(entry.module_path.as_deref()) {
+            Some(module) => self.severity_for(module),
+            None => self.default_level,
+        };
+        entry.severity() >= level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{CustomLevel, LogContext};
+
+    struct MinLength(usize);
+
+    impl Filter for MinLength {
+        fn is_enabled(&self, entry: &LogEntry) -> bool {
+            entry.message.len() >= self.0
+        }
+    }
+
+    #[test]
+    fn test_struct_filter() {
+        let filter = MinLength(5);
+        assert!(filter.is_enabled(&LogEntry::new(LogLevel::Info, "hello".to_string())));
+        assert!(!filter.is_enabled(&LogEntry::new(LogLevel::Info, "hi".to_string())));
+    }
+
+    #[test]
+    fn test_closure_filter() {
+        let filter = |entry: &LogEntry| entry.level >= LogLevel::Warn;
+        assert!(filter.is_enabled(&LogEntry::new(LogLevel::Error, "x".to_string())));
+        assert!(!filter.is_enabled(&LogEntry::new(LogLevel::Info, "x".to_string())));
+    }
+
+    fn entry_with(level: LogLevel, key: &str, value: impl Into<FieldValue>) -> LogEntry {
+        LogEntry::new(level, "msg".to_string())
+            .with_context(LogContext::new().with_field(key, value))
+    }
+
+    #[test]
+    fn test_field_filter_equals_with_fallback() {
+        let filter = FieldFilter::equals("tenant", "canary")
+            .min_level(LogLevel::Debug)
+            .otherwise(LogLevel::Info);
+
+        assert!(filter.is_enabled(&entry_with(LogLevel::Debug, "tenant", "canary")));
+        assert!(!filter.is_enabled(&entry_with(LogLevel::Trace, "tenant", "canary")));
+        assert!(!filter.is_enabled(&entry_with(LogLevel::Debug, "tenant", "stable")));
+        assert!(filter.is_enabled(&entry_with(LogLevel::Info, "tenant", "stable")));
+        assert!(filter.is_enabled(&LogEntry::new(LogLevel::Warn, "no ctx".to_string())));
+    }
+
+    #[test]
+    fn test_field_filter_rejects_non_matching_without_fallback() {
+        let filter = FieldFilter::exists("request_id");
+        assert!(filter.is_enabled(&entry_with(LogLevel::Info, "request_id", "abc")));
+        assert!(!filter.is_enabled(&entry_with(LogLevel::Error, "other", "abc")));
+    }
+
+    #[test]
+    fn test_field_filter_prefix_and_numeric() {
+        let prefix = FieldFilter::prefix("path", "/api/");
+        assert!(prefix.is_enabled(&entry_with(LogLevel::Info, "path", "/api/users")));
+        assert!(!prefix.is_enabled(&entry_with(LogLevel::Info, "path", "/static/app.js")));
+        assert!(!prefix.is_enabled(&entry_with(LogLevel::Info, "path", 42)));
+
+        let slow = FieldFilter::greater_than("latency_ms", 500.0);
+        assert!(slow.is_enabled(&entry_with(LogLevel::Info, "latency_ms", 750)));
+        assert!(slow.is_enabled(&entry_with(LogLevel::Info, "latency_ms", 500.5)));
+        assert!(!slow.is_enabled(&entry_with(LogLevel::Info, "latency_ms", 500)));
+        assert!(!slow.is_enabled(&entry_with(LogLevel::Info, "latency_ms", "750")));
+
+        let fast = FieldFilter::less_than("latency_ms", 10.0);
+        assert!(fast.is_enabled(&entry_with(LogLevel::Info, "latency_ms", 3)));
+    }
+
+    #[test]
+    fn test_field_condition_numeric_equality() {
+        assert!(FieldCondition::Equals(FieldValue::Int(5)).matches(&FieldValue::Float(5.0)));
+        assert!(FieldCondition::GreaterOrEqual(5.0).matches(&FieldValue::Int(5)));
+        assert!(FieldCondition::LessOrEqual(5.0).matches(&FieldValue::Int(5)));
+        assert!(!FieldCondition::Equals(FieldValue::Bool(true)).matches(&FieldValue::Int(1)));
+    }
+
+    #[test]
+    fn test_boxed_filter_chain() {
+        let chain: Vec<Box<dyn Filter>> = vec![
+            Box::new(MinLength(3)),
+            Box::new(|entry: &LogEntry| !entry.message.contains("secret")),
+        ];
+        let passes = |msg: &str| {
+            let entry = LogEntry::new(LogLevel::Info, msg.to_string());
+            chain.iter().all(|f| f.is_enabled(&entry))
+        };
+
+        assert!(passes("public"));
+        assert!(!passes("ab"));
+        assert!(!passes("my secret"));
+    }
+
+    #[test]
+    fn test_module_level_filter() {
+        let filter = ModuleLevelFilter::new(LogLevel::Info)
+            .module("app", LogLevel::Warn)
+            .module("app::db", LogLevel::Trace);
+
+        assert_eq!(filter.level_for("app::db::pool"), LogLevel::Trace);
+        assert_eq!(filter.level_for("app::http"), LogLevel::Warn);
+        assert_eq!(filter.level_for("application"), LogLevel::Info);
+
+        let at = |level, module: &str| {
+            LogEntry::new(level, "msg".to_string()).with_location("x.rs", 1, module)
+        };
+        assert!(filter.is_enabled(&at(LogLevel::Debug, "app::db")));
+        assert!(!filter.is_enabled(&at(LogLevel::Info, "app::http")));
+        assert!(!filter.is_enabled(&LogEntry::new(LogLevel::Debug, "msg".to_string())));
+
+        // An explicit target takes the place of the module path
+        let targeted = |level, target: &str| at(level, "app::http").with_target(target);
+        assert!(filter.is_enabled(&targeted(LogLevel::Debug, "app::db")));
+        assert!(!filter.is_enabled(&targeted(LogLevel::Debug, "other")));
+        assert!(!filter.is_enabled(&entry_with(LogLevel::Debug, "target", "app::db")));
+    }
+
+    #[test]
+    fn test_filters_compare_severities() {
+        let notice = CustomLevel::new("NOTICE", 25);
+        let at = |entry: LogEntry| entry.with_location("x.rs", 1, "app::jobs");
+
+        let modules = ModuleLevelFilter::new(LogLevel::Warn).module("app", &notice);
+        assert_eq!(modules.severity_for("app::jobs"), Severity(25));
+        assert_eq!(modules.level_for("app::jobs"), LogLevel::Info);
+        assert!(modules.is_enabled(&at(LogEntry::custom(&notice, "kept".to_string()))));
+        assert!(!modules.is_enabled(&at(LogEntry::new(LogLevel::Info, "x".to_string()))));
+        assert!(modules.is_enabled(&at(LogEntry::new(LogLevel::Warn, "x".to_string()))));
+
+        let tenant = FieldFilter::equals("tenant", "canary")
+            .min_level(Severity(25))
+            .otherwise(LogLevel::Error);
+        let with_tenant = |entry: LogEntry| {
+            entry.with_context(LogContext::new().with_field("tenant", "canary"))
+        };
+        assert!(tenant.is_enabled(&with_tenant(LogEntry::custom(&notice, "x".to_string()))));
+        assert!(!tenant.is_enabled(&with_tenant(LogEntry::new(LogLevel::Info, "x".to_string()))));
+        assert!(!tenant.is_enabled(&LogEntry::custom(&notice, "x".to_string())));
+    }
+}
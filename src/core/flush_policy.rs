@@ -0,0 +1,134 @@
+//! Flush policies for appender output
+//!
+//! Appenders such as [`FileAppender`](crate::appenders::FileAppender)
+//! buffer their output. The flush policy decides when the logger asks them
+//! to write it out, trading durability against the cost of a flush.
+//!
+//! Output an appender holds for an async batch is written when the batch
+//! ends under every policy; the policy only decides on the flush itself.
+
+use parking_lot::{Mutex, RwLock};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// When the logger flushes its appenders
+///
+/// [`Logger::flush`](super::Logger::flush), [`Logger::drain`] and shutdown
+/// always flush, whatever the policy.
+///
+/// [`Logger::drain`]: super::Logger::drain
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::prelude::*;
+/// use std::time::Duration;
+///
+/// let logger = Logger::builder()
+///     .async_mode(1000)
+///     .flush_policy(FlushPolicy::Interval(Duration::from_millis(500)))
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlushPolicy {
+    /// Flush after each batch written by the async worker
+    ///
+    /// Synchronous writes are not flushed; appenders write them when their
+    /// buffers fill up.
+    #[default]
+    Batch,
+
+    /// Flush after every entry, in synchronous and async mode
+    ///
+    /// Nothing stays buffered, at the cost of a flush per entry.
+    EveryEntry,
+
+    /// Flush after an entry once the interval has passed since the last one
+    ///
+    /// The interval is checked when entries are written, so output can stay
+    /// buffered while nothing is logged.
+    Interval(Duration),
+
+    /// Only flush when asked to
+    Manual,
+}
+
+impl fmt::Display for FlushPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlushPolicy::Batch => write!(f, "Batch"),
+            FlushPolicy::EveryEntry => write!(f, "EveryEntry"),
+            FlushPolicy::Interval(d) => write!(f, "Interval({:?})", d),
+            FlushPolicy::Manual => write!(f, "Manual"),
+        }
+    }
+}
+
+/// Flush policy shared between a logger and its async worker
+pub(crate) struct FlushControl {
+    policy: RwLock<FlushPolicy>,
+    last_flush: Mutex<Instant>,
+}
+
+impl FlushControl {
+    pub(crate) fn new(policy: FlushPolicy) -> Self {
+        Self {
+            policy: RwLock::new(policy),
+            last_flush: Mutex::new(Instant::now()),
+        }
+    }
+
+    pub(crate) fn policy(&self) -> FlushPolicy {
+        *self.policy.read()
+    }
+
+    pub(crate) fn set_policy(&self, policy: FlushPolicy) {
+        *self.policy.write() = policy;
+    }
+
+    /// Whether to flush after writing an entry
+    pub(crate) fn after_entry(&self) -> bool {
+        match self.policy() {
+            FlushPolicy::EveryEntry => true,
+            FlushPolicy::Interval(interval) => {
+                let mut last_flush = self.last_flush.lock();
+                if last_flush.elapsed() < interval {
+                    return false;
+                }
+                *last_flush = Instant::now();
+                true
+            }
+            FlushPolicy::Batch | FlushPolicy::Manual => false,
+        }
+    }
+
+    /// Whether to flush at the end of an async batch
+    pub(crate) fn after_batch(&self) -> bool {
+        self.policy() == FlushPolicy::Batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flush_decisions() {
+        let control = FlushControl::new(FlushPolicy::default());
+        assert!(control.after_batch());
+        assert!(!control.after_entry());
+
+        control.set_policy(FlushPolicy::EveryEntry);
+        assert!(control.after_entry());
+        assert!(!control.after_batch());
+
+        control.set_policy(FlushPolicy::Interval(Duration::from_secs(3600)));
+        assert!(!control.after_entry());
+        control.set_policy(FlushPolicy::Interval(Duration::ZERO));
+        assert!(control.after_entry());
+
+        control.set_policy(FlushPolicy::Manual);
+        assert!(!control.after_entry());
+        assert!(!control.after_batch());
+    }
+}
@@ -0,0 +1,156 @@
+//! Pluggable entry formatting for appenders
+//!
+//! Appenders that write text lines (console, file, rotating file, network)
+//! accept a [`Formatter`] via `with_formatter`, replacing their built-in
+//! layout while keeping their own I/O handling. The built-in
+//! [`OutputFormat`] variants and [`PatternLayout`] implement the trait, and
+//! so does any `Fn(&LogEntry, &mut String)` closure.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//!
+//! let appender = ConsoleAppender::new().with_formatter(Box::new(
+//!     |entry: &LogEntry, output: &mut String| {
+//!         output.push_str(entry.level.to_str());
+//!         output.push_str(": ");
+//!         output.push_str(&entry.message);
+//!     },
+//! ));
+//! ```
+
+use super::log_entry::LogEntry;
+use super::output_format::OutputFormat;
+use super::pattern::PatternLayout;
+use super::timestamp::{FormatterConfig, TimestampFormat};
+
+/// Formats a log entry into a single line of text
+///
+/// Implementations append to `output` and must not add a trailing
+/// newline; appenders terminate lines themselves.
+pub trait Formatter: Send + Sync {
+    /// Append the formatted entry to `output`
+    fn format(&self, entry: &LogEntry, output: &mut String);
+}
+
+impl<F> Formatter for F
+where
+    F: Fn(&LogEntry, &mut String) + Send + Sync,
+{
+    fn format(&self, entry: &LogEntry, output: &mut String) {
+        self(entry, output)
+    }
+}
+
+/// Formats with the default [`TimestampFormat`]; use [`OutputFormatter`]
+/// to choose another one
+impl Formatter for OutputFormat {
+    fn format(&self, entry: &LogEntry, output: &mut String) {
+        self.format_into(entry, &FormatterConfig::default(), output);
+    }
+}
+
+/// Formats `%d` with the default [`TimestampFormat`]
+impl Formatter for PatternLayout {
+    fn format(&self, entry: &LogEntry, output: &mut String) {
+        self.format_into(entry, &TimestampFormat::default(), output);
+    }
+}
+
+/// [`OutputFormat`] paired with a [`FormatterConfig`]
+///
+/// The config supplies the timestamp format and the JSON profile.
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::core::formatter::OutputFormatter;
+/// use rust_logger_system::prelude::*;
+///
+/// let formatter = OutputFormatter::new(OutputFormat::Logfmt)
+///     .with_timestamp_format(TimestampFormat::UnixMillis);
+/// let appender = ConsoleAppender::new().with_formatter(Box::new(formatter));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OutputFormatter {
+    format: OutputFormat,
+    config: FormatterConfig,
+}
+
+impl OutputFormatter {
+    /// Create a formatter using the default config
+    pub fn new(format: OutputFormat) -> Self {
+        Self {
+            format,
+            config: FormatterConfig::default(),
+        }
+    }
+
+    /// Set the timestamp format
+    #[must_use]
+    pub fn with_timestamp_format(mut self, timestamp_format: TimestampFormat) -> Self {
+        self.config.timestamp_format = timestamp_format;
+        self
+    }
+
+    /// Replace the formatter config
+    #[must_use]
+    pub fn with_config(mut self, config: FormatterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Get the output format
+    pub fn output_format(&self) -> &OutputFormat {
+        &self.format
+    }
+
+    /// Get the formatter config
+    pub fn config(&self) -> &FormatterConfig {
+        &self.config
+    }
+}
+
+impl Formatter for OutputFormatter {
+    fn format(&self, entry: &LogEntry, output: &mut String) {
+        self.format.format_into(entry, &self.config, output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::LogLevel;
+
+    fn render(formatter: &dyn Formatter, entry: &LogEntry) -> String {
+        let mut output = String::new();
+        formatter.format(entry, &mut output);
+        output
+    }
+
+    #[test]
+    fn test_output_formats_as_formatters() {
+        let entry = LogEntry::new(LogLevel::Info, "ready".to_string());
+
+        assert_eq!(
+            render(&OutputFormat::Json, &entry),
+            OutputFormat::Json.format(&entry, &TimestampFormat::default())
+        );
+
+        let logfmt = OutputFormatter::new(OutputFormat::Logfmt)
+            .with_timestamp_format(TimestampFormat::UnixMillis);
+        let expected = format!("timestamp={} ", entry.timestamp.timestamp_millis());
+        assert!(render(&logfmt, &entry).starts_with(&expected));
+
+        let pattern = PatternLayout::parse("%p|%m").unwrap();
+        assert_eq!(render(&pattern, &entry), "INFO|ready");
+    }
+
+    #[test]
+    fn test_closure_formatter() {
+        let formatter = |entry: &LogEntry, output: &mut String| output.push_str(&entry.message);
+        let entry = LogEntry::new(LogLevel::Warn, "low disk".to_string());
+        assert_eq!(render(&formatter, &entry), "low disk");
+    }
+}
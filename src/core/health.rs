@@ -0,0 +1,117 @@
+//! Appender health for readiness probes
+//!
+//! Each appender reports its own view through
+//! [`Appender::health`](super::Appender::health), for instance a network
+//! appender that lost its connection. The logger adds what it observed while
+//! writing: the last error, the number of failures since the last success and
+//! when an entry was last written. [`Logger::appender_health`] returns both.
+//!
+//! [`Logger::appender_health`]: super::Logger::appender_health
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//!
+//! let logger = Logger::builder().appender(ConsoleAppender::new()).build();
+//! logger.info("ready");
+//!
+//! let ready = logger.appender_health().iter().all(|health| health.is_healthy());
+//! assert!(ready);
+//! ```
+
+use std::fmt;
+use std::time::SystemTime;
+
+/// Health an appender reports about itself
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum HealthStatus {
+    /// Working normally
+    #[default]
+    Healthy,
+
+    /// Working, but with reduced guarantees (e.g. writing to a fallback)
+    Degraded(String),
+
+    /// Entries cannot currently be written
+    Unhealthy(String),
+}
+
+impl HealthStatus {
+    /// Whether the status is [`Healthy`](Self::Healthy)
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Self::Healthy)
+    }
+}
+
+impl fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Healthy => write!(f, "healthy"),
+            Self::Degraded(reason) => write!(f, "degraded: {}", reason),
+            Self::Unhealthy(reason) => write!(f, "unhealthy: {}", reason),
+        }
+    }
+}
+
+/// Health of one appender of a logger
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppenderHealth {
+    /// Registered appender name
+    pub name: String,
+
+    /// Whether the appender is enabled
+    pub enabled: bool,
+
+    /// What the appender reports about itself
+    pub status: HealthStatus,
+
+    /// Error of the last failed append or flush
+    pub last_error: Option<String>,
+
+    /// Failed appends and flushes since the last successful append
+    pub consecutive_failures: u64,
+
+    /// When an entry was last written successfully
+    pub last_success: Option<SystemTime>,
+}
+
+impl AppenderHealth {
+    /// Whether the appender reports itself healthy and its last write succeeded
+    ///
+    /// Disabled appenders are always considered healthy.
+    pub fn is_healthy(&self) -> bool {
+        !self.enabled || (self.status.is_healthy() && self.consecutive_failures == 0)
+    }
+}
+
+/// Outcomes of writes to one appender, kept by the logger
+#[derive(Debug, Default)]
+pub(crate) struct HealthTracker {
+    last_error: Option<String>,
+    consecutive_failures: u64,
+    last_success: Option<SystemTime>,
+}
+
+impl HealthTracker {
+    pub(crate) fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.last_success = Some(SystemTime::now());
+    }
+
+    pub(crate) fn record_failure(&mut self, error: String) {
+        self.consecutive_failures += 1;
+        self.last_error = Some(error);
+    }
+
+    pub(crate) fn report(&self, name: &str, enabled: bool, status: HealthStatus) -> AppenderHealth {
+        AppenderHealth {
+            name: name.to_string(),
+            enabled,
+            status,
+            last_error: self.last_error.clone(),
+            consecutive_failures: self.consecutive_failures,
+            last_success: self.last_success,
+        }
+    }
+}
@@ -0,0 +1,401 @@
+//! Hierarchical named loggers
+//!
+//! A [`ChildLogger`] writes through the appenders of its root [`Logger`]
+//! under a dot-separated name such as `db` or `db.pool`, log4j-style. It
+//! inherits its minimum level from the nearest ancestor that has one (see
+//! [`Logger::set_logger_level`]) and falls back to the root's level. Each
+//! child has persistent fields of its own, which its descendants inherit,
+//! and every entry carries the child's name as the `logger` field.
+//!
+//! A [`LoggerRegistry`] hands out one shared child per name, so fields and
+//! levels can be managed for a subsystem from one place.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//! use std::sync::Arc;
+//!
+//! let root = Arc::new(Logger::builder().appender(ConsoleAppender::new()).build());
+//! let db = root.child("db");
+//! db.context().set("component", "db");
+//!
+//! root.set_logger_level("db", LogLevel::Debug);
+//! db.debug("connection opened"); // logged although the root is at Info
+//! db.child("pool").debug("checked out"); // logger=db.pool, component=db
+//! ```
+
+use super::clock::Clock;
+use super::log_context::{FieldValue, LogContext, LoggerContext};
+use super::log_entry::LogEntry;
+use super::log_level::LogLevel;
+use super::logger::Logger;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Field carrying the name of the child logger an entry was logged through
+pub const LOGGER_NAME_FIELD: &str = "logger";
+
+/// Named logger sharing the appenders of a root [`Logger`]
+///
+/// Clones share the same fields. The plain forms of the logging macros
+/// (`info!(child, "...")`) accept a child logger.
+#[derive(Clone)]
+pub struct ChildLogger {
+    root: Arc<Logger>,
+    name: String,
+    /// Own fields first, then those of each ancestor
+    contexts: Vec<LoggerContext>,
+}
+
+impl Logger {
+    /// Create a child logger named `name`
+    ///
+    /// See [`ChildLogger`] and the [module docs](super::hierarchy).
+    pub fn child(self: &Arc<Self>, name: impl Into<String>) -> ChildLogger {
+        ChildLogger {
+            root: Arc::clone(self),
+            name: name.into(),
+            contexts: vec![LoggerContext::new()],
+        }
+    }
+}
+
+impl ChildLogger {
+    /// Create a child of this logger, named `<name>.<child>`
+    pub fn child(&self, name: &str) -> ChildLogger {
+        let mut contexts = Vec::with_capacity(self.contexts.len() + 1);
+        contexts.push(LoggerContext::new());
+        contexts.extend(self.contexts.iter().cloned());
+        ChildLogger {
+            root: Arc::clone(&self.root),
+            name: format!("{}.{}", self.name, name),
+            contexts,
+        }
+    }
+
+    /// Get the full dot-separated name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the root logger
+    pub fn root(&self) -> &Arc<Logger> {
+        &self.root
+    }
+
+    /// Get this logger's persistent fields
+    ///
+    /// They are added to entries of this logger and its descendants, and
+    /// take priority over fields of ancestors and of the root.
+    pub fn context(&self) -> &LoggerContext {
+        &self.contexts[0]
+    }
+
+    /// Set the minimum level of this logger and its descendants
+    ///
+    /// Shorthand for [`Logger::set_logger_level`] on the root.
+    pub fn set_level(&self, level: LogLevel) {
+        self.root.set_logger_level(self.name.clone(), level);
+    }
+
+    /// Remove the level set for this logger, inheriting from its parent
+    pub fn clear_level(&self) {
+        self.root.clear_logger_level(&self.name);
+    }
+
+    /// Get the minimum level in effect for this logger
+    pub fn effective_min_level(&self) -> LogLevel {
+        self.root.logger_level(&self.name)
+    }
+
+    /// Check whether an entry at `level` would pass the level check
+    pub fn enabled(&self, level: LogLevel) -> bool {
+        self.root.is_enabled() && level >= self.effective_min_level()
+    }
+
+    /// Get the time source of the root
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        self.root.clock()
+    }
+
+    pub fn log(&self, level: LogLevel, message: impl Into<String>) {
+        if self.enabled(level) {
+            self.send(LogEntry::new(level, message.into()));
+        }
+    }
+
+    /// Log a message with its source location
+    ///
+    /// See [`Logger::log_at`].
+    pub fn log_at(
+        &self,
+        level: LogLevel,
+        message: impl Into<String>,
+        file: &str,
+        line: u32,
+        module_path: &str,
+    ) {
+        if self.enabled(level) {
+            let entry =
+                LogEntry::new(level, message.into()).with_location(file, line, module_path);
+            self.send(entry);
+        }
+    }
+
+    /// Log preformatted arguments, formatting them only if the entry is kept
+    ///
+    /// See [`Logger::log_args`].
+    pub fn log_args(&self, level: LogLevel, args: fmt::Arguments<'_>) {
+        if self.enabled(level) {
+            self.root.send_args(self.with_contexts(LogEntry::new(level, String::new())), args);
+        }
+    }
+
+    /// Log preformatted arguments with their source location
+    ///
+    /// See [`Logger::log_args_at`].
+    pub fn log_args_at(
+        &self,
+        level: LogLevel,
+        args: fmt::Arguments<'_>,
+        file: &str,
+        line: u32,
+        module_path: &str,
+    ) {
+        if self.enabled(level) {
+            let entry = LogEntry::new(level, String::new()).with_location(file, line, module_path);
+            self.root.send_args(self.with_contexts(entry), args);
+        }
+    }
+
+    /// Log with structured context fields
+    ///
+    /// Entry fields take priority over the logger's persistent fields.
+    pub fn log_with_context(
+        &self,
+        level: LogLevel,
+        message: impl Into<String>,
+        context: LogContext,
+    ) {
+        if self.enabled(level) {
+            self.send(LogEntry::new(level, message.into()).with_context(context));
+        }
+    }
+
+    /// Log a fully constructed entry
+    pub fn log_entry(&self, entry: LogEntry) {
+        if self.enabled(entry.level) {
+            self.send(entry);
+        }
+    }
+
+    #[inline]
+    pub fn trace(&self, message: impl Into<String>) {
+        self.log(LogLevel::Trace, message);
+    }
+
+    #[inline]
+    pub fn debug(&self, message: impl Into<String>) {
+        self.log(LogLevel::Debug, message);
+    }
+
+    #[inline]
+    pub fn info(&self, message: impl Into<String>) {
+        self.log(LogLevel::Info, message);
+    }
+
+    #[inline]
+    pub fn warn(&self, message: impl Into<String>) {
+        self.log(LogLevel::Warn, message);
+    }
+
+    #[inline]
+    pub fn error(&self, message: impl Into<String>) {
+        self.log(LogLevel::Error, message);
+    }
+
+    #[inline]
+    pub fn fatal(&self, message: impl Into<String>) {
+        self.log(LogLevel::Fatal, message);
+    }
+
+    /// Add this logger's fields and name, then hand the entry to the root
+    fn send(&self, entry: LogEntry) {
+        self.root.send_entry(self.with_contexts(entry));
+    }
+
+    /// Merge the fields of this logger and its ancestors into `entry`
+    fn with_contexts(&self, mut entry: LogEntry) -> LogEntry {
+        let mut log_context = entry.context.take().unwrap_or_default();
+        for context in &self.contexts {
+            context.merge_into(&mut log_context);
+        }
+        if !log_context.field_map().contains_key(LOGGER_NAME_FIELD) {
+            log_context.add_field(LOGGER_NAME_FIELD, FieldValue::from(self.name.as_str()));
+        }
+        entry.context = Some(log_context);
+        entry
+    }
+}
+
+impl std::fmt::Debug for ChildLogger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChildLogger")
+            .field("name", &self.name)
+            .field("level", &self.effective_min_level())
+            .finish()
+    }
+}
+
+/// Registry handing out one shared [`ChildLogger`] per name
+///
+/// Asking for `db.pool` also creates `db`, so `db.pool` inherits the fields
+/// set on `db` whenever they are set.
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::prelude::*;
+/// use std::sync::Arc;
+///
+/// let registry = LoggerRegistry::new(Arc::new(Logger::new()));
+/// registry.get("db").context().set("component", "db");
+/// registry.set_level("db", LogLevel::Warn);
+///
+/// let pool = registry.get("db.pool");
+/// assert_eq!(pool.effective_min_level(), LogLevel::Warn);
+/// assert_eq!(registry.names(), ["db", "db.pool"]);
+/// ```
+pub struct LoggerRegistry {
+    root: Arc<Logger>,
+    loggers: RwLock<HashMap<String, ChildLogger>>,
+}
+
+impl LoggerRegistry {
+    /// Create a registry of children of `root`
+    pub fn new(root: Arc<Logger>) -> Self {
+        Self {
+            root,
+            loggers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Get the root logger
+    pub fn root(&self) -> &Arc<Logger> {
+        &self.root
+    }
+
+    /// Get the child logger `name`, creating it and its ancestors if needed
+    pub fn get(&self, name: &str) -> ChildLogger {
+        if let Some(logger) = self.loggers.read().get(name) {
+            return logger.clone();
+        }
+
+        let logger = match name.rsplit_once('.') {
+            Some((parent, last)) => self.get(parent).child(last),
+            None => self.root.child(name),
+        };
+        // Another thread may have created it in the meantime
+        self.loggers
+            .write()
+            .entry(name.to_string())
+            .or_insert(logger)
+            .clone()
+    }
+
+    /// Set the minimum level of `name` and its descendants
+    ///
+    /// See [`Logger::set_logger_level`].
+    pub fn set_level(&self, name: impl Into<String>, level: LogLevel) {
+        self.root.set_logger_level(name, level);
+    }
+
+    /// Remove the level set for `name`
+    pub fn clear_level(&self, name: &str) {
+        self.root.clear_logger_level(name);
+    }
+
+    /// Get the names of all loggers created so far, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.loggers.read().keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+impl std::fmt::Debug for LoggerRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoggerRegistry")
+            .field("loggers", &self.names())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CaptureAppender;
+    use parking_lot::Mutex;
+
+    fn capture_root() -> (Arc<Logger>, Arc<Mutex<Vec<LogEntry>>>) {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let root = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .global_field("service", "api")
+            .build();
+        (Arc::new(root), entries)
+    }
+
+    fn field(entry: &LogEntry, key: &str) -> Option<FieldValue> {
+        entry.context.as_ref()?.field_map().get(key).cloned()
+    }
+
+    #[test]
+    fn test_levels_are_inherited() {
+        let (root, entries) = capture_root();
+        let db = root.child("db");
+        let pool = db.child("pool");
+
+        db.debug("hidden");
+        db.set_level(LogLevel::Debug);
+        pool.debug("inherited");
+        root.debug("root unchanged");
+        root.set_logger_level("db.pool", LogLevel::Error);
+        pool.warn("overridden");
+        pool.clear_level();
+        pool.warn("inherited again");
+
+        let entries = entries.lock();
+        let messages: Vec<_> = entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["inherited", "inherited again"]);
+        assert_eq!(root.logger_level("http"), LogLevel::Info);
+    }
+
+    #[test]
+    fn test_fields_are_inherited() {
+        let (root, entries) = capture_root();
+        let registry = LoggerRegistry::new(Arc::clone(&root));
+        let pool = registry.get("db.pool");
+        registry.get("db").context().set("component", "db");
+        pool.context().set("pool_size", 8);
+
+        pool.log_with_context(
+            LogLevel::Info,
+            "checked out",
+            LogContext::new().with_field("component", "override"),
+        );
+        crate::info!(registry.get("db"), "plain");
+
+        let entries = entries.lock();
+        assert_eq!(field(&entries[0], "logger"), Some(FieldValue::from("db.pool")));
+        assert_eq!(field(&entries[0], "component"), Some(FieldValue::from("override")));
+        assert_eq!(field(&entries[0], "pool_size"), Some(FieldValue::from(8)));
+        assert_eq!(field(&entries[0], "service"), Some(FieldValue::from("api")));
+        assert_eq!(field(&entries[1], "logger"), Some(FieldValue::from("db")));
+        assert_eq!(field(&entries[1], "pool_size"), None);
+        assert_eq!(registry.names(), ["db", "db.pool"]);
+    }
+}
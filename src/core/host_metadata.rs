@@ -0,0 +1,218 @@
+//! Host and process metadata attached to every entry
+//!
+//! [`HostMetadata`] collects the fields that identify where an entry came
+//! from: hostname, process ID, service name, service version and deployment
+//! environment. They are computed once and added to the persistent context
+//! of the logger by [`LoggerBuilder::host_metadata`], so every entry carries
+//! them without any per-entry cost beyond the context merge.
+//!
+//! Service name, version and environment can be set in code and overridden
+//! by environment variables, so the same binary can be deployed with
+//! different values:
+//!
+//! | Variable                 | Field         |
+//! |--------------------------|---------------|
+//! | `LOGGER_HOSTNAME`        | `hostname`    |
+//! | `LOGGER_SERVICE_NAME`    | `service`     |
+//! | `LOGGER_SERVICE_VERSION` | `version`     |
+//! | `LOGGER_ENVIRONMENT`     | `environment` |
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::core::HostMetadata;
+//! use rust_logger_system::prelude::*;
+//!
+//! let logger = Logger::builder()
+//!     .host_metadata(
+//!         HostMetadata::detect()
+//!             .service("checkout")
+//!             .version(env!("CARGO_PKG_VERSION"))
+//!             .environment("production")
+//!             .env_overrides(),
+//!     )
+//!     .build();
+//!
+//! let fields = logger.context().get_fields();
+//! assert!(fields.contains_key("pid"));
+//! assert_eq!(fields.get("service"), Some(&"checkout".into()));
+//! ```
+//!
+//! [`LoggerBuilder::host_metadata`]: super::LoggerBuilder::host_metadata
+
+use super::config::ENV_OVERRIDE_PREFIX;
+use super::log_context::FieldValue;
+
+/// Context field holding the hostname
+pub const HOSTNAME_FIELD: &str = "hostname";
+/// Context field holding the process ID
+pub const PID_FIELD: &str = "pid";
+/// Context field holding the service name
+pub const SERVICE_FIELD: &str = "service";
+/// Context field holding the service version
+pub const VERSION_FIELD: &str = "version";
+/// Context field holding the deployment environment
+pub const ENVIRONMENT_FIELD: &str = "environment";
+
+/// Identity of the host, process and service, added to every entry
+///
+/// Unset values are left out of the entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostMetadata {
+    /// Name of the host
+    pub hostname: Option<String>,
+    /// ID of the process
+    pub pid: Option<u32>,
+    /// Name of the service
+    pub service: Option<String>,
+    /// Version of the service
+    pub version: Option<String>,
+    /// Deployment environment, e.g. `production` or `staging`
+    pub environment: Option<String>,
+}
+
+impl HostMetadata {
+    /// Create metadata without any values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create metadata with the hostname and process ID of this process
+    pub fn detect() -> Self {
+        Self {
+            hostname: detect_hostname(),
+            pid: Some(std::process::id()),
+            ..Self::default()
+        }
+    }
+
+    /// Set the hostname
+    #[must_use = "builder methods return a new value"]
+    pub fn hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.hostname = Some(hostname.into());
+        self
+    }
+
+    /// Set the service name
+    #[must_use = "builder methods return a new value"]
+    pub fn service(mut self, service: impl Into<String>) -> Self {
+        self.service = Some(service.into());
+        self
+    }
+
+    /// Set the service version
+    #[must_use = "builder methods return a new value"]
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Set the deployment environment
+    #[must_use = "builder methods return a new value"]
+    pub fn environment(mut self, environment: impl Into<String>) -> Self {
+        self.environment = Some(environment.into());
+        self
+    }
+
+    /// Override values with the `LOGGER_*` variables listed in the
+    /// [module documentation](self)
+    ///
+    /// Unset or empty variables leave the value unchanged.
+    #[must_use = "builder methods return a new value"]
+    pub fn env_overrides(self) -> Self {
+        self.lookup_overrides(ENV_OVERRIDE_PREFIX, |var| std::env::var(var).ok())
+    }
+
+    /// Override values with the variables named `<prefix>_HOSTNAME`,
+    /// `<prefix>_SERVICE_NAME`, ..., read through `lookup`
+    #[must_use = "builder methods return a new value"]
+    pub fn lookup_overrides(
+        mut self,
+        prefix: &str,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Self {
+        let get = |name: &str| {
+            let value = lookup(&format!("{}_{}", prefix, name))?;
+            let value = value.trim();
+            (!value.is_empty()).then(|| value.to_string())
+        };
+        let slots = [
+            ("HOSTNAME", &mut self.hostname),
+            ("SERVICE_NAME", &mut self.service),
+            ("SERVICE_VERSION", &mut self.version),
+            ("ENVIRONMENT", &mut self.environment),
+        ];
+        for (name, slot) in slots {
+            if let Some(value) = get(name) {
+                *slot = Some(value);
+            }
+        }
+        self
+    }
+
+    /// Get the context fields of the values that are set
+    pub fn fields(&self) -> Vec<(&'static str, FieldValue)> {
+        let string = |key, value: &Option<String>| Some((key, value.as_deref()?.into()));
+        [
+            string(HOSTNAME_FIELD, &self.hostname),
+            self.pid.map(|pid| (PID_FIELD, pid.into())),
+            string(SERVICE_FIELD, &self.service),
+            string(VERSION_FIELD, &self.version),
+            string(ENVIRONMENT_FIELD, &self.environment),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+/// Get the name of this host, or `None` if it cannot be determined
+fn detect_hostname() -> Option<String> {
+    #[cfg(unix)]
+    {
+        let mut buffer = [0u8; 256];
+        // SAFETY: gethostname writes at most `buffer.len()` bytes into the
+        // buffer and fails without touching it otherwise.
+        let ok = unsafe { libc::gethostname(buffer.as_mut_ptr().cast(), buffer.len()) } == 0;
+        if ok {
+            let end = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+            let name = String::from_utf8_lossy(&buffer[..end]).into_owned();
+            if !name.is_empty() {
+                return Some(name);
+            }
+        }
+    }
+    ["HOSTNAME", "COMPUTERNAME"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|name| !name.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_overrides() {
+        let detected = HostMetadata::detect();
+        assert_eq!(detected.pid, Some(std::process::id()));
+        assert!(detected.hostname.is_some());
+
+        let metadata = HostMetadata::new()
+            .service("api")
+            .version("1.0.0")
+            .environment("staging")
+            .lookup_overrides("APP", |var| match var {
+                "APP_ENVIRONMENT" => Some("production".to_string()),
+                "APP_SERVICE_VERSION" => Some(" ".to_string()),
+                _ => None,
+            });
+        assert_eq!(metadata.service.as_deref(), Some("api"));
+        assert_eq!(metadata.version.as_deref(), Some("1.0.0"));
+        assert_eq!(metadata.environment.as_deref(), Some("production"));
+        assert_eq!(metadata.hostname, None);
+
+        let keys: Vec<&str> = metadata.fields().iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, ["service", "version", "environment"]);
+    }
+}
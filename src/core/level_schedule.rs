@@ -0,0 +1,174 @@
+//! Time-based minimum level overrides
+//!
+//! A [`LevelSchedule`] holds daily time windows during which the logger's
+//! effective minimum level is replaced, e.g. to capture Debug output during
+//! a nightly maintenance window without redeploying.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//!
+//! // Debug between 02:00 and 04:00 UTC, Info otherwise
+//! let logger = Logger::builder()
+//!     .min_level(LogLevel::Info)
+//!     .level_schedule(LevelSchedule::new().between_hours(2, 4, LogLevel::Debug))
+//!     .build();
+//! ```
+
+use super::log_level::LogLevel;
+use chrono::{DateTime, Local, NaiveTime, Utc};
+
+/// A daily time window with its own minimum level
+///
+/// The window is half-open (`start..end`). If `end` is earlier than
+/// `start`, the window wraps around midnight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleWindow {
+    /// Time of day at which the window opens
+    pub start: NaiveTime,
+    /// Time of day at which the window closes
+    pub end: NaiveTime,
+    /// Minimum level while the window is active
+    pub level: LogLevel,
+}
+
+impl ScheduleWindow {
+    /// Check whether the given time of day falls inside this window
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Schedule of minimum level overrides
+///
+/// Windows are evaluated in the order they were added; the first window
+/// containing the current time wins. Outside all windows the logger's
+/// configured minimum level applies.
+#[derive(Debug, Clone, Default)]
+pub struct LevelSchedule {
+    windows: Vec<ScheduleWindow>,
+    use_local_time: bool,
+}
+
+impl LevelSchedule {
+    /// Create an empty schedule (times are interpreted as UTC)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a window between two times of day
+    #[must_use]
+    pub fn window(mut self, start: NaiveTime, end: NaiveTime, level: LogLevel) -> Self {
+        self.windows.push(ScheduleWindow { start, end, level });
+        self
+    }
+
+    /// Add a window between two whole hours (0-23)
+    ///
+    /// # Panics
+    ///
+    /// Panics if either hour is greater than 23
+    #[must_use]
+    pub fn between_hours(self, start_hour: u8, end_hour: u8, level: LogLevel) -> Self {
+        assert!(
+            start_hour <= 23 && end_hour <= 23,
+            "Hour must be between 0 and 23"
+        );
+        let at = |hour: u8| {
+            NaiveTime::from_hms_opt(u32::from(hour), 0, 0).expect("hour validated above")
+        };
+        self.window(at(start_hour), at(end_hour), level)
+    }
+
+    /// Interpret window times in the local timezone instead of UTC
+    #[must_use]
+    pub fn local_time(mut self, enabled: bool) -> Self {
+        self.use_local_time = enabled;
+        self
+    }
+
+    /// Get the configured windows
+    pub fn windows(&self) -> &[ScheduleWindow] {
+        &self.windows
+    }
+
+    /// Level override active at the given instant, if any
+    pub fn level_at(&self, now: DateTime<Utc>) -> Option<LogLevel> {
+        let time = if self.use_local_time {
+            now.with_timezone(&Local).time()
+        } else {
+            now.time()
+        };
+
+        self.windows
+            .iter()
+            .find(|window| window.contains(time))
+            .map(|window| window.level)
+    }
+
+    /// Level override active right now, if any
+    pub fn current_level(&self) -> Option<LogLevel> {
+        if self.windows.is_empty() {
+            return None;
+        }
+        self.level_at(Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 8, hour, minute, 0)
+            .single()
+            .expect("valid datetime")
+    }
+
+    #[test]
+    fn test_window_contains() {
+        let schedule = LevelSchedule::new().between_hours(2, 4, LogLevel::Debug);
+
+        assert_eq!(schedule.level_at(at(1, 59)), None);
+        assert_eq!(schedule.level_at(at(2, 0)), Some(LogLevel::Debug));
+        assert_eq!(schedule.level_at(at(3, 30)), Some(LogLevel::Debug));
+        assert_eq!(schedule.level_at(at(4, 0)), None);
+    }
+
+    #[test]
+    fn test_window_wraps_midnight() {
+        let schedule = LevelSchedule::new().between_hours(22, 2, LogLevel::Trace);
+
+        assert_eq!(schedule.level_at(at(23, 0)), Some(LogLevel::Trace));
+        assert_eq!(schedule.level_at(at(1, 0)), Some(LogLevel::Trace));
+        assert_eq!(schedule.level_at(at(12, 0)), None);
+    }
+
+    #[test]
+    fn test_first_matching_window_wins() {
+        let schedule = LevelSchedule::new()
+            .between_hours(8, 18, LogLevel::Warn)
+            .between_hours(0, 23, LogLevel::Debug);
+
+        assert_eq!(schedule.level_at(at(9, 0)), Some(LogLevel::Warn));
+        assert_eq!(schedule.level_at(at(20, 0)), Some(LogLevel::Debug));
+    }
+
+    #[test]
+    fn test_empty_schedule() {
+        let schedule = LevelSchedule::new();
+        assert_eq!(schedule.current_level(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Hour must be between 0 and 23")]
+    fn test_invalid_hour() {
+        let _ = LevelSchedule::new().between_hours(24, 2, LogLevel::Debug);
+    }
+}
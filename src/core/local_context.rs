@@ -0,0 +1,168 @@
+//! Context fields scoped to a closure or a future
+//!
+//! Unlike [`LoggerContext`](super::LoggerContext), which is shared by every
+//! thread using a logger, local fields are only attached to entries logged
+//! while the scope is active on the current thread. [`WithLocalFields`]
+//! re-enters the scope every time its future is polled, so the fields
+//! follow an async task across worker threads.
+//!
+//! Precedence: entry fields, then local fields (innermost scope first),
+//! then the logger's persistent context.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::core::local_context::with_local_fields;
+//! use rust_logger_system::prelude::*;
+//!
+//! let logger = Logger::new();
+//!
+//! with_local_fields(LogContext::new().with_field("request_id", "abc-123"), || {
+//!     logger.info("handling request"); // includes request_id
+//! });
+//! ```
+
+use super::log_context::LogContext;
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+thread_local! {
+    static LOCAL_FIELDS: RefCell<Vec<Arc<LogContext>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Run `f` with `fields` attached to every entry logged on this thread
+pub fn with_local_fields<R>(fields: LogContext, f: impl FnOnce() -> R) -> R {
+    enter(Arc::new(fields), f)
+}
+
+/// Merged snapshot of the local fields active on this thread
+pub fn local_fields() -> LogContext {
+    let mut context = LogContext::new();
+    merge_into(&mut context);
+    context
+}
+
+fn enter<R>(fields: Arc<LogContext>, f: impl FnOnce() -> R) -> R {
+    /// Pops the scope even if `f` panics
+    struct ScopeGuard;
+
+    impl Drop for ScopeGuard {
+        fn drop(&mut self) {
+            LOCAL_FIELDS.with(|stack| stack.borrow_mut().pop());
+        }
+    }
+
+    LOCAL_FIELDS.with(|stack| stack.borrow_mut().push(fields));
+    let _guard = ScopeGuard;
+    f()
+}
+
+/// Check whether any local scope is active on this thread
+pub(crate) fn is_active() -> bool {
+    LOCAL_FIELDS.with(|stack| !stack.borrow().is_empty())
+}
+
+/// Add local fields that are not already present in `log_context`
+pub(crate) fn merge_into(log_context: &mut LogContext) {
+    LOCAL_FIELDS.with(|stack| {
+        for fields in stack.borrow().iter().rev() {
+            for (key, value) in fields.field_map() {
+                if !log_context.field_map().contains_key(key) {
+                    log_context.add_field(key.clone(), value.clone());
+                }
+            }
+        }
+    });
+}
+
+/// Future that attaches local fields to entries logged while it is polled
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::core::local_context::WithLocalFields;
+/// use rust_logger_system::prelude::*;
+///
+/// # async fn handle(_logger: &Logger) {}
+/// async fn serve(logger: &Logger) {
+///     let fields = LogContext::new().with_field("request_id", "abc-123");
+///     WithLocalFields::new(fields, handle(logger)).await;
+/// }
+/// ```
+pub struct WithLocalFields<F> {
+    fields: Arc<LogContext>,
+    inner: Pin<Box<F>>,
+}
+
+impl<F: Future> WithLocalFields<F> {
+    /// Wrap a future so that `fields` are attached while it runs
+    pub fn new(fields: LogContext, future: F) -> Self {
+        Self {
+            fields: Arc::new(fields),
+            inner: Box::pin(future),
+        }
+    }
+}
+
+impl<F: Future> Future for WithLocalFields<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let fields = Arc::clone(&self.fields);
+        enter(fields, || self.inner.as_mut().poll(cx))
+    }
+}
+
+impl<F> std::fmt::Debug for WithLocalFields<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WithLocalFields")
+            .field("fields", &self.fields)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_scopes() {
+        let outer = LogContext::new()
+            .with_field("request_id", "abc")
+            .with_field("stage", "outer");
+        with_local_fields(outer, || {
+            with_local_fields(LogContext::new().with_field("stage", "inner"), || {
+                let fields = local_fields();
+                assert_eq!(fields.fields()["stage"], "inner".into());
+                assert_eq!(fields.fields()["request_id"], "abc".into());
+            });
+            assert_eq!(local_fields().fields()["stage"], "outer".into());
+        });
+        assert!(!is_active());
+    }
+
+    #[test]
+    fn test_scope_popped_on_panic() {
+        let result = std::panic::catch_unwind(|| {
+            with_local_fields(LogContext::new().with_field("k", 1), || panic!("boom"));
+        });
+        assert!(result.is_err());
+        assert!(!is_active());
+    }
+
+    #[tokio::test]
+    async fn test_future_fields_follow_polls() {
+        let fields = LogContext::new().with_field("request_id", "xyz");
+        let seen = WithLocalFields::new(fields, async {
+            tokio::task::yield_now().await;
+            local_fields()
+        })
+        .await;
+
+        assert_eq!(seen.fields()["request_id"], "xyz".into());
+        assert!(!is_active());
+    }
+}
@@ -5,21 +5,51 @@
 //! - `LoggerContext`: Persistent fields across all log entries
 //! - `ContextGuard`: RAII guard for scoped context
 
+use chrono::{DateTime, SecondsFormat, Utc};
+use indexmap::IndexMap;
 use parking_lot::RwLock;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::de::{MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use smallvec::SmallVec;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
 /// Value type for structured logging fields
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Composite values (`Array`, `Map`) can be nested arbitrarily. JSON output
+/// renders them natively; logfmt output flattens them into dotted keys
+/// (e.g. `user.id=42 items.0=a`).
+///
+/// Rendering of the remaining typed variants:
+///
+/// | Variant     | Text / logfmt              | JSON                   |
+/// |-------------|----------------------------|------------------------|
+/// | `U64`       | `18446744073709551615`     | number                 |
+/// | `Bytes`     | hex (`deadbeef`)           | base64 string          |
+/// | `Duration`  | `1.5s`, `250ms`            | seconds as a number    |
+/// | `Timestamp` | RFC 3339 (`...T10:30:45Z`) | RFC 3339 string        |
+///
+/// Deserialization is untagged, so bytes, durations and timestamps read back
+/// as their rendered string or number form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum FieldValue {
     String(String),
     Int(i64),
+    U64(u64),
     Float(f64),
     Bool(bool),
     Null,
+    Array(Vec<FieldValue>),
+    Map(BTreeMap<String, FieldValue>),
+    #[serde(serialize_with = "serialize_bytes", skip_deserializing)]
+    Bytes(Vec<u8>),
+    #[serde(serialize_with = "serialize_duration", skip_deserializing)]
+    Duration(Duration),
+    #[serde(skip_deserializing)]
+    Timestamp(DateTime<Utc>),
 }
 
 impl fmt::Display for FieldValue {
@@ -27,9 +57,14 @@ impl fmt::Display for FieldValue {
         match self {
             FieldValue::String(s) => write!(f, "{}", s),
             FieldValue::Int(i) => write!(f, "{}", i),
+            FieldValue::U64(u) => write!(f, "{}", u),
             FieldValue::Float(fl) => write!(f, "{}", fl),
             FieldValue::Bool(b) => write!(f, "{}", b),
             FieldValue::Null => write!(f, "null"),
+            FieldValue::Array(_) | FieldValue::Map(_) => write!(f, "{}", self.to_json_value()),
+            FieldValue::Bytes(bytes) => write!(f, "{}", to_hex(bytes)),
+            FieldValue::Duration(d) => write!(f, "{:?}", d),
+            FieldValue::Timestamp(ts) => write!(f, "{}", format_timestamp(ts)),
         }
     }
 }
@@ -41,15 +76,69 @@ impl FieldValue {
         match self {
             FieldValue::String(s) => serde_json::Value::String(s.clone()),
             FieldValue::Int(i) => serde_json::Value::Number((*i).into()),
+            FieldValue::U64(u) => serde_json::Value::Number((*u).into()),
             FieldValue::Float(f) => serde_json::Number::from_f64(*f)
                 .map(serde_json::Value::Number)
                 .unwrap_or(serde_json::Value::Null),
             FieldValue::Bool(b) => serde_json::Value::Bool(*b),
             FieldValue::Null => serde_json::Value::Null,
+            FieldValue::Array(items) => {
+                serde_json::Value::Array(items.iter().map(FieldValue::to_json_value).collect())
+            }
+            FieldValue::Map(map) => serde_json::Value::Object(
+                map.iter()
+                    .map(|(key, value)| (key.clone(), value.to_json_value()))
+                    .collect(),
+            ),
+            FieldValue::Bytes(bytes) => serde_json::Value::String(to_base64(bytes)),
+            FieldValue::Duration(d) => serde_json::Number::from_f64(d.as_secs_f64())
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            FieldValue::Timestamp(ts) => serde_json::Value::String(format_timestamp(ts)),
         }
     }
 }
 
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&to_base64(bytes))
+}
+
+fn serialize_duration<S: Serializer>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(d.as_secs_f64())
+}
+
+fn format_timestamp(ts: &DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// Lowercase hex encoding
+fn to_hex(bytes: &[u8]) -> String {
+    use fmt::Write;
+
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(out, "{:02x}", byte);
+    }
+    out
+}
+
+/// Standard base64 encoding with padding
+fn to_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+    out
+}
+
 impl From<String> for FieldValue {
     fn from(s: String) -> Self {
         FieldValue::String(s)
@@ -74,6 +163,24 @@ impl From<i32> for FieldValue {
     }
 }
 
+impl From<u32> for FieldValue {
+    fn from(u: u32) -> Self {
+        FieldValue::Int(i64::from(u))
+    }
+}
+
+impl From<u64> for FieldValue {
+    fn from(u: u64) -> Self {
+        FieldValue::U64(u)
+    }
+}
+
+impl From<usize> for FieldValue {
+    fn from(u: usize) -> Self {
+        FieldValue::U64(u as u64)
+    }
+}
+
 impl From<f64> for FieldValue {
     fn from(f: f64) -> Self {
         FieldValue::Float(f)
@@ -86,18 +193,301 @@ impl From<bool> for FieldValue {
     }
 }
 
+impl From<&[u8]> for FieldValue {
+    fn from(bytes: &[u8]) -> Self {
+        FieldValue::Bytes(bytes.to_vec())
+    }
+}
+
+impl From<Duration> for FieldValue {
+    fn from(d: Duration) -> Self {
+        FieldValue::Duration(d)
+    }
+}
+
+impl From<DateTime<Utc>> for FieldValue {
+    fn from(ts: DateTime<Utc>) -> Self {
+        FieldValue::Timestamp(ts)
+    }
+}
+
+impl<T: Into<FieldValue>> From<Vec<T>> for FieldValue {
+    fn from(items: Vec<T>) -> Self {
+        FieldValue::Array(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<K: Into<String>, V: Into<FieldValue>> From<BTreeMap<K, V>> for FieldValue {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        FieldValue::Map(map.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
+impl<K: Into<String>, V: Into<FieldValue>> From<HashMap<K, V>> for FieldValue {
+    fn from(map: HashMap<K, V>) -> Self {
+        FieldValue::Map(map.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
+/// Fields held in a single allocation before spilling to a larger one
+const INLINE_FIELDS: usize = 8;
+
+type FieldVec = SmallVec<[(String, FieldValue); INLINE_FIELDS]>;
+
+/// Insertion-ordered fields of a [`LogContext`]
+///
+/// Entries typically carry one to eight fields, so they are kept as a list
+/// of pairs searched linearly, all in one allocation. An empty map is a
+/// single pointer and allocates nothing. The API follows the parts of
+/// `IndexMap` that contexts need; it serializes as a map.
+#[derive(Clone, Default)]
+pub struct FieldMap {
+    entries: Option<Box<FieldVec>>,
+}
+
+/// Iterator over the fields of a [`FieldMap`]
+pub type FieldIter<'a> =
+    std::iter::Map<std::slice::Iter<'a, (String, FieldValue)>, PairRef<'a>>;
+
+/// Iterator over the fields of a [`FieldMap`] with mutable values
+pub type FieldIterMut<'a> =
+    std::iter::Map<std::slice::IterMut<'a, (String, FieldValue)>, PairMut<'a>>;
+
+type PairRef<'a> = fn(&'a (String, FieldValue)) -> (&'a String, &'a FieldValue);
+type PairMut<'a> = fn(&'a mut (String, FieldValue)) -> (&'a String, &'a mut FieldValue);
+
+impl FieldMap {
+    /// Create an empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of fields
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Check if there are no fields
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    fn as_slice(&self) -> &[(String, FieldValue)] {
+        self.entries.as_deref().map_or(&[], |entries| entries.as_slice())
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [(String, FieldValue)] {
+        match self.entries.as_deref_mut() {
+            Some(entries) => entries.as_mut_slice(),
+            None => &mut [],
+        }
+    }
+
+    fn position(&self, key: &str) -> Option<usize> {
+        self.as_slice().iter().position(|(k, _)| k == key)
+    }
+
+    /// Get the value of `key`
+    pub fn get(&self, key: &str) -> Option<&FieldValue> {
+        self.as_slice().iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Get mutable access to the value of `key`
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut FieldValue> {
+        self.as_mut_slice().iter_mut().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Check if `key` is set
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Set `key`, returning its previous value
+    ///
+    /// An existing key keeps its position.
+    pub fn insert(&mut self, key: String, value: FieldValue) -> Option<FieldValue> {
+        match self.get_mut(&key) {
+            Some(slot) => Some(std::mem::replace(slot, value)),
+            None => {
+                self.entries.get_or_insert_with(Box::default).push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Remove `key`, keeping the order of the other fields
+    pub fn shift_remove(&mut self, key: &str) -> Option<FieldValue> {
+        let index = self.position(key)?;
+        self.entries.as_mut().map(|entries| entries.remove(index).1)
+    }
+
+    /// Rename `from` to `to`, keeping its position
+    ///
+    /// A field already named `to` is replaced. Returns `false` if `from` is
+    /// not set.
+    pub fn rename(&mut self, from: &str, to: impl Into<String>) -> bool {
+        let to = to.into();
+        if from != to && self.contains_key(from) {
+            self.shift_remove(&to);
+        }
+        match self.position(from) {
+            Some(index) => {
+                self.as_mut_slice()[index].0 = to;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Keep only the fields for which `keep` returns true
+    pub fn retain(&mut self, mut keep: impl FnMut(&String, &mut FieldValue) -> bool) {
+        if let Some(entries) = self.entries.as_mut() {
+            entries.retain(|(k, v)| keep(k, v));
+        }
+    }
+
+    /// Remove all fields
+    pub fn clear(&mut self) {
+        if let Some(entries) = self.entries.as_mut() {
+            entries.clear();
+        }
+    }
+
+    /// Iterate over the fields in insertion order
+    pub fn iter(&self) -> FieldIter<'_> {
+        self.as_slice().iter().map(|(k, v)| (k, v))
+    }
+
+    /// Iterate over the fields with mutable values
+    pub fn iter_mut(&mut self) -> FieldIterMut<'_> {
+        self.as_mut_slice().iter_mut().map(|(k, v)| (&*k, v))
+    }
+
+    /// Iterate over the keys in insertion order
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.as_slice().iter().map(|(k, _)| k)
+    }
+
+    /// Iterate over the values in insertion order
+    pub fn values(&self) -> impl Iterator<Item = &FieldValue> {
+        self.as_slice().iter().map(|(_, v)| v)
+    }
+}
+
+impl PartialEq for FieldMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl fmt::Debug for FieldMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl std::ops::Index<&str> for FieldMap {
+    type Output = FieldValue;
+
+    /// # Panics
+    ///
+    /// Panics if `key` is not set.
+    fn index(&self, key: &str) -> &FieldValue {
+        self.get(key).unwrap_or_else(|| panic!("field '{}' is not set", key))
+    }
+}
+
+impl<'a> IntoIterator for &'a FieldMap {
+    type Item = (&'a String, &'a FieldValue);
+    type IntoIter = FieldIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut FieldMap {
+    type Item = (&'a String, &'a mut FieldValue);
+    type IntoIter = FieldIterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl IntoIterator for FieldMap {
+    type Item = (String, FieldValue);
+    type IntoIter = smallvec::IntoIter<[(String, FieldValue); INLINE_FIELDS]>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.map(|entries| *entries).unwrap_or_default().into_iter()
+    }
+}
+
+impl Extend<(String, FieldValue)> for FieldMap {
+    fn extend<I: IntoIterator<Item = (String, FieldValue)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl FromIterator<(String, FieldValue)> for FieldMap {
+    fn from_iter<I: IntoIterator<Item = (String, FieldValue)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl Serialize for FieldMap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldMap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldMapVisitor;
+
+        impl<'de> Visitor<'de> for FieldMapVisitor {
+            type Value = FieldMap;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a map of fields")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<FieldMap, A::Error> {
+                let mut map = FieldMap::new();
+                while let Some((key, value)) = access.next_entry()? {
+                    map.insert(key, value);
+                }
+                Ok(map)
+            }
+        }
+
+        deserializer.deserialize_map(FieldMapVisitor)
+    }
+}
+
 /// Context for structured logging with key-value fields
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+///
+/// Fields keep their insertion order, so formatted output is deterministic.
+/// Re-inserting an existing key updates its value in place.
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct LogContext {
-    fields: HashMap<String, FieldValue>,
+    #[serde(default)]
+    fields: FieldMap,
+    /// Copy of `fields` handed out by [`fields`](Self::fields), built on
+    /// demand and dropped on every change
+    #[serde(skip)]
+    indexed: OnceLock<Box<IndexMap<String, FieldValue>>>,
 }
 
 impl LogContext {
     /// Create a new empty log context
     pub fn new() -> Self {
-        Self {
-            fields: HashMap::new(),
-        }
+        Self::default()
     }
 
     /// Add a field to the context
@@ -106,7 +496,7 @@ impl LogContext {
         K: Into<String>,
         V: Into<FieldValue>,
     {
-        self.fields.insert(key.into(), value.into());
+        self.add_field(key, value);
         self
     }
 
@@ -116,14 +506,76 @@ impl LogContext {
         K: Into<String>,
         V: Into<FieldValue>,
     {
-        self.fields.insert(key.into(), value.into());
+        self.fields_mut().insert(key.into(), value.into());
+    }
+
+    /// Record an error as structured fields
+    ///
+    /// Adds `error.message` (the `Display` output), `error.type` and, if the
+    /// error has underlying causes, `error.chain` with the message of each
+    /// `source()` in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let err = "abc".parse::<i32>().unwrap_err();
+    /// let ctx = LogContext::new().with_error(&err);
+    ///
+    /// assert_eq!(ctx.fields()["error.message"], "invalid digit found in string".into());
+    /// assert_eq!(ctx.fields()["error.type"], "core::num::error::ParseIntError".into());
+    /// ```
+    #[must_use]
+    pub fn with_error<E>(mut self, error: &E) -> Self
+    where
+        E: std::error::Error + ?Sized,
+    {
+        self.add_error(error);
+        self
+    }
+
+    /// Record an error as structured fields (mutable version)
+    pub fn add_error<E>(&mut self, error: &E)
+    where
+        E: std::error::Error + ?Sized,
+    {
+        self.add_field("error.message", error.to_string());
+        self.add_field("error.type", error_type_name(error));
+
+        let mut chain = Vec::new();
+        let mut source = error.source();
+        while let Some(cause) = source {
+            chain.push(FieldValue::String(cause.to_string()));
+            source = cause.source();
+        }
+        if !chain.is_empty() {
+            self.add_field("error.chain", FieldValue::Array(chain));
+        }
+    }
+
+    /// Get all fields, in insertion order
+    ///
+    /// The map is copied from the context's own storage on the first call
+    /// after a change; [`field_map`](Self::field_map) borrows the fields
+    /// without copying.
+    pub fn fields(&self) -> &IndexMap<String, FieldValue> {
+        self.indexed.get_or_init(|| {
+            Box::new(self.fields.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        })
     }
 
-    /// Get all fields
-    pub fn fields(&self) -> &HashMap<String, FieldValue> {
+    /// Get all fields, in insertion order, as stored
+    pub fn field_map(&self) -> &FieldMap {
         &self.fields
     }
 
+    /// Get mutable access to the fields, for redaction and transformers
+    pub fn fields_mut(&mut self) -> &mut FieldMap {
+        self.indexed.take();
+        &mut self.fields
+    }
+
     /// Check if context has any fields
     pub fn is_empty(&self) -> bool {
         self.fields.is_empty()
@@ -139,12 +591,43 @@ impl LogContext {
     }
 }
 
+impl fmt::Debug for LogContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogContext").field("fields", &self.fields).finish()
+    }
+}
+
 impl fmt::Display for LogContext {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.format_fields())
     }
 }
 
+/// Best-effort type name of an error
+///
+/// Uses the static type when known. For trait objects, falls back to the
+/// leading identifier of the `Debug` output (e.g. `Custom` or `ParseIntError`).
+fn error_type_name<E>(error: &E) -> String
+where
+    E: std::error::Error + ?Sized,
+{
+    let name = std::any::type_name::<E>();
+    if !name.starts_with("dyn ") {
+        return name.to_string();
+    }
+
+    let debug = format!("{:?}", error);
+    let ident: String = debug
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    if ident.is_empty() {
+        name.to_string()
+    } else {
+        ident
+    }
+}
+
 /// Logger-level persistent context for structured logging
 ///
 /// `LoggerContext` stores fields that persist across all log entries.
@@ -168,14 +651,14 @@ impl fmt::Display for LogContext {
 /// ```
 #[derive(Debug, Clone)]
 pub struct LoggerContext {
-    fields: Arc<RwLock<HashMap<String, FieldValue>>>,
+    fields: Arc<RwLock<IndexMap<String, FieldValue>>>,
 }
 
 impl LoggerContext {
     /// Create a new empty logger context
     pub fn new() -> Self {
         Self {
-            fields: Arc::new(RwLock::new(HashMap::new())),
+            fields: Arc::new(RwLock::new(IndexMap::new())),
         }
     }
 
@@ -192,7 +675,7 @@ impl LoggerContext {
 
     /// Remove a field from the context
     pub fn remove(&self, key: &str) {
-        self.fields.write().remove(key);
+        self.fields.write().shift_remove(key);
     }
 
     /// Clear all fields from the context
@@ -200,8 +683,8 @@ impl LoggerContext {
         self.fields.write().clear();
     }
 
-    /// Get a clone of all fields
-    pub fn get_fields(&self) -> HashMap<String, FieldValue> {
+    /// Get a clone of all fields, in insertion order
+    pub fn get_fields(&self) -> IndexMap<String, FieldValue> {
         self.fields.read().clone()
     }
 
@@ -223,7 +706,7 @@ impl LoggerContext {
         for (key, value) in fields.iter() {
             // Only insert if the key doesn't exist (entry-level takes priority)
             if !log_context.fields.contains_key(key) {
-                log_context.fields.insert(key.clone(), value.clone());
+                log_context.fields_mut().insert(key.clone(), value.clone());
             }
         }
     }
@@ -232,15 +715,33 @@ impl LoggerContext {
     pub fn to_log_context(&self) -> LogContext {
         let fields = self.fields.read();
         LogContext {
-            fields: fields.clone(),
+            fields: fields.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            indexed: OnceLock::new(),
         }
     }
 
-    /// Get the internal fields Arc for creating ContextGuard
+    /// Set a field for the lifetime of the returned guard
+    ///
+    /// When the guard is dropped, the field is restored to the value it had
+    /// before (or removed if it was not set).
+    pub fn scoped<K, V>(&self, key: K, value: V) -> ContextGuard
+    where
+        K: Into<String>,
+        V: Into<FieldValue>,
+    {
+        ContextGuard::new(Arc::clone(&self.fields)).and(key, value)
+    }
+
+    /// Set several fields for the lifetime of the returned guard
     ///
-    /// This is used internally for creating RAII guards.
-    pub(crate) fn inner_fields(&self) -> Arc<RwLock<HashMap<String, FieldValue>>> {
-        Arc::clone(&self.fields)
+    /// All fields are restored together when the guard is dropped.
+    pub fn scoped_fields(&self, fields: LogContext) -> ContextGuard {
+        fields
+            .fields
+            .into_iter()
+            .fold(ContextGuard::new(Arc::clone(&self.fields)), |guard, (key, value)| {
+                guard.and(key, value)
+            })
     }
 }
 
@@ -252,12 +753,16 @@ impl Default for LoggerContext {
 
 /// RAII guard for scoped context fields
 ///
-/// When dropped, automatically removes the field from the logger context.
-/// This is useful for adding temporary context fields for a specific scope.
+/// A guard owns one or more fields of the logger context. When dropped, each
+/// field is restored to the value it had when the guard set it, or removed if
+/// it was not set before. Guards can therefore be nested: an inner guard that
+/// shadows a field of an outer scope hands the outer value back on drop.
 ///
 /// # Example
 ///
-/// ```ignore
+/// ```
+/// use rust_logger_system::Logger;
+///
 /// let logger = Logger::builder().build();
 ///
 /// {
@@ -265,28 +770,77 @@ impl Default for LoggerContext {
 ///     logger.info("Processing request");  // Includes request_id
 /// }
 /// // request_id automatically removed here
+/// assert!(logger.context().is_empty());
+///
+/// // One guard can own several fields, and nested guards restore
+/// // the values they shadowed
+/// let _outer = logger.with_context("tenant", "acme").and("region", "eu");
+/// {
+///     let _inner = logger.with_context("tenant", "globex");
+///     assert_eq!(logger.context().get_fields()["tenant"], "globex".into());
+/// }
+/// assert_eq!(logger.context().get_fields()["tenant"], "acme".into());
 /// ```
+#[must_use = "the context fields are removed as soon as the guard is dropped"]
 pub struct ContextGuard {
-    context: Arc<RwLock<HashMap<String, FieldValue>>>,
-    key: String,
+    context: Arc<RwLock<IndexMap<String, FieldValue>>>,
+    /// Keys set by this guard, with the values they replaced
+    previous: Vec<(String, Option<FieldValue>)>,
 }
 
 impl ContextGuard {
-    /// Create a new context guard
-    pub(crate) fn new(context: Arc<RwLock<HashMap<String, FieldValue>>>, key: String) -> Self {
-        Self { context, key }
+    /// Create an empty guard over the given context fields
+    pub(crate) fn new(context: Arc<RwLock<IndexMap<String, FieldValue>>>) -> Self {
+        Self {
+            context,
+            previous: Vec::new(),
+        }
+    }
+
+    /// Set another field owned by this guard
+    pub fn and<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<FieldValue>,
+    {
+        let key = key.into();
+        let previous = self.context.write().insert(key.clone(), value.into());
+        self.previous.push((key, previous));
+        self
+    }
+
+    /// Keys owned by this guard, in the order they were set
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.previous.iter().map(|(key, _)| key.as_str())
     }
 }
 
 impl Drop for ContextGuard {
     fn drop(&mut self) {
-        self.context.write().remove(&self.key);
+        let mut fields = self.context.write();
+        // Restore in reverse so a key set twice by this guard ends up with
+        // the value it had before the guard
+        for (key, previous) in self.previous.drain(..).rev() {
+            match previous {
+                Some(value) => fields.insert(key, value),
+                None => fields.shift_remove(&key),
+            };
+        }
+    }
+}
+
+impl fmt::Debug for ContextGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextGuard")
+            .field("keys", &self.keys().collect::<Vec<_>>())
+            .finish()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
 
     #[test]
     fn test_log_context_creation() {
@@ -383,4 +937,255 @@ mod tests {
             _ => panic!("Expected string value"),
         }
     }
+
+    #[test]
+    fn test_context_guard_restores_shadowed_value() {
+        let ctx = LoggerContext::new();
+        ctx.set("tenant", "acme");
+
+        {
+            let _guard = ctx.scoped("tenant", "globex");
+            assert_eq!(ctx.get_fields()["tenant"], "globex".into());
+        }
+
+        assert_eq!(ctx.get_fields()["tenant"], "acme".into());
+    }
+
+    #[test]
+    fn test_context_guard_multiple_fields() {
+        let ctx = LoggerContext::new();
+
+        {
+            let guard = ctx.scoped("request_id", "abc").and("user_id", 42);
+            assert_eq!(guard.keys().collect::<Vec<_>>(), vec!["request_id", "user_id"]);
+            assert_eq!(ctx.len(), 2);
+        }
+
+        assert!(ctx.is_empty());
+    }
+
+    #[test]
+    fn test_context_guard_nesting() {
+        let ctx = LoggerContext::new();
+
+        let outer = ctx.scoped_fields(
+            LogContext::new()
+                .with_field("request_id", "outer")
+                .with_field("stage", "parse"),
+        );
+        {
+            let _inner = ctx.scoped("stage", "validate").and("rule", "schema");
+            let fields = ctx.get_fields();
+            assert_eq!(fields["request_id"], "outer".into());
+            assert_eq!(fields["stage"], "validate".into());
+            assert_eq!(fields["rule"], "schema".into());
+        }
+
+        let fields = ctx.get_fields();
+        assert_eq!(fields["stage"], "parse".into());
+        assert!(!fields.contains_key("rule"));
+
+        drop(outer);
+        assert!(ctx.is_empty());
+    }
+
+    #[test]
+    fn test_context_guard_same_key_twice() {
+        let ctx = LoggerContext::new();
+        ctx.set("attempt", 0);
+
+        {
+            let _guard = ctx.scoped("attempt", 1).and("attempt", 2);
+            assert_eq!(ctx.get_fields()["attempt"], 2.into());
+        }
+
+        assert_eq!(ctx.get_fields()["attempt"], 0.into());
+    }
+
+    #[test]
+    fn test_composite_field_values() {
+        let ids: FieldValue = vec![3, 7, 11].into();
+        assert_eq!(
+            ids,
+            FieldValue::Array(vec![FieldValue::Int(3), FieldValue::Int(7), FieldValue::Int(11)])
+        );
+        assert_eq!(ids.to_string(), "[3,7,11]");
+
+        let mut user = BTreeMap::new();
+        user.insert("id", FieldValue::Int(42));
+        user.insert("roles", vec!["admin", "ops"].into());
+        let user: FieldValue = user.into();
+        assert_eq!(
+            user.to_json_value(),
+            serde_json::json!({"id": 42, "roles": ["admin", "ops"]})
+        );
+    }
+
+    #[test]
+    fn test_composite_field_values_deserialize() {
+        let value: FieldValue = serde_json::from_str(r#"{"ids":[1,2],"ok":true}"#).unwrap();
+        let FieldValue::Map(map) = value else {
+            panic!("expected map");
+        };
+        assert_eq!(map["ids"], vec![1, 2].into());
+        assert_eq!(map["ok"], FieldValue::Bool(true));
+    }
+
+    #[test]
+    fn test_typed_field_values() {
+        assert_eq!(FieldValue::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(FieldValue::from(u64::MAX).to_json_value(), serde_json::json!(u64::MAX));
+        assert_eq!(FieldValue::from(7u32), FieldValue::Int(7));
+
+        let bytes = FieldValue::from(&b"\xde\xad\xbe\xef"[..]);
+        assert_eq!(bytes.to_string(), "deadbeef");
+        assert_eq!(bytes.to_json_value(), "3q2+7w==");
+
+        let d = FieldValue::from(Duration::from_millis(1500));
+        assert_eq!(d.to_string(), "1.5s");
+        assert_eq!(d.to_json_value(), serde_json::json!(1.5));
+
+        let ts = Utc.with_ymd_and_hms(2025, 1, 8, 10, 30, 45).unwrap();
+        let ts = FieldValue::from(ts);
+        assert_eq!(ts.to_string(), "2025-01-08T10:30:45Z");
+        assert_eq!(serde_json::to_string(&ts).unwrap(), "\"2025-01-08T10:30:45Z\"");
+    }
+
+    #[test]
+    fn test_base64_padding() {
+        assert_eq!(to_base64(b""), "");
+        assert_eq!(to_base64(b"f"), "Zg==");
+        assert_eq!(to_base64(b"fo"), "Zm8=");
+        assert_eq!(to_base64(b"foo"), "Zm9v");
+        assert_eq!(to_base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_u64_deserializes_beyond_i64() {
+        let value: FieldValue = serde_json::from_str("18446744073709551615").unwrap();
+        assert_eq!(value, FieldValue::U64(u64::MAX));
+        let value: FieldValue = serde_json::from_str("5").unwrap();
+        assert_eq!(value, FieldValue::Int(5));
+    }
+
+    #[derive(Debug)]
+    struct QueryError {
+        source: std::io::Error,
+    }
+
+    impl fmt::Display for QueryError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "query failed")
+        }
+    }
+
+    impl std::error::Error for QueryError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.source)
+        }
+    }
+
+    #[test]
+    fn test_with_error_records_chain() {
+        let err = QueryError {
+            source: std::io::Error::new(std::io::ErrorKind::TimedOut, "connection timed out"),
+        };
+        let ctx = LogContext::new().with_error(&err);
+        let fields = ctx.fields();
+
+        assert_eq!(fields["error.message"], "query failed".into());
+        assert!(fields["error.type"].to_string().ends_with("QueryError"));
+        assert_eq!(fields["error.chain"], vec!["connection timed out"].into());
+    }
+
+    #[test]
+    fn test_with_error_trait_object() {
+        let err: Box<dyn std::error::Error> = Box::new(QueryError {
+            source: std::io::Error::other("disk full"),
+        });
+        let ctx = LogContext::new().with_error(err.as_ref());
+
+        assert_eq!(ctx.fields()["error.type"], "QueryError".into());
+        assert_eq!(ctx.fields()["error.message"], "query failed".into());
+    }
+
+    #[test]
+    fn test_with_error_without_source() {
+        let err = "x".parse::<i32>().unwrap_err();
+        let ctx = LogContext::new().with_error(&err);
+        assert!(!ctx.fields().contains_key("error.chain"));
+    }
+
+    #[test]
+    fn test_fields_keep_insertion_order() {
+        let ctx = LogContext::new()
+            .with_field("zeta", 1)
+            .with_field("alpha", 2)
+            .with_field("mid", 3)
+            .with_field("zeta", 4);
+
+        assert_eq!(ctx.fields().keys().collect::<Vec<_>>(), vec!["zeta", "alpha", "mid"]);
+        assert_eq!(ctx.format_fields(), "zeta=4 alpha=2 mid=3");
+    }
+
+    #[test]
+    fn test_field_map_spills_and_roundtrips() {
+        let mut map: FieldMap = (0..INLINE_FIELDS + 2)
+            .map(|i| (format!("k{}", i), FieldValue::from(i as i64)))
+            .collect();
+        assert_eq!(map.insert("k1".to_string(), "updated".into()), Some(1.into()));
+        for i in (0..INLINE_FIELDS).filter(|i| *i != 1) {
+            assert_eq!(map.shift_remove(&format!("k{}", i)), Some((i as i64).into()));
+        }
+        assert_eq!(map.keys().collect::<Vec<_>>(), vec!["k1", "k8", "k9"]);
+        assert_eq!(map["k1"], "updated".into());
+
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"k1":"updated","k8":8,"k9":9}"#);
+        assert_eq!(serde_json::from_str::<FieldMap>(&json).unwrap(), map);
+
+        map.clear();
+        assert_eq!(map, FieldMap::new());
+    }
+
+    #[test]
+    fn test_fields_view_follows_changes() {
+        // An entry without fields pays for a pointer, not for inline slots
+        assert!(std::mem::size_of::<LogContext>() <= 3 * std::mem::size_of::<usize>());
+
+        let mut ctx = LogContext::new().with_field("a", 1);
+        let fields: &IndexMap<String, FieldValue> = ctx.fields();
+        assert_eq!(fields.get_index(0), Some((&"a".to_string(), &1.into())));
+
+        ctx.add_field("b", 2);
+        ctx.fields_mut().rename("a", "c");
+        assert_eq!(ctx.fields().keys().collect::<Vec<_>>(), vec!["c", "b"]);
+        assert_eq!(ctx.field_map().keys().collect::<Vec<_>>(), vec!["c", "b"]);
+    }
+
+    #[test]
+    fn test_merge_appends_persistent_fields_in_order() {
+        let logger_ctx = LoggerContext::new();
+        logger_ctx.set("service", "api");
+        logger_ctx.set("env", "prod");
+        logger_ctx.set("request_id", "ignored");
+
+        let mut entry_ctx = LogContext::new().with_field("request_id", "abc");
+        logger_ctx.merge_into(&mut entry_ctx);
+
+        assert_eq!(entry_ctx.format_fields(), "request_id=abc service=api env=prod");
+    }
+
+    #[test]
+    fn test_guard_restores_without_reordering() {
+        let ctx = LoggerContext::new();
+        ctx.set("a", 1);
+        ctx.set("b", 2);
+
+        {
+            let _guard = ctx.scoped("c", 3).and("a", 10);
+        }
+
+        assert_eq!(ctx.get_fields().keys().collect::<Vec<_>>(), vec!["a", "b"]);
+    }
 }
@@ -1,34 +1,63 @@
 //! Log entry structure
 
-use super::log_context::LogContext;
+use super::entry_pool;
+use super::log_context::{FieldValue, LogContext};
 use super::log_level::LogLevel;
+use super::overflow_policy::LogPriority;
+use super::severity::{CustomLevel, Severity};
+use super::structured_entry::TracingContext;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Distinct strings [`intern`] keeps per thread before it stops caching
+const INTERN_CAPACITY: usize = 1024;
 
 // Thread-local caches for thread information to avoid repeated allocations
 thread_local! {
-    static THREAD_ID_CACHE: RefCell<Option<String>> = const { RefCell::new(None) };
-    static THREAD_NAME_CACHE: RefCell<Option<Option<String>>> = const { RefCell::new(None) };
+    static THREAD_ID_CACHE: RefCell<Option<Arc<str>>> = const { RefCell::new(None) };
+    static THREAD_NAME_CACHE: RefCell<Option<Option<Arc<str>>>> = const { RefCell::new(None) };
+    static INTERNED: RefCell<HashSet<Arc<str>>> = RefCell::new(HashSet::new());
+}
+
+/// Shared copy of a string that recurs across entries, such as a file or
+/// module path
+///
+/// Repeated values are allocated once per thread; every entry then holds a
+/// reference to the same string.
+pub fn intern(value: &str) -> Arc<str> {
+    INTERNED.with(|interned| {
+        let mut interned = interned.borrow_mut();
+        if let Some(shared) = interned.get(value) {
+            return Arc::clone(shared);
+        }
+        let shared: Arc<str> = Arc::from(value);
+        if interned.len() < INTERN_CAPACITY {
+            interned.insert(Arc::clone(&shared));
+        }
+        shared
+    })
 }
 
 /// Get cached thread ID, computing and caching it on first access
-fn get_thread_id() -> String {
+fn get_thread_id() -> Arc<str> {
     THREAD_ID_CACHE.with(|cache| {
         let mut cache = cache.borrow_mut();
         if cache.is_none() {
-            *cache = Some(format!("{:?}", std::thread::current().id()));
+            *cache = Some(Arc::from(format!("{:?}", std::thread::current().id())));
         }
         cache.as_ref().expect("thread_id cache initialized in previous line").clone()
     })
 }
 
 /// Get cached thread name, computing and caching it on first access
-fn get_thread_name() -> Option<String> {
+fn get_thread_name() -> Option<Arc<str>> {
     THREAD_NAME_CACHE.with(|cache| {
         let mut cache = cache.borrow_mut();
         if cache.is_none() {
-            *cache = Some(std::thread::current().name().map(String::from));
+            *cache = Some(std::thread::current().name().map(Arc::from));
         }
         cache.as_ref().expect("thread_name cache initialized in previous line").clone()
     })
@@ -39,13 +68,25 @@ pub struct LogEntry {
     pub level: LogLevel,
     pub message: String,
     pub timestamp: DateTime<Utc>,
-    pub file: Option<String>,
+    pub file: Option<Arc<str>>,
     pub line: Option<u32>,
-    pub module_path: Option<String>,
-    pub thread_id: String,
-    pub thread_name: Option<String>,
+    pub module_path: Option<Arc<str>>,
+    /// Component the entry is about, for per-target levels; defaults to the
+    /// module path, as with the `log` and `tracing` crates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<Arc<str>>,
+    /// Shared by all entries of a thread, so cloning an entry does not copy it
+    pub thread_id: Arc<str>,
+    pub thread_name: Option<Arc<str>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<LogContext>,
+    /// Distributed tracing identifiers (trace ID, span ID)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracing: Option<TracingContext>,
+    /// User-defined level the entry was logged at; `level` then holds the
+    /// nearest built-in level below it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_level: Option<CustomLevel>,
 }
 
 impl LogEntry {
@@ -53,31 +94,66 @@ impl LogEntry {
     ///
     /// Replaces newlines, carriage returns, and tabs with escape sequences
     /// to prevent attackers from injecting fake log entries.
-    fn sanitize_message(message: &str) -> String {
-        message
-            .replace('\n', "\\n")
-            .replace('\r', "\\r")
-            .replace('\t', "\\t")
+    fn sanitize_message(message: String) -> String {
+        if !message.contains(['\n', '\r', '\t']) {
+            return message;
+        }
+        let mut sanitized = entry_pool::take(message.len() + 8);
+        for c in message.chars() {
+            match c {
+                '\n' => sanitized.push_str("\\n"),
+                '\r' => sanitized.push_str("\\r"),
+                '\t' => sanitized.push_str("\\t"),
+                c => sanitized.push(c),
+            }
+        }
+        entry_pool::recycle(message);
+        sanitized
     }
 
     pub fn new(level: LogLevel, message: String) -> Self {
         Self {
             level,
-            message: Self::sanitize_message(&message),
+            message: Self::sanitize_message(message),
             timestamp: Utc::now(),
             file: None,
             line: None,
             module_path: None,
+            target: None,
             thread_id: get_thread_id(),
             thread_name: get_thread_name(),
             context: None,
+            tracing: None,
+            custom_level: None,
         }
     }
 
+    /// Create an entry at a user-defined level
+    pub fn custom(level: &CustomLevel, message: String) -> Self {
+        let mut entry = Self::new(level.level(), message);
+        entry.custom_level = Some(level.clone());
+        entry
+    }
+
+    /// Replace the message, escaping it as [`new`](Self::new) does
+    pub(crate) fn set_message(&mut self, message: String) {
+        self.message = Self::sanitize_message(message);
+    }
+
+    /// Record the source location; the module path also becomes the target
+    /// unless one is set
     pub fn with_location(mut self, file: &str, line: u32, module_path: &str) -> Self {
-        self.file = Some(file.to_string());
+        self.file = Some(intern(file));
         self.line = Some(line);
-        self.module_path = Some(module_path.to_string());
+        let module_path = intern(module_path);
+        self.target.get_or_insert_with(|| Arc::clone(&module_path));
+        self.module_path = Some(module_path);
+        self
+    }
+
+    /// Set the target, overriding the module path as the entry's component
+    pub fn with_target(mut self, target: &str) -> Self {
+        self.target = Some(intern(target));
         self
     }
 
@@ -85,4 +161,44 @@ impl LogEntry {
         self.context = Some(context);
         self
     }
+
+    /// Add a context field, creating the context if there is none
+    pub fn add_field<K, V>(&mut self, key: K, value: V)
+    where
+        K: Into<String>,
+        V: Into<FieldValue>,
+    {
+        self.context.get_or_insert_with(LogContext::new).add_field(key, value);
+    }
+
+    /// Attach distributed tracing identifiers
+    pub fn with_tracing(mut self, tracing: TracingContext) -> Self {
+        self.tracing = Some(tracing);
+        self
+    }
+
+    /// Get the severity, of the custom level if there is one
+    pub fn severity(&self) -> Severity {
+        match self.custom_level {
+            Some(ref custom) => custom.severity(),
+            None => self.level.severity(),
+        }
+    }
+
+    /// Get the level name to write, of the custom level if there is one
+    pub fn level_name(&self) -> &str {
+        match self.custom_level {
+            Some(ref custom) => custom.name(),
+            None => self.level.to_str(),
+        }
+    }
+
+    /// Get the priority for overflow handling, of the custom level if there
+    /// is one
+    pub fn priority(&self) -> LogPriority {
+        match self.custom_level {
+            Some(ref custom) => custom.priority(),
+            None => self.level.priority(),
+        }
+    }
 }
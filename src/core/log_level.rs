@@ -1,6 +1,7 @@
 //! Log level definitions
 
 use super::overflow_policy::LogPriority;
+use super::severity::Severity;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
@@ -48,6 +49,14 @@ impl LogLevel {
         }
     }
 
+    /// Get the numeric severity of the level
+    ///
+    /// Levels are ten apart, leaving room for
+    /// [`CustomLevel`](super::CustomLevel)s between them.
+    pub const fn severity(&self) -> Severity {
+        Severity(*self as u8 * 10)
+    }
+
     /// Get the priority level for overflow handling
     ///
     /// Critical logs (Error, Fatal) should never be dropped.
@@ -72,6 +81,74 @@ impl LogLevel {
     }
 }
 
+/// Most verbose level the logging macros are compiled for
+///
+/// Set with the `max_level_*` features and, for builds without debug
+/// assertions, the `release_max_level_*` features, which take precedence.
+/// `None` (`*_off`) compiles every macro call away. Features are additive,
+/// so when several of a kind are enabled the most verbose one applies.
+/// Without any of them, all levels are compiled in.
+///
+/// Macro calls below this level are removed at compile time and cost
+/// nothing, whatever the logger's runtime level. Direct [`Logger`] method
+/// calls are not affected.
+///
+/// [`Logger`]: crate::Logger
+pub const STATIC_MAX_LEVEL: Option<LogLevel> = static_max_level();
+
+const fn static_max_level() -> Option<LogLevel> {
+    if cfg!(not(debug_assertions)) {
+        if cfg!(feature = "release_max_level_trace") {
+            return Some(LogLevel::Trace);
+        } else if cfg!(feature = "release_max_level_debug") {
+            return Some(LogLevel::Debug);
+        } else if cfg!(feature = "release_max_level_info") {
+            return Some(LogLevel::Info);
+        } else if cfg!(feature = "release_max_level_warn") {
+            return Some(LogLevel::Warn);
+        } else if cfg!(feature = "release_max_level_error") {
+            return Some(LogLevel::Error);
+        } else if cfg!(feature = "release_max_level_off") {
+            return None;
+        }
+    }
+
+    if cfg!(feature = "max_level_trace") {
+        Some(LogLevel::Trace)
+    } else if cfg!(feature = "max_level_debug") {
+        Some(LogLevel::Debug)
+    } else if cfg!(feature = "max_level_info") {
+        Some(LogLevel::Info)
+    } else if cfg!(feature = "max_level_warn") {
+        Some(LogLevel::Warn)
+    } else if cfg!(feature = "max_level_error") {
+        Some(LogLevel::Error)
+    } else if cfg!(feature = "max_level_off") {
+        None
+    } else {
+        Some(LogLevel::Trace)
+    }
+}
+
+/// Check whether `level` is compiled in under [`STATIC_MAX_LEVEL`]
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::core::{static_level_enabled, LogLevel};
+///
+/// // Fatal is only compiled out by the `*_off` features
+/// # #[cfg(not(any(feature = "max_level_off", feature = "release_max_level_off")))]
+/// assert!(static_level_enabled(LogLevel::Fatal));
+/// ```
+#[inline]
+pub const fn static_level_enabled(level: LogLevel) -> bool {
+    match STATIC_MAX_LEVEL {
+        Some(max) => level as u8 >= max as u8,
+        None => false,
+    }
+}
+
 impl fmt::Display for LogLevel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.to_str())
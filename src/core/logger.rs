@@ -2,19 +2,45 @@
 
 use super::{
     appender::Appender,
-    error::Result,
+    circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState},
+    clock::{self, Clock},
+    config::{self, EnvOverrides},
+    diagnostics::{self, DiagnosticEvent},
+    entry_pool,
+    env_config::{EnvConfig, DEFAULT_ENV_VAR},
+    enricher::{Enricher, EnricherChain},
+    error::{LoggerError, Result},
+    filter::Filter,
+    flush_policy::{FlushControl, FlushPolicy},
+    events::{EventBus, LoggerEvent, SubscriptionId},
+    health::{AppenderHealth, HealthStatus, HealthTracker},
+    host_metadata::HostMetadata,
+    level_schedule::LevelSchedule,
+    local_context,
     log_context::{ContextGuard, FieldValue, LogContext, LoggerContext},
     log_entry::LogEntry,
     log_level::LogLevel,
-    metrics::LoggerMetrics,
+    metrics::{AppenderMetrics, LoggerMetrics},
+    metrics_sink::{MetricsPublisher, MetricsSink},
     overflow_policy::{LogPriority, OverflowCallback, OverflowPolicy, PriorityConfig},
+    queue::{self, AsyncQueue, QueueSender},
+    redaction::{RedactionConfig, Redactor},
+    runtime_config::{self, RuntimeConfig},
     sampling::{LogSampler, SamplingConfig},
+    severity::{CustomLevel, LevelRegistry, Severity},
+    span::{self, Span},
+    suppression::{BurstSuppressor, SuppressionConfig, SuppressionDecision},
+    timed::TimedGuard,
+    transformer::Transformer,
+    wal::{WalConfig, WriteAheadLog},
 };
-use crossbeam_channel::{bounded, Sender, TrySendError};
-use parking_lot::RwLock;
-use std::sync::Arc;
-use std::thread;
-use std::time::Duration;
+use crate::appenders::{AppenderGroup, ConsoleAppender, FileAppender};
+use crossbeam_channel::{RecvTimeoutError, TrySendError};
+use super::sync::{self, thread, AtomicBool, AtomicU8, InFlight, Mutex, Ordering, RwLock};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 
 /// Default shutdown timeout for logger cleanup (5 seconds)
 ///
@@ -22,15 +48,250 @@ use std::time::Duration;
 /// For custom timeout control, use the `shutdown()` method instead.
 pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Largest scratch buffer kept by an appender between entries
+const MAX_SCRATCH_CAPACITY: usize = 64 * 1024;
+
+/// An appender together with the name it is registered under
+struct NamedAppender {
+    name: String,
+    appender: Box<dyn Appender>,
+    /// Counters registered in the logger's [`LoggerMetrics`] under `name`
+    metrics: Arc<AppenderMetrics>,
+    health: HealthTracker,
+    /// Subscribers of the logger, for events caused by this appender
+    events: Arc<EventBus>,
+    /// Optional breaker skipping the appender while it keeps failing
+    breaker: Option<CircuitBreaker>,
+    /// Time source of the breaker's probe interval
+    clock: Arc<dyn Clock>,
+    /// Consecutive panics that quarantine the appender, if enabled
+    quarantine_after: Option<u32>,
+    /// Consecutive panics so far
+    panics: u32,
+    /// Whether the appender is skipped for panicking too often
+    quarantined: bool,
+    /// Buffer the appender formats entries into, kept across entries
+    scratch: String,
+}
+
+impl NamedAppender {
+    /// Whether the appender is skipped, because it is quarantined or disabled
+    ///
+    /// Disabling matches either the registered name or the appender's own name
+    fn is_disabled(&self, disabled: &HashSet<String>) -> bool {
+        self.quarantined
+            || disabled.contains(&self.name)
+            || disabled.contains(self.appender.name())
+    }
+
+    /// Count a panic, quarantining the appender at the configured limit
+    fn record_panic(&mut self) {
+        self.panics = self.panics.saturating_add(1);
+        if self.quarantined || self.quarantine_after.is_none_or(|limit| self.panics < limit) {
+            return;
+        }
+        self.quarantined = true;
+        diagnostics::report(DiagnosticEvent::AppenderQuarantined {
+            appender: self.name.clone(),
+            panics: self.panics,
+        });
+        self.events.emit(|| LoggerEvent::AppenderQuarantined {
+            appender: self.name.clone(),
+            panics: self.panics,
+        });
+    }
+
+    /// Flush with panic isolation, unless the circuit breaker is open
+    fn flush_isolated(&mut self) {
+        self.write_out_isolated(|appender| appender.flush());
+    }
+
+    /// End a batch with panic isolation, unless the circuit breaker is open
+    fn end_batch_isolated(&mut self) {
+        self.write_out_isolated(|appender| appender.end_batch());
+    }
+
+    /// Run `write_out` on the appender, reporting failures as failed flushes
+    fn write_out_isolated(&mut self, write_out: impl FnOnce(&mut dyn Appender) -> Result<()>) {
+        if self.is_short_circuited() {
+            return;
+        }
+
+        let flush_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            write_out(self.appender.as_mut())
+        }));
+
+        match flush_result {
+            Ok(Ok(())) => {
+                // Flush succeeded
+            }
+            Ok(Err(e)) => {
+                diagnostics::report(DiagnosticEvent::FlushFailed {
+                    appender: self.name.clone(),
+                    error: e.to_string(),
+                });
+                self.metrics.record_flush_failure();
+                self.health.record_failure(e.to_string());
+            }
+            Err(panic_info) => {
+                let error = format!("panicked: {}", panic_message(&*panic_info));
+                diagnostics::report(DiagnosticEvent::FlushFailed {
+                    appender: self.name.clone(),
+                    error: error.clone(),
+                });
+                self.metrics.record_flush_failure();
+                self.health.record_failure(error);
+                self.record_panic();
+            }
+        }
+    }
+
+    /// Whether the circuit breaker is open, so the appender is skipped
+    fn is_short_circuited(&self) -> bool {
+        self.breaker.as_ref().is_some_and(CircuitBreaker::is_open)
+    }
+
+    /// Write one entry with panic isolation, returning whether it was lost
+    ///
+    /// An appender whose circuit breaker is open is not called and the entry
+    /// counts as lost.
+    fn append_isolated(&mut self, entry: &LogEntry, publisher: Option<&MetricsPublisher>) -> bool {
+        if let Some(breaker) = self.breaker.as_mut() {
+            let before = breaker.state();
+            if !breaker.allow(self.clock.now()) {
+                self.metrics.record_short_circuited();
+                return true;
+            }
+            self.circuit_changed(before);
+        }
+
+        self.scratch.clear();
+        let start = Instant::now();
+        let append_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            self.appender.append_formatted(entry, &mut self.scratch)
+        }));
+        if self.scratch.capacity() > MAX_SCRATCH_CAPACITY {
+            self.scratch = String::new();
+        }
+        let elapsed = start.elapsed();
+        self.metrics.record_latency(elapsed);
+        if let Some(publisher) = publisher {
+            publisher.record_append(&self.name, elapsed);
+        }
+
+        let failed = match append_result {
+            Ok(Ok(())) => {
+                // Success - appender handled the log entry
+                self.panics = 0;
+                self.metrics.record_written();
+                self.health.record_success();
+                while let Some(event) = self.appender.take_event() {
+                    self.events.emit(|| event.into_logger_event(&self.name));
+                }
+                false
+            }
+            Ok(Err(e)) => {
+                // Appender returned an error (not a panic)
+                diagnostics::report(DiagnosticEvent::AppenderFailed {
+                    appender: self.name.clone(),
+                    error: e.to_string(),
+                });
+                self.metrics.record_failed();
+                self.health.record_failure(e.to_string());
+                self.events.emit(|| LoggerEvent::AppenderFailed {
+                    appender: self.name.clone(),
+                    error: e.to_string(),
+                });
+                true
+            }
+            Err(panic_info) => {
+                let message = panic_message(&*panic_info);
+                self.health.record_failure(format!("panicked: {}", message));
+                self.events.emit(|| LoggerEvent::AppenderFailed {
+                    appender: self.name.clone(),
+                    error: format!("panicked: {}", message),
+                });
+                diagnostics::report(DiagnosticEvent::AppenderPanicked {
+                    appender: self.name.clone(),
+                    message,
+                });
+                self.metrics.record_panicked();
+                self.record_panic();
+                true
+            }
+        };
+
+        if let Some(breaker) = self.breaker.as_mut() {
+            let before = breaker.state();
+            if failed {
+                breaker.record_failure(self.clock.now());
+            } else {
+                breaker.record_success();
+            }
+            self.circuit_changed(before);
+        }
+        failed
+    }
+
+    /// Report a change of the breaker state from `before`, if any
+    fn circuit_changed(&self, before: CircuitState) {
+        let Some(breaker) = self.breaker.as_ref() else {
+            return;
+        };
+        let state = breaker.state();
+        if state == before {
+            return;
+        }
+        self.metrics.record_circuit_state(state);
+        diagnostics::report(DiagnosticEvent::CircuitChanged {
+            appender: self.name.clone(),
+            state,
+            failures: breaker.failures(),
+        });
+        self.events.emit(|| LoggerEvent::CircuitChanged {
+            appender: self.name.clone(),
+            from: before,
+            to: state,
+        });
+    }
+}
+
+/// A registered appender, locked on its own
+///
+/// Writing an entry only locks the appenders it is written to, so one slow
+/// appender does not hold up the others or changes to the list.
+#[derive(Clone)]
+struct AppenderSlot {
+    name: String,
+    appender: Arc<Mutex<NamedAppender>>,
+}
+
+/// The registered appenders, in the order they are called
+///
+/// The list is replaced rather than changed in place: writers take a
+/// snapshot and release the list lock before calling any appender, so adding
+/// or removing an appender never waits for a batch to be written.
+type AppenderList = Arc<[AppenderSlot]>;
+
 pub struct Logger {
     min_level: Arc<RwLock<LogLevel>>,
-    appenders: Arc<RwLock<Vec<Box<dyn Appender>>>>,
-    sender: Option<Sender<LogEntry>>,
+    /// Threshold between two built-in levels, replacing `min_level`'s
+    min_severity: RwLock<Option<Severity>>,
+    appenders: Arc<RwLock<AppenderList>>,
+    sender: Option<QueueSender>,
+    /// Entries sent to the async worker that it has not finished writing
+    pending: Arc<InFlight>,
     async_handle: Option<thread::JoinHandle<()>>,
     /// Metrics for observability (dropped count, total logged, etc.)
     metrics: Arc<LoggerMetrics>,
+    /// Optional sink the metrics are published to, shared with the worker
+    metrics_publisher: Arc<OnceLock<MetricsPublisher>>,
     /// Policy for handling queue overflow
-    overflow_policy: OverflowPolicy,
+    overflow_policy: RwLock<OverflowPolicy>,
+    /// When appenders are flushed, shared with the async worker
+    flush: Arc<FlushControl>,
+    /// Serializes [`reconfigure`](Logger::reconfigure) calls
+    reconfigure_lock: Mutex<()>,
     /// Optional callback for overflow notifications
     on_overflow: Option<OverflowCallback>,
     /// Configuration for priority-based log preservation
@@ -38,7 +299,47 @@ pub struct Logger {
     /// Persistent context fields added to all log entries
     context: LoggerContext,
     /// Optional sampler for high-volume log filtering
-    sampler: Option<LogSampler>,
+    ///
+    /// Installed at build time or lazily by the runtime rate setters.
+    sampler: OnceLock<LogSampler>,
+    /// Time source of the sampler, the burst suppressor and the circuit
+    /// breakers
+    clock: Arc<dyn Clock>,
+    /// Filter chain evaluated before an entry is enqueued or written
+    filters: Vec<Box<dyn Filter>>,
+    /// Transformers applied after the filters
+    transformers: Vec<Box<dyn Transformer>>,
+    /// Optional per-call-site burst suppression
+    suppressor: Option<BurstSuppressor>,
+    /// Global kill switch; when false, all log calls are ignored
+    enabled: AtomicBool,
+    /// Names of appenders that are temporarily disabled
+    disabled_appenders: Arc<RwLock<HashSet<String>>>,
+    /// Optional time-based overrides of the minimum level
+    level_schedule: Option<LevelSchedule>,
+    /// Enrichers run before redaction and the appenders
+    enrichers: Arc<OnceLock<EnricherChain>>,
+    /// Optional redaction applied before entries reach the appenders
+    redactor: Arc<OnceLock<Redactor>>,
+    /// Minimum levels of named child loggers (see [`ChildLogger`])
+    ///
+    /// [`ChildLogger`]: super::ChildLogger
+    logger_levels: RwLock<HashMap<String, LogLevel>>,
+    /// User-defined levels registered at build time
+    levels: LevelRegistry,
+    /// Subscribers of lifecycle events
+    events: Arc<EventBus>,
+    /// Set by [`shutdown`](Self::shutdown) so dropping does not report again
+    shut_down: bool,
+    /// Breaker settings given to every appender added afterwards
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Lowest [`LogPriority`] the async worker writes, raised by
+    /// [`drain_prioritized`](Self::drain_prioritized)
+    drain_floor: Arc<AtomicU8>,
+    /// Optional write-ahead log of the async queue, shared with the worker
+    wal: Arc<OnceLock<WriteAheadLog>>,
+    /// Panic limit given to every appender added afterwards
+    quarantine_after: Option<u32>,
 }
 
 impl Logger {
@@ -46,15 +347,37 @@ impl Logger {
     pub fn new() -> Self {
         Self {
             min_level: Arc::new(RwLock::new(LogLevel::Info)),
-            appenders: Arc::new(RwLock::new(Vec::new())),
+            min_severity: RwLock::new(None),
+            appenders: Arc::new(RwLock::new(Arc::from(Vec::new()))),
             sender: None,
+            pending: Arc::new(InFlight::new()),
             async_handle: None,
             metrics: Arc::new(LoggerMetrics::new()),
-            overflow_policy: OverflowPolicy::AlertAndDrop,
+            metrics_publisher: Arc::new(OnceLock::new()),
+            overflow_policy: RwLock::new(OverflowPolicy::AlertAndDrop),
+            flush: Arc::new(FlushControl::new(FlushPolicy::default())),
+            reconfigure_lock: Mutex::new(()),
             on_overflow: None,
             priority_config: PriorityConfig::default(),
             context: LoggerContext::new(),
-            sampler: None,
+            sampler: OnceLock::new(),
+            clock: clock::system_clock(),
+            filters: Vec::new(),
+            transformers: Vec::new(),
+            suppressor: None,
+            enabled: AtomicBool::new(true),
+            disabled_appenders: Arc::new(RwLock::new(HashSet::new())),
+            level_schedule: None,
+            enrichers: Arc::new(OnceLock::new()),
+            redactor: Arc::new(OnceLock::new()),
+            logger_levels: RwLock::new(HashMap::new()),
+            levels: LevelRegistry::new(),
+            events: Arc::new(EventBus::default()),
+            shut_down: false,
+            circuit_breaker: None,
+            drain_floor: Arc::new(AtomicU8::new(LogPriority::Normal as u8)),
+            wal: Arc::new(OnceLock::new()),
+            quarantine_after: None,
         }
     }
 
@@ -76,11 +399,43 @@ impl Logger {
         on_overflow: Option<OverflowCallback>,
         priority_config: PriorityConfig,
     ) -> Self {
-        let (sender, receiver) = bounded(buffer_size);
-        let appenders: Arc<RwLock<Vec<Box<dyn Appender>>>> = Arc::new(RwLock::new(Vec::new()));
+        Self::spawn_async(
+            queue::bounded(AsyncQueue::default(), buffer_size),
+            overflow_policy,
+            on_overflow,
+            priority_config,
+            OnceLock::new(),
+        )
+    }
+
+    /// Create an async logger whose worker starts with `metrics_publisher`
+    fn spawn_async(
+        (sender, receiver): (QueueSender, queue::QueueReceiver),
+        overflow_policy: OverflowPolicy,
+        on_overflow: Option<OverflowCallback>,
+        priority_config: PriorityConfig,
+        metrics_publisher: OnceLock<MetricsPublisher>,
+    ) -> Self {
+        let appenders: Arc<RwLock<AppenderList>> = Arc::new(RwLock::new(Arc::from(Vec::new())));
         let appenders_clone = Arc::clone(&appenders);
         let metrics = Arc::new(LoggerMetrics::new());
         let metrics_clone = Arc::clone(&metrics);
+        let metrics_publisher = Arc::new(metrics_publisher);
+        let publisher_clone = Arc::clone(&metrics_publisher);
+        let disabled_appenders = Arc::new(RwLock::new(HashSet::new()));
+        let disabled_clone = Arc::clone(&disabled_appenders);
+        let enrichers: Arc<OnceLock<EnricherChain>> = Arc::new(OnceLock::new());
+        let enrichers_clone = Arc::clone(&enrichers);
+        let redactor: Arc<OnceLock<Redactor>> = Arc::new(OnceLock::new());
+        let redactor_clone = Arc::clone(&redactor);
+        let pending = Arc::new(InFlight::new());
+        let pending_clone = Arc::clone(&pending);
+        let flush = Arc::new(FlushControl::new(FlushPolicy::default()));
+        let flush_clone = Arc::clone(&flush);
+        let drain_floor = Arc::new(AtomicU8::new(LogPriority::Normal as u8));
+        let floor_clone = Arc::clone(&drain_floor);
+        let wal: Arc<OnceLock<WriteAheadLog>> = Arc::new(OnceLock::new());
+        let wal_clone = Arc::clone(&wal);
 
         let handle = thread::spawn(move || {
             // Batch processing: collect multiple entries before writing
@@ -90,15 +445,50 @@ impl Logger {
 
             let mut batch = Vec::with_capacity(BATCH_SIZE);
 
+            // Write the batch, then release it from the write-ahead log once
+            // flushed
+            let finish = |batch: &mut Vec<LogEntry>| {
+                let received = batch.len();
+                Self::shed(batch, &floor_clone, &metrics_clone, &pending_clone);
+                if !batch.is_empty() {
+                    for entry in batch.iter_mut() {
+                        Self::prepare(&enrichers_clone, &redactor_clone, entry);
+                    }
+                    Self::process_batch(
+                        &appenders_clone,
+                        &disabled_clone,
+                        &flush_clone,
+                        batch,
+                        &metrics_clone,
+                        publisher_clone.get(),
+                    );
+                    pending_clone.sub(batch.len());
+                }
+                if let Some(wal) = wal_clone.get() {
+                    Self::flush_isolated(&Self::snapshot(&appenders_clone), &disabled_clone);
+                    wal.complete(received);
+                }
+                entry_pool::recycle_batch(batch);
+            };
+
             loop {
-                // Try to receive first entry (blocking)
-                match receiver.recv() {
+                // Try to receive first entry (blocking), waking up to publish
+                // metrics if a sink is installed
+                let received = match publisher_clone.get() {
+                    Some(publisher) => match receiver.recv_timeout(publisher.interval()) {
+                        Err(RecvTimeoutError::Timeout) => {
+                            publisher.publish_due(&metrics_clone);
+                            continue;
+                        }
+                        result => result.map_err(drop),
+                    },
+                    None => receiver.recv().map_err(drop),
+                };
+                match received {
                     Ok(entry) => batch.push(entry),
-                    Err(_) => {
+                    Err(()) => {
                         // Channel closed, flush remaining batch and exit
-                        if !batch.is_empty() {
-                            Self::process_batch(&appenders_clone, &batch, &metrics_clone);
-                        }
+                        finish(&mut batch);
                         break;
                     }
                 }
@@ -113,11 +503,10 @@ impl Logger {
 
                 // Process batch when full or after timeout
                 if batch.len() >= BATCH_SIZE {
-                    Self::process_batch(&appenders_clone, &batch, &metrics_clone);
-                    batch.clear();
+                    finish(&mut batch);
                 } else if !batch.is_empty() {
                     // Small batch - wait a bit for more entries
-                    thread::sleep(std::time::Duration::from_millis(BATCH_TIMEOUT_MS));
+                    thread::sleep(Duration::from_millis(BATCH_TIMEOUT_MS));
 
                     // Try one more time to collect entries
                     while batch.len() < BATCH_SIZE {
@@ -128,24 +517,71 @@ impl Logger {
                     }
 
                     // Process whatever we have
-                    Self::process_batch(&appenders_clone, &batch, &metrics_clone);
-                    batch.clear();
+                    finish(&mut batch);
+                }
+
+                if let Some(publisher) = publisher_clone.get() {
+                    publisher.publish_due(&metrics_clone);
                 }
             }
         });
 
         Self {
             min_level: Arc::new(RwLock::new(LogLevel::Info)),
+            min_severity: RwLock::new(None),
             appenders,
             sender: Some(sender),
+            pending,
             async_handle: Some(handle),
             metrics,
-            overflow_policy,
+            metrics_publisher,
+            overflow_policy: RwLock::new(overflow_policy),
+            flush,
+            reconfigure_lock: Mutex::new(()),
             on_overflow,
             priority_config,
             context: LoggerContext::new(),
-            sampler: None,
+            sampler: OnceLock::new(),
+            clock: clock::system_clock(),
+            filters: Vec::new(),
+            transformers: Vec::new(),
+            suppressor: None,
+            enabled: AtomicBool::new(true),
+            disabled_appenders,
+            level_schedule: None,
+            enrichers,
+            redactor,
+            logger_levels: RwLock::new(HashMap::new()),
+            levels: LevelRegistry::new(),
+            events: Arc::new(EventBus::default()),
+            shut_down: false,
+            circuit_breaker: None,
+            drain_floor,
+            wal,
+            quarantine_after: None,
+        }
+    }
+
+    /// Discard entries below the priority floor set while draining for exit
+    fn shed(
+        batch: &mut Vec<LogEntry>,
+        floor: &AtomicU8,
+        metrics: &LoggerMetrics,
+        pending: &InFlight,
+    ) {
+        let floor = floor.load(Ordering::Acquire);
+        if floor == LogPriority::Normal as u8 {
+            return;
         }
+        let before = batch.len();
+        batch.retain(|entry| {
+            let keep = entry.priority() as u8 >= floor;
+            if !keep {
+                metrics.record_dropped_at(entry.level);
+            }
+            keep
+        });
+        pending.sub(before - batch.len());
     }
 
     /// Process a batch of log entries
@@ -156,273 +592,973 @@ impl Logger {
     /// to prevent a single failing appender from disrupting the entire logger.
     /// If one appender panics, other appenders will continue to receive log entries.
     fn process_batch(
-        appenders: &Arc<RwLock<Vec<Box<dyn Appender>>>>,
-        batch: &[LogEntry],
+        appenders: &Arc<RwLock<AppenderList>>,
+        disabled: &RwLock<HashSet<String>>,
+        flush: &FlushControl,
+        batch: &mut [LogEntry],
         metrics: &Arc<LoggerMetrics>,
+        publisher: Option<&MetricsPublisher>,
     ) {
-        let mut appenders_guard = appenders.write();
-
-        // Process each entry in the batch
-        for entry in batch {
-            let mut has_error = false;
+        let appenders = Self::snapshot(appenders);
+        let mut lost = vec![false; batch.len()];
+
+        // Write the batch one appender at a time, so each appender is locked
+        // once per batch and the others stay available meanwhile
+        for slot in appenders.iter() {
+            let mut named = slot.appender.lock();
+            if named.is_disabled(&disabled.read()) {
+                continue;
+            }
+            named.appender.begin_batch();
 
             // Per-appender panic isolation: wrap each appender call separately
-            for (idx, appender) in appenders_guard.iter_mut().enumerate() {
-                let append_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                    appender.append(entry)
-                }));
-
-                match append_result {
-                    Ok(Ok(())) => {
-                        // Success - appender handled the log entry
-                    }
-                    Ok(Err(e)) => {
-                        // Appender returned an error (not a panic)
-                        eprintln!("[LOGGER ERROR] Appender #{} failed: {}", idx, e);
-                        has_error = true;
-                    }
-                    Err(panic_info) => {
-                        // Appender panicked - extract panic message
-                        let panic_msg = if let Some(s) = panic_info.downcast_ref::<&str>() {
-                            s.to_string()
-                        } else if let Some(s) = panic_info.downcast_ref::<String>() {
-                            s.clone()
-                        } else {
-                            "Unknown panic".to_string()
-                        };
-                        eprintln!(
-                            "[LOGGER CRITICAL] Appender #{} panicked: {}. \
-                             Other appenders continue to function.",
-                            idx, panic_msg
-                        );
-                        has_error = true;
-                    }
+            for (entry, lost) in batch.iter().zip(lost.iter_mut()) {
+                *lost |= named.append_isolated(entry, publisher);
+                if flush.after_entry() {
+                    named.flush_isolated();
                 }
             }
 
-            if has_error {
-                metrics.record_dropped();
+            // Write out what the appender held for the batch, then flush
+            // if the policy asks for it
+            named.end_batch_isolated();
+            if flush.after_batch() {
+                named.flush_isolated();
+            }
+        }
+
+        for (entry, lost) in batch.iter().zip(lost) {
+            if lost {
+                metrics.record_dropped_at(entry.level);
             } else {
-                metrics.record_logged();
+                metrics.record_logged_at(entry.level);
             }
         }
+    }
 
-        // Flush after each batch to ensure timely writes
-        // Also use per-appender panic isolation for flush operations
-        for (idx, appender) in appenders_guard.iter_mut().enumerate() {
-            let flush_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                appender.flush()
-            }));
+    /// Take the current list of appenders, releasing the list lock
+    fn snapshot(appenders: &RwLock<AppenderList>) -> AppenderList {
+        Arc::clone(&appenders.read())
+    }
 
-            match flush_result {
-                Ok(Ok(())) => {
-                    // Flush succeeded
-                }
-                Ok(Err(e)) => {
-                    eprintln!("[LOGGER ERROR] Appender #{} flush failed: {}", idx, e);
-                }
-                Err(panic_info) => {
-                    let panic_msg = if let Some(s) = panic_info.downcast_ref::<&str>() {
-                        s.to_string()
-                    } else if let Some(s) = panic_info.downcast_ref::<String>() {
-                        s.clone()
-                    } else {
-                        "Unknown panic".to_string()
-                    };
-                    eprintln!(
-                        "[LOGGER CRITICAL] Appender #{} panicked during flush: {}. \
-                         Other appenders continue to function.",
-                        idx, panic_msg
-                    );
-                }
+    /// Flush every enabled appender with per-appender panic isolation
+    fn flush_isolated(appenders: &[AppenderSlot], disabled: &RwLock<HashSet<String>>) {
+        for slot in appenders.iter() {
+            let mut named = slot.appender.lock();
+            if !named.is_disabled(&disabled.read()) {
+                named.flush_isolated();
             }
         }
     }
 
+    /// Run the enrichers, then redaction, on an entry about to be written
+    ///
+    /// In async mode this runs on the worker thread.
+    fn prepare(
+        enrichers: &OnceLock<EnricherChain>,
+        redactor: &OnceLock<Redactor>,
+        entry: &mut LogEntry,
+    ) {
+        if let Some(enrichers) = enrichers.get() {
+            enrichers.enrich(entry);
+        }
+        if let Some(redactor) = redactor.get() {
+            redactor.redact(entry);
+        }
+    }
+
+    /// Enrich and redact (if configured) and write an entry synchronously
+    fn write_sync(&self, appenders: &[AppenderSlot], mut entry: LogEntry) {
+        Self::prepare(&self.enrichers, &self.redactor, &mut entry);
+        let publisher = self.metrics_publisher.get();
+        Self::process_sync(appenders, &self.disabled_appenders, &entry, &self.metrics, publisher);
+        if self.flush.after_entry() {
+            Self::flush_isolated(appenders, &self.disabled_appenders);
+        }
+        // The async worker publishes on its own schedule
+        if let (Some(publisher), None) = (publisher, &self.sender) {
+            publisher.publish_due(&self.metrics);
+        }
+    }
+
     /// Process log entry synchronously with per-appender panic isolation
     ///
     /// This helper ensures that even in synchronous logging, one failing appender
     /// doesn't prevent other appenders from receiving log entries.
     fn process_sync(
-        appenders: &mut Vec<Box<dyn Appender>>,
+        appenders: &[AppenderSlot],
+        disabled: &RwLock<HashSet<String>>,
         entry: &LogEntry,
         metrics: &Arc<LoggerMetrics>,
+        publisher: Option<&MetricsPublisher>,
     ) -> bool {
         let mut has_error = false;
 
-        for (idx, appender) in appenders.iter_mut().enumerate() {
-            let append_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                appender.append(entry)
-            }));
-
-            match append_result {
-                Ok(Ok(())) => {
-                    // Success
-                }
-                Ok(Err(e)) => {
-                    eprintln!("[LOGGER ERROR] Appender #{} failed (sync): {}", idx, e);
-                    has_error = true;
-                }
-                Err(panic_info) => {
-                    let panic_msg = if let Some(s) = panic_info.downcast_ref::<&str>() {
-                        s.to_string()
-                    } else if let Some(s) = panic_info.downcast_ref::<String>() {
-                        s.clone()
-                    } else {
-                        "Unknown panic".to_string()
-                    };
-                    eprintln!(
-                        "[LOGGER CRITICAL] Appender #{} panicked (sync): {}. \
-                         Other appenders continue to function.",
-                        idx, panic_msg
-                    );
-                    has_error = true;
-                }
+        for slot in appenders.iter() {
+            let mut named = slot.appender.lock();
+            if named.is_disabled(&disabled.read()) {
+                continue;
             }
+
+            has_error |= named.append_isolated(entry, publisher);
         }
 
         if has_error {
-            metrics.record_dropped();
+            metrics.record_dropped_at(entry.level);
         } else {
-            metrics.record_logged();
+            metrics.record_logged_at(entry.level);
         }
 
         has_error
     }
 
+    /// Add an appender, registered under its own [`name`](Appender::name)
+    ///
+    /// If that name is already taken, a numeric suffix is added (`file`,
+    /// `file-2`, `file-3`, ...). See [`list_appenders`](Self::list_appenders).
     pub fn add_appender(&mut self, appender: Box<dyn Appender>) {
-        let mut appenders = self.appenders.write();
-        appenders.push(appender);
+        self.attach_appender(appender);
     }
 
-    pub fn set_min_level(&mut self, level: LogLevel) {
-        let mut min_level = self.min_level.write();
-        *min_level = level;
+    /// Add an appender to a logger that may be shared
+    pub(crate) fn attach_appender(&self, appender: Box<dyn Appender>) {
+        let base = appender.name().to_string();
+        self.attach_named_appender(&base, appender);
     }
 
-    pub fn log(&self, level: LogLevel, message: impl Into<String>) {
-        if level < *self.min_level.read() {
-            return;
-        }
-
-        // Apply sampling if configured
-        if let Some(ref sampler) = self.sampler {
-            // Extract category from context if available (will be set after context merge)
-            // For now, use None as category since we don't have context yet
-            if !sampler.should_sample(level, None) {
-                return;
-            }
-        }
-
-        let mut entry = LogEntry::new(level, message.into());
-
-        // Merge persistent context if present
-        if !self.context.is_empty() {
-            let mut log_context = entry.context.take().unwrap_or_default();
-            self.context.merge_into(&mut log_context);
-            entry.context = Some(log_context);
+    /// Register an appender under `base`, adding a suffix if it is taken
+    fn attach_named_appender(&self, base: &str, appender: Box<dyn Appender>) {
+        let mut appenders = self.appenders.write();
+        let mut name = base.to_string();
+        let mut suffix = 1;
+        while appenders.iter().any(|slot| slot.name == name) {
+            suffix += 1;
+            name = format!("{}-{}", base, suffix);
         }
-
-        self.send_entry(entry);
+        Self::push_slot(&mut appenders, self.named_appender(name, appender));
     }
 
-    /// Internal method to send a log entry with overflow handling
-    fn send_entry(&self, entry: LogEntry) {
-        if let Some(ref sender) = self.sender {
-            let priority = entry.level.priority();
-
-            match sender.try_send(entry) {
-                Ok(()) => {
-                    // Successfully queued
-                }
-                Err(TrySendError::Full(entry)) => {
-                    self.handle_overflow(entry, priority);
-                }
-                Err(TrySendError::Disconnected(_)) => {
-                    // Logger is shutting down, silently ignore
-                }
-            }
-        } else {
-            let mut appenders = self.appenders.write();
-            Self::process_sync(&mut appenders, &entry, &self.metrics);
-        }
+    /// Add an appender to the end of the list, replacing the list
+    fn push_slot(appenders: &mut AppenderList, named: NamedAppender) {
+        let mut slots = appenders.to_vec();
+        slots.push(AppenderSlot {
+            name: named.name.clone(),
+            appender: Arc::new(Mutex::new(named)),
+        });
+        *appenders = slots.into();
     }
 
-    /// Handle queue overflow based on configured policy and log priority
-    fn handle_overflow(&self, entry: LogEntry, priority: LogPriority) {
-        self.metrics.record_queue_full();
-
-        // Handle Critical priority logs (Error, Fatal)
-        if priority == LogPriority::Critical && self.priority_config.preserve_critical {
-            self.force_write_critical(entry);
-            return;
+    fn named_appender(&self, name: String, appender: Box<dyn Appender>) -> NamedAppender {
+        NamedAppender {
+            metrics: self.metrics.register_appender(&name),
+            name,
+            appender,
+            health: HealthTracker::default(),
+            events: Arc::clone(&self.events),
+            breaker: self.circuit_breaker.map(CircuitBreaker::new),
+            clock: Arc::clone(&self.clock),
+            quarantine_after: self.quarantine_after,
+            panics: 0,
+            quarantined: false,
+            scratch: String::new(),
         }
+    }
 
-        // Handle High priority logs (Warn) with retry
-        if priority == LogPriority::High && self.priority_config.preserve_high {
-            if self.retry_high_priority(entry) {
-                return;
-            }
-            // If retry failed, continue to normal overflow handling below
-            // (entry was consumed by retry_high_priority)
-            return;
+    /// Add an appender under a name of your choice at runtime
+    ///
+    /// The name is used by [`remove_appender`](Self::remove_appender),
+    /// [`replace_appender`](Self::replace_appender) and
+    /// [`set_appender_enabled`](Self::set_appender_enabled).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an appender is already registered under `name`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::new();
+    /// logger.add_named_appender("stdout", Box::new(ConsoleAppender::new()))?;
+    /// assert_eq!(logger.list_appenders(), ["stdout"]);
+    /// # Ok::<(), LoggerError>(())
+    /// ```
+    pub fn add_named_appender(
+        &self,
+        name: impl Into<String>,
+        appender: Box<dyn Appender>,
+    ) -> Result<()> {
+        let name = name.into();
+        let mut appenders = self.appenders.write();
+        if appenders.iter().any(|slot| slot.name == name) {
+            return Err(LoggerError::config(
+                "appenders",
+                format!("an appender named '{}' is already registered", name),
+            ));
         }
+        Self::push_slot(&mut appenders, self.named_appender(name, appender));
+        Ok(())
+    }
 
-        match &self.overflow_policy {
-            OverflowPolicy::DropNewest => {
-                // Silently drop but track metrics
-                self.metrics.record_dropped();
-            }
-
-            OverflowPolicy::DropOldest => {
-                // Note: True DropOldest requires access to the receiver side
-                // which we don't have. Fall back to AlertAndDrop with a warning.
-                self.alert_and_drop(entry, true);
-            }
+    /// Remove the appender registered under `name`
+    ///
+    /// Entries logged before the call are written first (see
+    /// [`drain`](Self::drain)), then the appender is flushed and dropped.
+    /// Returns `false` if no appender has that name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder()
+    ///     .appender(ConsoleAppender::new())
+    ///     .build();
+    ///
+    /// assert!(logger.remove_appender("console"));
+    /// assert!(logger.list_appenders().is_empty());
+    /// ```
+    pub fn remove_appender(&self, name: &str) -> bool {
+        self.drain(DEFAULT_SHUTDOWN_TIMEOUT);
+        let removed = {
+            let mut appenders = self.appenders.write();
+            let Some(idx) = appenders.iter().position(|slot| slot.name == name) else {
+                return false;
+            };
+            let mut slots = appenders.to_vec();
+            let removed = slots.remove(idx);
+            *appenders = slots.into();
+            removed
+        };
+        // A writer may still hold a snapshot with the appender in it
+        Self::retire(&mut removed.appender.lock());
+        true
+    }
 
-            OverflowPolicy::Block => {
-                // Block until space is available
-                self.metrics.record_block();
-                if let Some(ref sender) = self.sender {
-                    // send() blocks until successful
-                    let _ = sender.send(entry);
-                }
+    /// Replace the appender registered under `name` with `appender`
+    ///
+    /// The new appender keeps the name and position of the old one. Entries
+    /// logged before the call are written to the old appender, which is then
+    /// flushed and dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no appender has that name; `appender` is not
+    /// added in that case.
+    pub fn replace_appender(&self, name: &str, appender: Box<dyn Appender>) -> Result<()> {
+        self.drain(DEFAULT_SHUTDOWN_TIMEOUT);
+        let Some(slot) = self.find_slot(name) else {
+            return Err(LoggerError::config(
+                "appenders",
+                format!("no appender named '{}' is registered", name),
+            ));
+        };
+        let mut replaced = {
+            let mut named = slot.appender.lock();
+            // The new appender starts with a closed circuit, out of quarantine
+            named.metrics.record_circuit_state(CircuitState::Closed);
+            NamedAppender {
+                name: named.name.clone(),
+                appender: std::mem::replace(&mut named.appender, appender),
+                metrics: Arc::clone(&named.metrics),
+                health: std::mem::take(&mut named.health),
+                events: Arc::clone(&named.events),
+                breaker: std::mem::replace(
+                    &mut named.breaker,
+                    self.circuit_breaker.map(CircuitBreaker::new),
+                ),
+                clock: Arc::clone(&named.clock),
+                quarantine_after: named.quarantine_after,
+                panics: std::mem::take(&mut named.panics),
+                quarantined: std::mem::take(&mut named.quarantined),
+                scratch: String::new(),
             }
+        };
+        Self::retire(&mut replaced);
+        Ok(())
+    }
 
-            OverflowPolicy::BlockWithTimeout(timeout) => {
-                self.metrics.record_block();
-                if let Some(ref sender) = self.sender {
-                    match sender.send_timeout(entry, *timeout) {
-                        Ok(()) => {
-                            // Successfully sent after waiting
-                        }
-                        Err(crossbeam_channel::SendTimeoutError::Timeout(entry)) => {
-                            // Timeout expired, drop the log
-                            self.alert_and_drop(entry, false);
-                        }
-                        Err(crossbeam_channel::SendTimeoutError::Disconnected(_)) => {
-                            // Logger shutting down
-                        }
-                    }
-                }
-            }
+    /// Find the appender registered under `name`
+    fn find_slot(&self, name: &str) -> Option<AppenderSlot> {
+        self.appenders.read().iter().find(|slot| slot.name == name).cloned()
+    }
 
-            OverflowPolicy::AlertAndDrop => {
-                self.alert_and_drop(entry, false);
-            }
+    /// Flush an appender that was taken out of the logger
+    fn retire(named: &mut NamedAppender) {
+        if let Err(e) = named.appender.flush() {
+            diagnostics::report(DiagnosticEvent::FlushFailed {
+                appender: named.name.clone(),
+                error: e.to_string(),
+            });
+            named.metrics.record_flush_failure();
+            named.health.record_failure(e.to_string());
         }
     }
 
-    /// Retry sending a high priority log entry with configurable retry count
+    /// Get the registered names of all appenders, in the order they are called
     ///
-    /// Returns true if the entry was successfully sent or written synchronously.
-    fn retry_high_priority(&self, entry: LogEntry) -> bool {
-        if let Some(ref sender) = self.sender {
-            let retry_count = self.priority_config.high_priority_retry_count;
-            let retry_delay = Duration::from_micros(100);
+    /// Disabled appenders are included.
+    pub fn list_appenders(&self) -> Vec<String> {
+        self.appenders
+            .read()
+            .iter()
+            .map(|slot| slot.name.clone())
+            .collect()
+    }
+
+    /// Get the health of every appender, in the order they are called
+    ///
+    /// Combines [`Appender::health`] with the outcome of the logger's recent
+    /// writes to the appender. See [`AppenderHealth::is_healthy`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder().named_appender("stdout", ConsoleAppender::new()).build();
+    /// logger.info("hello");
+    ///
+    /// let health = &logger.appender_health()[0];
+    /// assert_eq!(health.name, "stdout");
+    /// assert!(health.is_healthy());
+    /// assert!(health.last_success.is_some());
+    /// ```
+    pub fn appender_health(&self) -> Vec<AppenderHealth> {
+        let appenders = Self::snapshot(&self.appenders);
+        appenders
+            .iter()
+            .map(|slot| {
+                let named = slot.appender.lock();
+                let enabled = !named.is_disabled(&self.disabled_appenders.read());
+                let status = if named.quarantined {
+                    HealthStatus::Unhealthy(format!("quarantined after {} panics", named.panics))
+                } else {
+                    named.appender.health()
+                };
+                named.health.report(&named.name, enabled, status)
+            })
+            .collect()
+    }
+
+    /// Call `callback` with every [`LoggerEvent`] of this logger
+    ///
+    /// See [`events`](super::events) for when callbacks run. Returns an id
+    /// for [`unsubscribe`](Self::unsubscribe).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::core::LoggerEvent;
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let mut logger = Logger::builder().appender(ConsoleAppender::new()).build();
+    /// logger.subscribe(|event: &LoggerEvent| println!("logger event: {:?}", event));
+    /// logger.shutdown(DEFAULT_SHUTDOWN_TIMEOUT);
+    /// ```
+    pub fn subscribe<F>(&self, callback: F) -> SubscriptionId
+    where
+        F: Fn(&LoggerEvent) + Send + Sync + 'static,
+    {
+        self.events.subscribe(Arc::new(callback))
+    }
+
+    /// Remove a subscription; returns `false` if it was already removed
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        self.events.unsubscribe(id)
+    }
+
+    /// Add a filter to the end of the filter chain
+    ///
+    /// An entry is only logged if every filter in the chain accepts it.
+    pub fn add_filter(&mut self, filter: Box<dyn Filter>) {
+        self.filters.push(filter);
+    }
+
+    /// Enable or disable all logging at runtime
+    ///
+    /// This is an emergency kill switch: while disabled, every log call
+    /// returns immediately without formatting, filtering, or enqueueing.
+    /// Appenders stay registered and resume receiving entries once
+    /// logging is re-enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::Logger;
+    ///
+    /// let logger = Logger::new();
+    /// logger.set_enabled(false);
+    /// logger.info("Ignored");
+    /// logger.set_enabled(true);
+    /// ```
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Check whether logging is currently enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Check whether an entry at `level` would pass the level check
+    ///
+    /// True if logging is enabled and `level` is at or above the effective
+    /// minimum level. Use it to skip expensive work for disabled levels;
+    /// the logging macros do so before formatting their arguments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::new();
+    /// assert!(logger.enabled(LogLevel::Info));
+    /// assert!(!logger.enabled(LogLevel::Debug));
+    /// ```
+    pub fn enabled(&self, level: LogLevel) -> bool {
+        self.severity_enabled(level.severity())
+    }
+
+    /// Get the time source set with [`LoggerBuilder::clock`]
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Check whether entries of `severity` would be logged
+    ///
+    /// Like [`enabled`](Self::enabled), for the severity of a
+    /// [`CustomLevel`]: it is compared with the
+    /// [`effective_min_severity`](Self::effective_min_severity).
+    pub fn severity_enabled(&self, severity: Severity) -> bool {
+        self.is_enabled() && severity >= self.effective_min_severity()
+    }
+
+    /// Check whether `entry` passes the minimum level, by its severity
+    fn entry_enabled(&self, entry: &LogEntry) -> bool {
+        self.severity_enabled(entry.severity())
+    }
+
+    /// Enable or disable appenders by name at runtime
+    ///
+    /// Disabled appenders are skipped for both writes and flushes but are
+    /// not removed from the logger. `name` matches an appender's registered
+    /// name as well as its own [`name`](Appender::name), so all appenders of
+    /// one kind can be disabled together.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder()
+    ///     .appender(ConsoleAppender::new())
+    ///     .build();
+    ///
+    /// logger.set_appender_enabled("console", false);
+    /// assert!(!logger.is_appender_enabled("console"));
+    /// ```
+    pub fn set_appender_enabled(&self, name: &str, enabled: bool) {
+        let mut disabled = self.disabled_appenders.write();
+        if enabled {
+            disabled.remove(name);
+        } else {
+            disabled.insert(name.to_string());
+        }
+    }
+
+    /// Check whether appenders with the given name are enabled
+    pub fn is_appender_enabled(&self, name: &str) -> bool {
+        !self.disabled_appenders.read().contains(name)
+    }
+
+    /// Registered names of the appenders quarantined for panicking
+    ///
+    /// See [`LoggerBuilder::quarantine_after`].
+    pub fn quarantined_appenders(&self) -> Vec<String> {
+        Self::snapshot(&self.appenders)
+            .iter()
+            .filter(|slot| slot.appender.lock().quarantined)
+            .map(|slot| slot.name.clone())
+            .collect()
+    }
+
+    /// Call a quarantined appender again, for instance after fixing its cause
+    ///
+    /// Returns `false` if no appender is registered as `name` or it is not
+    /// quarantined. It is quarantined again after as many consecutive
+    /// panics as before.
+    pub fn reinstate_appender(&self, name: &str) -> bool {
+        let Some(slot) = self.find_slot(name) else {
+            return false;
+        };
+        let mut named = slot.appender.lock();
+        let quarantined = std::mem::take(&mut named.quarantined);
+        named.panics = 0;
+        quarantined
+    }
+
+    pub fn set_min_level(&mut self, level: LogLevel) {
+        self.store_min_level(level);
+    }
+
+    /// Change the minimum level of a logger that may be shared
+    pub(crate) fn store_min_level(&self, level: LogLevel) {
+        let mut min_level = self.min_level.write();
+        *min_level = level;
+        *self.min_severity.write() = None;
+    }
+
+    /// Set the minimum severity, for a threshold between built-in levels
+    ///
+    /// Entries are kept at or above `severity`, which may be the severity of
+    /// a [`CustomLevel`]; the minimum level becomes the nearest built-in
+    /// level below it. Setting a minimum level replaces the severity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::core::CustomLevel;
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let notice = CustomLevel::new("NOTICE", 25);
+    /// let mut logger = Logger::new();
+    /// logger.set_min_severity(&notice);
+    ///
+    /// assert!(logger.severity_enabled(notice.severity()));
+    /// assert!(!logger.enabled(LogLevel::Info));
+    /// assert_eq!(logger.effective_min_level(), LogLevel::Info);
+    /// ```
+    pub fn set_min_severity(&mut self, severity: impl Into<Severity>) {
+        let severity = severity.into();
+        let mut min_level = self.min_level.write();
+        *min_level = severity.level();
+        *self.min_severity.write() = Some(severity);
+    }
+
+    /// Get the minimum level currently in effect
+    ///
+    /// This is the level from an active [`LevelSchedule`] window if one
+    /// applies, otherwise the configured minimum level.
+    pub fn effective_min_level(&self) -> LogLevel {
+        self.level_schedule
+            .as_ref()
+            .and_then(LevelSchedule::current_level)
+            .unwrap_or_else(|| *self.min_level.read())
+    }
+
+    /// Get the minimum severity currently in effect
+    ///
+    /// This is the severity set with
+    /// [`set_min_severity`](Self::set_min_severity) unless a
+    /// [`LevelSchedule`] window applies, otherwise the severity of the
+    /// [`effective_min_level`](Self::effective_min_level).
+    pub fn effective_min_severity(&self) -> Severity {
+        let scheduled = self.level_schedule.as_ref().and_then(LevelSchedule::current_level);
+        match (scheduled, *self.min_severity.read()) {
+            (None, Some(severity)) => severity,
+            _ => self.effective_min_level().severity(),
+        }
+    }
+
+    /// Set the minimum level of the child logger `name` and its descendants
+    ///
+    /// Names are dot-separated paths such as `db` or `db.pool`; a level set
+    /// for `db` applies to `db.pool` unless that has a level of its own.
+    /// Child loggers without a level use the logger's
+    /// [`effective_min_level`](Self::effective_min_level).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::new();
+    /// logger.set_logger_level("db", LogLevel::Debug);
+    /// assert_eq!(logger.logger_level("db.pool"), LogLevel::Debug);
+    /// assert_eq!(logger.logger_level("http"), LogLevel::Info);
+    /// ```
+    pub fn set_logger_level(&self, name: impl Into<String>, level: LogLevel) {
+        self.logger_levels.write().insert(name.into(), level);
+    }
+
+    /// Remove the level set for the child logger `name`
+    ///
+    /// It inherits from its parent again.
+    pub fn clear_logger_level(&self, name: &str) {
+        self.logger_levels.write().remove(name);
+    }
+
+    /// Get the minimum level in effect for the child logger `name`
+    pub fn logger_level(&self, name: &str) -> LogLevel {
+        let levels = self.logger_levels.read();
+        let mut path = name;
+        loop {
+            if let Some(level) = levels.get(path) {
+                return *level;
+            }
+            match path.rsplit_once('.') {
+                Some((parent, _)) => path = parent,
+                None => return self.effective_min_level(),
+            }
+        }
+    }
+
+    pub fn log(&self, level: LogLevel, message: impl Into<String>) {
+        if !self.enabled(level) {
+            return;
+        }
+
+        self.send_entry(LogEntry::new(level, message.into()));
+    }
+
+    /// Log a message with its source location
+    ///
+    /// The logging macros call this with `file!()`, `line!()` and
+    /// `module_path!()`, so entries record where they were logged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::new();
+    /// logger.log_at(LogLevel::Info, "ready", file!(), line!(), module_path!());
+    /// ```
+    pub fn log_at(
+        &self,
+        level: LogLevel,
+        message: impl Into<String>,
+        file: &str,
+        line: u32,
+        module_path: &str,
+    ) {
+        if !self.enabled(level) {
+            return;
+        }
+
+        let entry = LogEntry::new(level, message.into()).with_location(file, line, module_path);
+        self.send_entry(entry);
+    }
+
+    /// Log preformatted arguments, formatting them only if the entry is kept
+    ///
+    /// Unlike [`log`](Self::log), nothing is formatted or allocated for the
+    /// message when the level is disabled or the entry is sampled out. The
+    /// logging macros call [`log_args_at`](Self::log_args_at).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::new();
+    /// logger.log_args(LogLevel::Info, format_args!("{} items", 3));
+    /// ```
+    pub fn log_args(&self, level: LogLevel, args: fmt::Arguments<'_>) {
+        if self.enabled(level) {
+            self.send_args(LogEntry::new(level, String::new()), args);
+        }
+    }
+
+    /// Log preformatted arguments with their source location
+    ///
+    /// See [`log_args`](Self::log_args).
+    pub fn log_args_at(
+        &self,
+        level: LogLevel,
+        args: fmt::Arguments<'_>,
+        file: &str,
+        line: u32,
+        module_path: &str,
+    ) {
+        if self.enabled(level) {
+            let entry = LogEntry::new(level, String::new()).with_location(file, line, module_path);
+            self.send_args(entry, args);
+        }
+    }
+
+    /// Send an entry whose message is formatted from `args` once sampling
+    /// has kept it
+    pub(crate) fn send_args(&self, entry: LogEntry, args: fmt::Arguments<'_>) {
+        if let Some(mut entry) = self.admit(entry) {
+            entry.set_message(entry_pool::format_message(args));
+            self.dispatch_admitted(entry, false);
+        }
+    }
+
+    /// Internal method to send a log entry with overflow handling
+    ///
+    /// Merges local fields, the persistent context and the ambient span
+    /// context (see [`Span`]), then applies sampling, the filter chain and
+    /// burst suppression, in that order, before enqueueing the entry.
+    pub(crate) fn send_entry(&self, entry: LogEntry) {
+        self.dispatch(entry, false);
+    }
+
+    /// Run the pipeline of [`send_entry`](Self::send_entry)
+    ///
+    /// With `bypass_queue`, the entry is written on the calling thread even
+    /// in async mode.
+    fn dispatch(&self, entry: LogEntry, bypass_queue: bool) {
+        if let Some(entry) = self.admit(entry) {
+            self.dispatch_admitted(entry, bypass_queue);
+        }
+    }
+
+    /// Merge the contexts into `entry` and apply sampling
+    ///
+    /// Returns `None` if the entry is sampled out.
+    fn admit(&self, mut entry: LogEntry) -> Option<LogEntry> {
+        // Merge local fields, then persistent context (earlier sources take priority)
+        if local_context::is_active() {
+            let mut log_context = entry.context.take().unwrap_or_default();
+            local_context::merge_into(&mut log_context);
+            entry.context = Some(log_context);
+        }
+
+        if !self.context.is_empty() {
+            let mut log_context = entry.context.take().unwrap_or_default();
+            self.context.merge_into(&mut log_context);
+            entry.context = Some(log_context);
+        }
+
+        if entry.tracing.is_none() {
+            entry.tracing = span::ambient_context();
+        }
+
+        // Apply sampling if configured (category and key come from the merged context)
+        if let Some(sampler) = self.sampler.get() {
+            if !sampler.should_sample_context(entry.level, entry.context.as_ref()) {
+                self.metrics.record_sampled_out(entry.level);
+                return None;
+            }
+        }
+        Some(entry)
+    }
+
+    /// Apply the filter chain, the transformers and burst suppression to an
+    /// admitted entry, then write or enqueue it
+    fn dispatch_admitted(&self, mut entry: LogEntry, bypass_queue: bool) {
+        if !self.filters.iter().all(|filter| filter.is_enabled(&entry)) {
+            return;
+        }
+
+        for transformer in &self.transformers {
+            match transformer.transform(entry) {
+                Some(transformed) => entry = transformed,
+                None => return,
+            }
+        }
+
+        if let Some(ref suppressor) = self.suppressor {
+            let decision = suppressor.check(&entry);
+            if suppressor.has_summaries() {
+                self.emit_suppression_summaries(false);
+            }
+            match decision {
+                SuppressionDecision::Allow => {}
+                SuppressionDecision::AllowWithSummary(suppressed) => {
+                    self.enqueue(suppressor.summary_entry(&entry, suppressed));
+                }
+                SuppressionDecision::Suppress => return,
+            }
+        }
+
+        if bypass_queue {
+            self.force_write_critical(entry);
+        } else {
+            self.enqueue(entry);
+        }
+    }
+
+    /// Write the summaries of closed burst suppression windows
+    ///
+    /// With `close_all`, windows still open are closed and reported too.
+    fn emit_suppression_summaries(&self, close_all: bool) {
+        let Some(ref suppressor) = self.suppressor else {
+            return;
+        };
+        let summaries = if close_all {
+            suppressor.close_all()
+        } else {
+            suppressor.take_summaries()
+        };
+        for summary in summaries {
+            self.enqueue(summary);
+        }
+    }
+
+    /// Log an entry and flush, without leaving critical entries queued
+    ///
+    /// For paths where the process may end right after logging, such as the
+    /// panic hook. If `preserve_critical` is enabled, critical entries are
+    /// written on the calling thread instead of going through the async
+    /// queue.
+    pub(crate) fn log_entry_and_flush(&self, entry: LogEntry) {
+        if !self.entry_enabled(&entry) {
+            return;
+        }
+
+        let bypass_queue = self.sender.is_some()
+            && self.priority_config.preserve_critical
+            && entry.priority() == LogPriority::Critical;
+        self.dispatch(entry, bypass_queue);
+        let _ = self.flush();
+    }
+
+    /// Enqueue an entry (async mode) or write it directly (sync mode)
+    fn enqueue(&self, entry: LogEntry) {
+        if let Some(ref sender) = self.sender {
+            let priority = entry.priority();
+
+            self.pending.add(1);
+            match self.settle_send(self.send_queued(entry, |e| sender.try_send(e))) {
+                Ok(()) => {
+                    // Successfully queued
+                }
+                Err(TrySendError::Full(entry)) => {
+                    self.handle_overflow(entry, priority);
+                }
+                Err(TrySendError::Disconnected(entry)) => {
+                    // The worker has stopped; nothing will write the entry
+                    self.metrics.record_dropped_at(entry.level);
+                }
+            }
+        } else {
+            self.write_sync(&Self::snapshot(&self.appenders), entry);
+        }
+    }
+
+    /// Write entries recovered from a write-ahead log, ahead of new ones
+    fn write_recovered(&self, entries: Vec<LogEntry>) {
+        if entries.is_empty() {
+            return;
+        }
+        let appenders = Self::snapshot(&self.appenders);
+        for entry in entries {
+            self.write_sync(&appenders, entry);
+        }
+        Self::flush_isolated(&appenders, &self.disabled_appenders);
+    }
+
+    /// Send an entry to the worker, recording it in the write-ahead log if
+    /// one is configured
+    ///
+    /// `send` returns the error if the entry was not queued.
+    fn send_queued<E>(
+        &self,
+        mut entry: LogEntry,
+        send: impl FnOnce(LogEntry) -> Option<E>,
+    ) -> std::result::Result<(), E> {
+        let send = |entry| send(entry).map_or(Ok(()), Err);
+        let Some(wal) = self.wal.get() else {
+            return send(entry);
+        };
+        // Secrets must not reach the file
+        if let Some(redactor) = self.redactor.get() {
+            redactor.redact(&mut entry);
+        }
+        wal.record(entry, send)
+    }
+
+    /// Undo the pending count taken before a send that failed
+    ///
+    /// Entries are counted before they are sent so the worker cannot finish
+    /// one before it has been counted.
+    fn settle_send<E>(&self, result: std::result::Result<(), E>) -> std::result::Result<(), E> {
+        if result.is_err() {
+            self.pending.sub(1);
+        }
+        result
+    }
+
+    /// Handle queue overflow based on configured policy and log priority
+    fn handle_overflow(&self, entry: LogEntry, priority: LogPriority) {
+        self.metrics.record_queue_full();
+
+        // Handle Critical priority logs (Error, Fatal)
+        if priority == LogPriority::Critical && self.priority_config.preserve_critical {
+            self.force_write_critical(entry);
+            return;
+        }
+
+        // Handle High priority logs (Warn) with retry
+        if priority == LogPriority::High && self.priority_config.preserve_high {
+            if self.retry_high_priority(entry) {
+                return;
+            }
+            // If retry failed, continue to normal overflow handling below
+            // (entry was consumed by retry_high_priority)
+            return;
+        }
+
+        let overflow_policy = self.overflow_policy.read().clone();
+        match &overflow_policy {
+            OverflowPolicy::DropNewest => {
+                // Silently drop but track metrics
+                self.metrics.record_dropped_at(entry.level);
+            }
+
+            OverflowPolicy::DropOldest => {
+                // Note: True DropOldest requires access to the receiver side
+                // which we don't have. Fall back to AlertAndDrop with a warning.
+                self.alert_and_drop(entry, true);
+            }
+
+            OverflowPolicy::Block => {
+                // Block until space is available
+                self.metrics.record_block();
+                if let Some(ref sender) = self.sender {
+                    // send() blocks until successful
+                    self.pending.add(1);
+                    let sent = self.send_queued(entry, |e| sender.send(e));
+                    if let Err(unsent) = self.settle_send(sent) {
+                        self.metrics.record_dropped_at(unsent.0.level);
+                    }
+                }
+            }
+
+            OverflowPolicy::BlockWithTimeout(timeout) => {
+                self.metrics.record_block();
+                if let Some(ref sender) = self.sender {
+                    self.pending.add(1);
+                    let sent = self.send_queued(entry, |e| sender.send_timeout(e, *timeout));
+                    match self.settle_send(sent) {
+                        Ok(()) => {
+                            // Successfully sent after waiting
+                        }
+                        Err(crossbeam_channel::SendTimeoutError::Timeout(entry)) => {
+                            // Timeout expired, drop the log
+                            self.alert_and_drop(entry, false);
+                        }
+                        Err(crossbeam_channel::SendTimeoutError::Disconnected(entry)) => {
+                            // Logger shutting down
+                            self.metrics.record_dropped_at(entry.level);
+                        }
+                    }
+                }
+            }
+
+            OverflowPolicy::AlertAndDrop => {
+                self.alert_and_drop(entry, false);
+            }
+        }
+    }
+
+    /// Retry sending a high priority log entry with configurable retry count
+    ///
+    /// Returns true if the entry was successfully sent or written synchronously.
+    fn retry_high_priority(&self, entry: LogEntry) -> bool {
+        if let Some(ref sender) = self.sender {
+            let retry_count = self.priority_config.high_priority_retry_count;
+            let retry_delay = Duration::from_micros(100);
 
             for _ in 0..retry_count {
-                match sender.try_send(entry.clone()) {
+                self.pending.add(1);
+                let sent = self.send_queued(entry.clone(), |e| sender.try_send(e));
+                match self.settle_send(sent) {
                     Ok(()) => {
                         self.metrics.record_critical_preserved(); // Reuse metric for high priority
                         return true;
@@ -432,6 +1568,7 @@ impl Logger {
                         thread::sleep(retry_delay);
                     }
                     Err(TrySendError::Disconnected(_)) => {
+                        self.metrics.record_dropped_at(entry.level);
                         return false; // Logger shutting down
                     }
                 }
@@ -446,44 +1583,32 @@ impl Logger {
     /// Force write a critical log entry synchronously
     fn force_write_critical(&self, entry: LogEntry) {
         self.metrics.record_critical_preserved();
+        let fallbacks = self.metrics.record_sync_fallback();
+        diagnostics::report(DiagnosticEvent::SyncFallback {
+            total: fallbacks + 1,
+        });
 
-        if self.priority_config.block_on_critical {
-            // Block to ensure critical logs are written
-            let mut appenders = self.appenders.write();
-            Self::process_sync(&mut appenders, &entry, &self.metrics);
-        } else {
-            // Try non-blocking lock first
-            if let Some(mut appenders) = self.appenders.try_write() {
-                Self::process_sync(&mut appenders, &entry, &self.metrics);
-            } else {
-                // Fallback: still block for critical logs to ensure they're written
-                let mut appenders = self.appenders.write();
-                Self::process_sync(&mut appenders, &entry, &self.metrics);
-            }
-        }
+        // Each appender is locked on its own, so this only waits for the
+        // worker to finish the appender it is writing to
+        self.write_sync(&Self::snapshot(&self.appenders), entry);
     }
 
     /// Drop a log entry with alert notification
-    fn alert_and_drop(&self, _entry: LogEntry, is_drop_oldest_fallback: bool) {
-        let dropped_count = self.metrics.record_dropped();
+    fn alert_and_drop(&self, entry: LogEntry, is_drop_oldest_fallback: bool) {
+        let dropped_count = self.metrics.record_dropped_at(entry.level);
 
         // Alert on first drop and periodically thereafter
         let should_alert = dropped_count == 0 || (dropped_count + 1).is_multiple_of(1000);
 
         if should_alert {
-            if is_drop_oldest_fallback {
-                eprintln!(
-                    "[LOGGER WARNING] Queue full, {} logs dropped. \
-                     Note: DropOldest policy not fully supported, using AlertAndDrop.",
-                    dropped_count + 1
-                );
-            } else {
-                eprintln!(
-                    "[LOGGER WARNING] Queue full, {} logs dropped. \
-                     Consider increasing buffer size or using a different overflow policy.",
-                    dropped_count + 1
-                );
-            }
+            diagnostics::report(DiagnosticEvent::BufferFull {
+                dropped: dropped_count + 1,
+                drop_oldest_fallback: is_drop_oldest_fallback,
+            });
+
+            self.events.emit(|| LoggerEvent::EntriesDropped {
+                count: dropped_count + 1,
+            });
 
             // Call user-provided callback if available
             if let Some(ref callback) = self.on_overflow {
@@ -514,16 +1639,23 @@ impl Logger {
         self.metrics.queue_full_events()
     }
 
-    /// Get the number of synchronous fallback events (blocking events)
+    /// Get the number of synchronous fallback events
     ///
-    /// This counter tracks how many times the logger had to block
-    /// due to overflow policy configuration.
+    /// This counter tracks how many entries were written on the calling
+    /// thread because the async queue was full (see
+    /// [`PriorityConfig::preserve_critical`]). Waits for queue space under
+    /// a blocking overflow policy are counted by
+    /// [`LoggerMetrics::block_events`].
     pub fn sync_fallback_count(&self) -> u64 {
-        self.metrics.block_events()
+        self.metrics.sync_fallbacks()
     }
 
     /// Get the logger metrics for detailed observability
     ///
+    /// The same instance is updated by the calling threads, the async
+    /// worker and the synchronous fallback path, and includes counters for
+    /// each appender (see [`LoggerMetrics::appender`]).
+    ///
     /// # Example
     ///
     /// ```
@@ -569,15 +1701,209 @@ impl Logger {
     /// }
     /// ```
     pub fn sampler(&self) -> Option<&LogSampler> {
-        self.sampler.as_ref()
+        self.sampler.get()
+    }
+
+    /// Update the base sampling rate at runtime
+    ///
+    /// Takes effect immediately for subsequent log calls, without rebuilding
+    /// the logger. If sampling was not configured, a sampler with default
+    /// settings is installed first. The rate is clamped to `0.0..=1.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::{Logger, SamplingConfig};
+    ///
+    /// let logger = Logger::builder()
+    ///     .with_sampling(SamplingConfig::new(0.1))
+    ///     .build();
+    ///
+    /// // Incident: log everything for a while
+    /// logger.set_sampling_rate(1.0);
+    /// assert_eq!(logger.sampler().unwrap().config().rate, 1.0);
+    /// ```
+    pub fn set_sampling_rate(&self, rate: f64) {
+        self.sampler_or_default().set_rate(rate);
+    }
+
+    /// Update the sampling rate for a category at runtime
+    ///
+    /// Applies to entries whose context `"category"` field matches. If
+    /// sampling was not configured, a sampler with default settings is
+    /// installed first. The rate is clamped to `0.0..=1.0`.
+    pub fn set_category_rate(&self, category: impl Into<String>, rate: f64) {
+        self.sampler_or_default().set_category_rate(category, rate);
+    }
+
+    fn sampler_or_default(&self) -> &LogSampler {
+        self.sampler.get_or_init(|| {
+            LogSampler::with_clock(SamplingConfig::default(), Arc::clone(&self.clock))
+        })
+    }
+
+    /// Get the settings that [`reconfigure`](Self::reconfigure) can change
+    pub fn runtime_config(&self) -> RuntimeConfig {
+        RuntimeConfig {
+            min_level: *self.min_level.read(),
+            sampling: self.sampler.get().map(LogSampler::config).unwrap_or_default(),
+            overflow_policy: self.overflow_policy.read().clone(),
+            flush_policy: self.flush.policy(),
+            formatter: None,
+        }
+    }
+
+    /// Change several settings of a running logger at once
+    ///
+    /// `f` receives the current [`RuntimeConfig`]; the result is validated
+    /// and applied as a whole. Queued entries are kept, and the async worker
+    /// picks up formatter and flush changes between entries, never in the
+    /// middle of one. Calls are serialized, so concurrent changes are not
+    /// lost. Do not log from `f` on a synchronous logger.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving the logger unchanged, if the result fails
+    /// [`RuntimeConfig::validate`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::new();
+    /// logger.reconfigure(|config| {
+    ///     config.min_level = LogLevel::Debug;
+    ///     config.flush_policy = FlushPolicy::EveryEntry;
+    /// })?;
+    /// assert_eq!(logger.effective_min_level(), LogLevel::Debug);
+    ///
+    /// assert!(logger.reconfigure(|config| config.sampling.rate = 2.0).is_err());
+    /// # Ok::<(), LoggerError>(())
+    /// ```
+    pub fn reconfigure(&self, f: impl FnOnce(&mut RuntimeConfig)) -> Result<()> {
+        let _serialized = self.reconfigure_lock.lock();
+        let current = self.runtime_config();
+        let mut updated = current.clone();
+        f(&mut updated);
+        updated.validate()?;
+
+        // Locking an appender waits for it to finish its current batch
+        if let Some(ref formatter) = updated.formatter {
+            for slot in Self::snapshot(&self.appenders).iter() {
+                slot.appender.lock().appender.set_formatter_config(formatter);
+            }
+        }
+        if updated.min_level != current.min_level {
+            self.store_min_level(updated.min_level);
+        }
+        if updated.sampling != current.sampling {
+            self.sampler_or_default().set_config(updated.sampling);
+        }
+        *self.overflow_policy.write() = updated.overflow_policy;
+        self.flush.set_policy(updated.flush_policy);
+        Ok(())
+    }
+
+    /// Get a reference to the burst suppressor, if configured
+    ///
+    /// Returns `None` if burst suppression is not enabled.
+    pub fn suppressor(&self) -> Option<&BurstSuppressor> {
+        self.suppressor.as_ref()
+    }
+
+    pub fn flush(&self) -> Result<()> {
+        self.emit_suppression_summaries(false);
+        for slot in Self::snapshot(&self.appenders).iter() {
+            let mut named = slot.appender.lock();
+            if named.is_disabled(&self.disabled_appenders.read()) {
+                continue;
+            }
+            if let Err(e) = named.appender.flush() {
+                named.metrics.record_flush_failure();
+                named.health.record_failure(e.to_string());
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Wait for the async queue to drain, then flush all appenders
+    ///
+    /// Unlike [`shutdown`](Self::shutdown), the logger stays usable. Entries
+    /// logged by other threads while draining are waited for as well. In
+    /// synchronous mode this is the same as [`flush`](Self::flush).
+    ///
+    /// Returns `false` if entries were still queued after `timeout` or a
+    /// flush failed.
+    pub fn drain(&self, timeout: Duration) -> bool {
+        self.pending.wait_idle(timeout) && self.flush().is_ok()
+    }
+
+    /// Drain the queue like [`drain`](Self::drain), shedding low-priority
+    /// entries if it runs late
+    ///
+    /// Meant for a process that is about to exit with entries still queued.
+    /// Once half of `timeout` has passed, queued entries of
+    /// [`LogPriority::Normal`] are discarded instead of written, and after
+    /// three quarters only [`LogPriority::Critical`] entries are, so that
+    /// warnings and errors make it out before the deadline. Discarded entries
+    /// count as dropped. Entries logged afterwards are written normally.
+    ///
+    /// Returns `false` if entries were still queued after `timeout` or a
+    /// flush failed.
+    pub fn drain_prioritized(&self, timeout: Duration) -> bool {
+        let start = std::time::Instant::now();
+        while self.pending.get() > 0 && start.elapsed() < timeout {
+            let floor = if start.elapsed() >= timeout.mul_f64(0.75) {
+                LogPriority::Critical
+            } else if start.elapsed() >= timeout / 2 {
+                LogPriority::High
+            } else {
+                LogPriority::Normal
+            };
+            self.drain_floor.store(floor as u8, Ordering::Release);
+            self.pending.wait_idle(Duration::from_millis(1));
+        }
+        self.drain_floor.store(LogPriority::Normal as u8, Ordering::Release);
+
+        self.pending.get() == 0 && self.flush().is_ok()
     }
 
-    pub fn flush(&self) -> Result<()> {
-        let mut appenders = self.appenders.write();
-        for appender in appenders.iter_mut() {
-            appender.flush()?;
+    /// Drain with [`drain_prioritized`](Self::drain_prioritized) on behalf
+    /// of an exit hook, reporting it as a shutdown
+    pub(crate) fn shutdown_for_exit(&self, timeout: Duration) -> bool {
+        self.events.emit(|| LoggerEvent::ShutdownStarted);
+        self.emit_suppression_summaries(true);
+        let clean = self.drain_prioritized(timeout);
+        self.events.emit(|| LoggerEvent::ShutdownFinished { clean });
+        diagnostics::flush_diagnostics();
+        clean
+    }
+
+    /// Log a Fatal entry, drain the queue and exit the process
+    ///
+    /// See [`log_entry_and_exit`](Self::log_entry_and_exit).
+    pub fn fatal_and_exit(&self, message: impl Into<String>, code: i32) -> ! {
+        self.log_entry_and_exit(LogEntry::new(LogLevel::Fatal, message.into()), code)
+    }
+
+    /// Log an entry, drain the queue and exit the process with `code`
+    ///
+    /// Entries already queued are written first, waiting up to
+    /// [`DEFAULT_SHUTDOWN_TIMEOUT`]. The entry itself is then written on the
+    /// calling thread, even in async mode, and all appenders are flushed
+    /// before [`std::process::exit`] is called, so the message is not lost
+    /// when the process ends before the async worker runs.
+    ///
+    /// Used by the [`fatal_abort!`](crate::fatal_abort) macro.
+    pub fn log_entry_and_exit(&self, entry: LogEntry, code: i32) -> ! {
+        self.drain(DEFAULT_SHUTDOWN_TIMEOUT);
+        if self.entry_enabled(&entry) {
+            self.dispatch(entry, self.sender.is_some());
         }
-        Ok(())
+        let _ = self.flush();
+        std::process::exit(code)
     }
 
     #[inline]
@@ -610,6 +1936,32 @@ impl Logger {
         self.log(LogLevel::Fatal, message);
     }
 
+    /// Log a message at a user-defined level
+    ///
+    /// The entry is kept if the severity of `level` is at or above the
+    /// severity of the minimum level, and is written with the name of
+    /// `level`. `level` need not be registered with
+    /// [`LoggerBuilder::custom_level`].
+    pub fn log_custom(&self, level: &CustomLevel, message: impl Into<String>) {
+        if !self.severity_enabled(level.severity()) {
+            return;
+        }
+
+        self.send_entry(LogEntry::custom(level, message.into()));
+    }
+
+    /// Look up a custom level registered with
+    /// [`LoggerBuilder::custom_level`], ignoring case
+    pub fn custom_level(&self, name: &str) -> Option<&CustomLevel> {
+        self.levels.get(name)
+    }
+
+    /// Get the custom levels registered with
+    /// [`LoggerBuilder::custom_level`]
+    pub fn custom_levels(&self) -> &LevelRegistry {
+        &self.levels
+    }
+
     /// Log with structured context fields
     ///
     /// Entry-level context fields take priority over logger-level persistent fields.
@@ -621,34 +1973,36 @@ impl Logger {
         message: impl Into<String>,
         context: LogContext,
     ) {
-        if level < *self.min_level.read() {
+        if !self.enabled(level) {
             return;
         }
 
-        // Apply sampling if configured
-        if let Some(ref sampler) = self.sampler {
-            // Extract category from context for category-specific sampling
-            let category = context.fields().get("category").and_then(|v| {
-                if let super::log_context::FieldValue::String(s) = v {
-                    Some(s.as_str())
-                } else {
-                    None
-                }
-            });
-
-            if !sampler.should_sample(level, category) {
-                return;
-            }
-        }
-
-        let mut merged_context = context;
+        let entry = LogEntry::new(level, message.into()).with_context(context);
+        self.send_entry(entry);
+    }
 
-        // Merge persistent context (entry-level takes priority)
-        if !self.context.is_empty() {
-            self.context.merge_into(&mut merged_context);
+    /// Log a fully constructed entry
+    ///
+    /// The entry goes through the same pipeline as any other log call
+    /// (level check, persistent context, sampling, filters). This is the
+    /// entry point for bridges from other logging frameworks, which need to
+    /// preserve source location and other metadata.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::new();
+    /// let entry = LogEntry::new(LogLevel::Info, "from elsewhere".to_string())
+    ///     .with_location("src/main.rs", 42, "my_app");
+    /// logger.log_entry(entry);
+    /// ```
+    pub fn log_entry(&self, entry: LogEntry) {
+        if !self.entry_enabled(&entry) {
+            return;
         }
 
-        let entry = LogEntry::new(level, message.into()).with_context(merged_context);
         self.send_entry(entry);
     }
 
@@ -674,7 +2028,8 @@ impl Logger {
 
     /// Add a scoped context field with automatic cleanup
     ///
-    /// Returns a guard that removes the field when dropped.
+    /// Returns a guard that restores the previous value (or removes the
+    /// field) when dropped. Chain [`ContextGuard::and`] to scope more fields.
     /// This is useful for adding temporary context for a specific operation.
     ///
     /// # Example
@@ -698,9 +2053,109 @@ impl Logger {
         K: Into<String>,
         V: Into<FieldValue>,
     {
-        let key_str = key.into();
-        self.context.set(key_str.clone(), value);
-        ContextGuard::new(self.context.inner_fields(), key_str)
+        self.context.scoped(key, value)
+    }
+
+    /// Add several scoped context fields under a single guard
+    ///
+    /// When the guard is dropped, every field is restored to its previous
+    /// value, or removed if it was not set before.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::new();
+    ///
+    /// {
+    ///     let _guard = logger.with_scoped_fields(
+    ///         LogContext::new()
+    ///             .with_field("request_id", "abc-123")
+    ///             .with_field("user_id", 42),
+    ///     );
+    ///     logger.info("Processing request");  // Includes both fields
+    /// }
+    ///
+    /// assert!(logger.context().is_empty());
+    /// ```
+    pub fn with_scoped_fields(&self, fields: LogContext) -> ContextGuard {
+        self.context.scoped_fields(fields)
+    }
+
+    /// Add a context field for the lifetime of the returned guard
+    ///
+    /// Equivalent to [`with_scoped_context`](Self::with_scoped_context).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::Logger;
+    ///
+    /// let logger = Logger::new();
+    ///
+    /// {
+    ///     let _guard = logger.with_context("request_id", "abc-123");
+    ///     assert_eq!(logger.context().len(), 1);
+    /// }
+    /// assert!(logger.context().is_empty());
+    /// ```
+    pub fn with_context<K, V>(&self, key: K, value: V) -> ContextGuard
+    where
+        K: Into<String>,
+        V: Into<FieldValue>,
+    {
+        self.with_scoped_context(key, value)
+    }
+
+    /// Start a timed span at Info level
+    ///
+    /// Logs `<name> started` now and `<name> finished` with the elapsed time
+    /// when the returned guard is dropped. Entries logged on this thread
+    /// while the guard is alive carry the span's trace and span IDs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::Logger;
+    ///
+    /// let logger = Logger::new();
+    ///
+    /// {
+    ///     let span = logger.span("db.query");
+    ///     logger.info("running query");  // Tagged with span.id()
+    /// }  // Logs "db.query finished" with an `elapsed` field
+    /// ```
+    pub fn span(&self, name: impl Into<String>) -> Span<'_> {
+        self.span_at(LogLevel::Info, name)
+    }
+
+    /// Start a timed span whose start and finish entries use `level`
+    pub fn span_at(&self, level: LogLevel, name: impl Into<String>) -> Span<'_> {
+        Span::enter(self, level, name.into())
+    }
+
+    /// Time an operation, logging its duration at Info when the guard drops
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::Logger;
+    ///
+    /// let logger = Logger::new();
+    ///
+    /// {
+    ///     let _timed = logger.timed("rebuild index");
+    ///     // ...
+    /// }  // Logs "rebuild index completed" with a `duration_ms` field
+    /// ```
+    pub fn timed(&self, name: impl Into<String>) -> TimedGuard<'_> {
+        self.timed_at(LogLevel::Info, name)
+    }
+
+    /// Time an operation, logging its duration at `level`
+    pub fn timed_at(&self, level: LogLevel, name: impl Into<String>) -> TimedGuard<'_> {
+        TimedGuard::new(self, level, name.into())
     }
 
     /// Helper for structured info logging
@@ -748,39 +2203,46 @@ impl Logger {
     /// // logger.shutdown(DEFAULT_SHUTDOWN_TIMEOUT);
     /// ```
     pub fn shutdown(&mut self, timeout: Duration) -> bool {
+        self.events.emit(|| LoggerEvent::ShutdownStarted);
+        let clean = self.stop(timeout);
+        self.shut_down = true;
+        self.events.emit(|| LoggerEvent::ShutdownFinished { clean });
+        clean
+    }
+
+    /// Drain the queue within `timeout`, stop the worker and flush
+    fn stop(&mut self, timeout: Duration) -> bool {
+        self.emit_suppression_summaries(true);
+
         // Close the channel to signal worker thread
         drop(self.sender.take());
 
-        // Wait for async worker to finish draining all messages
+        // Wait for async worker to finish draining all messages, then join
+        // it to check for panics
         if let Some(handle) = self.async_handle.take() {
-            let start = std::time::Instant::now();
-
-            loop {
-                if handle.is_finished() {
-                    // Thread finished, join it to check for panics
-                    if let Err(e) = handle.join() {
-                        eprintln!("[LOGGER ERROR] Async worker thread panicked during shutdown: {:?}", e);
-                        return false;
-                    }
-                    break;
+            match sync::join_timeout(handle, timeout) {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    diagnostics::report(DiagnosticEvent::WorkerPanicked {
+                        message: panic_message(&*e),
+                    });
+                    return false;
                 }
-
-                if start.elapsed() >= timeout {
-                    eprintln!(
-                        "[LOGGER WARNING] Async worker thread did not finish within timeout. \
-                         Some logs may be lost."
-                    );
+                Err(_) => {
+                    diagnostics::report(DiagnosticEvent::ShutdownTimeout { timeout });
                     return false;
                 }
-
-                // Small sleep to avoid busy-waiting
-                thread::sleep(std::time::Duration::from_millis(10));
             }
         }
+        if let Some(wal) = self.wal.get() {
+            wal.settle();
+        }
 
         // Final flush
         if let Err(e) = self.flush() {
-            eprintln!("[LOGGER ERROR] Failed to flush during shutdown: {}", e);
+            diagnostics::report(DiagnosticEvent::ShutdownFlushFailed {
+                error: e.to_string(),
+            });
             return false;
         }
 
@@ -788,6 +2250,17 @@ impl Logger {
     }
 }
 
+/// Extract the message of a caught panic
+pub(crate) fn panic_message(panic_info: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = panic_info.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = panic_info.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
+
 impl Default for Logger {
     fn default() -> Self {
         Self::new()
@@ -796,54 +2269,63 @@ impl Default for Logger {
 
 impl Drop for Logger {
     fn drop(&mut self) {
+        if !self.shut_down {
+            self.events.emit(|| LoggerEvent::ShutdownStarted);
+        }
+        let mut clean = true;
+        self.emit_suppression_summaries(true);
+
         // Close the channel first to signal worker thread to finish
         // This allows the worker to drain all pending messages before exiting
         drop(self.sender.take());
 
-        // Wait for async worker to finish draining all messages
+        // Wait for async worker to finish draining all messages, with a
+        // timeout to prevent hanging indefinitely
         if let Some(handle) = self.async_handle.take() {
-            // Use a timeout to prevent hanging indefinitely
-            let start = std::time::Instant::now();
             let timeout = DEFAULT_SHUTDOWN_TIMEOUT;
-
-            // Attempt to join the thread with timeout
-            loop {
-                if handle.is_finished() {
-                    // Thread finished, join it to check for panics
-                    if let Err(e) = handle.join() {
-                        eprintln!("[LOGGER ERROR] Async worker thread panicked during shutdown: {:?}", e);
-                    }
-                    break;
+            match sync::join_timeout(handle, timeout) {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    diagnostics::report(DiagnosticEvent::WorkerPanicked {
+                        message: panic_message(&*e),
+                    });
+                    clean = false;
                 }
-
-                if start.elapsed() >= timeout {
-                    eprintln!(
-                        "[LOGGER WARNING] Async worker thread did not finish within {:?} timeout. \
-                         Some logs may be lost.",
-                        timeout
-                    );
-                    break;
+                Err(_) => {
+                    diagnostics::report(DiagnosticEvent::ShutdownTimeout { timeout });
+                    clean = false;
                 }
-
-                // Small sleep to avoid busy-waiting
-                thread::sleep(std::time::Duration::from_millis(10));
             }
         }
+        if let Some(wal) = self.wal.get() {
+            wal.settle();
+        }
 
         // Final flush of any synchronous appenders
         if let Err(e) = self.flush() {
-            eprintln!("[LOGGER ERROR] Failed to flush during shutdown: {}", e);
+            diagnostics::report(DiagnosticEvent::ShutdownFlushFailed {
+                error: e.to_string(),
+            });
+            clean = false;
+        }
+
+        if !self.shut_down {
+            self.events.emit(|| LoggerEvent::ShutdownFinished { clean });
+        }
+
+        if let Some(publisher) = self.metrics_publisher.get() {
+            publisher.publish(&self.metrics);
         }
 
         // Report any dropped logs
         let dropped = self.metrics.dropped_count();
         if dropped > 0 {
-            eprintln!(
-                "[LOGGER WARNING] Logger shutting down with {} dropped logs (drop rate: {:.2}%)",
+            diagnostics::report(DiagnosticEvent::DroppedAtShutdown {
                 dropped,
-                self.metrics.drop_rate()
-            );
+                drop_rate: self.metrics.drop_rate(),
+            });
         }
+        diagnostics::flush_diagnostics();
     }
 }
 
@@ -866,12 +2348,33 @@ impl Drop for Logger {
 /// ```
 pub struct LoggerBuilder {
     min_level: LogLevel,
-    appenders: Vec<Box<dyn Appender>>,
+    min_severity: Option<Severity>,
+    /// Appenders with the name they are registered under, if chosen
+    appenders: Vec<(Option<String>, Box<dyn Appender>)>,
     async_buffer: Option<usize>,
+    async_queue: AsyncQueue,
     overflow_policy: OverflowPolicy,
     on_overflow: Option<OverflowCallback>,
     priority_config: PriorityConfig,
     sampling_config: Option<SamplingConfig>,
+    filters: Vec<Box<dyn Filter>>,
+    transformers: Vec<Box<dyn Transformer>>,
+    enrichers: Vec<Box<dyn Enricher>>,
+    suppression_config: Option<SuppressionConfig>,
+    level_schedule: Option<LevelSchedule>,
+    context: LoggerContext,
+    redaction_config: Option<RedactionConfig>,
+    disabled_appenders: HashSet<String>,
+    logger_levels: HashMap<String, LogLevel>,
+    custom_levels: Vec<CustomLevel>,
+    flush_policy: FlushPolicy,
+    metrics_sink: Option<(Arc<dyn MetricsSink>, Duration)>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    write_ahead_log: Option<WalConfig>,
+    quarantine_after: Option<u32>,
+    clock: Arc<dyn Clock>,
+    /// Log opened by [`try_build`](Self::try_build), with recovered entries
+    opened_wal: Option<(WriteAheadLog, Vec<LogEntry>)>,
 }
 
 impl LoggerBuilder {
@@ -879,12 +2382,31 @@ impl LoggerBuilder {
     pub fn new() -> Self {
         Self {
             min_level: LogLevel::Info,
+            min_severity: None,
             appenders: Vec::new(),
             async_buffer: None,
+            async_queue: AsyncQueue::default(),
             overflow_policy: OverflowPolicy::AlertAndDrop,
             on_overflow: None,
             priority_config: PriorityConfig::default(),
             sampling_config: None,
+            filters: Vec::new(),
+            transformers: Vec::new(),
+            enrichers: Vec::new(),
+            suppression_config: None,
+            level_schedule: None,
+            context: LoggerContext::new(),
+            redaction_config: None,
+            disabled_appenders: HashSet::new(),
+            logger_levels: HashMap::new(),
+            custom_levels: Vec::new(),
+            flush_policy: FlushPolicy::default(),
+            metrics_sink: None,
+            circuit_breaker: None,
+            write_ahead_log: None,
+            quarantine_after: None,
+            clock: clock::system_clock(),
+            opened_wal: None,
         }
     }
 
@@ -892,13 +2414,151 @@ impl LoggerBuilder {
     #[must_use = "builder methods return a new value"]
     pub fn min_level(mut self, level: LogLevel) -> Self {
         self.min_level = level;
+        self.min_severity = None;
+        self
+    }
+
+    /// Set the minimum severity instead of a level
+    ///
+    /// For thresholds between two built-in levels, such as keeping a
+    /// `NOTICE` custom level while dropping `INFO`; see
+    /// [`Logger::set_min_severity`].
+    #[must_use = "builder methods return a new value"]
+    pub fn min_severity(mut self, severity: impl Into<Severity>) -> Self {
+        self.min_severity = Some(severity.into());
         self
     }
 
     /// Add an appender
     #[must_use = "builder methods return a new value"]
     pub fn appender<A: Appender + 'static>(mut self, appender: A) -> Self {
-        self.appenders.push(Box::new(appender));
+        self.appenders.push((None, Box::new(appender)));
+        self
+    }
+
+    /// Add an already boxed appender
+    #[must_use = "builder methods return a new value"]
+    pub fn boxed_appender(mut self, appender: Box<dyn Appender>) -> Self {
+        self.appenders.push((None, appender));
+        self
+    }
+
+    /// Add an appender registered under `name`
+    ///
+    /// See [`Logger::remove_appender`] and [`Logger::replace_appender`].
+    /// Names must be unique; [`try_build`](Self::try_build) rejects
+    /// duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder()
+    ///     .named_appender("stdout", ConsoleAppender::new())
+    ///     .build();
+    /// assert_eq!(logger.list_appenders(), ["stdout"]);
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn named_appender<A: Appender + 'static>(
+        mut self,
+        name: impl Into<String>,
+        appender: A,
+    ) -> Self {
+        self.appenders.push((Some(name.into()), Box::new(appender)));
+        self
+    }
+
+    /// Add a failover group, registered under the group's name
+    ///
+    /// Each entry at or above the group's level is written to the first
+    /// member that accepts it; see [`crate::appenders::group`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::appenders::AppenderGroup;
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder()
+    ///     .appender_group(
+    ///         AppenderGroup::new("errors")
+    ///             .member(FileAppender::new("errors.log").unwrap())
+    ///             .member(ConsoleAppender::new())
+    ///             .min_level(LogLevel::Error),
+    ///     )
+    ///     .build();
+    /// # drop(logger);
+    /// # std::fs::remove_file("errors.log").ok();
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn appender_group(mut self, group: AppenderGroup) -> Self {
+        self.appenders.push((Some(group.name().to_string()), Box::new(group)));
+        self
+    }
+
+    /// Start with appenders of the given name disabled
+    ///
+    /// See [`Logger::set_appender_enabled`].
+    #[must_use = "builder methods return a new value"]
+    pub fn disable_appender(mut self, name: impl Into<String>) -> Self {
+        self.disabled_appenders.insert(name.into());
+        self
+    }
+
+    /// Set when appenders are flushed (default: [`FlushPolicy::Batch`])
+    #[must_use = "builder methods return a new value"]
+    pub fn flush_policy(mut self, policy: FlushPolicy) -> Self {
+        self.flush_policy = policy;
+        self
+    }
+
+    /// Publish the logger's metrics to `sink` every `interval`
+    ///
+    /// The sink also receives the duration of every append. See
+    /// [`MetricsSink`] and [`DEFAULT_METRICS_INTERVAL`].
+    ///
+    /// [`DEFAULT_METRICS_INTERVAL`]: super::DEFAULT_METRICS_INTERVAL
+    #[must_use = "builder methods return a new value"]
+    pub fn metrics_sink<S: MetricsSink + 'static>(mut self, sink: S, interval: Duration) -> Self {
+        self.metrics_sink = Some((Arc::new(sink), interval));
+        self
+    }
+
+    /// Set the minimum level of a child logger
+    ///
+    /// See [`Logger::set_logger_level`].
+    #[must_use = "builder methods return a new value"]
+    pub fn logger_level(mut self, name: impl Into<String>, level: LogLevel) -> Self {
+        self.logger_levels.insert(name.into(), level);
+        self
+    }
+
+    /// Register a user-defined level
+    ///
+    /// Registered levels can be looked up by name with
+    /// [`Logger::custom_level`]. Names must differ from the built-in levels
+    /// and from each other; [`try_build`](Self::try_build) rejects
+    /// conflicts, and [`build`](Self::build) keeps the first level of a
+    /// name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::core::CustomLevel;
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder()
+    ///     .custom_level(CustomLevel::new("NOTICE", 25))
+    ///     .custom_level(CustomLevel::new("AUDIT", 35).with_priority(LogPriority::Critical))
+    ///     .build();
+    ///
+    /// let audit = logger.custom_level("audit").expect("registered").clone();
+    /// logger.log_custom(&audit, "user 42 deleted");
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn custom_level(mut self, level: CustomLevel) -> Self {
+        self.custom_levels.push(level);
         self
     }
 
@@ -911,6 +2571,17 @@ impl LoggerBuilder {
         self
     }
 
+    /// Select the queue between the logging threads and the async worker
+    ///
+    /// [`AsyncQueue::LockFree`] suits extreme rates from many threads; the
+    /// default crossbeam channel uses less CPU while the logger is idle.
+    /// Requires [`async_mode`](Self::async_mode).
+    #[must_use = "builder methods return a new value"]
+    pub fn async_queue(mut self, queue: AsyncQueue) -> Self {
+        self.async_queue = queue;
+        self
+    }
+
     /// Set the overflow policy for async logging
     ///
     /// Determines what happens when the async buffer is full.
@@ -935,25 +2606,91 @@ impl LoggerBuilder {
 
     /// Set a callback for overflow notifications
     ///
-    /// The callback is invoked when logs are dropped due to queue overflow.
-    /// The parameter is the total count of dropped logs.
+    /// The callback is invoked when logs are dropped due to queue overflow.
+    /// The parameter is the total count of dropped logs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    /// use std::sync::Arc;
+    ///
+    /// let logger = Logger::builder()
+    ///     .async_mode(100)
+    ///     .on_overflow(Arc::new(|count| {
+    ///         eprintln!("Warning: {} logs dropped", count);
+    ///     }))
+    ///     .build();
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn on_overflow(mut self, callback: OverflowCallback) -> Self {
+        self.on_overflow = Some(callback);
+        self
+    }
+
+    /// Skip appenders that keep failing, probing them again on an interval
+    ///
+    /// Each appender gets its own breaker; see
+    /// [`crate::core::circuit_breaker`]. Without one, a broken appender is
+    /// called and reported for every entry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::core::CircuitBreakerConfig;
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder()
+    ///     .appender(ConsoleAppender::new())
+    ///     .circuit_breaker(CircuitBreakerConfig::default())
+    ///     .build();
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(config);
+        self
+    }
+
+    /// Stop calling an appender after `panics` consecutive panics
+    ///
+    /// A panicking appender is isolated, but still called for every entry,
+    /// paying for the unwinding and a report each time. A quarantined
+    /// appender is skipped like a disabled one, reported once as
+    /// [`LoggerEvent::AppenderQuarantined`] and
+    /// [`DiagnosticEvent::AppenderQuarantined`], and shows as unhealthy
+    /// until [`Logger::reinstate_appender`] is called. Panics while
+    /// flushing count as well; a successful append resets the count.
     ///
     /// # Example
     ///
     /// ```
     /// use rust_logger_system::prelude::*;
-    /// use std::sync::Arc;
     ///
     /// let logger = Logger::builder()
-    ///     .async_mode(100)
-    ///     .on_overflow(Arc::new(|count| {
-    ///         eprintln!("Warning: {} logs dropped", count);
-    ///     }))
+    ///     .appender(ConsoleAppender::new())
+    ///     .quarantine_after(10)
     ///     .build();
+    /// assert!(logger.quarantined_appenders().is_empty());
     /// ```
     #[must_use = "builder methods return a new value"]
-    pub fn on_overflow(mut self, callback: OverflowCallback) -> Self {
-        self.on_overflow = Some(callback);
+    pub fn quarantine_after(mut self, panics: u32) -> Self {
+        self.quarantine_after = Some(panics.max(1));
+        self
+    }
+
+    /// Record queued entries in a file so a crash does not lose them
+    ///
+    /// Entries a previous process left in the file are written to the
+    /// appenders when the logger is built, before any new entry. Only
+    /// applies in async mode; appenders are then flushed after every batch.
+    /// See [`crate::core::wal`].
+    ///
+    /// [`build`](Self::build) reports a file that cannot be opened as
+    /// [`DiagnosticEvent::WalFailed`] and runs without it;
+    /// [`try_build`](Self::try_build) returns the error.
+    #[must_use = "builder methods return a new value"]
+    pub fn write_ahead_log(mut self, config: WalConfig) -> Self {
+        self.write_ahead_log = Some(config);
         self
     }
 
@@ -1001,6 +2738,8 @@ impl LoggerBuilder {
     ///         adaptive: true,
     ///         adaptive_threshold: 50000,
     ///         adaptive_min_rate: 0.001,
+    ///         key_field: None,
+    ///         mode: SamplingMode::Random,
     ///     })
     ///     .build();
     /// ```
@@ -1034,29 +2773,566 @@ impl LoggerBuilder {
         self
     }
 
+    /// Add a filter to the logger's filter chain
+    ///
+    /// Filters are evaluated in the order they are added, after the level
+    /// check and sampling, and before the entry is enqueued. Any type
+    /// implementing [`Filter`] can be used, including closures of the form
+    /// `Fn(&LogEntry) -> bool`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder()
+    ///     .filter(|entry: &LogEntry| !entry.message.starts_with("heartbeat"))
+    ///     .build();
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn filter<F: Filter + 'static>(mut self, filter: F) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Add a transformer to the logger's transformation chain
+    ///
+    /// Transformers run in the order they are added, after the filters, and
+    /// may rewrite an entry or drop it by returning `None`. Any type
+    /// implementing [`Transformer`] can be used, including closures of the
+    /// form `Fn(LogEntry) -> Option<LogEntry>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder()
+    ///     .transformer(|mut entry: LogEntry| {
+    ///         entry.message = entry.message.trim_end().to_string();
+    ///         Some(entry)
+    ///     })
+    ///     .build();
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn transformer<T: Transformer + 'static>(mut self, transformer: T) -> Self {
+        self.transformers.push(Box::new(transformer));
+        self
+    }
+
+    /// Add an enricher to the logger's enrichment pipeline
+    ///
+    /// Enrichers run in the order they are added, after the filters and
+    /// before redaction and the appenders; in async mode on the worker
+    /// thread. Any type implementing [`Enricher`] can be used, including
+    /// closures of the form `Fn(&mut LogEntry)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder()
+    ///     .enricher(|entry: &mut LogEntry| entry.add_field("pod", "checkout-7d9f"))
+    ///     .build();
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn enricher<E: Enricher + 'static>(mut self, enricher: E) -> Self {
+        self.enrichers.push(Box::new(enricher));
+        self
+    }
+
+    /// Enable burst suppression for repetitive messages
+    ///
+    /// Each call site (or message template) may log at most
+    /// `max_per_window` entries per window. Further entries are dropped and
+    /// summarized by a single `suppressed N similar messages` entry once the
+    /// window has closed, on the next entry, flush or shutdown.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// let logger = Logger::builder()
+    ///     .burst_suppression(
+    ///         SuppressionConfig::new(10, Duration::from_secs(60))
+    ///             .with_key(SuppressionKey::Template),
+    ///     )
+    ///     .build();
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn burst_suppression(mut self, config: SuppressionConfig) -> Self {
+        self.suppression_config = Some(config);
+        self
+    }
+
+    /// Read the time for sampling, burst suppression, circuit breaker probes
+    /// and [`throttle!`](crate::throttle) from `clock`
+    ///
+    /// Tests pass a [`ManualClock`](super::ManualClock) to move through
+    /// suppression windows, adaptive sampling rates and probe intervals
+    /// without sleeping.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::core::ManualClock;
+    /// use rust_logger_system::prelude::*;
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let clock = Arc::new(ManualClock::new());
+    /// let logger = Logger::builder()
+    ///     .burst_suppression(SuppressionConfig::new(1, Duration::from_secs(60)))
+    ///     .clock(clock.clone())
+    ///     .build();
+    ///
+    /// logger.warn("retrying");
+    /// logger.warn("retrying"); // suppressed
+    /// clock.advance(Duration::from_secs(60));
+    /// logger.warn("retrying"); // a new window
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Override the minimum level during scheduled time windows
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// // Verbose logging during the nightly maintenance window
+    /// let logger = Logger::builder()
+    ///     .min_level(LogLevel::Info)
+    ///     .level_schedule(
+    ///         LevelSchedule::new()
+    ///             .between_hours(1, 3, LogLevel::Debug)
+    ///             .local_time(true),
+    ///     )
+    ///     .build();
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn level_schedule(mut self, schedule: LevelSchedule) -> Self {
+        self.level_schedule = Some(schedule);
+        self
+    }
+
+    /// Mask sensitive fields and message substrings before they are written
+    ///
+    /// In async mode redaction runs on the worker thread.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder()
+    ///     .redaction(RedactionConfig::pii().with_key_pattern("ssn"))
+    ///     .build();
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn redaction(mut self, config: RedactionConfig) -> Self {
+        self.redaction_config = Some(config);
+        self
+    }
+
+    /// Add a persistent field included in every log entry
+    ///
+    /// Equivalent to calling `logger.context().set(key, value)` after
+    /// building. Entry-level fields with the same key take priority.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder()
+    ///     .global_field("service", "api-gateway")
+    ///     .global_field("version", "1.2.3")
+    ///     .build();
+    ///
+    /// assert_eq!(logger.context().len(), 2);
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn global_field<K, V>(self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<FieldValue>,
+    {
+        self.context.set(key, value);
+        self
+    }
+
+    /// Add host and process metadata to every log entry
+    ///
+    /// The hostname, process ID, service name, version and environment that
+    /// are set in `metadata` become persistent fields, like
+    /// [`global_field`](Self::global_field). See
+    /// [`host_metadata`](super::host_metadata) for the field names and the
+    /// environment variables that override them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder()
+    ///     .host_metadata(HostMetadata::detect().service("api-gateway").env_overrides())
+    ///     .build();
+    ///
+    /// assert!(logger.context().get_fields().contains_key("pid"));
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn host_metadata(self, metadata: HostMetadata) -> Self {
+        for (key, value) in metadata.fields() {
+            self.context.set(key, value);
+        }
+        self
+    }
+
+    /// Configure levels and console output from an environment variable
+    ///
+    /// Reads a `RUST_LOG`-style directive string (see
+    /// [`env_config`](super::env_config) for the syntax), applies the level
+    /// directives and adds a console appender honoring the `format` and
+    /// `color` directives. Further appenders can be added as usual.
+    ///
+    /// If the variable is unset the configured levels are kept. An invalid
+    /// directive string is reported to the diagnostics handler and ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// // APP_LOG="info,my_app::db=debug,format=logfmt"
+    /// let logger = Logger::builder()
+    ///     .min_level(LogLevel::Warn)  // used when APP_LOG is unset
+    ///     .parse_env("APP_LOG")
+    ///     .build();
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn parse_env(self, var: &str) -> Self {
+        match EnvConfig::from_env(var) {
+            Ok(config) => self.env_config(config.unwrap_or_default()),
+            Err(e) => {
+                diagnostics::report(DiagnosticEvent::ConfigIgnored {
+                    source: var.to_string(),
+                    error: e.to_string(),
+                });
+                self.env_config(EnvConfig::default())
+            }
+        }
+    }
+
+    /// Apply `LOGGER_*` environment variable overrides
+    ///
+    /// Overrides the level, sampling rate, async queue size and overflow
+    /// policy set so far, and adds a file appender for `LOGGER_FILE_PATH`;
+    /// see [`EnvOverrides`] for the variables. Call it last so the
+    /// variables take precedence. Invalid values are reported to the
+    /// [diagnostics handler](super::diagnostics) and ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// // LOGGER_LEVEL=debug overrides the level below
+    /// let logger = Logger::builder()
+    ///     .min_level(LogLevel::Warn)
+    ///     .appender(ConsoleAppender::new())
+    ///     .env_overrides()
+    ///     .build();
+    /// ```
+    #[must_use = "builder methods return a new value"]
+    pub fn env_overrides(self) -> Self {
+        match EnvOverrides::from_env() {
+            Ok(overrides) => self.apply_env_overrides(&overrides),
+            Err(e) => {
+                diagnostics::report(DiagnosticEvent::ConfigIgnored {
+                    source: "environment overrides".to_string(),
+                    error: e.to_string(),
+                });
+                self
+            }
+        }
+    }
+
+    /// Apply parsed environment overrides
+    ///
+    /// Same as [`env_overrides`](Self::env_overrides) with already parsed
+    /// values.
+    #[must_use = "builder methods return a new value"]
+    pub fn apply_env_overrides(mut self, overrides: &EnvOverrides) -> Self {
+        if let Some(level) = overrides.level {
+            self.min_level = level;
+        }
+        if let Some(rate) = overrides.sampling_rate {
+            self.sampling_config.get_or_insert_with(SamplingConfig::default).rate = rate;
+        }
+        if let Some(size) = overrides.async_buffer {
+            self.async_buffer = Some(size);
+        }
+        if let Some(policy) = overrides
+            .overflow_policy
+            .as_deref()
+            .and_then(config::overridden_overflow_policy)
+        {
+            self.overflow_policy = policy;
+        }
+        if let Some(ref path) = overrides.file_path {
+            match FileAppender::new(path) {
+                Ok(appender) => self = self.appender(appender),
+                Err(e) => diagnostics::report(DiagnosticEvent::ConfigIgnored {
+                    source: path.display().to_string(),
+                    error: e.to_string(),
+                }),
+            }
+        }
+        self
+    }
+
+    /// Apply parsed environment settings
+    ///
+    /// Same as [`parse_env`](Self::parse_env) with an already parsed
+    /// configuration.
+    #[must_use = "builder methods return a new value"]
+    pub fn env_config(mut self, config: EnvConfig) -> Self {
+        let base_level = config.default_level.unwrap_or(self.min_level);
+        if let Some(filter) = config.module_filter(base_level) {
+            self.filters.push(Box::new(filter));
+        }
+        self.min_level = config.min_level().map_or(base_level, |level| level.min(base_level));
+
+        let console = ConsoleAppender::new()
+            .with_color_mode(config.colors.unwrap_or_default())
+            .with_output_format(config.format.unwrap_or_default());
+        self.appender(console)
+    }
+
+    /// Check the settings for mistakes that [`build`](Self::build) accepts
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoggerError::InvalidConfiguration`] naming the first
+    /// problem found:
+    ///
+    /// - an async buffer size of zero
+    /// - an overflow policy or priority settings without async mode, where
+    ///   they have no effect
+    /// - a zero timeout for [`OverflowPolicy::BlockWithTimeout`]
+    /// - `block_on_critical` without `preserve_critical`
+    /// - sample rates outside `0.0..=1.0`, or adaptive sampling with a zero
+    ///   threshold
+    /// - two appenders added with the same [`named_appender`] name
+    /// - a zero [`metrics_sink`](Self::metrics_sink) interval
+    /// - a [`write_ahead_log`](Self::write_ahead_log) without async mode
+    /// - a [`custom_level`](Self::custom_level) named like a built-in level
+    ///   or another custom level
+    ///
+    /// [`named_appender`]: Self::named_appender
+    pub fn validate(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        for name in self.appenders.iter().filter_map(|(name, _)| name.as_deref()) {
+            if !names.insert(name) {
+                return Err(LoggerError::config(
+                    "appenders",
+                    format!("more than one appender is named '{}'", name),
+                ));
+            }
+        }
+
+        let default_priority = PriorityConfig::default();
+        match self.async_buffer {
+            Some(0) => {
+                return Err(LoggerError::config(
+                    "async_mode",
+                    "buffer size must be at least 1",
+                ));
+            }
+            Some(_) => {}
+            None if self.overflow_policy != OverflowPolicy::default() => {
+                return Err(LoggerError::config(
+                    "overflow_policy",
+                    "has no effect without async_mode",
+                ));
+            }
+            None if self.async_queue != AsyncQueue::default() => {
+                return Err(LoggerError::config(
+                    "async_queue",
+                    "has no effect without async_mode",
+                ));
+            }
+            None if self.priority_config != default_priority => {
+                return Err(LoggerError::config(
+                    "priority_config",
+                    "has no effect without async_mode",
+                ));
+            }
+            None => {}
+        }
+
+        runtime_config::check_overflow_policy(&self.overflow_policy)?;
+        if self.priority_config.block_on_critical && !self.priority_config.preserve_critical {
+            return Err(LoggerError::config(
+                "priority_config",
+                "block_on_critical requires preserve_critical",
+            ));
+        }
+
+        if let Some(ref sampling) = self.sampling_config {
+            runtime_config::check_sampling(sampling)?;
+        }
+
+        if matches!(self.metrics_sink, Some((_, interval)) if interval.is_zero()) {
+            return Err(LoggerError::config("metrics_sink", "interval must not be zero"));
+        }
+
+        let mut levels = LevelRegistry::new();
+        for level in &self.custom_levels {
+            levels.register(level.clone())?;
+        }
+
+        if self.write_ahead_log.is_some() && self.async_buffer.is_none() {
+            return Err(LoggerError::config(
+                "write_ahead_log",
+                "has no effect without async_mode",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Validate the settings and build the Logger
+    ///
+    /// Like [`build`](Self::build), but rejects the mistakes listed under
+    /// [`validate`](Self::validate). A logger without appenders is allowed,
+    /// since appenders can be added later, but a warning is printed to
+    /// stderr.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoggerError::InvalidConfiguration`] if validation fails,
+    /// or [`LoggerError::IoOperation`] if the write-ahead log cannot be
+    /// opened.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let result = Logger::builder()
+    ///     .appender(ConsoleAppender::new())
+    ///     .async_mode(0)
+    ///     .try_build();
+    /// assert!(result.is_err());
+    /// ```
+    pub fn try_build(mut self) -> Result<Logger> {
+        self.validate()?;
+        if let Some(ref config) = self.write_ahead_log {
+            let opened = WriteAheadLog::open(config.clone()).map_err(|e| {
+                let path = config.path.display().to_string();
+                LoggerError::io_operation("opening write-ahead log", path, e)
+            })?;
+            self.opened_wal = Some(opened);
+        }
+        if self.appenders.is_empty() {
+            diagnostics::report(DiagnosticEvent::NoAppenders);
+        }
+        Ok(self.build())
+    }
+
     /// Build the Logger
+    ///
+    /// Settings are not checked; see [`try_build`](Self::try_build).
     pub fn build(self) -> Logger {
+        let wal = self.opened_wal.or_else(|| {
+            let config = self.write_ahead_log?;
+            let path = config.path.clone();
+            WriteAheadLog::open(config)
+                .map_err(|e| {
+                    diagnostics::report(DiagnosticEvent::WalFailed {
+                        path,
+                        error: e.to_string(),
+                    });
+                })
+                .ok()
+        });
+        let publisher = match self.metrics_sink {
+            Some((sink, interval)) => OnceLock::from(MetricsPublisher::new(sink, interval)),
+            None => OnceLock::new(),
+        };
         let mut logger = if let Some(size) = self.async_buffer {
-            Logger::with_async_config(
-                size,
+            Logger::spawn_async(
+                queue::bounded(self.async_queue, size),
                 self.overflow_policy,
                 self.on_overflow,
                 self.priority_config,
+                publisher,
             )
         } else {
             let mut l = Logger::new();
             l.priority_config = self.priority_config;
+            l.metrics_publisher = Arc::new(publisher);
             l
         };
 
-        logger.set_min_level(self.min_level);
-        for appender in self.appenders {
-            logger.add_appender(appender);
+        logger.set_min_level(self.min_level);
+        if let Some(severity) = self.min_severity {
+            logger.set_min_severity(severity);
+        }
+        *logger.disabled_appenders.write() = self.disabled_appenders;
+        *logger.logger_levels.write() = self.logger_levels;
+        for level in self.custom_levels {
+            let _ = logger.levels.register(level);
+        }
+        logger.flush.set_policy(self.flush_policy);
+        logger.circuit_breaker = self.circuit_breaker;
+        logger.quarantine_after = self.quarantine_after;
+        logger.clock = self.clock;
+        for (name, appender) in self.appenders {
+            match name {
+                Some(name) => logger.attach_named_appender(&name, appender),
+                None => logger.attach_appender(appender),
+            }
+        }
+        logger.filters = self.filters;
+        logger.transformers = self.transformers;
+        logger.level_schedule = self.level_schedule;
+        logger.context = self.context;
+
+        // Configure sampling if enabled
+        if let Some(config) = self.sampling_config {
+            let sampler = LogSampler::with_clock(config, Arc::clone(&logger.clock));
+            logger.sampler = OnceLock::from(sampler);
+        }
+
+        if let Some(config) = self.suppression_config {
+            let suppressor = BurstSuppressor::with_clock(config, Arc::clone(&logger.clock));
+            logger.suppressor = Some(suppressor);
+        }
+
+        if !self.enrichers.is_empty() {
+            let _ = logger.enrichers.set(EnricherChain::new(self.enrichers));
+        }
+
+        if let Some(config) = self.redaction_config {
+            let _ = logger.redactor.set(Redactor::new(config));
         }
 
-        // Configure sampling if enabled
-        if let Some(config) = self.sampling_config {
-            logger.sampler = Some(LogSampler::new(config));
+        if let Some((wal, recovered)) = wal {
+            logger.write_recovered(recovered);
+            wal.clear();
+            if logger.sender.is_some() {
+                let _ = logger.wal.set(wal);
+            }
         }
 
         logger
@@ -1085,12 +3361,34 @@ impl Logger {
     pub fn builder() -> LoggerBuilder {
         LoggerBuilder::new()
     }
+
+    /// Create a console logger configured from the `RUST_LOG` variable
+    ///
+    /// Shorthand for `Logger::builder().parse_env("RUST_LOG").build()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::Logger;
+    ///
+    /// // RUST_LOG="warn,my_app=debug,format=json"
+    /// let logger = Logger::from_env();
+    /// ```
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::builder().parse_env(DEFAULT_ENV_VAR).build()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::appenders::ConsoleAppender;
+    use crate::core::{FormatterConfig, HealthStatus};
+    use crate::testing::{CaptureAppender, FailingAppender, PanickingAppender, SlowAppender};
+    use indexmap::IndexMap;
+    use std::sync::atomic::AtomicUsize;
+    use std::thread;
 
     #[test]
     fn test_builder_basic() {
@@ -1136,6 +3434,52 @@ mod tests {
         assert_eq!(logger.dropped_count(), 0);
     }
 
+    #[test]
+    fn test_try_build_validation() {
+        let component = |builder: LoggerBuilder| match builder.try_build() {
+            Err(LoggerError::InvalidConfiguration { component, .. }) => component,
+            Err(e) => panic!("unexpected error {}", e),
+            Ok(_) => panic!("invalid configuration accepted"),
+        };
+
+        assert_eq!(component(Logger::builder().async_mode(0)), "async_mode");
+        assert_eq!(component(Logger::builder().async_queue(AsyncQueue::LockFree)), "async_queue");
+        assert_eq!(
+            component(Logger::builder().overflow_policy(OverflowPolicy::Block)),
+            "overflow_policy"
+        );
+        assert_eq!(
+            component(
+                Logger::builder()
+                    .async_mode(16)
+                    .overflow_policy(OverflowPolicy::BlockWithTimeout(Duration::ZERO))
+            ),
+            "overflow_policy"
+        );
+        assert_eq!(
+            component(Logger::builder().async_mode(16).priority_config(PriorityConfig {
+                preserve_critical: false,
+                ..PriorityConfig::default()
+            })),
+            "priority_config"
+        );
+        // The SamplingConfig constructors clamp rates, but the fields are public
+        let mut sampling = SamplingConfig::new(0.5);
+        sampling.rate = 1.5;
+        assert_eq!(component(Logger::builder().with_sampling(sampling)), "sampling");
+        let mut sampling = SamplingConfig::new(0.5);
+        sampling.category_rates.insert("db".to_string(), f64::NAN);
+        assert_eq!(component(Logger::builder().with_sampling(sampling)), "sampling");
+
+        let logger = Logger::builder()
+            .appender(ConsoleAppender::new())
+            .async_mode(16)
+            .overflow_policy(OverflowPolicy::DropNewest)
+            .sample_rate(0.5)
+            .try_build();
+        assert!(logger.is_ok());
+    }
+
     #[test]
     fn test_builder_default() {
         let builder = LoggerBuilder::default();
@@ -1145,6 +3489,78 @@ mod tests {
         assert_eq!(logger.dropped_count(), 0);
     }
 
+    #[test]
+    fn test_lock_free_queue_delivers_and_blocks() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let logger = Arc::new(
+            Logger::builder()
+                .appender(CaptureAppender::new(Arc::clone(&entries)))
+                .async_mode(64)
+                .async_queue(AsyncQueue::LockFree)
+                .overflow_policy(OverflowPolicy::Block)
+                .build(),
+        );
+
+        let threads: Vec<_> = (0..4)
+            .map(|t| {
+                let logger = Arc::clone(&logger);
+                thread::spawn(move || {
+                    for i in 0..250 {
+                        logger.info(format!("{}-{}", t, i));
+                    }
+                })
+            })
+            .collect();
+        for handle in threads {
+            handle.join().unwrap();
+        }
+        assert!(logger.drain(Duration::from_secs(5)));
+
+        assert_eq!(entries.lock().len(), 1000);
+        assert_eq!(logger.dropped_count(), 0);
+    }
+
+    #[test]
+    fn test_lock_free_queue_with_single_slot() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let mut logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .async_mode(1)
+            .async_queue(AsyncQueue::LockFree)
+            .overflow_policy(OverflowPolicy::Block)
+            .build();
+
+        for i in 0..5 {
+            logger.info(format!("message {}", i));
+        }
+        assert!(logger.shutdown(Duration::from_secs(5)));
+        assert_eq!(entries.lock().len(), 5);
+    }
+
+    #[test]
+    fn test_appenders_change_while_batch_is_written() {
+        let slow = SlowAppender::new(Duration::from_millis(200));
+        let calls = slow.handle();
+        let logger = Logger::builder().appender(slow).async_mode(16).build();
+
+        logger.info("held");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while calls.calls() == 0 {
+            assert!(Instant::now() < deadline, "worker never reached the appender");
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        // The worker is inside the slow appender with the batch
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let capture = CaptureAppender::new(Arc::clone(&entries));
+        logger.add_named_appender("late", Box::new(capture)).unwrap();
+        assert_eq!(logger.list_appenders(), ["slow", "late"]);
+
+        logger.info("after");
+        assert!(logger.drain(Duration::from_secs(5)));
+        assert_eq!(captured_messages(&entries), ["after"]);
+    }
+
     #[test]
     fn test_overflow_policy_drop_newest() {
         let logger = Logger::builder()
@@ -1158,8 +3574,8 @@ mod tests {
             logger.debug(format!("Message {}", i));
         }
 
-        // Give async thread time to process
-        std::thread::sleep(std::time::Duration::from_millis(100));
+        // Wait for the async thread to process the rest
+        assert!(logger.drain(Duration::from_secs(5)));
 
         // Metrics should be available - either something was logged or dropped
         let metrics = logger.metrics();
@@ -1182,7 +3598,7 @@ mod tests {
         }
 
         // Wait for processing
-        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(logger.drain(Duration::from_secs(5)));
 
         assert_eq!(logger.dropped_count(), 0);
     }
@@ -1198,7 +3614,7 @@ mod tests {
             logger.info(format!("Message {}", i));
         }
 
-        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(logger.drain(Duration::from_secs(5)));
         assert_eq!(logger.dropped_count(), 0);
     }
 
@@ -1222,7 +3638,7 @@ mod tests {
             logger.debug(format!("Message {}", i));
         }
 
-        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(logger.drain(Duration::from_secs(5)));
 
         // Callback may or may not have been called depending on timing
         // Just verify no panic occurred
@@ -1245,7 +3661,7 @@ mod tests {
         logger.error("Critical error");
         logger.fatal("Fatal error");
 
-        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert!(logger.drain(Duration::from_secs(5)));
 
         // Critical logs should have been preserved
         let metrics = logger.metrics();
@@ -1262,6 +3678,810 @@ mod tests {
         assert_eq!(LogLevel::Fatal.priority(), LogPriority::Critical);
     }
 
+    /// Messages of the entries captured in `entries`
+    fn captured_messages(entries: &parking_lot::Mutex<Vec<LogEntry>>) -> Vec<String> {
+        entries.lock().iter().map(|entry| entry.message.clone()).collect()
+    }
+
+    #[test]
+    fn test_filter_chain() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .filter(|entry: &LogEntry| !entry.message.contains("noise"))
+            .filter(|entry: &LogEntry| entry.message.len() > 2)
+            .build();
+
+        logger.info("keep me");
+        logger.info("some noise");
+        logger.info("no");
+        logger.warn("also kept");
+
+        assert_eq!(captured_messages(&entries), vec!["keep me", "also kept"]);
+    }
+
+    #[test]
+    fn test_burst_suppression_summary() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let clock = Arc::new(crate::core::ManualClock::new());
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .burst_suppression(SuppressionConfig::new(2, Duration::from_secs(20)))
+            .clock(clock.clone())
+            .build();
+
+        for i in 0..5 {
+            logger.error(format!("request {} failed", i));
+        }
+        clock.advance(Duration::from_secs(20));
+        logger.error("request 5 failed");
+
+        assert_eq!(
+            captured_messages(&entries),
+            vec![
+                "request 0 failed",
+                "request 1 failed",
+                "suppressed 3 similar messages",
+                "request 5 failed",
+            ]
+        );
+        assert_eq!(logger.suppressor().unwrap().suppressed_total(), 3);
+    }
+
+    #[test]
+    fn test_burst_suppression_summary_after_burst_stops() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let clock = Arc::new(crate::core::ManualClock::new());
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .burst_suppression(SuppressionConfig::new(1, Duration::from_secs(60)))
+            .clock(clock.clone())
+            .build();
+
+        for _ in 0..3 {
+            logger.error("disk full");
+        }
+        clock.advance(Duration::from_secs(60));
+        logger.flush().unwrap();
+        for _ in 0..2 {
+            logger.warn("slow request");
+        }
+        drop(logger);
+
+        assert_eq!(
+            captured_messages(&entries),
+            vec![
+                "disk full",
+                "suppressed 2 similar messages",
+                "slow request",
+                "suppressed 1 similar messages",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_kill_switch_and_appender_toggle() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .build();
+
+        logger.info("one");
+        logger.set_enabled(false);
+        assert!(!logger.is_enabled());
+        logger.info("dropped by kill switch");
+        logger.set_enabled(true);
+
+        logger.set_appender_enabled("capture", false);
+        assert!(!logger.is_appender_enabled("capture"));
+        logger.info("skipped by disabled appender");
+        logger.set_appender_enabled("capture", true);
+        logger.info("two");
+
+        assert_eq!(captured_messages(&entries), vec!["one", "two"]);
+    }
+
+    #[test]
+    fn test_level_schedule_overrides_min_level() {
+        let logger = Logger::builder().min_level(LogLevel::Warn).build();
+        assert_eq!(logger.effective_min_level(), LogLevel::Warn);
+
+        // Two half-day windows cover the whole day
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .min_level(LogLevel::Warn)
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .level_schedule(
+                LevelSchedule::new()
+                    .between_hours(0, 12, LogLevel::Debug)
+                    .between_hours(12, 0, LogLevel::Debug),
+            )
+            .build();
+
+        assert_eq!(logger.effective_min_level(), LogLevel::Debug);
+        logger.debug("scheduled debug");
+        logger.trace("still filtered");
+        assert_eq!(captured_messages(&entries), vec!["scheduled debug"]);
+    }
+
+    #[test]
+    fn test_runtime_sampling_rate() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .build();
+        assert!(logger.sampler().is_none());
+
+        logger.set_sampling_rate(0.0);
+        logger.info("dropped");
+        logger.error("always sampled");
+
+        logger.set_sampling_rate(1.0);
+        logger.set_category_rate("noisy", 0.0);
+        logger.log_with_context(
+            LogLevel::Info,
+            "noisy",
+            LogContext::new().with_field("category", "noisy"),
+        );
+        logger.info("kept");
+
+        assert_eq!(captured_messages(&entries), vec!["always sampled", "kept"]);
+    }
+
+    #[test]
+    fn test_log_args_formats_only_kept_entries() {
+        struct Counted<'a>(&'a AtomicUsize);
+
+        impl fmt::Display for Counted<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                f.write_str("counted\ttab")
+            }
+        }
+
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .build();
+        let formatted = AtomicUsize::new(0);
+
+        logger.log_args(LogLevel::Debug, format_args!("{}", Counted(&formatted)));
+        logger.set_sampling_rate(0.0);
+        crate::info!(logger, "{}", Counted(&formatted));
+        assert_eq!(formatted.load(Ordering::SeqCst), 0);
+
+        crate::error!(logger, "{}", Counted(&formatted));
+        assert_eq!(formatted.load(Ordering::SeqCst), 1);
+        assert_eq!(captured_messages(&entries), vec!["counted\\ttab"]);
+    }
+
+    /// Context fields of the entries captured in `entries`
+    fn captured_fields(
+        entries: &parking_lot::Mutex<Vec<LogEntry>>,
+    ) -> Vec<IndexMap<String, FieldValue>> {
+        let entries = entries.lock();
+        let fields = |entry: &LogEntry| entry.context.as_ref().map(|ctx| ctx.fields().clone());
+        entries.iter().map(|entry| fields(entry).unwrap_or_default()).collect()
+    }
+
+    #[test]
+    fn test_global_fields_and_context_guard() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .global_field("service", "api")
+            .build();
+
+        {
+            let _guard = logger.with_context("request_id", "abc-123");
+            logger.info("inside");
+        }
+        logger.log_with_context(
+            LogLevel::Info,
+            "override",
+            LogContext::new().with_field("service", "worker"),
+        );
+
+        let contexts = captured_fields(&entries);
+        assert_eq!(contexts.len(), 2);
+        assert_eq!(contexts[0].get("service"), Some(&"api".into()));
+        assert_eq!(contexts[0].get("request_id"), Some(&"abc-123".into()));
+        assert_eq!(contexts[1].get("service"), Some(&"worker".into()));
+        assert!(!contexts[1].contains_key("request_id"));
+    }
+
+    #[test]
+    fn test_host_metadata_fields() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .host_metadata(HostMetadata::new().hostname("web-1").service("api"))
+            .build();
+        logger.info("started");
+
+        let contexts = captured_fields(&entries);
+        assert_eq!(contexts[0].get("hostname"), Some(&"web-1".into()));
+        assert_eq!(contexts[0].get("service"), Some(&"api".into()));
+        assert!(!contexts[0].contains_key("pid"));
+    }
+
+    #[test]
+    fn test_enrichers_run_on_worker_before_redaction() {
+        let caller = std::thread::current().id();
+        let logger = crate::testing::CapturingLogger::from_builder(
+            Logger::builder()
+                .async_mode(16)
+                .enricher(move |entry: &mut LogEntry| {
+                    let worker = std::thread::current().id() != caller;
+                    entry.add_field("password", "hunter2");
+                    entry.add_field("on_worker", worker);
+                })
+                .redaction(RedactionConfig::pii()),
+        );
+        logger.info("login");
+        assert!(logger.drain(Duration::from_secs(5)));
+
+        let entries = logger.entries();
+        let context = entries[0].context.as_ref().unwrap();
+        assert_ne!(context.fields().get("password"), Some(&"hunter2".into()));
+        assert_eq!(context.fields().get("on_worker"), Some(&true.into()));
+    }
+
+    #[test]
+    fn test_transformers_rewrite_and_drop() {
+        let logger = crate::testing::CapturingLogger::from_builder(
+            Logger::builder()
+                .filter(|entry: &LogEntry| entry.message != "filtered")
+                .transformer(|entry: LogEntry| (entry.message != "legacy").then_some(entry))
+                .transformer(|mut entry: LogEntry| {
+                    entry.message = entry.message.to_uppercase();
+                    Some(entry)
+                }),
+        );
+        logger.info("filtered");
+        logger.info("legacy");
+        logger.info("kept");
+
+        let messages: Vec<String> = logger.entries().into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, ["KEPT"]);
+    }
+
+    #[test]
+    fn test_env_config_levels() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .env_config(EnvConfig::parse("warn,my_app::db=debug,color=never").unwrap())
+            .build();
+        logger.set_appender_enabled("console", false);
+        assert_eq!(logger.effective_min_level(), LogLevel::Debug);
+
+        let at = |level, message: &str, module: &str| {
+            LogEntry::new(level, message.to_string()).with_location("x.rs", 1, module)
+        };
+        logger.log_entry(at(LogLevel::Debug, "db query", "my_app::db::pool"));
+        logger.log_entry(at(LogLevel::Info, "api hidden", "my_app::api"));
+        logger.log_entry(at(LogLevel::Warn, "api shown", "my_app::api"));
+        logger.debug("no module");
+
+        assert_eq!(captured_messages(&entries), ["db query", "api shown"]);
+    }
+
+    #[test]
+    fn test_builder_env_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("override.log");
+        let overrides = EnvOverrides {
+            level: Some(LogLevel::Debug),
+            file_path: Some(path.clone()),
+            sampling_rate: Some(1.0),
+            ..EnvOverrides::default()
+        };
+
+        let logger = Logger::builder()
+            .min_level(LogLevel::Error)
+            .apply_env_overrides(&overrides)
+            .build();
+        assert_eq!(logger.effective_min_level(), LogLevel::Debug);
+        assert!(logger.sampler().is_some());
+
+        logger.debug("to the override file");
+        logger.flush().unwrap();
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("to the override file"));
+    }
+
+    #[test]
+    fn test_redaction_before_appenders() {
+        for async_mode in [false, true] {
+            let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+            let mut builder = Logger::builder()
+                .appender(CaptureAppender::new(Arc::clone(&entries)))
+                .redaction(RedactionConfig::new().with_key_pattern("password").with_emails());
+            if async_mode {
+                builder = builder.async_mode(16);
+            }
+            let mut logger = builder.build();
+
+            logger.log_with_context(
+                LogLevel::Info,
+                "welcome bob@example.com",
+                LogContext::new().with_field("password", "hunter2"),
+            );
+            assert!(logger.shutdown(DEFAULT_SHUTDOWN_TIMEOUT));
+
+            assert_eq!(captured_messages(&entries), vec!["welcome [REDACTED]"]);
+            assert_eq!(captured_fields(&entries)[0]["password"], "[REDACTED]".into());
+        }
+    }
+
+    #[test]
+    fn test_custom_levels_use_severity() {
+        let notice = CustomLevel::new("NOTICE", 25);
+        let audit = CustomLevel::new("AUDIT", 35).with_priority(LogPriority::Critical);
+        let logs = crate::testing::CapturingLogger::from_builder(
+            Logger::builder()
+                .min_level(LogLevel::Warn)
+                .custom_level(notice.clone())
+                .custom_level(audit.clone()),
+        );
+        assert_eq!(logs.custom_level("audit"), Some(&audit));
+        assert_eq!(logs.custom_levels().iter().count(), 2);
+
+        logs.log_custom(&notice, "below warn");
+        logs.log_custom(&audit, "above warn");
+        let entries = logs.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].level, LogLevel::Warn);
+        assert_eq!(entries[0].level_name(), "AUDIT");
+        assert_eq!(entries[0].severity(), Severity(35));
+        assert_eq!(entries[0].priority(), LogPriority::Critical);
+
+        let config = FormatterConfig::new();
+        let text = crate::core::OutputFormat::Text.format_with_config(&entries[0], &config);
+        assert!(text.contains("[AUDIT]"), "{}", text);
+
+        let conflict = Logger::builder().custom_level(CustomLevel::new("warn", 31)).try_build();
+        assert!(conflict.is_err());
+    }
+
+    #[test]
+    fn test_min_severity_between_levels() {
+        let notice = CustomLevel::new("NOTICE", 25);
+        let logs = crate::testing::CapturingLogger::from_builder(
+            Logger::builder().min_severity(&notice).custom_level(notice.clone()),
+        );
+        assert_eq!(logs.effective_min_level(), LogLevel::Info);
+        assert!(!logs.enabled(LogLevel::Info));
+
+        logs.info("dropped");
+        logs.log_custom(&notice, "kept");
+        logs.warn("also kept");
+        let messages: Vec<_> = logs.entries().into_iter().map(|entry| entry.message).collect();
+        assert_eq!(messages, ["kept", "also kept"]);
+    }
+
+    #[test]
+    fn test_drain_writes_queued_entries() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .async_mode(1000)
+            .overflow_policy(OverflowPolicy::Block)
+            .build();
+
+        for i in 0..200 {
+            logger.info(format!("message {}", i));
+        }
+        assert!(logger.drain(DEFAULT_SHUTDOWN_TIMEOUT));
+        assert_eq!(entries.lock().len(), 200);
+        assert_eq!(logger.pending.get(), 0);
+
+        // The logger keeps working after a drain
+        logger.info("after drain");
+        assert!(logger.drain(DEFAULT_SHUTDOWN_TIMEOUT));
+        assert_eq!(entries.lock().last().unwrap().message, "after drain");
+    }
+
+    #[test]
+    fn test_drain_times_out_while_entries_are_queued() {
+        let slow = SlowAppender::new(Duration::from_millis(20));
+        let handle = slow.handle();
+        let logger = Logger::builder()
+            .appender(slow)
+            .async_mode(100)
+            .overflow_policy(OverflowPolicy::Block)
+            .build();
+
+        for i in 0..20 {
+            logger.info(format!("message {}", i));
+        }
+        assert!(!logger.drain(Duration::from_millis(30)));
+        assert!(logger.pending.get() > 0);
+
+        assert!(logger.drain(DEFAULT_SHUTDOWN_TIMEOUT));
+        assert_eq!(handle.calls(), 20);
+    }
+
+    #[test]
+    fn test_drain_prioritized_sheds_low_priority() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let capture = CaptureAppender::new(Arc::clone(&entries));
+        let logger = Logger::builder()
+            .appender(SlowAppender::wrap(capture, Duration::from_millis(2)))
+            .async_mode(1000)
+            .overflow_policy(OverflowPolicy::Block)
+            .build();
+        for i in 0..500 {
+            logger.info(format!("message {}", i));
+        }
+        logger.error("must be written");
+
+        assert!(logger.drain_prioritized(Duration::from_millis(400)));
+        let written: Vec<_> = entries.lock().iter().map(|entry| entry.level).collect();
+        assert_eq!(written.last(), Some(&LogLevel::Error));
+        assert!(written.len() < 501);
+        assert_eq!(logger.metrics().level(LogLevel::Info).dropped, 501 - written.len() as u64);
+    }
+
+    #[test]
+    fn test_write_ahead_log_recovers_after_crash() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = WalConfig::new(dir.path().join("queue.wal"));
+        // The worker never gets past the first entry
+        let crashed = Logger::builder()
+            .appender(SlowAppender::new(Duration::from_secs(3600)))
+            .async_mode(100)
+            .write_ahead_log(config.clone())
+            .try_build()
+            .unwrap();
+        crashed.info("first");
+        crashed.error("second");
+        // The process dies before the worker wrote either entry
+        std::mem::forget(crashed);
+
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .async_mode(100)
+            .write_ahead_log(config.clone())
+            .try_build()
+            .unwrap();
+        assert_eq!(captured_messages(&entries), ["first", "second"]);
+
+        logger.info("third");
+        drop(logger);
+        assert_eq!(captured_messages(&entries), ["first", "second", "third"]);
+        assert_eq!(std::fs::metadata(&config.path).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_appender_registry() {
+        let first = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let second = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let replacement = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let capture = |entries: &Arc<parking_lot::Mutex<Vec<LogEntry>>>| {
+            CaptureAppender::new(Arc::clone(entries))
+        };
+        let logger = Logger::builder()
+            .appender(capture(&first))
+            .appender(capture(&second))
+            .named_appender("audit", ConsoleAppender::new())
+            .disable_appender("audit")
+            .async_mode(1000)
+            .overflow_policy(OverflowPolicy::Block)
+            .build();
+        assert_eq!(logger.list_appenders(), ["capture", "capture-2", "audit"]);
+
+        for i in 0..100 {
+            logger.info(format!("message {}", i));
+        }
+        assert!(logger.remove_appender("capture"));
+        assert_eq!(first.lock().len(), 100);
+        assert!(!logger.remove_appender("capture"));
+
+        logger
+            .replace_appender("capture-2", Box::new(capture(&replacement)))
+            .unwrap();
+        assert_eq!(second.lock().len(), 100);
+        assert!(logger
+            .replace_appender("missing", Box::new(ConsoleAppender::new()))
+            .is_err());
+        assert!(logger
+            .add_named_appender("audit", Box::new(ConsoleAppender::new()))
+            .is_err());
+
+        logger.info("after replace");
+        assert!(logger.drain(DEFAULT_SHUTDOWN_TIMEOUT));
+        assert_eq!(logger.list_appenders(), ["capture-2", "audit"]);
+        assert_eq!(first.lock().len(), 100);
+        assert_eq!(second.lock().len(), 100);
+        assert_eq!(captured_messages(&replacement), ["after replace"]);
+
+        let duplicate = Logger::builder()
+            .named_appender("out", ConsoleAppender::new())
+            .named_appender("out", ConsoleAppender::new())
+            .try_build();
+        assert!(duplicate.is_err());
+    }
+
+    #[test]
+    fn test_appender_metrics() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .appender(FailingAppender::every(2).with_failing_flush(true))
+            .appender(PanickingAppender::after(2))
+            .async_mode(100)
+            .flush_policy(FlushPolicy::Manual)
+            .build();
+
+        logger.info("one");
+        logger.info("two");
+        logger.fatal("three");
+        assert!(!logger.drain(DEFAULT_SHUTDOWN_TIMEOUT));
+
+        let metrics = logger.metrics();
+        let capture = metrics.appender("capture").unwrap();
+        let failing = metrics.appender("failing").unwrap();
+        let panicking = metrics.appender("panicking").unwrap();
+        assert_eq!(capture.written(), 3);
+        assert_eq!((failing.written(), failing.failed()), (2, 1));
+        assert_eq!((panicking.written(), panicking.panicked()), (2, 1));
+        assert_eq!(failing.flush_failures(), 1);
+        assert_eq!(metrics.appender_errors(), 2);
+        assert_eq!(metrics.dropped_count(), 2);
+        assert_eq!(metrics.level(LogLevel::Info).dropped, 1);
+        assert_eq!(metrics.level(LogLevel::Fatal).dropped, 1);
+
+        // Counters follow the name across a replacement
+        let replacement = FailingAppender::always().with_failing_flush(true);
+        logger.replace_appender("capture", Box::new(replacement)).unwrap();
+        logger.info("four");
+        logger.drain(DEFAULT_SHUTDOWN_TIMEOUT);
+        assert_eq!(capture.written(), 3);
+        assert_eq!(capture.failed(), 1);
+        assert_eq!(metrics.flush_errors(), 3);
+    }
+
+    #[test]
+    fn test_quarantine_after_panics() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let panicking = PanickingAppender::on_trigger();
+        let faults = panicking.handle();
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .appender(panicking)
+            .quarantine_after(2)
+            .build();
+        let events = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        logger.subscribe(move |event: &LoggerEvent| {
+            if let LoggerEvent::AppenderQuarantined { .. } = event {
+                sink.lock().push(event.clone());
+            }
+        });
+
+        // Only consecutive panics count; a successful append resets them
+        faults.trigger();
+        logger.error("one");
+        faults.recover();
+        logger.info("two");
+        faults.trigger();
+        logger.error("three");
+        logger.error("four");
+        let metrics = logger.metrics().appender("panicking").unwrap();
+        assert_eq!(metrics.panicked(), 3);
+        assert_eq!(logger.quarantined_appenders(), ["panicking"]);
+        assert_eq!(entries.lock().len(), 4);
+        assert_eq!(
+            *events.lock(),
+            [LoggerEvent::AppenderQuarantined {
+                appender: "panicking".to_string(),
+                panics: 2,
+            }]
+        );
+        let health = logger.appender_health();
+        assert!(matches!(health[1].status, HealthStatus::Unhealthy(_)));
+
+        // Skipped while quarantined
+        logger.error("five");
+        assert_eq!(faults.calls(), 4);
+
+        assert!(logger.reinstate_appender("panicking"));
+        assert!(!logger.reinstate_appender("panicking"));
+        logger.error("six");
+        assert_eq!(metrics.panicked(), 4);
+        assert!(logger.quarantined_appenders().is_empty());
+    }
+
+    #[test]
+    fn test_appender_health() {
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::default()))
+            .appender(FailingAppender::always())
+            .flush_policy(FlushPolicy::Manual)
+            .build();
+
+        logger.info("one");
+        logger.info("two");
+        let health = logger.appender_health();
+        assert_eq!(health[0].name, "capture");
+        assert!(health[0].is_healthy());
+        assert!(health[0].last_success.is_some());
+        assert_eq!(health[1].consecutive_failures, 2);
+        let last_error = health[1].last_error.as_deref();
+        assert_eq!(last_error, Some("Writer error: injected failure in 'failing'"));
+        assert_eq!(health[1].status, HealthStatus::Healthy);
+        assert!(!health[1].is_healthy());
+
+        logger.set_appender_enabled("failing", false);
+        assert!(logger.appender_health()[1].is_healthy());
+    }
+
+    #[test]
+    fn test_circuit_breaker() {
+        use crate::core::{CircuitBreakerConfig, CircuitState};
+
+        let interval = Duration::from_secs(30);
+        let clock = Arc::new(crate::core::ManualClock::new());
+        let logger = Logger::builder()
+            .appender(FailingAppender::always())
+            .circuit_breaker(CircuitBreakerConfig::new(3, interval))
+            .flush_policy(FlushPolicy::Manual)
+            .clock(clock.clone())
+            .build();
+        let events = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let received = Arc::clone(&events);
+        logger.subscribe(move |event: &LoggerEvent| {
+            if let LoggerEvent::CircuitChanged { to, .. } = event {
+                received.lock().push(*to);
+            }
+        });
+
+        for i in 0..10 {
+            logger.info(format!("entry {}", i));
+        }
+        let failing = logger.metrics().appender("failing").unwrap();
+        assert_eq!(failing.failed(), 3);
+        assert_eq!(failing.short_circuited(), 7);
+        assert_eq!(failing.circuit_state(), CircuitState::Open);
+        assert_eq!(logger.metrics().dropped_count(), 10);
+
+        // Still open just before the interval ends
+        clock.advance(interval - Duration::from_millis(1));
+        logger.info("skipped");
+        assert_eq!(failing.short_circuited(), 8);
+
+        // The probe fails and opens the circuit again
+        clock.advance(Duration::from_millis(1));
+        logger.info("probe");
+        assert_eq!(failing.failed(), 4);
+        assert_eq!(failing.circuit_trips(), 2);
+        assert_eq!(
+            *events.lock(),
+            [CircuitState::Open, CircuitState::HalfOpen, CircuitState::Open]
+        );
+    }
+
+    #[test]
+    fn test_subscribe() {
+        use crate::appenders::{RotatingFileAppender, RotationPolicy};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.log");
+        let policy = RotationPolicy::new().with_max_size(100).with_max_backups(2);
+        let mut logger = Logger::builder()
+            .named_appender("rotating", RotatingFileAppender::with_policy(&path, policy).unwrap())
+            .appender(FailingAppender::always().with_failing_flush(true))
+            .build();
+
+        let events = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let received = Arc::clone(&events);
+        logger.subscribe(move |event: &LoggerEvent| received.lock().push(event.clone()));
+        let ignored = logger.subscribe(|_: &LoggerEvent| {});
+        assert!(logger.unsubscribe(ignored));
+        assert!(!logger.unsubscribe(ignored));
+
+        for i in 0..10 {
+            logger.info(format!("entry number {}", i));
+        }
+        assert!(!logger.shutdown(DEFAULT_SHUTDOWN_TIMEOUT));
+        drop(logger);
+
+        let events = events.lock();
+        assert!(events.contains(&LoggerEvent::AppenderFailed {
+            appender: "failing".to_string(),
+            error: "Writer error: injected failure in 'failing'".to_string(),
+        }));
+        assert!(events.contains(&LoggerEvent::RotationCompleted {
+            appender: "rotating".to_string(),
+            path: path.clone(),
+            backup: dir.path().join("events.log.1"),
+        }));
+        assert_eq!(
+            events[events.len() - 2..],
+            [
+                LoggerEvent::ShutdownStarted,
+                LoggerEvent::ShutdownFinished { clean: false }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_batches_written_without_flush() {
+        for policy in [FlushPolicy::Manual, FlushPolicy::Interval(Duration::from_secs(3600))] {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("app.log");
+            let logger = Logger::builder()
+                .appender(FileAppender::new(&path).unwrap())
+                .async_mode(1000)
+                .overflow_policy(OverflowPolicy::Block)
+                .flush_policy(policy)
+                .build();
+
+            // More than the file's write buffer holds, so the lines reach
+            // the file once the batch is written, without a flush
+            for i in 0..200 {
+                logger.info(format!("{:0>100}", i));
+            }
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while std::fs::metadata(&path).unwrap().len() < 8192 {
+                assert!(Instant::now() < deadline, "batch held back under {}", policy);
+                thread::sleep(Duration::from_millis(5));
+            }
+        }
+    }
+
+    #[test]
+    fn test_reconfigure() {
+        let entries = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .appender(FileAppender::new(&path).unwrap())
+            .async_mode(1000)
+            .overflow_policy(OverflowPolicy::Block)
+            .build();
+
+        for i in 0..100 {
+            logger.info(format!("message {}", i));
+        }
+        logger
+            .reconfigure(|config| {
+                assert_eq!(config.min_level, LogLevel::Info);
+                assert_eq!(config.overflow_policy, OverflowPolicy::Block);
+                config.min_level = LogLevel::Debug;
+                config.sampling.category_rates.insert("audit".to_string(), 0.5);
+                config.overflow_policy = OverflowPolicy::DropNewest;
+                config.flush_policy = FlushPolicy::EveryEntry;
+                config.formatter = Some(FormatterConfig::new().with_include_file_location(true));
+            })
+            .unwrap();
+        logger.log_at(LogLevel::Debug, "located", "src/main.rs", 7, "app");
+
+        let config = logger.runtime_config();
+        assert_eq!(config.min_level, LogLevel::Debug);
+        assert_eq!(config.sampling.category_rates["audit"], 0.5);
+        assert_eq!(config.overflow_policy, OverflowPolicy::DropNewest);
+        assert_eq!(config.flush_policy, FlushPolicy::EveryEntry);
+
+        assert!(logger.drain(DEFAULT_SHUTDOWN_TIMEOUT));
+        assert_eq!(entries.lock().len(), 101);
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(written.lines().count(), 101);
+        assert!(written.lines().last().unwrap().contains("src/main.rs:7"));
+
+        let rejected = logger.reconfigure(|config| {
+            config.min_level = LogLevel::Error;
+            config.sampling.rate = 1.5;
+        });
+        assert!(rejected.is_err());
+        assert_eq!(logger.effective_min_level(), LogLevel::Debug);
+    }
+
     #[test]
     fn test_metrics_drop_rate() {
         let metrics = LoggerMetrics::new();
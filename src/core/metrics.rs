@@ -2,8 +2,16 @@
 //!
 //! Provides counters and statistics for monitoring logger health,
 //! including dropped log counts, queue overflow events, and throughput.
+//! Every logger keeps one [`LoggerMetrics`] instance, shared with its async
+//! worker, with a breakdown per appender in [`AppenderMetrics`].
 
-use std::sync::atomic::{AtomicU64, Ordering};
+use super::circuit_breaker::CircuitState;
+use super::log_level::LogLevel;
+use parking_lot::RwLock;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Metrics for logger observability
 ///
@@ -41,6 +49,15 @@ pub struct LoggerMetrics {
 
     /// Number of critical logs that were force-written
     critical_logs_preserved: AtomicU64,
+
+    /// Number of entries written synchronously because the queue was full
+    sync_fallbacks: AtomicU64,
+
+    /// Counters of each appender, by registered name
+    appenders: RwLock<BTreeMap<String, Arc<AppenderMetrics>>>,
+
+    /// Counters of each level, indexed by `LogLevel as usize`
+    levels: [LevelCounters; LEVELS.len()],
 }
 
 impl LoggerMetrics {
@@ -52,6 +69,9 @@ impl LoggerMetrics {
             queue_full_events: AtomicU64::new(0),
             block_events: AtomicU64::new(0),
             critical_logs_preserved: AtomicU64::new(0),
+            sync_fallbacks: AtomicU64::new(0),
+            appenders: RwLock::new(BTreeMap::new()),
+            levels: [const { LevelCounters::new() }; LEVELS.len()],
         }
     }
 
@@ -85,18 +105,139 @@ impl LoggerMetrics {
         self.critical_logs_preserved.load(Ordering::Relaxed)
     }
 
+    /// Get the number of entries written synchronously because the queue
+    /// was full
+    #[inline]
+    pub fn sync_fallbacks(&self) -> u64 {
+        self.sync_fallbacks.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of failed appender calls, summed over all appenders
+    ///
+    /// An entry that two appenders fail to write counts twice. Panics are
+    /// included.
+    pub fn appender_errors(&self) -> u64 {
+        self.appenders
+            .read()
+            .values()
+            .map(|a| a.failed() + a.panicked())
+            .sum()
+    }
+
+    /// Get the number of failed flushes, summed over all appenders
+    pub fn flush_errors(&self) -> u64 {
+        self.appenders.read().values().map(|a| a.flush_failures()).sum()
+    }
+
+    /// Get the counters of the appender registered under `name`
+    ///
+    /// Counters are kept after an appender is removed, and continue when an
+    /// appender is replaced under the same name.
+    pub fn appender(&self, name: &str) -> Option<Arc<AppenderMetrics>> {
+        self.appenders.read().get(name).cloned()
+    }
+
+    /// Get the counters of every appender, sorted by name
+    pub fn appenders(&self) -> Vec<(String, Arc<AppenderMetrics>)> {
+        self.appenders
+            .read()
+            .iter()
+            .map(|(name, metrics)| (name.clone(), Arc::clone(metrics)))
+            .collect()
+    }
+
+    /// Get the counters for `name`, creating them if needed
+    pub(crate) fn register_appender(&self, name: &str) -> Arc<AppenderMetrics> {
+        if let Some(metrics) = self.appenders.read().get(name) {
+            return Arc::clone(metrics);
+        }
+        Arc::clone(self.appenders.write().entry(name.to_string()).or_default())
+    }
+
+    /// Get the counters of entries at `level`
+    ///
+    /// Only entries recorded with the `_at` methods, which the logger uses,
+    /// are broken down by level.
+    pub fn level(&self, level: LogLevel) -> LevelCounts {
+        self.levels[level as usize].snapshot()
+    }
+
+    /// Get the counters of every level, from `Trace` to `Fatal`
+    pub fn levels(&self) -> Vec<(LogLevel, LevelCounts)> {
+        LEVELS.iter().map(|&level| (level, self.level(level))).collect()
+    }
+
+    /// Get the drop rate of entries at `level` or above, as a percentage
+    ///
+    /// Returns 0.0 if no such entries have been processed. Sampled-out
+    /// entries do not count as dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let metrics = LoggerMetrics::new();
+    /// metrics.record_sampled_out(LogLevel::Debug);
+    /// metrics.record_logged_at(LogLevel::Warn);
+    /// metrics.record_dropped_at(LogLevel::Error);
+    /// assert_eq!(metrics.drop_rate_at_or_above(LogLevel::Warn), 50.0);
+    /// ```
+    pub fn drop_rate_at_or_above(&self, level: LogLevel) -> f64 {
+        let (logged, dropped) = LEVELS[level as usize..]
+            .iter()
+            .map(|&level| self.level(level))
+            .fold((0, 0), |(logged, dropped), counts| {
+                (logged + counts.logged, dropped + counts.dropped)
+            });
+        let total = (logged + dropped) as f64;
+        if total == 0.0 {
+            0.0
+        } else {
+            (dropped as f64 / total) * 100.0
+        }
+    }
+
+    /// Get the number of entries discarded by sampling, over all levels
+    pub fn sampled_out(&self) -> u64 {
+        self.levels
+            .iter()
+            .map(|counters| counters.sampled_out.load(Ordering::Relaxed))
+            .sum()
+    }
+
     /// Record a dropped log
     #[inline]
     pub fn record_dropped(&self) -> u64 {
         self.dropped_count.fetch_add(1, Ordering::Relaxed)
     }
 
+    /// Record a dropped log at `level`
+    #[inline]
+    pub fn record_dropped_at(&self, level: LogLevel) -> u64 {
+        self.levels[level as usize].dropped.fetch_add(1, Ordering::Relaxed);
+        self.record_dropped()
+    }
+
     /// Record a successfully logged entry
     #[inline]
     pub fn record_logged(&self) -> u64 {
         self.total_logged.fetch_add(1, Ordering::Relaxed)
     }
 
+    /// Record a successfully logged entry at `level`
+    #[inline]
+    pub fn record_logged_at(&self, level: LogLevel) -> u64 {
+        self.levels[level as usize].logged.fetch_add(1, Ordering::Relaxed);
+        self.record_logged()
+    }
+
+    /// Record an entry at `level` discarded by sampling
+    #[inline]
+    pub fn record_sampled_out(&self, level: LogLevel) -> u64 {
+        self.levels[level as usize].sampled_out.fetch_add(1, Ordering::Relaxed)
+    }
+
     /// Record a queue full event
     #[inline]
     pub fn record_queue_full(&self) -> u64 {
@@ -115,6 +256,12 @@ impl LoggerMetrics {
         self.critical_logs_preserved.fetch_add(1, Ordering::Relaxed)
     }
 
+    /// Record an entry written synchronously because the queue was full
+    #[inline]
+    pub fn record_sync_fallback(&self) -> u64 {
+        self.sync_fallbacks.fetch_add(1, Ordering::Relaxed)
+    }
+
     /// Get drop rate as a percentage (0.0 - 100.0)
     ///
     /// Returns 0.0 if no logs have been processed.
@@ -137,6 +284,13 @@ impl LoggerMetrics {
         self.queue_full_events.store(0, Ordering::Relaxed);
         self.block_events.store(0, Ordering::Relaxed);
         self.critical_logs_preserved.store(0, Ordering::Relaxed);
+        self.sync_fallbacks.store(0, Ordering::Relaxed);
+        for metrics in self.appenders.read().values() {
+            metrics.reset();
+        }
+        for counters in &self.levels {
+            counters.reset();
+        }
     }
 }
 
@@ -155,6 +309,386 @@ impl Clone for LoggerMetrics {
             queue_full_events: AtomicU64::new(self.queue_full_events()),
             block_events: AtomicU64::new(self.block_events()),
             critical_logs_preserved: AtomicU64::new(self.critical_logs_preserved()),
+            sync_fallbacks: AtomicU64::new(self.sync_fallbacks()),
+            appenders: RwLock::new(
+                self.appenders
+                    .read()
+                    .iter()
+                    .map(|(name, metrics)| (name.clone(), Arc::new(metrics.as_ref().clone())))
+                    .collect(),
+            ),
+            levels: std::array::from_fn(|i| LevelCounters::from(self.levels[i].snapshot())),
+        }
+    }
+}
+
+/// Levels in the order of [`LoggerMetrics::levels`]
+const LEVELS: [LogLevel; 6] = [
+    LogLevel::Trace,
+    LogLevel::Debug,
+    LogLevel::Info,
+    LogLevel::Warn,
+    LogLevel::Error,
+    LogLevel::Fatal,
+];
+
+/// Counters for one level
+#[derive(Debug)]
+struct LevelCounters {
+    logged: AtomicU64,
+    dropped: AtomicU64,
+    sampled_out: AtomicU64,
+}
+
+impl LevelCounters {
+    const fn new() -> Self {
+        Self {
+            logged: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            sampled_out: AtomicU64::new(0),
+        }
+    }
+
+    fn snapshot(&self) -> LevelCounts {
+        LevelCounts {
+            logged: self.logged.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            sampled_out: self.sampled_out.load(Ordering::Relaxed),
+        }
+    }
+
+    fn reset(&self) {
+        self.logged.store(0, Ordering::Relaxed);
+        self.dropped.store(0, Ordering::Relaxed);
+        self.sampled_out.store(0, Ordering::Relaxed);
+    }
+}
+
+impl From<LevelCounts> for LevelCounters {
+    fn from(counts: LevelCounts) -> Self {
+        Self {
+            logged: AtomicU64::new(counts.logged),
+            dropped: AtomicU64::new(counts.dropped),
+            sampled_out: AtomicU64::new(counts.sampled_out),
+        }
+    }
+}
+
+/// Entry counts for one level, see [`LoggerMetrics::level`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LevelCounts {
+    /// Entries written
+    pub logged: u64,
+
+    /// Entries lost to overflow or appender failures
+    pub dropped: u64,
+
+    /// Entries discarded by sampling
+    pub sampled_out: u64,
+}
+
+/// Counters for one appender
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::prelude::*;
+///
+/// let logger = Logger::builder().appender(ConsoleAppender::new()).build();
+/// logger.info("hello");
+///
+/// let console = logger.metrics().appender("console").unwrap();
+/// assert_eq!(console.written(), 1);
+/// assert_eq!(console.failed(), 0);
+/// ```
+#[derive(Debug, Default)]
+pub struct AppenderMetrics {
+    /// Entries the appender accepted
+    written: AtomicU64,
+
+    /// Entries the appender returned an error for
+    failed: AtomicU64,
+
+    /// Entries the appender panicked on
+    panicked: AtomicU64,
+
+    /// Flushes that failed or panicked
+    flush_failures: AtomicU64,
+
+    /// Entries skipped because the circuit breaker was open
+    short_circuited: AtomicU64,
+
+    /// Times the circuit breaker opened
+    circuit_trips: AtomicU64,
+
+    /// Current [`CircuitState`], as its index
+    circuit_state: AtomicU8,
+
+    /// Time taken by `append`, successful or not
+    latency: LatencyHistogram,
+}
+
+impl AppenderMetrics {
+    /// Get the number of entries written
+    #[inline]
+    pub fn written(&self) -> u64 {
+        self.written.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of entries the appender returned an error for
+    #[inline]
+    pub fn failed(&self) -> u64 {
+        self.failed.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of entries the appender panicked on
+    #[inline]
+    pub fn panicked(&self) -> u64 {
+        self.panicked.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of failed flushes
+    #[inline]
+    pub fn flush_failures(&self) -> u64 {
+        self.flush_failures.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of entries skipped because the circuit breaker was open
+    #[inline]
+    pub fn short_circuited(&self) -> u64 {
+        self.short_circuited.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of times the circuit breaker opened
+    #[inline]
+    pub fn circuit_trips(&self) -> u64 {
+        self.circuit_trips.load(Ordering::Relaxed)
+    }
+
+    /// Get the current state of the circuit breaker
+    ///
+    /// Always [`CircuitState::Closed`] without a breaker; see
+    /// [`crate::core::circuit_breaker`].
+    #[inline]
+    pub fn circuit_state(&self) -> CircuitState {
+        match self.circuit_state.load(Ordering::Relaxed) {
+            1 => CircuitState::Open,
+            2 => CircuitState::HalfOpen,
+            _ => CircuitState::Closed,
+        }
+    }
+
+    /// Get the distribution of append durations
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::builder().appender(ConsoleAppender::new()).build();
+    /// logger.info("hello");
+    ///
+    /// let latency = logger.metrics().appender("console").unwrap().latency().clone();
+    /// assert_eq!(latency.count(), 1);
+    /// println!("p99 append time: {:?}", latency.percentile(0.99));
+    /// ```
+    #[inline]
+    pub fn latency(&self) -> &LatencyHistogram {
+        &self.latency
+    }
+
+    /// Record a written entry
+    #[inline]
+    pub fn record_written(&self) -> u64 {
+        self.written.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Record an entry the appender returned an error for
+    #[inline]
+    pub fn record_failed(&self) -> u64 {
+        self.failed.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Record an entry the appender panicked on
+    #[inline]
+    pub fn record_panicked(&self) -> u64 {
+        self.panicked.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Record a failed flush
+    #[inline]
+    pub fn record_flush_failure(&self) -> u64 {
+        self.flush_failures.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Record the time one append took
+    #[inline]
+    pub fn record_latency(&self, duration: Duration) {
+        self.latency.record(duration);
+    }
+
+    /// Record an entry skipped because the circuit breaker was open
+    #[inline]
+    pub fn record_short_circuited(&self) -> u64 {
+        self.short_circuited.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Record a new circuit breaker state, counting a trip when it opened
+    pub fn record_circuit_state(&self, state: CircuitState) {
+        let index = match state {
+            CircuitState::Closed => 0,
+            CircuitState::Open => 1,
+            CircuitState::HalfOpen => 2,
+        };
+        if self.circuit_state.swap(index, Ordering::Relaxed) != index && index == 1 {
+            self.circuit_trips.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Reset all counters to zero
+    pub fn reset(&self) {
+        self.written.store(0, Ordering::Relaxed);
+        self.failed.store(0, Ordering::Relaxed);
+        self.panicked.store(0, Ordering::Relaxed);
+        self.flush_failures.store(0, Ordering::Relaxed);
+        self.short_circuited.store(0, Ordering::Relaxed);
+        self.circuit_trips.store(0, Ordering::Relaxed);
+        self.latency.reset();
+    }
+}
+
+impl Clone for AppenderMetrics {
+    /// Create a snapshot of the current counters
+    fn clone(&self) -> Self {
+        Self {
+            written: AtomicU64::new(self.written()),
+            failed: AtomicU64::new(self.failed()),
+            panicked: AtomicU64::new(self.panicked()),
+            flush_failures: AtomicU64::new(self.flush_failures()),
+            short_circuited: AtomicU64::new(self.short_circuited()),
+            circuit_trips: AtomicU64::new(self.circuit_trips()),
+            circuit_state: AtomicU8::new(self.circuit_state.load(Ordering::Relaxed)),
+            latency: self.latency.clone(),
+        }
+    }
+}
+
+/// Number of latency buckets
+///
+/// Bucket `i` counts durations below `2^i` microseconds; the last bucket
+/// also takes everything longer (about 8 seconds and up).
+const LATENCY_BUCKETS: usize = 24;
+
+/// Histogram of durations with power-of-two microsecond buckets
+///
+/// Recording is a few relaxed atomic operations, cheap enough to run for
+/// every append. Percentiles are reported as the upper bound of the bucket
+/// they fall in, so they are accurate to within a factor of two.
+#[derive(Debug, Default)]
+pub struct LatencyHistogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS],
+    count: AtomicU64,
+    sum_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+}
+
+impl LatencyHistogram {
+    /// Create an empty histogram
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one duration
+    pub fn record(&self, duration: Duration) {
+        let micros = duration.as_micros().min(u64::MAX as u128) as u64;
+        let bucket = (u64::BITS - micros.leading_zeros()) as usize;
+        self.buckets[bucket.min(LATENCY_BUCKETS - 1)].fetch_add(1, Ordering::Relaxed);
+
+        let nanos = duration.as_nanos().min(u64::MAX as u128) as u64;
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    /// Get the number of recorded durations
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Get the mean duration (zero if nothing was recorded)
+    pub fn mean(&self) -> Duration {
+        match self.count() {
+            0 => Duration::ZERO,
+            count => Duration::from_nanos(self.sum_nanos.load(Ordering::Relaxed) / count),
+        }
+    }
+
+    /// Get the longest recorded duration
+    #[inline]
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Estimate the duration below which the fraction `q` of durations fall
+    ///
+    /// `q` is clamped to `0.0..=1.0`. Returns the upper bound of the bucket
+    /// holding the percentile, capped at [`max`](Self::max).
+    pub fn percentile(&self, q: f64) -> Duration {
+        let count = self.count();
+        if count == 0 {
+            return Duration::ZERO;
+        }
+        let rank = ((q.clamp(0.0, 1.0) * count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (upper, in_bucket) in self.buckets() {
+            seen += in_bucket;
+            if seen >= rank {
+                return upper.min(self.max());
+            }
+        }
+        self.max()
+    }
+
+    /// Get the bucket counts, each with the exclusive upper bound of the bucket
+    ///
+    /// The last bucket is unbounded; its bound is reported as [`Duration::MAX`].
+    pub fn buckets(&self) -> Vec<(Duration, u64)> {
+        self.buckets
+            .iter()
+            .enumerate()
+            .map(|(i, bucket)| {
+                let upper = if i == LATENCY_BUCKETS - 1 {
+                    Duration::MAX
+                } else {
+                    Duration::from_micros(1 << i)
+                };
+                (upper, bucket.load(Ordering::Relaxed))
+            })
+            .collect()
+    }
+
+    /// Reset the histogram
+    pub fn reset(&self) {
+        for bucket in &self.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.count.store(0, Ordering::Relaxed);
+        self.sum_nanos.store(0, Ordering::Relaxed);
+        self.max_nanos.store(0, Ordering::Relaxed);
+    }
+}
+
+impl Clone for LatencyHistogram {
+    /// Create a snapshot of the current histogram
+    fn clone(&self) -> Self {
+        Self {
+            buckets: std::array::from_fn(|i| {
+                AtomicU64::new(self.buckets[i].load(Ordering::Relaxed))
+            }),
+            count: AtomicU64::new(self.count()),
+            sum_nanos: AtomicU64::new(self.sum_nanos.load(Ordering::Relaxed)),
+            max_nanos: AtomicU64::new(self.max_nanos.load(Ordering::Relaxed)),
         }
     }
 }
@@ -241,4 +775,83 @@ mod tests {
         assert_eq!(metrics.dropped_count(), 2);
         assert_eq!(snapshot.dropped_count(), 1);
     }
+
+    #[test]
+    fn test_appender_breakdown() {
+        let metrics = LoggerMetrics::new();
+        let file = metrics.register_appender("file");
+        assert!(Arc::ptr_eq(&file, &metrics.register_appender("file")));
+        file.record_written();
+        file.record_failed();
+        file.record_flush_failure();
+        metrics.register_appender("console").record_panicked();
+
+        assert_eq!(metrics.appender_errors(), 2);
+        assert_eq!(metrics.flush_errors(), 1);
+        let names: Vec<_> = metrics.appenders().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["console", "file"]);
+
+        let snapshot = metrics.clone();
+        metrics.reset();
+        assert_eq!(metrics.appender("file").unwrap().written(), 0);
+        assert_eq!(snapshot.appender("file").unwrap().written(), 1);
+    }
+
+    #[test]
+    fn test_level_breakdown() {
+        let metrics = LoggerMetrics::new();
+        for _ in 0..8 {
+            metrics.record_sampled_out(LogLevel::Debug);
+        }
+        metrics.record_dropped_at(LogLevel::Debug);
+        metrics.record_logged_at(LogLevel::Debug);
+        for _ in 0..3 {
+            metrics.record_logged_at(LogLevel::Warn);
+        }
+        metrics.record_dropped_at(LogLevel::Error);
+
+        assert_eq!(metrics.total_logged(), 4);
+        assert_eq!(metrics.dropped_count(), 2);
+        assert_eq!(metrics.sampled_out(), 8);
+        assert_eq!(
+            metrics.level(LogLevel::Debug),
+            LevelCounts {
+                logged: 1,
+                dropped: 1,
+                sampled_out: 8
+            }
+        );
+        assert_eq!(metrics.drop_rate_at_or_above(LogLevel::Warn), 25.0);
+        assert_eq!(metrics.drop_rate_at_or_above(LogLevel::Fatal), 0.0);
+        assert_eq!(metrics.levels().len(), 6);
+
+        let snapshot = metrics.clone();
+        metrics.reset();
+        assert_eq!(metrics.level(LogLevel::Warn), LevelCounts::default());
+        assert_eq!(snapshot.level(LogLevel::Warn).logged, 3);
+    }
+
+    #[test]
+    fn test_latency_histogram() {
+        let histogram = LatencyHistogram::new();
+        assert_eq!(histogram.percentile(0.5), Duration::ZERO);
+
+        for _ in 0..98 {
+            histogram.record(Duration::from_micros(3));
+        }
+        histogram.record(Duration::from_micros(100));
+        histogram.record(Duration::from_millis(20));
+
+        assert_eq!(histogram.count(), 100);
+        assert_eq!(histogram.max(), Duration::from_millis(20));
+        assert_eq!(histogram.percentile(0.5), Duration::from_micros(4));
+        assert_eq!(histogram.percentile(0.99), Duration::from_micros(128));
+        assert_eq!(histogram.percentile(1.0), Duration::from_millis(20));
+        assert_eq!(histogram.buckets()[2], (Duration::from_micros(4), 98));
+
+        let snapshot = histogram.clone();
+        histogram.reset();
+        assert_eq!(histogram.count(), 0);
+        assert_eq!(snapshot.count(), 100);
+    }
 }
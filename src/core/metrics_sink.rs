@@ -0,0 +1,164 @@
+//! Publishing logger metrics to an external metrics system
+//!
+//! A [`MetricsSink`] installed with [`LoggerBuilder::metrics_sink`] receives
+//! the logger's [`LoggerMetrics`] at a fixed interval and the duration of
+//! every append. In async mode the worker thread publishes, also while no
+//! entries arrive; in synchronous mode the logging thread does, when it
+//! writes an entry after the interval has passed. A final snapshot is
+//! published when the logger is dropped.
+//!
+//! With the `otel-metrics` feature, `bridge::OtelMetricsSink` publishes to
+//! OpenTelemetry meters.
+//!
+//! [`LoggerBuilder::metrics_sink`]: super::LoggerBuilder::metrics_sink
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::core::MetricsSink;
+//! use rust_logger_system::prelude::*;
+//! use std::time::Duration;
+//!
+//! struct StatsdSink;
+//!
+//! impl MetricsSink for StatsdSink {
+//!     fn publish(&self, metrics: &LoggerMetrics) {
+//!         println!("logger.dropped:{}|g", metrics.dropped_count());
+//!     }
+//! }
+//!
+//! let logger = Logger::builder()
+//!     .async_mode(1000)
+//!     .metrics_sink(StatsdSink, Duration::from_secs(10))
+//!     .build();
+//! ```
+
+use super::metrics::LoggerMetrics;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default interval between publications of a [`MetricsSink`]
+pub const DEFAULT_METRICS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Receiver of logger metrics
+///
+/// Both methods are called on the thread writing entries, so they should
+/// return quickly.
+pub trait MetricsSink: Send + Sync {
+    /// Receive the current counters
+    ///
+    /// Counters are cumulative; a sink that reports deltas has to keep the
+    /// previous values.
+    fn publish(&self, metrics: &LoggerMetrics);
+
+    /// Receive the time an appender took to write one entry
+    ///
+    /// `appender` is the registered name (see
+    /// [`Logger::list_appenders`](super::Logger::list_appenders)). The
+    /// default implementation ignores the measurement.
+    fn record_append(&self, appender: &str, duration: Duration) {
+        let _ = (appender, duration);
+    }
+}
+
+impl<S: MetricsSink + ?Sized> MetricsSink for Arc<S> {
+    fn publish(&self, metrics: &LoggerMetrics) {
+        (**self).publish(metrics);
+    }
+
+    fn record_append(&self, appender: &str, duration: Duration) {
+        (**self).record_append(appender, duration);
+    }
+}
+
+/// A [`MetricsSink`] with the interval it is published at
+pub(crate) struct MetricsPublisher {
+    sink: Arc<dyn MetricsSink>,
+    interval: Duration,
+    last_publish: Mutex<Instant>,
+}
+
+impl MetricsPublisher {
+    pub(crate) fn new(sink: Arc<dyn MetricsSink>, interval: Duration) -> Self {
+        Self {
+            sink,
+            interval,
+            last_publish: Mutex::new(Instant::now()),
+        }
+    }
+
+    pub(crate) fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Publish if the interval has passed since the last publication
+    pub(crate) fn publish_due(&self, metrics: &LoggerMetrics) {
+        {
+            let mut last_publish = self.last_publish.lock();
+            if last_publish.elapsed() < self.interval {
+                return;
+            }
+            *last_publish = Instant::now();
+        }
+        self.sink.publish(metrics);
+    }
+
+    pub(crate) fn publish(&self, metrics: &LoggerMetrics) {
+        *self.last_publish.lock() = Instant::now();
+        self.sink.publish(metrics);
+    }
+
+    pub(crate) fn record_append(&self, appender: &str, duration: Duration) {
+        self.sink.record_append(appender, duration);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Logger;
+    use crate::testing::CaptureAppender;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct CountingSink {
+        published: AtomicUsize,
+        last_logged: Mutex<u64>,
+        appends: Mutex<Vec<String>>,
+    }
+
+    impl MetricsSink for CountingSink {
+        fn publish(&self, metrics: &LoggerMetrics) {
+            self.published.fetch_add(1, Ordering::SeqCst);
+            *self.last_logged.lock() = metrics.total_logged();
+        }
+
+        fn record_append(&self, appender: &str, _duration: Duration) {
+            self.appends.lock().push(appender.to_string());
+        }
+    }
+
+    #[test]
+    fn test_worker_publishes_while_idle() {
+        let sink = Arc::new(CountingSink::default());
+        let logger = Logger::builder()
+            .appender(CaptureAppender::new(Arc::default()))
+            .async_mode(100)
+            .metrics_sink(Arc::clone(&sink), Duration::from_millis(10))
+            .build();
+
+        logger.info("one");
+        logger.info("two");
+        let start = Instant::now();
+        while *sink.last_logged.lock() < 2 && start.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(*sink.last_logged.lock(), 2);
+        assert_eq!(*sink.appends.lock(), ["capture", "capture"]);
+
+        let published = sink.published.load(Ordering::SeqCst);
+        drop(logger);
+        assert!(sink.published.load(Ordering::SeqCst) > published);
+    }
+}
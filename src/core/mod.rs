@@ -1,31 +1,109 @@
 //! Core logger types and traits
 
+pub(crate) mod ansi;
+#[cfg(feature = "admin")]
+pub mod admin;
 pub mod appender;
 pub mod async_appender;
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+pub mod binary_format;
+pub mod circuit_breaker;
+pub mod clock;
+pub mod config;
+pub mod config_watcher;
+pub mod diagnostics;
+pub mod enricher;
+pub mod entry_pool;
+pub mod env_config;
 pub mod error;
+pub mod events;
+pub mod filter;
+pub mod flush_policy;
+pub mod formatter;
+pub mod health;
+pub mod hierarchy;
+pub mod host_metadata;
+pub mod level_schedule;
+pub mod local_context;
 pub mod log_context;
 pub mod log_entry;
 pub mod log_level;
 pub mod logger;
 pub mod metrics;
+pub mod metrics_sink;
 pub mod output_format;
 pub mod overflow_policy;
+pub mod pattern;
+pub mod presets;
+pub mod queue;
+pub mod reader;
+pub mod redaction;
+pub mod runtime_config;
 pub mod sampling;
+pub mod schema;
+pub mod severity;
+pub mod span;
 pub mod structured_builder;
 pub mod structured_entry;
+pub mod suppression;
+pub(crate) mod sync;
+pub mod theme;
+pub mod timed;
 pub mod timestamp;
+pub mod transformer;
+pub mod wal;
 
+#[cfg(feature = "admin")]
+pub use admin::AdminServer;
 pub use appender::Appender;
 pub use async_appender::AsyncAppender;
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+pub use binary_format::BinaryFormat;
+pub use circuit_breaker::{CircuitBreakerConfig, CircuitState};
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use config::{
+    AppenderConfig, EnvOverrides, LoggerConfig, SamplingSettings, ENV_OVERRIDE_PREFIX,
+};
+pub use config_watcher::{
+    ConfigWatcher, ConfigWatcherBuilder, ReloadErrorCallback, DEFAULT_WATCH_INTERVAL,
+};
+pub use diagnostics::{
+    set_diagnostics_handler, DiagnosticEvent, DiagnosticsHandler, StderrDiagnostics,
+    ThrottleConfig, Throttled,
+};
+pub use enricher::Enricher;
+pub use env_config::{EnvConfig, DEFAULT_ENV_VAR};
 pub use error::{LoggerError, Result};
-pub use log_context::{ContextGuard, FieldValue, LogContext, LoggerContext};
-pub use log_entry::LogEntry;
-pub use log_level::LogLevel;
+pub use events::{AppenderEvent, EventCallback, LoggerEvent, SubscriptionId};
+pub use filter::{FieldCondition, FieldFilter, Filter, ModuleLevelFilter};
+pub use flush_policy::FlushPolicy;
+pub use formatter::{Formatter, OutputFormatter};
+pub use health::{AppenderHealth, HealthStatus};
+pub use hierarchy::{ChildLogger, LoggerRegistry, LOGGER_NAME_FIELD};
+pub use host_metadata::HostMetadata;
+pub use level_schedule::{LevelSchedule, ScheduleWindow};
+pub use log_context::{ContextGuard, FieldMap, FieldValue, LogContext, LoggerContext};
+pub use log_entry::{intern, LogEntry};
+pub use log_level::{static_level_enabled, LogLevel, STATIC_MAX_LEVEL};
 pub use logger::{Logger, LoggerBuilder, DEFAULT_SHUTDOWN_TIMEOUT};
-pub use metrics::LoggerMetrics;
-pub use output_format::OutputFormat;
+pub use metrics::{AppenderMetrics, LatencyHistogram, LevelCounts, LoggerMetrics};
+pub use metrics_sink::{MetricsSink, DEFAULT_METRICS_INTERVAL};
+pub use output_format::{JsonProfile, OutputFormat, ECS_VERSION};
 pub use overflow_policy::{LogPriority, OverflowCallback, OverflowPolicy, PriorityConfig};
-pub use sampling::{LogSampler, SamplerMetrics, SamplingConfig};
+pub use pattern::PatternLayout;
+pub use queue::AsyncQueue;
+pub use reader::{InputFormat, LogReader};
+pub use redaction::{RedactionConfig, Redactor};
+pub use runtime_config::RuntimeConfig;
+pub use sampling::{LogSampler, SamplerMetrics, SamplingConfig, SamplingMode};
+pub use schema::{Schema, SchemaField, SchemaVersion, SCHEMA_VERSION_FIELD};
+pub use severity::{CustomLevel, LevelRegistry, Severity};
+pub use span::{current_span, Span};
 pub use structured_builder::StructuredLogBuilder;
 pub use structured_entry::{StructuredLogEntry, TracingContext};
+pub use suppression::{BurstSuppressor, SuppressionConfig, SuppressionDecision, SuppressionKey};
+pub use theme::{ConsoleTheme, TextStyle};
+pub use timed::TimedGuard;
 pub use timestamp::{FormatterConfig, TimestampFormat};
+pub use transformer::{RenameFields, Transformer};
+pub use wal::WalConfig;
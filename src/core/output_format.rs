@@ -4,9 +4,45 @@
 //! - Text: Human-readable format (default)
 //! - Json: Machine-readable JSON format
 //! - Logfmt: Key-value format compatible with log aggregation tools
+//! - Pattern: log4j-style conversion pattern (see [`PatternLayout`])
+//! - OtelJson: OpenTelemetry LogRecord in OTLP/JSON encoding
+//! - Pretty: Multi-line layout for local development
+//!
+//! JSON output can follow the Elastic Common Schema instead of the default
+//! field names; see [`JsonProfile`].
 
+use super::error::Result;
+use super::log_context::FieldValue;
 use super::log_entry::LogEntry;
-use super::timestamp::TimestampFormat;
+use super::pattern::PatternLayout;
+use super::schema::{SchemaVersion, SCHEMA_VERSION_FIELD};
+use super::theme::{ConsoleTheme, TextStyle};
+use super::timestamp::{FormatterConfig, TimestampFormat};
+use std::fmt::Write;
+
+/// ECS version reported in the `ecs.version` field
+pub const ECS_VERSION: &str = "8.11.0";
+
+/// Field naming used by [`OutputFormat::Json`]
+///
+/// Selected through [`FormatterConfig::with_json_profile`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonProfile {
+    /// This crate's own field names (`timestamp`, `level`, `thread_id`, ...)
+    /// with context fields at the top level
+    #[default]
+    Standard,
+
+    /// Elastic Common Schema field names
+    ///
+    /// Emits `@timestamp`, `log.level`, `message`, `ecs.version`,
+    /// `process.thread.name`, `log.origin.*`, `log.logger`, `trace.id` and
+    /// `span.id`. String context fields are nested under `labels`, all
+    /// other context fields under `custom`.
+    ///
+    /// Example: `{"@timestamp":"2025-01-08T10:30:45Z","log.level":"INFO","message":"Request processed","ecs.version":"8.11.0",...}`
+    Ecs,
+}
 
 /// Output format for log entries
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -19,52 +55,202 @@ pub enum OutputFormat {
 
     /// JSON format for machine processing
     ///
-    /// Example: `{"timestamp":"2025-01-08T10:30:45Z","level":"INFO","message":"Request processed"}`
+    /// Example: `{"schema_version":"1.1","timestamp":"2025-01-08T10:30:45Z","level":"INFO",...}`
+    ///
+    /// The `schema_version` field identifies the layout; see
+    /// [`Schema`](super::Schema).
     Json,
 
     /// Logfmt format (key=value pairs)
     ///
     /// Example: `timestamp=2025-01-08T10:30:45Z level=INFO message="Request processed"`
+    ///
+    /// Array and map fields are flattened into dotted keys, e.g.
+    /// `user.id=42 failed_ids.0=7 failed_ids.1=9`.
     Logfmt,
+
+    /// log4j-style conversion pattern, parsed once by [`OutputFormat::pattern`]
+    ///
+    /// Example: `%d{ISO8601} %-5p [%t] %c - %m %X{request_id}`
+    Pattern(PatternLayout),
+
+    /// OpenTelemetry Logs data model, one OTLP/JSON `LogRecord` per line
+    ///
+    /// Can be tailed by an OpenTelemetry Collector's filelog receiver without
+    /// a transform. The timestamp is always `timeUnixNano`; thread and
+    /// source location become `thread.name`, `code.filepath`, `code.lineno`
+    /// and `code.namespace` attributes next to the context fields.
+    ///
+    /// Example: `{"timeUnixNano":"1736332245000000000","severityNumber":9,"severityText":"INFO","body":{"stringValue":"Request processed"},"attributes":[...]}`
+    OtelJson,
+
+    /// Multi-line layout for reading logs during local development
+    ///
+    /// The first line holds the timestamp, level and message; the source
+    /// location and each context field follow on their own indented lines.
+    /// Multi-line values such as backtraces are indented as a block and
+    /// arrays (e.g. error cause chains) are listed one item per line.
+    /// [`ConsoleAppender`](crate::appenders::ConsoleAppender) styles it
+    /// with its [`ConsoleTheme`] when colors are enabled.
+    ///
+    /// Example:
+    ///
+    /// ```text
+    /// 2025-01-08T10:30:45Z  WARN retrying request
+    ///     at src/net.rs:12
+    ///     attempt: 3
+    ///     causes:
+    ///       - connection reset
+    ///       - broken pipe
+    /// ```
+    Pretty,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    /// Parse a format name: `text`, `json`, `logfmt`, `otel` or `pretty`
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "logfmt" => Ok(OutputFormat::Logfmt),
+            "otel" => Ok(OutputFormat::OtelJson),
+            "pretty" => Ok(OutputFormat::Pretty),
+            other => Err(format!("unknown format '{}'", other)),
+        }
+    }
 }
 
 impl OutputFormat {
+    /// Create a [`OutputFormat::Pattern`] from a log4j-style conversion pattern
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern is invalid; see [`PatternLayout`]
+    /// for the supported conversions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::core::OutputFormat;
+    ///
+    /// let format = OutputFormat::pattern("%d{ISO8601} %-5p [%t] %c - %m%n").unwrap();
+    /// ```
+    pub fn pattern(pattern: &str) -> Result<Self> {
+        PatternLayout::parse(pattern).map(OutputFormat::Pattern)
+    }
+
     /// Format a log entry according to this output format
     pub fn format(&self, entry: &LogEntry, timestamp_format: &TimestampFormat) -> String {
         match self {
-            OutputFormat::Text => self.format_text(entry, timestamp_format),
+            OutputFormat::Text => self.format_text(entry, timestamp_format, None),
             OutputFormat::Json => self.format_json(entry, timestamp_format),
             OutputFormat::Logfmt => self.format_logfmt(entry, timestamp_format),
+            OutputFormat::Pattern(layout) => layout.format(entry, timestamp_format),
+            OutputFormat::OtelJson => self.format_otel(entry),
+            OutputFormat::Pretty => self.format_pretty(entry, timestamp_format, None),
+        }
+    }
+
+    /// Format a log entry using the timestamp format, JSON profile and, for
+    /// text, the source location settings of `config`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::core::{FormatterConfig, JsonProfile, OutputFormat};
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let config = FormatterConfig::new().with_json_profile(JsonProfile::Ecs);
+    /// let entry = LogEntry::new(LogLevel::Info, "ready".to_string());
+    ///
+    /// let line = OutputFormat::Json.format_with_config(&entry, &config);
+    /// assert!(line.contains(r#""log.level":"INFO""#));
+    /// ```
+    pub fn format_with_config(&self, entry: &LogEntry, config: &FormatterConfig) -> String {
+        match (self, config.json_profile) {
+            (OutputFormat::Json, JsonProfile::Ecs) => {
+                self.format_ecs(entry, &config.timestamp_format)
+            }
+            (OutputFormat::Text, _) => {
+                let location = config.location(entry);
+                self.format_text(entry, &config.timestamp_format, location.as_deref())
+            }
+            _ => self.format(entry, &config.timestamp_format),
+        }
+    }
+
+    /// Append the formatted entry to `output`, like
+    /// [`format_with_config`](Self::format_with_config)
+    ///
+    /// Text and pattern layouts are written directly into `output`, so a
+    /// buffer reused across entries saves their allocations.
+    pub fn format_into(&self, entry: &LogEntry, config: &FormatterConfig, output: &mut String) {
+        match self {
+            OutputFormat::Text => self.write_text(entry, config, output),
+            OutputFormat::Pattern(layout) => {
+                layout.format_into(entry, &config.timestamp_format, output)
+            }
+            format => output.push_str(&format.format_with_config(entry, config)),
+        }
+    }
+
+    /// Format as human-readable text, with `location` after the thread name
+    fn format_text(
+        &self,
+        entry: &LogEntry,
+        timestamp_format: &TimestampFormat,
+        location: Option<&str>,
+    ) -> String {
+        let mut output = String::new();
+        output.push('[');
+        timestamp_format.format_into(&entry.timestamp, &mut output);
+        let thread_name = entry.thread_name.as_ref().unwrap_or(&entry.thread_id);
+        let _ = write!(output, "] [{:5}] {} ", entry.level_name(), thread_name);
+        if let Some(location) = location {
+            output.push_str(location);
+            output.push(' ');
         }
+        self.finish_text(entry, &mut output);
+        output
     }
 
-    /// Format as human-readable text
-    fn format_text(&self, entry: &LogEntry, timestamp_format: &TimestampFormat) -> String {
-        let timestamp_str = timestamp_format.format(&entry.timestamp);
+    /// Append text output to `output`, taking the location from `config`
+    fn write_text(&self, entry: &LogEntry, config: &FormatterConfig, output: &mut String) {
+        output.push('[');
+        config.timestamp_format.format_into(&entry.timestamp, output);
         let thread_name = entry.thread_name.as_ref().unwrap_or(&entry.thread_id);
+        let _ = write!(output, "] [{:5}] {} ", entry.level_name(), thread_name);
+        if config.push_location(entry, output) {
+            output.push(' ');
+        }
+        self.finish_text(entry, output);
+    }
 
-        let base = format!(
-            "[{}] [{:5}] {} - {}",
-            timestamp_str,
-            entry.level.to_str(),
-            thread_name,
-            entry.message
-        );
+    /// Append the message and context fields of text output
+    fn finish_text(&self, entry: &LogEntry, output: &mut String) {
+        output.push_str("- ");
+        output.push_str(&entry.message);
 
         // Append context fields if present
         if let Some(ref context) = entry.context {
-            if !context.is_empty() {
-                return format!("{} {}", base, context.format_fields());
+            for (key, value) in context.field_map() {
+                let _ = write!(output, " {}={}", key, value);
             }
         }
-
-        base
     }
 
     /// Format as JSON
     fn format_json(&self, entry: &LogEntry, timestamp_format: &TimestampFormat) -> String {
         let mut json_obj = serde_json::Map::new();
 
+        // Add layout version
+        json_obj.insert(
+            SCHEMA_VERSION_FIELD.to_string(),
+            serde_json::Value::String(SchemaVersion::CURRENT.to_string()),
+        );
+
         // Add timestamp
         json_obj.insert(
             "timestamp".to_string(),
@@ -74,7 +260,7 @@ impl OutputFormat {
         // Add level
         json_obj.insert(
             "level".to_string(),
-            serde_json::Value::String(entry.level.to_str().to_string()),
+            serde_json::Value::String(entry.level_name().to_string()),
         );
 
         // Add message
@@ -86,18 +272,18 @@ impl OutputFormat {
         // Add thread info
         json_obj.insert(
             "thread_id".to_string(),
-            serde_json::Value::String(entry.thread_id.clone()),
+            serde_json::Value::String(entry.thread_id.to_string()),
         );
         if let Some(ref name) = entry.thread_name {
             json_obj.insert(
                 "thread_name".to_string(),
-                serde_json::Value::String(name.clone()),
+                serde_json::Value::String(name.to_string()),
             );
         }
 
         // Add location info if present
         if let Some(ref file) = entry.file {
-            json_obj.insert("file".to_string(), serde_json::Value::String(file.clone()));
+            json_obj.insert("file".to_string(), serde_json::Value::String(file.to_string()));
         }
         if let Some(line) = entry.line {
             json_obj.insert("line".to_string(), serde_json::Value::Number(line.into()));
@@ -105,13 +291,21 @@ impl OutputFormat {
         if let Some(ref module_path) = entry.module_path {
             json_obj.insert(
                 "module_path".to_string(),
-                serde_json::Value::String(module_path.clone()),
+                serde_json::Value::String(module_path.to_string()),
             );
         }
+        if let Some(ref target) = entry.target {
+            json_obj.insert("target".to_string(), serde_json::Value::String(target.to_string()));
+        }
+
+        // Add tracing identifiers if present
+        if let Some(ref tracing) = entry.tracing {
+            tracing.insert_json(&mut json_obj);
+        }
 
         // Add context fields if present
         if let Some(ref context) = entry.context {
-            for (key, value) in context.fields() {
+            for (key, value) in context.field_map() {
                 json_obj.insert(key.clone(), value.to_json_value());
             }
         }
@@ -119,6 +313,138 @@ impl OutputFormat {
         serde_json::to_string(&serde_json::Value::Object(json_obj)).unwrap_or_default()
     }
 
+    /// Format as JSON with Elastic Common Schema field names
+    fn format_ecs(&self, entry: &LogEntry, timestamp_format: &TimestampFormat) -> String {
+        use serde_json::Value;
+
+        let mut json_obj = serde_json::Map::new();
+        json_obj.insert(
+            "@timestamp".to_string(),
+            self.format_timestamp_json(entry, timestamp_format),
+        );
+        json_obj.insert(
+            "log.level".to_string(),
+            Value::String(entry.level_name().to_string()),
+        );
+        json_obj.insert("message".to_string(), Value::String(entry.message.clone()));
+        json_obj.insert("ecs.version".to_string(), Value::String(ECS_VERSION.to_string()));
+        json_obj.insert(
+            "process.thread.name".to_string(),
+            Value::String(entry.thread_name.as_ref().unwrap_or(&entry.thread_id).to_string()),
+        );
+
+        if let Some(logger) = entry.target.as_ref().or(entry.module_path.as_ref()) {
+            json_obj.insert("log.logger".to_string(), Value::String(logger.to_string()));
+        }
+        if let Some(ref file) = entry.file {
+            json_obj.insert("log.origin.file.name".to_string(), Value::String(file.to_string()));
+        }
+        if let Some(line) = entry.line {
+            json_obj.insert("log.origin.file.line".to_string(), Value::Number(line.into()));
+        }
+
+        if let Some(ref tracing) = entry.tracing {
+            json_obj.insert("trace.id".to_string(), Value::String(tracing.trace_id.clone()));
+            json_obj.insert("span.id".to_string(), Value::String(tracing.span_id.clone()));
+        }
+
+        if let Some(ref context) = entry.context {
+            let mut labels = serde_json::Map::new();
+            let mut custom = serde_json::Map::new();
+            for (key, value) in context.field_map() {
+                match value {
+                    FieldValue::String(s) => labels.insert(key.clone(), Value::String(s.clone())),
+                    _ => custom.insert(key.clone(), value.to_json_value()),
+                };
+            }
+            if !labels.is_empty() {
+                json_obj.insert("labels".to_string(), Value::Object(labels));
+            }
+            if !custom.is_empty() {
+                json_obj.insert("custom".to_string(), Value::Object(custom));
+            }
+        }
+
+        serde_json::to_string(&Value::Object(json_obj)).unwrap_or_default()
+    }
+
+    /// Format as an OTLP/JSON `LogRecord`
+    fn format_otel(&self, entry: &LogEntry) -> String {
+        use serde_json::{json, Value};
+
+        let mut attributes = Vec::new();
+        let mut push = |key: &str, value: Value| {
+            attributes.push(json!({"key": key, "value": value}));
+        };
+
+        push(
+            "thread.name",
+            json!({"stringValue": entry.thread_name.as_ref().unwrap_or(&entry.thread_id)}),
+        );
+        if let Some(ref file) = entry.file {
+            push("code.filepath", json!({"stringValue": file}));
+        }
+        if let Some(line) = entry.line {
+            push("code.lineno", json!({"intValue": line.to_string()}));
+        }
+        if let Some(ref module_path) = entry.module_path {
+            push("code.namespace", json!({"stringValue": module_path}));
+        }
+        if let Some(ref target) = entry.target {
+            push("target", json!({"stringValue": target}));
+        }
+        if let Some(ref context) = entry.context {
+            for (key, value) in context.field_map() {
+                push(key, Self::otel_any_value(value));
+            }
+        }
+
+        let nanos = entry.timestamp.timestamp_nanos_opt().unwrap_or_default();
+        let mut record = json!({
+            "timeUnixNano": nanos.to_string(),
+            "observedTimeUnixNano": nanos.to_string(),
+            "severityNumber": entry.severity().otel_number(),
+            "severityText": entry.level_name(),
+            "body": {"stringValue": entry.message},
+            "attributes": attributes,
+        });
+        if let (Some(ref tracing), Value::Object(ref mut record)) = (&entry.tracing, &mut record) {
+            record.insert("traceId".to_string(), Value::String(tracing.trace_id.clone()));
+            record.insert("spanId".to_string(), Value::String(tracing.span_id.clone()));
+        }
+
+        serde_json::to_string(&record).unwrap_or_default()
+    }
+
+    /// Convert a field value to an OTLP/JSON `AnyValue`
+    fn otel_any_value(value: &FieldValue) -> serde_json::Value {
+        use serde_json::json;
+
+        match value {
+            FieldValue::String(s) => json!({"stringValue": s}),
+            // OTLP/JSON encodes 64-bit integers as strings
+            FieldValue::Int(i) => json!({"intValue": i.to_string()}),
+            FieldValue::U64(u) => json!({"intValue": u.to_string()}),
+            FieldValue::Float(f) => json!({"doubleValue": f}),
+            FieldValue::Bool(b) => json!({"boolValue": b}),
+            FieldValue::Null => json!({}),
+            FieldValue::Array(items) => json!({
+                "arrayValue": {"values": items.iter().map(Self::otel_any_value).collect::<Vec<_>>()}
+            }),
+            FieldValue::Map(map) => json!({
+                "kvlistValue": {
+                    "values": map
+                        .iter()
+                        .map(|(key, item)| json!({"key": key, "value": Self::otel_any_value(item)}))
+                        .collect::<Vec<_>>()
+                }
+            }),
+            FieldValue::Bytes(_) | FieldValue::Duration(_) | FieldValue::Timestamp(_) => {
+                json!({"stringValue": value.to_string()})
+            }
+        }
+    }
+
     /// Format timestamp for JSON output
     fn format_timestamp_json(
         &self,
@@ -139,6 +465,82 @@ impl OutputFormat {
         }
     }
 
+    /// Format as the multi-line developer layout, styled with `theme` if given
+    pub(crate) fn format_pretty(
+        &self,
+        entry: &LogEntry,
+        timestamp_format: &TimestampFormat,
+        theme: Option<&ConsoleTheme>,
+    ) -> String {
+        let style = |text: String, style: fn(&ConsoleTheme) -> TextStyle| match theme {
+            Some(theme) => style(theme).paint(&text),
+            None => text,
+        };
+
+        let timestamp = style(timestamp_format.format(&entry.timestamp), ConsoleTheme::timestamp);
+        let level = match theme {
+            Some(theme) => theme.level(entry.level).paint(&format!("{:>5}", entry.level_name())),
+            None => format!("{:>5}", entry.level_name()),
+        };
+        let mut output = format!("{} {} ", timestamp, level);
+        if let Some(ref target) = entry.target {
+            output.push_str(&style(format!("{}:", target), |_| TextStyle::new().dimmed()));
+            output.push(' ');
+        }
+        output.push_str(&entry.message);
+
+        if let Some(ref file) = entry.file {
+            let location = match entry.line {
+                Some(line) => format!("at {}:{}", file, line),
+                None => format!("at {}", file),
+            };
+            output.push_str("\n    ");
+            output.push_str(&style(location, |_| TextStyle::new().dimmed()));
+        }
+
+        let mut push_field = |key: &str, value: &FieldValue| {
+            output.push_str("\n    ");
+            output.push_str(&style(format!("{}:", key), ConsoleTheme::context_key));
+            match value {
+                FieldValue::Array(items) if !items.is_empty() => {
+                    for item in items {
+                        Self::push_pretty_block(&mut output, "      - ", &item.to_string());
+                    }
+                }
+                FieldValue::String(s) if s.contains('\n') => {
+                    Self::push_pretty_block(&mut output, "      ", s.trim_end());
+                }
+                _ => {
+                    output.push(' ');
+                    output.push_str(&value.to_string());
+                }
+            }
+        };
+
+        if let Some(ref tracing) = entry.tracing {
+            push_field("trace_id", &FieldValue::String(tracing.trace_id.clone()));
+            push_field("span_id", &FieldValue::String(tracing.span_id.clone()));
+        }
+        if let Some(ref context) = entry.context {
+            for (key, value) in context.field_map() {
+                push_field(key, value);
+            }
+        }
+
+        output
+    }
+
+    /// Append `text` on new lines, the first prefixed with `prefix` and the
+    /// rest indented to match
+    fn push_pretty_block(output: &mut String, prefix: &str, text: &str) {
+        let indent = " ".repeat(prefix.len());
+        for (index, line) in text.lines().enumerate() {
+            output.push('\n');
+            output.push_str(if index == 0 { prefix } else { &indent });
+            output.push_str(line);
+        }
+    }
+
     /// Format as logfmt (key=value pairs)
     fn format_logfmt(&self, entry: &LogEntry, timestamp_format: &TimestampFormat) -> String {
         let mut parts = Vec::new();
@@ -150,7 +552,7 @@ impl OutputFormat {
         ));
 
         // Add level
-        parts.push(format!("level={}", entry.level.to_str()));
+        parts.push(format!("level={}", entry.level_name()));
 
         // Add message (always quoted for safety)
         parts.push(format!("message={}", self.quote_logfmt_value(&entry.message)));
@@ -180,28 +582,64 @@ impl OutputFormat {
                 self.escape_logfmt_value(module_path)
             ));
         }
+        if let Some(ref target) = entry.target {
+            parts.push(format!("target={}", self.escape_logfmt_value(target)));
+        }
+
+        // Add tracing identifiers if present
+        if let Some(ref tracing) = entry.tracing {
+            parts.push(format!("trace_id={}", self.escape_logfmt_value(&tracing.trace_id)));
+            parts.push(format!("span_id={}", self.escape_logfmt_value(&tracing.span_id)));
+            if let Some(ref parent) = tracing.parent_span_id {
+                parts.push(format!("parent_span_id={}", self.escape_logfmt_value(parent)));
+            }
+        }
 
         // Add context fields if present
         if let Some(ref context) = entry.context {
-            for (key, value) in context.fields() {
-                let formatted_value = match value {
-                    super::log_context::FieldValue::String(s) => self.quote_logfmt_value(s),
-                    super::log_context::FieldValue::Int(i) => i.to_string(),
-                    super::log_context::FieldValue::Float(f) => f.to_string(),
-                    super::log_context::FieldValue::Bool(b) => b.to_string(),
-                    super::log_context::FieldValue::Null => "null".to_string(),
-                };
-                parts.push(format!("{}={}", self.escape_logfmt_key(key), formatted_value));
+            for (key, value) in context.field_map() {
+                self.push_logfmt_field(&mut parts, &self.escape_logfmt_key(key), value);
             }
         }
 
         parts.join(" ")
     }
 
+    /// Append a logfmt field, flattening arrays and maps into dotted keys
+    fn push_logfmt_field(&self, parts: &mut Vec<String>, key: &str, value: &FieldValue) {
+        let formatted_value = match value {
+            FieldValue::String(s) => self.quote_logfmt_value(s),
+            FieldValue::Int(i) => i.to_string(),
+            FieldValue::U64(u) => u.to_string(),
+            FieldValue::Float(f) => f.to_string(),
+            FieldValue::Bool(b) => b.to_string(),
+            FieldValue::Null => "null".to_string(),
+            FieldValue::Bytes(_) | FieldValue::Duration(_) | FieldValue::Timestamp(_) => {
+                value.to_string()
+            }
+            FieldValue::Array(items) if items.is_empty() => "[]".to_string(),
+            FieldValue::Map(map) if map.is_empty() => "{}".to_string(),
+            FieldValue::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.push_logfmt_field(parts, &format!("{}.{}", key, index), item);
+                }
+                return;
+            }
+            FieldValue::Map(map) => {
+                for (name, item) in map {
+                    let nested_key = format!("{}.{}", key, self.escape_logfmt_key(name));
+                    self.push_logfmt_field(parts, &nested_key, item);
+                }
+                return;
+            }
+        };
+        parts.push(format!("{}={}", key, formatted_value));
+    }
+
     /// Escape a logfmt key (remove spaces and special chars)
     fn escape_logfmt_key(&self, key: &str) -> String {
         key.chars()
-            .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
+            .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-' || *c == '.')
             .collect()
     }
 
@@ -223,7 +661,102 @@ impl OutputFormat {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{LogContext, LogLevel};
+    use crate::core::{LogContext, LogLevel, TracingContext};
+
+    #[test]
+    fn test_pretty_format() {
+        let context = LogContext::new()
+            .with_field("attempt", 3)
+            .with_field("causes", vec!["connection reset", "broken pipe"])
+            .with_field("backtrace", "0: main\n1: start\n");
+        let entry = LogEntry::new(LogLevel::Warn, "retrying request".to_string())
+            .with_location("src/net.rs", 12, "app::net")
+            .with_context(context);
+
+        let output = OutputFormat::Pretty.format(&entry, &TimestampFormat::Iso8601);
+        let timestamp = TimestampFormat::Iso8601.format(&entry.timestamp);
+        assert_eq!(
+            output,
+            format!(
+                "{}  WARN app::net: retrying request\n    at src/net.rs:12\n    attempt: 3\
+                 \n    causes:\
+                 \n      - connection reset\n      - broken pipe\n    backtrace:\n      0: main\
+                 \n      1: start",
+                timestamp
+            )
+        );
+        assert!(!output.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_otel_json() {
+        let context = LogContext::new()
+            .with_field("user", "alice")
+            .with_field("attempt", 3)
+            .with_field("tags", vec!["a", "b"]);
+        let entry = LogEntry::new(LogLevel::Warn, "retrying".to_string())
+            .with_location("src/net.rs", 12, "app::net")
+            .with_context(context)
+            .with_tracing(TracingContext::new("4bf92f35".to_string(), "00f067aa".to_string()));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&OutputFormat::OtelJson.format(&entry, &TimestampFormat::Unix))
+                .unwrap();
+
+        let nanos = entry.timestamp.timestamp_nanos_opt().unwrap().to_string();
+        assert_eq!(json["timeUnixNano"], nanos.as_str());
+        assert_eq!(json["severityNumber"], 13);
+        assert_eq!(json["severityText"], "WARN");
+        assert_eq!(json["body"]["stringValue"], "retrying");
+        assert_eq!(json["traceId"], "4bf92f35");
+        assert_eq!(json["spanId"], "00f067aa");
+
+        let attribute = |key: &str| {
+            json["attributes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|attr| attr["key"] == key)
+                .map(|attr| attr["value"].clone())
+                .unwrap()
+        };
+        assert_eq!(attribute("code.lineno")["intValue"], "12");
+        assert_eq!(attribute("code.namespace")["stringValue"], "app::net");
+        assert_eq!(attribute("user")["stringValue"], "alice");
+        assert_eq!(attribute("attempt")["intValue"], "3");
+        assert_eq!(attribute("tags")["arrayValue"]["values"][1]["stringValue"], "b");
+    }
+
+    #[test]
+    fn test_ecs_profile() {
+        let context = LogContext::new()
+            .with_field("user", "alice")
+            .with_field("attempt", 3);
+        let entry = LogEntry::new(LogLevel::Warn, "retrying".to_string())
+            .with_location("src/net.rs", 12, "app::net")
+            .with_context(context)
+            .with_tracing(TracingContext::new("trace-1".to_string(), "span-1".to_string()));
+        let config = FormatterConfig::new().with_json_profile(JsonProfile::Ecs);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&OutputFormat::Json.format_with_config(&entry, &config)).unwrap();
+
+        assert!(json["@timestamp"].is_string());
+        assert_eq!(json["log.level"], "WARN");
+        assert_eq!(json["message"], "retrying");
+        assert_eq!(json["ecs.version"], ECS_VERSION);
+        assert!(json["process.thread.name"].is_string());
+        assert_eq!(json["log.logger"], "app::net");
+        assert_eq!(json["log.origin.file.line"], 12);
+        assert_eq!(json["trace.id"], "trace-1");
+        assert_eq!(json["labels"]["user"], "alice");
+        assert_eq!(json["custom"]["attempt"], 3);
+        assert!(json.get("user").is_none());
+
+        // The profile only affects JSON output
+        let text = OutputFormat::Text.format_with_config(&entry, &config);
+        assert_eq!(text, OutputFormat::Text.format(&entry, &config.timestamp_format));
+    }
 
     #[test]
     fn test_text_format() {
@@ -235,6 +768,53 @@ mod tests {
         assert!(result.contains("Test message"));
     }
 
+    #[test]
+    fn test_text_format_with_location() {
+        let entry = LogEntry::new(LogLevel::Info, "Test message".to_string())
+            .with_location("src/main.rs", 7, "app");
+
+        let plain = OutputFormat::Text.format(&entry, &TimestampFormat::Iso8601);
+        assert!(!plain.contains("src/main.rs"));
+
+        let config = FormatterConfig::new().with_include_file_location(true);
+        let result = OutputFormat::Text.format_with_config(&entry, &config);
+        assert!(result.contains(" src/main.rs:7 - Test message"));
+    }
+
+    #[test]
+    fn test_text_format_with_target() {
+        let entry = LogEntry::new(LogLevel::Info, "Test message".to_string())
+            .with_target("hyper")
+            .with_location("src/main.rs", 7, "app");
+        let config = FormatterConfig::new()
+            .with_timestamp_format(TimestampFormat::UnixMillis)
+            .with_include_file_location(true)
+            .with_include_module_path(true);
+
+        let result = OutputFormat::Text.format_with_config(&entry, &config);
+        let expected = format!(
+            "[{}] [INFO ] {} app (hyper) src/main.rs:7 - Test message",
+            entry.timestamp.timestamp_millis(),
+            entry.thread_name.as_ref().unwrap_or(&entry.thread_id),
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_format_into_reuses_buffer() {
+        let entry = LogEntry::new(LogLevel::Warn, "disk low".to_string())
+            .with_location("src/disk.rs", 3, "app::disk")
+            .with_context(LogContext::new().with_field("free_mb", 12));
+        let config = FormatterConfig::new().with_include_file_location(true);
+
+        let mut buffer = String::new();
+        for format in [OutputFormat::Text, OutputFormat::Json, OutputFormat::Logfmt] {
+            buffer.clear();
+            format.format_into(&entry, &config, &mut buffer);
+            assert_eq!(buffer, format.format_with_config(&entry, &config));
+        }
+    }
+
     #[test]
     fn test_text_format_with_context() {
         let context = LogContext::new()
@@ -263,6 +843,7 @@ mod tests {
         assert_eq!(parsed["level"], "ERROR");
         assert_eq!(parsed["message"], "Error occurred");
         assert!(parsed["timestamp"].is_string());
+        assert_eq!(parsed["schema_version"], "1.1");
     }
 
     #[test]
@@ -327,4 +908,59 @@ mod tests {
         let format = OutputFormat::default();
         assert_eq!(format, OutputFormat::Text);
     }
+
+    #[test]
+    fn test_composite_fields() {
+        let mut user = std::collections::BTreeMap::new();
+        user.insert("id", FieldValue::Int(42));
+        user.insert("name", "alice".into());
+        let context = LogContext::new()
+            .with_field("failed_ids", vec![7, 9])
+            .with_field("user", user)
+            .with_field("tags", Vec::<String>::new());
+
+        let entry = LogEntry::new(LogLevel::Warn, "Batch failed".to_string()).with_context(context);
+
+        let logfmt = OutputFormat::Logfmt.format(&entry, &TimestampFormat::Iso8601);
+        assert!(logfmt.contains("failed_ids.0=7 failed_ids.1=9"));
+        assert!(logfmt.contains("user.id=42 user.name=\"alice\""));
+        assert!(logfmt.contains("tags=[]"));
+
+        let json = OutputFormat::Json.format(&entry, &TimestampFormat::Iso8601);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["failed_ids"], serde_json::json!([7, 9]));
+        assert_eq!(parsed["user"]["name"], "alice");
+    }
+
+    #[test]
+    fn test_logfmt_fields_are_deterministic() {
+        let context = LogContext::new()
+            .with_field("user", "alice")
+            .with_field("action", "login")
+            .with_field("attempt", 2);
+        let entry = LogEntry::new(LogLevel::Info, "Login".to_string()).with_context(context);
+
+        let result = OutputFormat::Logfmt.format(&entry, &TimestampFormat::Iso8601);
+        assert!(result.ends_with("user=\"alice\" action=\"login\" attempt=2"));
+
+        let text = OutputFormat::Text.format(&entry, &TimestampFormat::Iso8601);
+        assert!(text.ends_with("Login user=alice action=login attempt=2"));
+    }
+
+    #[test]
+    fn test_tracing_ids() {
+        let entry = LogEntry::new(LogLevel::Info, "traced".to_string()).with_tracing(
+            TracingContext::new("4bf92f3577b34da6".to_string(), "00f067aa0ba902b7".to_string())
+                .with_parent("a3ce929d0e0e4736".to_string()),
+        );
+
+        let json = OutputFormat::Json.format(&entry, &TimestampFormat::Iso8601);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["trace_id"], "4bf92f3577b34da6");
+        assert_eq!(parsed["span_id"], "00f067aa0ba902b7");
+        assert_eq!(parsed["parent_span_id"], "a3ce929d0e0e4736");
+
+        let logfmt = OutputFormat::Logfmt.format(&entry, &TimestampFormat::Iso8601);
+        assert!(logfmt.contains("trace_id=4bf92f3577b34da6 span_id=00f067aa0ba902b7"));
+    }
 }
@@ -52,7 +52,7 @@ pub enum OverflowPolicy {
     /// Good balance between preservation and responsiveness.
     BlockWithTimeout(Duration),
 
-    /// Drop but alert via callback and stderr
+    /// Drop but alert via callback and the diagnostics handler
     ///
     /// This is the recommended default. Logs are dropped when queue is full,
     /// but operators are alerted so they can take action.
@@ -126,6 +126,7 @@ pub type OverflowCallback = Arc<dyn Fn(u64) + Send + Sync>;
 /// };
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PriorityConfig {
     /// Whether to preserve critical logs (Error, Fatal) - never drop them
     ///
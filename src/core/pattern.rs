@@ -0,0 +1,402 @@
+//! log4j-style pattern layouts
+//!
+//! A [`PatternLayout`] is parsed once from a conversion pattern such as
+//! `%d{ISO8601} %-5p [%t] %c - %m%n` and then renders entries without
+//! re-parsing. Use it through [`OutputFormat::Pattern`].
+//!
+//! # Conversions
+//!
+//! | Conversion | Output |
+//! |------------|--------|
+//! | `%d`, `%date` | Timestamp in the appender's [`TimestampFormat`] |
+//! | `%d{ISO8601}` | `2025-01-08T10:30:45,123` |
+//! | `%d{ABSOLUTE}` | `10:30:45,123` |
+//! | `%d{DATE}` | `08 Jan 2025 10:30:45,123` |
+//! | `%d{<strftime>}` | Timestamp in a custom strftime format, e.g. `%d{%H:%M}` |
+//! | `%p`, `%level` | Level |
+//! | `%t`, `%thread` | Thread name, or thread ID for unnamed threads |
+//! | `%c`, `%logger` | Target (by default the module path), `root` if unknown |
+//! | `%c{n}` | Last `n` segments of the target |
+//! | `%m`, `%msg`, `%message` | Message |
+//! | `%X{key}`, `%mdc{key}` | Context field `key` (empty if missing) |
+//! | `%X` | All context fields as `key=value` pairs |
+//! | `%F`, `%file` / `%L`, `%line` / `%l`, `%location` | File / line / `file:line` |
+//! | `%n` | Newline |
+//! | `%%` | Literal `%` |
+//!
+//! Conversions accept log4j format modifiers between `%` and the name:
+//! `-` left-aligns, a number sets the minimum width, and `.n` keeps at most
+//! the last `n` characters (e.g. `%-5p`, `%10.10c`).
+//!
+//! Appenders terminate every entry with a newline, so a trailing `%n` is
+//! ignored.
+//!
+//! Timestamps are rendered in UTC.
+//!
+//! [`OutputFormat::Pattern`]: super::OutputFormat::Pattern
+
+use super::error::{LoggerError, Result};
+use super::log_entry::LogEntry;
+use super::timestamp::TimestampFormat;
+use chrono::format::{Item, StrftimeItems};
+use std::fmt::Write;
+
+/// Conversion names, longest first so that prefixes match greedily
+const CONVERSION_NAMES: &[&str] = &[
+    "location", "message", "logger", "thread", "level", "date", "file", "line", "msg", "mdc", "c",
+    "d", "F", "l", "L", "m", "n", "p", "t", "X",
+];
+
+/// A parsed conversion pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternLayout {
+    pattern: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Conversion(Conversion, Modifier),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Conversion {
+    /// Timestamp; `None` uses the appender's timestamp format
+    Date(Option<String>),
+    Level,
+    Thread,
+    Category(Option<usize>),
+    Message,
+    Newline,
+    Field(String),
+    AllFields,
+    File,
+    Line,
+    Location,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Modifier {
+    left_align: bool,
+    min_width: usize,
+    max_width: Option<usize>,
+}
+
+impl PatternLayout {
+    /// Parse a conversion pattern
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unknown conversions, unterminated `{` options,
+    /// invalid `%c{n}` precisions and invalid strftime date formats.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::core::PatternLayout;
+    ///
+    /// assert!(PatternLayout::parse("%d{ISO8601} %-5p [%t] %c - %m%n").is_ok());
+    /// assert!(PatternLayout::parse("%q").is_err());
+    /// ```
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut rest = pattern;
+
+        while let Some(pos) = rest.find('%') {
+            literal.push_str(&rest[..pos]);
+            rest = &rest[pos + 1..];
+
+            if let Some(after) = rest.strip_prefix('%') {
+                literal.push('%');
+                rest = after;
+                continue;
+            }
+
+            let (modifier, after) = parse_modifier(rest);
+            let Some(name) = CONVERSION_NAMES.iter().find(|name| after.starts_with(**name)) else {
+                return Err(invalid(pattern, format!("unknown conversion '%{}'", after)));
+            };
+            rest = &after[name.len()..];
+
+            let mut option = None;
+            if let Some(after) = rest.strip_prefix('{') {
+                let Some(end) = after.find('}') else {
+                    return Err(invalid(pattern, format!("unterminated option for '%{}'", name)));
+                };
+                option = Some(after[..end].to_string());
+                rest = &after[end + 1..];
+            }
+
+            if !literal.is_empty() {
+                segments.push(Segment::Literal(std::mem::take(&mut literal)));
+            }
+            let conversion = conversion_for(pattern, name, option)?;
+            segments.push(Segment::Conversion(conversion, modifier));
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        if matches!(segments.last(), Some(Segment::Conversion(Conversion::Newline, _))) {
+            segments.pop();
+        }
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            segments,
+        })
+    }
+
+    /// Get the source pattern
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Render an entry
+    ///
+    /// `timestamp_format` is used for `%d` without an option.
+    pub fn format(&self, entry: &LogEntry, timestamp_format: &TimestampFormat) -> String {
+        let mut output = String::new();
+        self.format_into(entry, timestamp_format, &mut output);
+        output
+    }
+
+    /// Render an entry, appending to `output`
+    pub fn format_into(
+        &self,
+        entry: &LogEntry,
+        timestamp_format: &TimestampFormat,
+        output: &mut String,
+    ) {
+        let mut value = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => output.push_str(text),
+                Segment::Conversion(conversion, modifier) => {
+                    value.clear();
+                    render(conversion, entry, timestamp_format, &mut value);
+                    modifier.apply(&value, output);
+                }
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for PatternLayout {
+    type Err = LoggerError;
+
+    fn from_str(pattern: &str) -> Result<Self> {
+        Self::parse(pattern)
+    }
+}
+
+fn invalid(pattern: &str, message: String) -> LoggerError {
+    LoggerError::formatter("pattern", format!("{} in '{}'", message, pattern))
+}
+
+/// Parse `-`, minimum width and `.max` width
+fn parse_modifier(input: &str) -> (Modifier, &str) {
+    let mut modifier = Modifier::default();
+    let mut rest = input;
+
+    if let Some(after) = rest.strip_prefix('-') {
+        modifier.left_align = true;
+        rest = after;
+    }
+
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    modifier.min_width = rest[..digits].parse().unwrap_or(0);
+    rest = &rest[digits..];
+
+    if let Some(after) = rest.strip_prefix('.') {
+        let digits = after.len() - after.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        modifier.max_width = after[..digits].parse().ok();
+        rest = &after[digits..];
+    }
+
+    (modifier, rest)
+}
+
+fn conversion_for(pattern: &str, name: &str, option: Option<String>) -> Result<Conversion> {
+    Ok(match name {
+        "d" | "date" => Conversion::Date(match option.as_deref() {
+            None => None,
+            Some("ISO8601") => Some("%Y-%m-%dT%H:%M:%S,%3f".to_string()),
+            Some("ABSOLUTE") => Some("%H:%M:%S,%3f".to_string()),
+            Some("DATE") => Some("%d %b %Y %H:%M:%S,%3f".to_string()),
+            Some(custom) => {
+                if StrftimeItems::new(custom).any(|item| matches!(item, Item::Error)) {
+                    return Err(invalid(pattern, format!("invalid date format '{}'", custom)));
+                }
+                Some(custom.to_string())
+            }
+        }),
+        "p" | "level" => Conversion::Level,
+        "t" | "thread" => Conversion::Thread,
+        "c" | "logger" => Conversion::Category(match option {
+            None => None,
+            Some(precision) => match precision.parse() {
+                Ok(n) if n > 0 => Some(n),
+                _ => return Err(invalid(pattern, format!("invalid precision '{}'", precision))),
+            },
+        }),
+        "m" | "msg" | "message" => Conversion::Message,
+        "n" => Conversion::Newline,
+        "X" | "mdc" => option.map_or(Conversion::AllFields, Conversion::Field),
+        "F" | "file" => Conversion::File,
+        "L" | "line" => Conversion::Line,
+        "l" | "location" => Conversion::Location,
+        _ => unreachable!("conversion names are listed in CONVERSION_NAMES"),
+    })
+}
+
+fn render(
+    conversion: &Conversion,
+    entry: &LogEntry,
+    timestamp_format: &TimestampFormat,
+    out: &mut String,
+) {
+    match conversion {
+        Conversion::Date(None) => out.push_str(&timestamp_format.format(&entry.timestamp)),
+        Conversion::Date(Some(format)) => {
+            let _ = write!(out, "{}", entry.timestamp.format(format));
+        }
+        Conversion::Level => out.push_str(entry.level_name()),
+        Conversion::Thread => {
+            out.push_str(entry.thread_name.as_deref().unwrap_or(&entry.thread_id));
+        }
+        Conversion::Category(precision) => {
+            let category = entry
+                .target
+                .as_deref()
+                .or(entry.module_path.as_deref())
+                .unwrap_or("root");
+            match precision {
+                Some(n) => {
+                    let segments: Vec<&str> = category.split("::").collect();
+                    out.push_str(&segments[segments.len().saturating_sub(*n)..].join("::"));
+                }
+                None => out.push_str(category),
+            }
+        }
+        Conversion::Message => out.push_str(&entry.message),
+        Conversion::Newline => out.push('\n'),
+        Conversion::Field(key) => {
+            if let Some(value) = entry.context.as_ref().and_then(|ctx| ctx.field_map().get(key)) {
+                let _ = write!(out, "{}", value);
+            }
+        }
+        Conversion::AllFields => {
+            if let Some(ref context) = entry.context {
+                out.push_str(&context.format_fields());
+            }
+        }
+        Conversion::File => out.push_str(entry.file.as_deref().unwrap_or("?")),
+        Conversion::Line => match entry.line {
+            Some(line) => {
+                let _ = write!(out, "{}", line);
+            }
+            None => out.push('?'),
+        },
+        Conversion::Location => {
+            let _ = write!(
+                out,
+                "{}:{}",
+                entry.file.as_deref().unwrap_or("?"),
+                entry.line.map_or_else(|| "?".to_string(), |line| line.to_string())
+            );
+        }
+    }
+}
+
+impl Modifier {
+    /// Append `value` to `out`, truncated and padded as configured
+    fn apply(&self, value: &str, out: &mut String) {
+        let mut value = value;
+        let mut len = value.chars().count();
+        if let Some(max) = self.max_width {
+            if len > max {
+                // log4j truncates from the beginning
+                let skip = value.char_indices().nth(len - max).map_or(0, |(i, _)| i);
+                value = &value[skip..];
+                len = max;
+            }
+        }
+
+        let padding = self.min_width.saturating_sub(len);
+        if self.left_align {
+            out.push_str(value);
+            out.extend(std::iter::repeat_n(' ', padding));
+        } else {
+            out.extend(std::iter::repeat_n(' ', padding));
+            out.push_str(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{LogContext, LogLevel};
+    use chrono::TimeZone;
+
+    fn entry() -> LogEntry {
+        let mut entry = LogEntry::new(LogLevel::Warn, "disk almost full".to_string())
+            .with_location("src/disk.rs", 42, "my_app::storage::disk")
+            .with_context(LogContext::new().with_field("mount", "/var").with_field("used", 93));
+        entry.timestamp = chrono::Utc.with_ymd_and_hms(2025, 1, 8, 10, 30, 45).unwrap()
+            + chrono::Duration::milliseconds(123);
+        entry.thread_name = Some("worker-1".into());
+        entry
+    }
+
+    fn render(pattern: &str) -> String {
+        PatternLayout::parse(pattern)
+            .unwrap()
+            .format(&entry(), &TimestampFormat::Iso8601)
+    }
+
+    #[test]
+    fn test_log4j_layout() {
+        assert_eq!(
+            render("%d{ISO8601} %-5p [%t] %c - %m%n"),
+            "2025-01-08T10:30:45,123 WARN  [worker-1] my_app::storage::disk - disk almost full"
+        );
+    }
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(render("%d"), "2025-01-08T10:30:45.123Z");
+        assert_eq!(render("%d{ABSOLUTE}"), "10:30:45,123");
+        assert_eq!(render("%d{DATE}"), "08 Jan 2025 10:30:45,123");
+        assert_eq!(render("%date{%Y/%m/%d}"), "2025/01/08");
+    }
+
+    #[test]
+    fn test_fields_and_location() {
+        assert_eq!(render("%X{mount} %X{used} [%X{missing}]"), "/var 93 []");
+        assert_eq!(render("%X"), "mount=/var used=93");
+        assert_eq!(render("%F:%L %l"), "src/disk.rs:42 src/disk.rs:42");
+        assert_eq!(render("%c{2}|%logger{1}"), "storage::disk|disk");
+    }
+
+    #[test]
+    fn test_modifiers_and_literals() {
+        assert_eq!(render("[%6p]"), "[  WARN]");
+        assert_eq!(render("[%-6level]"), "[WARN  ]");
+        assert_eq!(render("[%.4m]"), "[full]");
+        assert_eq!(render("100%% %msg%nnext"), "100% disk almost full\nnext");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(PatternLayout::parse("%q").is_err());
+        assert!(PatternLayout::parse("%X{unterminated").is_err());
+        assert!(PatternLayout::parse("%c{zero}").is_err());
+        assert!(PatternLayout::parse("%d{%Q}").is_err());
+        assert!(PatternLayout::parse("%").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Ready-made logger configurations
+//!
+//! Each preset is available as a finished [`Logger`] and as a
+//! [`LoggerBuilder`] that can be adjusted before building:
+//!
+//! | Preset | Output | Level | Mode |
+//! |--------|--------|-------|------|
+//! | [`dev`](Logger::dev) | pretty console | Debug | synchronous |
+//! | [`production`](Logger::production) | rotating JSON file | Info | async, sampled |
+//! | [`json_service`](Logger::json_service) | JSON lines on stdout | Info | async |
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//!
+//! let logger = Logger::dev();
+//! logger.debug("Visible in development");
+//!
+//! let logger = LoggerBuilder::json_service("checkout")
+//!     .global_field("region", "eu-west-1")
+//!     .build();
+//! ```
+
+use super::error::Result;
+use super::formatter::OutputFormatter;
+use super::log_level::LogLevel;
+use super::logger::{Logger, LoggerBuilder};
+use super::output_format::OutputFormat;
+use super::overflow_policy::PriorityConfig;
+use super::sampling::SamplingConfig;
+use crate::appenders::{ConsoleAppender, RotatingFileAppender, RotationPolicy, RotationStrategy};
+use std::path::Path;
+use std::time::Duration;
+
+/// Queue size of the async presets
+const PRESET_QUEUE_SIZE: usize = 10_000;
+
+/// Size at which the production log file is rotated (100 MiB)
+const PRODUCTION_MAX_FILE_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Rotated production log files kept
+const PRODUCTION_MAX_BACKUPS: usize = 14;
+
+/// Messages per second above which the production preset starts sampling
+const PRODUCTION_SAMPLING_THRESHOLD: usize = 10_000;
+
+impl LoggerBuilder {
+    /// Builder for local development
+    ///
+    /// Debug level and a synchronous console appender using the multi-line
+    /// [`OutputFormat::Pretty`] layout, so entries appear immediately.
+    #[must_use]
+    pub fn dev() -> Self {
+        Logger::builder()
+            .min_level(LogLevel::Debug)
+            .appender(ConsoleAppender::new().with_output_format(OutputFormat::Pretty))
+    }
+
+    /// Builder for a production service writing to `path`
+    ///
+    /// - one JSON object per line in a file rotated daily or at 100 MiB,
+    ///   keeping 14 compressed backups
+    /// - Info level, async mode with a queue of 10,000 entries
+    /// - Error and Fatal entries are never dropped and Warn entries are
+    ///   retried when the queue is full ([`PriorityConfig::default`])
+    /// - adaptive sampling of Trace to Info above 10,000 messages per second,
+    ///   down to a rate of 0.1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log file cannot be created.
+    pub fn production(path: impl AsRef<Path>) -> Result<Self> {
+        let policy = RotationPolicy::new()
+            .with_strategy(RotationStrategy::hybrid(
+                PRODUCTION_MAX_FILE_SIZE,
+                Duration::from_secs(24 * 60 * 60),
+            ))
+            .with_max_backups(PRODUCTION_MAX_BACKUPS)
+            .with_compression(true);
+        let file = RotatingFileAppender::with_policy(path, policy)?
+            .with_formatter(Box::new(OutputFormatter::new(OutputFormat::Json)));
+
+        Ok(Logger::builder()
+            .min_level(LogLevel::Info)
+            .appender(file)
+            .async_mode(PRESET_QUEUE_SIZE)
+            .priority_config(PriorityConfig::default())
+            .with_sampling(
+                SamplingConfig::new(1.0)
+                    .with_always_sample(vec![LogLevel::Warn, LogLevel::Error, LogLevel::Fatal])
+                    .with_adaptive(PRODUCTION_SAMPLING_THRESHOLD, 0.1),
+            ))
+    }
+
+    /// Builder for a containerized service logging JSON to stdout
+    ///
+    /// Info level, async mode, and [`ConsoleAppender::json`] so log
+    /// collectors see one JSON object per line. Every entry carries the
+    /// `service` field.
+    #[must_use]
+    pub fn json_service(service_name: impl Into<String>) -> Self {
+        Logger::builder()
+            .min_level(LogLevel::Info)
+            .appender(ConsoleAppender::json())
+            .async_mode(PRESET_QUEUE_SIZE)
+            .global_field("service", service_name.into())
+    }
+}
+
+impl Logger {
+    /// Logger for local development
+    ///
+    /// See [`LoggerBuilder::dev`].
+    #[must_use]
+    pub fn dev() -> Self {
+        LoggerBuilder::dev().build()
+    }
+
+    /// Logger for a production service writing to `path`
+    ///
+    /// See [`LoggerBuilder::production`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log file cannot be created.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_logger_system::Logger;
+    ///
+    /// let logger = Logger::production("/var/log/app/app.log")?;
+    /// logger.info("Service started");
+    /// # Ok::<(), rust_logger_system::LoggerError>(())
+    /// ```
+    pub fn production(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(LoggerBuilder::production(path)?.build())
+    }
+
+    /// Logger for a containerized service logging JSON to stdout
+    ///
+    /// See [`LoggerBuilder::json_service`].
+    #[must_use]
+    pub fn json_service(service_name: impl Into<String>) -> Self {
+        LoggerBuilder::json_service(service_name).build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::FieldValue;
+
+    #[test]
+    fn test_presets() {
+        let dev = LoggerBuilder::dev();
+        assert!(dev.validate().is_ok());
+        let dev = dev.build();
+        assert_eq!(dev.effective_min_level(), LogLevel::Debug);
+        assert_eq!(dev.list_appenders(), ["console"]);
+
+        let service = Logger::json_service("checkout");
+        assert_eq!(service.effective_min_level(), LogLevel::Info);
+        assert_eq!(service.context().get_fields()["service"], FieldValue::from("checkout"));
+    }
+
+    #[test]
+    fn test_production_writes_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("app.log");
+        let builder = LoggerBuilder::production(&path).unwrap();
+        assert!(builder.validate().is_ok());
+        let logger = builder.build();
+        assert!(logger.sampler().unwrap().config().adaptive);
+
+        logger.info("started");
+        logger.debug("not at info");
+        assert!(logger.drain(Duration::from_secs(5)));
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = written.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let value: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(value["message"], "started");
+    }
+}
@@ -0,0 +1,431 @@
+//! Queue between the logging threads and the async worker
+//!
+//! By default entries travel through a bounded crossbeam channel. For
+//! extreme throughput, [`AsyncQueue::LockFree`] replaces it with a bounded
+//! ring in which producers claim slots with a single compare-and-swap and
+//! never take a lock, and the worker only parks once the ring is empty.
+//!
+//! Both queues hold the same number of entries and report a full queue the
+//! same way, so the [`OverflowPolicy`](super::OverflowPolicy) behaves
+//! identically. The ring trades the channel's blocking primitives for
+//! spinning: blocking sends under [`OverflowPolicy::Block`] back off with
+//! short sleeps, and the worker spins briefly before parking.
+//!
+//! [`OverflowPolicy::Block`]: super::OverflowPolicy::Block
+
+use super::log_entry::LogEntry;
+use super::sync::channel;
+use crossbeam_channel::{
+    RecvTimeoutError, SendError, SendTimeoutError, TryRecvError, TrySendError,
+};
+use crossbeam_utils::{Backoff, CachePadded};
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+/// Queue implementation of an async logger
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::prelude::*;
+///
+/// let logger = Logger::builder()
+///     .async_mode(65_536)
+///     .async_queue(AsyncQueue::LockFree)
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AsyncQueue {
+    /// Bounded crossbeam channel
+    #[default]
+    Channel,
+
+    /// Bounded lock-free multi-producer ring
+    LockFree,
+}
+
+/// Longest sleep between attempts of a blocking send to a full ring
+const MAX_SEND_SLEEP: Duration = Duration::from_micros(100);
+
+/// Create a queue of `kind` holding up to `capacity` entries
+pub(crate) fn bounded(kind: AsyncQueue, capacity: usize) -> (QueueSender, QueueReceiver) {
+    match kind {
+        // The ring parks threads on its own, which loom cannot model
+        AsyncQueue::LockFree if cfg!(not(loom)) => {
+            let ring = Arc::new(Ring::new(capacity));
+            (QueueSender::Ring(Arc::clone(&ring)), QueueReceiver::Ring(ring))
+        }
+        _ => {
+            let (sender, receiver) = channel::bounded(capacity);
+            (QueueSender::Channel(sender), QueueReceiver::Channel(receiver))
+        }
+    }
+}
+
+/// Sending half, shared by the logging threads
+pub(crate) enum QueueSender {
+    Channel(channel::Sender<LogEntry>),
+    Ring(Arc<Ring>),
+}
+
+impl QueueSender {
+    // The send methods return the error, holding the entry, if it was not
+    // queued
+
+    pub(crate) fn try_send(&self, entry: LogEntry) -> Option<TrySendError<LogEntry>> {
+        match self {
+            Self::Channel(sender) => sender.try_send(entry).err(),
+            Self::Ring(ring) => ring.try_push(entry),
+        }
+    }
+
+    /// Wait until there is room for `entry`
+    pub(crate) fn send(&self, entry: LogEntry) -> Option<SendError<LogEntry>> {
+        match self {
+            Self::Channel(sender) => sender.send(entry).err(),
+            Self::Ring(ring) => ring.push_until(entry, None).map(|e| SendError(e.into_inner())),
+        }
+    }
+
+    /// Wait up to `timeout` for room for `entry`
+    pub(crate) fn send_timeout(
+        &self,
+        entry: LogEntry,
+        timeout: Duration,
+    ) -> Option<SendTimeoutError<LogEntry>> {
+        match self {
+            Self::Channel(sender) => sender.send_timeout(entry, timeout).err(),
+            Self::Ring(ring) => ring.push_until(entry, Some(Instant::now() + timeout)),
+        }
+    }
+}
+
+impl Drop for QueueSender {
+    fn drop(&mut self) {
+        if let Self::Ring(ring) = self {
+            ring.senders_gone.store(true, Ordering::SeqCst);
+            ring.wake_receiver();
+        }
+    }
+}
+
+/// Receiving half, owned by the worker
+pub(crate) enum QueueReceiver {
+    Channel(channel::Receiver<LogEntry>),
+    Ring(Arc<Ring>),
+}
+
+impl QueueReceiver {
+    pub(crate) fn try_recv(&self) -> Result<LogEntry, TryRecvError> {
+        match self {
+            Self::Channel(receiver) => receiver.try_recv(),
+            Self::Ring(ring) => ring.pop(),
+        }
+    }
+
+    /// Wait for the next entry; fails once the queue is empty and the
+    /// sender is gone
+    pub(crate) fn recv(&self) -> Result<LogEntry, RecvTimeoutError> {
+        match self {
+            Self::Channel(receiver) => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            Self::Ring(ring) => ring.pop_until(None),
+        }
+    }
+
+    pub(crate) fn recv_timeout(&self, timeout: Duration) -> Result<LogEntry, RecvTimeoutError> {
+        match self {
+            Self::Channel(receiver) => receiver.recv_timeout(timeout),
+            Self::Ring(ring) => ring.pop_until(Some(Instant::now() + timeout)),
+        }
+    }
+}
+
+impl Drop for QueueReceiver {
+    fn drop(&mut self) {
+        if let Self::Ring(ring) = self {
+            ring.receiver_gone.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Slot of the ring
+///
+/// `stamp` tells producers and the consumer whose turn it is: a slot at
+/// position `p` can be written when its stamp is `p` and read when it is
+/// `p + 1`; reading sets it to `p + one_lap`, the next lap's write turn.
+/// Positions carry the lap above the slot index, so the two turns stay
+/// distinct even with a single slot.
+struct Slot {
+    stamp: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<LogEntry>>,
+}
+
+/// Bounded multi-producer queue after Dmitry Vyukov's array queue
+pub(crate) struct Ring {
+    slots: Box<[Slot]>,
+    /// Position increment of one full lap, a power of two above the
+    /// capacity; the bits below it index the slot
+    one_lap: usize,
+    /// Next position to write
+    tail: CachePadded<AtomicUsize>,
+    /// Next position to read; only the worker moves it
+    head: CachePadded<AtomicUsize>,
+    /// Worker parked on an empty ring
+    receiver: OnceLock<Thread>,
+    receiver_parked: AtomicBool,
+    senders_gone: AtomicBool,
+    receiver_gone: AtomicBool,
+}
+
+// Values are only accessed by the thread that won the slot's turn
+unsafe impl Send for Ring {}
+unsafe impl Sync for Ring {}
+
+impl Ring {
+    fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let slots = (0..capacity)
+            .map(|position| Slot {
+                stamp: AtomicUsize::new(position),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            })
+            .collect();
+        Self {
+            slots,
+            one_lap: (capacity + 1).next_power_of_two(),
+            tail: CachePadded::new(AtomicUsize::new(0)),
+            head: CachePadded::new(AtomicUsize::new(0)),
+            receiver: OnceLock::new(),
+            receiver_parked: AtomicBool::new(false),
+            senders_gone: AtomicBool::new(false),
+            receiver_gone: AtomicBool::new(false),
+        }
+    }
+
+    fn try_push(&self, entry: LogEntry) -> Option<TrySendError<LogEntry>> {
+        if self.receiver_gone.load(Ordering::Relaxed) {
+            return Some(TrySendError::Disconnected(entry));
+        }
+        let backoff = Backoff::new();
+        let mut position = self.tail.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[position & (self.one_lap - 1)];
+            let stamp = slot.stamp.load(Ordering::Acquire);
+            if stamp == position {
+                match self.tail.compare_exchange_weak(
+                    position,
+                    self.next_position(position),
+                    Ordering::SeqCst,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        unsafe { (*slot.value.get()).write(entry) };
+                        slot.stamp.store(position.wrapping_add(1), Ordering::Release);
+                        self.wake_receiver();
+                        return None;
+                    }
+                    Err(current) => {
+                        position = current;
+                        backoff.spin();
+                    }
+                }
+            } else if stamp.wrapping_add(self.one_lap) == position.wrapping_add(1) {
+                // The slot still holds the entry of the previous lap
+                return Some(TrySendError::Full(entry));
+            } else {
+                // Another producer claimed this position
+                backoff.spin();
+                position = self.tail.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Push, retrying while the ring is full until `deadline`
+    fn push_until(
+        &self,
+        mut entry: LogEntry,
+        deadline: Option<Instant>,
+    ) -> Option<SendTimeoutError<LogEntry>> {
+        let mut sleep = Duration::from_micros(1);
+        loop {
+            match self.try_push(entry) {
+                None => return None,
+                Some(TrySendError::Disconnected(unsent)) => {
+                    return Some(SendTimeoutError::Disconnected(unsent));
+                }
+                Some(TrySendError::Full(unsent)) => entry = unsent,
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Some(SendTimeoutError::Timeout(entry));
+            }
+            thread::sleep(sleep);
+            sleep = (sleep * 2).min(MAX_SEND_SLEEP);
+        }
+    }
+
+    fn pop(&self) -> Result<LogEntry, TryRecvError> {
+        let position = self.head.load(Ordering::Relaxed);
+        let slot = &self.slots[position & (self.one_lap - 1)];
+        if slot.stamp.load(Ordering::Acquire) != position.wrapping_add(1) {
+            return Err(if self.senders_gone.load(Ordering::SeqCst) && self.is_empty() {
+                TryRecvError::Disconnected
+            } else {
+                TryRecvError::Empty
+            });
+        }
+        let entry = unsafe { (*slot.value.get()).assume_init_read() };
+        slot.stamp.store(position.wrapping_add(self.one_lap), Ordering::Release);
+        self.head.store(self.next_position(position), Ordering::Release);
+        Ok(entry)
+    }
+
+    /// Position after `position`, moving to the next lap after the last slot
+    fn next_position(&self, position: usize) -> usize {
+        let index = position & (self.one_lap - 1);
+        if index + 1 < self.slots.len() {
+            position.wrapping_add(1)
+        } else {
+            (position & !(self.one_lap - 1)).wrapping_add(self.one_lap)
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tail.load(Ordering::SeqCst) == self.head.load(Ordering::Relaxed)
+    }
+
+    /// Pop, parking while the ring is empty until `deadline`
+    fn pop_until(&self, deadline: Option<Instant>) -> Result<LogEntry, RecvTimeoutError> {
+        let backoff = Backoff::new();
+        loop {
+            match self.pop() {
+                Ok(entry) => return Ok(entry),
+                Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+                Err(TryRecvError::Empty) => {}
+            }
+            if !backoff.is_completed() {
+                backoff.snooze();
+                continue;
+            }
+            let timeout = match deadline {
+                Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(timeout) if !timeout.is_zero() => Some(timeout),
+                    _ => return Err(RecvTimeoutError::Timeout),
+                },
+                None => None,
+            };
+
+            self.receiver.get_or_init(thread::current);
+            self.receiver_parked.store(true, Ordering::SeqCst);
+            // A producer that wrote before seeing the flag left an entry
+            if self.is_empty() && !self.senders_gone.load(Ordering::SeqCst) {
+                match timeout {
+                    Some(timeout) => thread::park_timeout(timeout),
+                    None => thread::park(),
+                }
+            }
+            self.receiver_parked.store(false, Ordering::SeqCst);
+        }
+    }
+
+    fn wake_receiver(&self) {
+        if self.receiver_parked.swap(false, Ordering::SeqCst) {
+            if let Some(receiver) = self.receiver.get() {
+                receiver.unpark();
+            }
+        }
+    }
+}
+
+impl Drop for Ring {
+    fn drop(&mut self) {
+        while self.pop().is_ok() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::LogLevel;
+
+    fn entry(message: &str) -> LogEntry {
+        LogEntry::new(LogLevel::Info, message.to_string())
+    }
+
+    #[test]
+    fn test_ring_reports_full_and_disconnected() {
+        let (sender, receiver) = bounded(AsyncQueue::LockFree, 2);
+        assert!(sender.try_send(entry("one")).is_none());
+        assert!(sender.try_send(entry("two")).is_none());
+        assert!(matches!(sender.try_send(entry("three")), Some(TrySendError::Full(_))));
+        assert!(matches!(
+            sender.send_timeout(entry("three"), Duration::from_millis(5)),
+            Some(SendTimeoutError::Timeout(_))
+        ));
+
+        assert_eq!(receiver.try_recv().unwrap().message, "one");
+        assert!(sender.try_send(entry("three")).is_none());
+        drop(sender);
+        assert_eq!(receiver.recv().unwrap().message, "two");
+        assert_eq!(receiver.recv().unwrap().message, "three");
+        assert_eq!(receiver.recv().unwrap_err(), RecvTimeoutError::Disconnected);
+    }
+
+    #[test]
+    fn test_ring_with_single_slot() {
+        let (sender, receiver) = bounded(AsyncQueue::LockFree, 1);
+        for round in 0..3 {
+            let message = format!("entry {}", round);
+            assert!(sender.try_send(entry(&message)).is_none());
+            assert!(matches!(sender.try_send(entry("extra")), Some(TrySendError::Full(_))));
+            assert_eq!(receiver.try_recv().unwrap().message, message);
+            assert_eq!(receiver.try_recv().unwrap_err(), TryRecvError::Empty);
+        }
+        drop(sender);
+        assert_eq!(receiver.recv().unwrap_err(), RecvTimeoutError::Disconnected);
+    }
+
+    #[test]
+    fn test_ring_delivers_from_many_producers() {
+        const PRODUCERS: usize = 4;
+        const PER_PRODUCER: usize = 2_000;
+
+        let (sender, receiver) = bounded(AsyncQueue::LockFree, 64);
+        let consumer = thread::spawn(move || {
+            let mut received = Vec::new();
+            while let Ok(entry) = receiver.recv() {
+                received.push(entry.message);
+            }
+            received
+        });
+        let sender = Arc::new(sender);
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|producer| {
+                let sender = Arc::clone(&sender);
+                thread::spawn(move || {
+                    for i in 0..PER_PRODUCER {
+                        assert!(sender.send(entry(&format!("{}-{}", producer, i))).is_none());
+                    }
+                })
+            })
+            .collect();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        drop(sender);
+
+        let received = consumer.join().unwrap();
+        assert_eq!(received.len(), PRODUCERS * PER_PRODUCER);
+        for producer in 0..PRODUCERS {
+            let order: Vec<usize> = received
+                .iter()
+                .filter_map(|message| message.strip_prefix(&format!("{}-", producer)))
+                .map(|i| i.parse().unwrap())
+                .collect();
+            assert_eq!(order, (0..PER_PRODUCER).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,672 @@
+//! Reading log files back into entries
+//!
+//! [`LogReader`] parses the output of [`OutputFormat::Text`],
+//! [`OutputFormat::Json`] (one object per line, also the form written by
+//! [`StructuredLogEntry::to_json`]) and [`OutputFormat::Logfmt`] line by line,
+//! so analysis tools and tests can work with entries instead of strings.
+//!
+//! Lines that do not parse are skipped and counted; see
+//! [`LogReader::skipped`]. A [`strict`](LogReader::strict) reader returns
+//! them as errors instead.
+//!
+//! Some information does not survive every format:
+//! - Text output does not separate context fields from the message, so they
+//!   stay part of the message. Tracing identifiers are not written at all.
+//! - Logfmt flattens arrays and maps into dotted keys (`tags.0`, `user.id`),
+//!   which are read back as separate fields.
+//! - JSON records of an incompatible [`SchemaVersion`] are treated as
+//!   malformed.
+//! - Timestamps keep the precision of the [`TimestampFormat`] they were
+//!   written with.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::core::reader::{InputFormat, LogReader};
+//! use rust_logger_system::core::LogLevel;
+//!
+//! let log = "\
+//! [2025-01-08T10:30:45.123Z] [INFO ] main - server started
+//! not a log line
+//! [2025-01-08T10:30:46.000Z] [WARN ] worker-1 - disk almost full
+//! ";
+//! let mut reader = LogReader::new(log.as_bytes(), InputFormat::Text);
+//! let entries: Vec<_> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+//!
+//! assert_eq!(entries.len(), 2);
+//! assert_eq!(entries[1].level, LogLevel::Warn);
+//! assert_eq!(entries[1].message, "disk almost full");
+//! assert_eq!(reader.skipped(), 1);
+//! ```
+
+use super::error::{LoggerError, Result};
+use super::log_context::{FieldValue, LogContext};
+use super::log_entry::{intern, LogEntry};
+use super::log_level::LogLevel;
+use super::schema::{SchemaVersion, SCHEMA_VERSION_FIELD};
+use super::severity::LevelRegistry;
+use super::structured_entry::{StructuredLogEntry, TracingContext};
+use super::timestamp::TimestampFormat;
+use chrono::{DateTime, Utc};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Layout of the lines read by a [`LogReader`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InputFormat {
+    /// Output of [`OutputFormat::Text`](super::OutputFormat::Text)
+    Text,
+    /// One JSON object per line, as written by
+    /// [`OutputFormat::Json`](super::OutputFormat::Json)
+    Json,
+    /// Output of [`OutputFormat::Logfmt`](super::OutputFormat::Logfmt)
+    Logfmt,
+    /// Decide for every line: `{` starts JSON, `[` starts text and anything
+    /// else is read as logfmt
+    #[default]
+    Auto,
+}
+
+impl InputFormat {
+    /// Get the name of this format
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InputFormat::Text => "text",
+            InputFormat::Json => "json",
+            InputFormat::Logfmt => "logfmt",
+            InputFormat::Auto => "auto",
+        }
+    }
+
+    /// Resolve [`InputFormat::Auto`] for `line`
+    fn detect(self, line: &str) -> Self {
+        match self {
+            InputFormat::Auto => match line.trim_start().chars().next() {
+                Some('{') => InputFormat::Json,
+                Some('[') => InputFormat::Text,
+                _ => InputFormat::Logfmt,
+            },
+            format => format,
+        }
+    }
+}
+
+impl std::str::FromStr for InputFormat {
+    type Err = String;
+
+    /// Parse a format name: `text`, `json`, `jsonl`, `logfmt` or `auto`
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "text" => Ok(InputFormat::Text),
+            "json" | "jsonl" => Ok(InputFormat::Json),
+            "logfmt" => Ok(InputFormat::Logfmt),
+            "auto" => Ok(InputFormat::Auto),
+            other => Err(format!("unknown format '{}'", other)),
+        }
+    }
+}
+
+/// Streaming parser turning log lines back into [`LogEntry`] values
+///
+/// Iterating yields one entry per parsed line; blank lines are ignored.
+/// Errors are only returned for failed reads, or for malformed lines if the
+/// reader is [`strict`](Self::strict).
+pub struct LogReader<R> {
+    reader: R,
+    format: InputFormat,
+    timestamp_format: TimestampFormat,
+    strict: bool,
+    levels: LevelRegistry,
+    line_number: u64,
+    skipped: u64,
+    buffer: Vec<u8>,
+}
+
+impl LogReader<BufReader<File>> {
+    /// Open the log file at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened.
+    pub fn open(path: impl AsRef<Path>, format: InputFormat) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| {
+            LoggerError::io_operation("opening log file", path.display().to_string(), e)
+        })?;
+        Ok(Self::new(BufReader::new(file), format))
+    }
+}
+
+impl<R: BufRead> LogReader<R> {
+    /// Read lines of `format` from `reader`
+    ///
+    /// Timestamps are expected in [`TimestampFormat::Iso8601`] or another
+    /// RFC 3339 form; see [`with_timestamp_format`](Self::with_timestamp_format).
+    pub fn new(reader: R, format: InputFormat) -> Self {
+        Self {
+            reader,
+            format,
+            timestamp_format: TimestampFormat::default(),
+            strict: false,
+            levels: LevelRegistry::new(),
+            line_number: 0,
+            skipped: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Parse timestamps written with `format`
+    ///
+    /// Numeric JSON timestamps are read as seconds or microseconds for
+    /// [`TimestampFormat::Unix`] and [`TimestampFormat::UnixMicros`], and as
+    /// milliseconds otherwise.
+    #[must_use = "builder methods return a new value"]
+    pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.timestamp_format = format;
+        self
+    }
+
+    /// Accept the custom level names of `levels` besides the built-in ones
+    #[must_use = "builder methods return a new value"]
+    pub fn with_levels(mut self, levels: LevelRegistry) -> Self {
+        self.levels = levels;
+        self
+    }
+
+    /// Return malformed lines as errors instead of skipping them
+    #[must_use = "builder methods return a new value"]
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Get the number of malformed lines skipped so far
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Get the number of lines read so far
+    pub fn line_number(&self) -> u64 {
+        self.line_number
+    }
+
+    /// Read the entries as [`StructuredLogEntry`] values
+    pub fn structured(self) -> impl Iterator<Item = Result<StructuredLogEntry>> {
+        self.map(|entry| entry.map(|entry| StructuredLogEntry::from(&entry)))
+    }
+
+    /// Parse a single line
+    fn parse_line(
+        &self,
+        line: &str,
+        format: InputFormat,
+    ) -> std::result::Result<LogEntry, String> {
+        match format {
+            InputFormat::Text => self.parse_text(line),
+            InputFormat::Json => self.parse_json(line),
+            _ => self.parse_logfmt(line),
+        }
+    }
+
+    /// Parse `[timestamp] [LEVEL] thread [module [(target)]] [file:line] - message`
+    fn parse_text(&self, line: &str) -> std::result::Result<LogEntry, String> {
+        let rest = line.strip_prefix('[').ok_or("missing timestamp")?;
+        let (timestamp, rest) = rest.split_once("] [").ok_or("missing level")?;
+        let (level, rest) = rest.split_once("] ").ok_or("missing level")?;
+        let (header, message) = rest.split_once(" - ").ok_or("missing message")?;
+
+        let mut entry = new_entry(
+            self.parse_timestamp(timestamp)?,
+            LogLevel::Info,
+            message.to_string(),
+        );
+        self.apply_level(&mut entry, level.trim())?;
+        let mut header = header.split(' ').filter(|part| !part.is_empty());
+        entry.thread_id = intern(header.next().ok_or("missing thread")?);
+        let location: Vec<&str> = header.collect();
+        let file = match location.as_slice() {
+            [] => return Ok(entry),
+            [file] => file,
+            [module, file] => {
+                entry.module_path = Some(intern(module));
+                entry.target = entry.module_path.clone();
+                file
+            }
+            [module, target, file] if target.starts_with('(') && target.ends_with(')') => {
+                entry.module_path = Some(intern(module));
+                entry.target = Some(intern(&target[1..target.len() - 1]));
+                file
+            }
+            _ => return Err("unexpected text before message".to_string()),
+        };
+        match file.rsplit_once(':').and_then(|(file, line)| Some((file, line.parse().ok()?))) {
+            Some((file, line)) => {
+                entry.file = Some(intern(file));
+                entry.line = Some(line);
+            }
+            None => entry.file = Some(intern(file)),
+        }
+        Ok(entry)
+    }
+
+    /// Parse a JSON object with standard or [`StructuredLogEntry`] field names
+    fn parse_json(&self, line: &str) -> std::result::Result<LogEntry, String> {
+        let mut object: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(line).map_err(|e| e.to_string())?;
+        if let Some(version) = object.remove(SCHEMA_VERSION_FIELD) {
+            let version: SchemaVersion =
+                serde_json::from_value(version).map_err(|e| e.to_string())?;
+            version.check().map_err(|e| e.to_string())?;
+        }
+        let timestamp = match object.remove("timestamp") {
+            Some(serde_json::Value::String(timestamp)) => self.parse_timestamp(&timestamp)?,
+            Some(serde_json::Value::Number(number)) => {
+                let number = number.as_i64().ok_or("invalid timestamp")?;
+                let timestamp = match self.timestamp_format {
+                    TimestampFormat::Unix => DateTime::from_timestamp(number, 0),
+                    TimestampFormat::UnixMicros => DateTime::from_timestamp_micros(number),
+                    _ => DateTime::from_timestamp_millis(number),
+                };
+                timestamp.ok_or("timestamp out of range")?
+            }
+            _ => return Err("missing timestamp".to_string()),
+        };
+        let level = match object.remove("level") {
+            Some(serde_json::Value::String(level)) => level,
+            _ => return Err("missing level".to_string()),
+        };
+        let message = match object.remove("message") {
+            Some(serde_json::Value::String(message)) => message,
+            _ => return Err("missing message".to_string()),
+        };
+        let mut entry = new_entry(timestamp, LogLevel::Info, message);
+        self.apply_level(&mut entry, &level)?;
+
+        let mut take_string = |key: &str| match object.remove(key) {
+            Some(serde_json::Value::String(value)) => Some(value),
+            Some(serde_json::Value::Number(value)) => Some(value.to_string()),
+            _ => None,
+        };
+        if let Some(thread_id) = take_string("thread_id") {
+            entry.thread_id = intern(&thread_id);
+        }
+        entry.thread_name = take_string("thread_name").map(|name| intern(&name));
+        entry.file = take_string("file").map(|file| intern(&file));
+        entry.module_path = take_string("module_path").map(|path| intern(&path));
+        entry.target = take_string("target").map(|target| intern(&target));
+        let trace_id = take_string("trace_id");
+        let span_id = take_string("span_id");
+        let parent_span_id = take_string("parent_span_id");
+        entry.line = object
+            .remove("line")
+            .and_then(|line| line.as_u64())
+            .and_then(|line| u32::try_from(line).ok());
+        entry.tracing = match (trace_id, span_id) {
+            (Some(trace_id), Some(span_id)) => {
+                let tracing = TracingContext::new(trace_id, span_id);
+                Some(match parent_span_id {
+                    Some(parent) => tracing.with_parent(parent),
+                    None => tracing,
+                })
+            }
+            _ => object
+                .remove("tracing")
+                .and_then(|tracing| serde_json::from_value(tracing).ok()),
+        };
+
+        let mut context = LogContext::new();
+        for (key, value) in object {
+            let value: FieldValue = serde_json::from_value(value).map_err(|e| e.to_string())?;
+            context.add_field(key, value);
+        }
+        if !context.is_empty() {
+            entry.context = Some(context);
+        }
+        Ok(entry)
+    }
+
+    /// Parse space-separated `key=value` pairs
+    fn parse_logfmt(&self, line: &str) -> std::result::Result<LogEntry, String> {
+        let mut timestamp = None;
+        let mut level = None;
+        let mut message = None;
+        let mut entry = new_entry(DateTime::UNIX_EPOCH, LogLevel::Info, String::new());
+        let mut trace_id = None;
+        let mut span_id = None;
+        let mut parent_span_id = None;
+        let mut context = LogContext::new();
+
+        for pair in split_logfmt(line)? {
+            let (key, value) = (pair.key, pair.value);
+            match key {
+                "timestamp" => timestamp = Some(self.parse_timestamp(&value)?),
+                "level" => level = Some(value),
+                "message" => message = Some(value),
+                "thread_id" => entry.thread_id = intern(&value),
+                "thread_name" => entry.thread_name = Some(intern(&value)),
+                "file" => entry.file = Some(intern(&value)),
+                "line" => entry.line = Some(value.parse().map_err(|_| "invalid line number")?),
+                "module_path" => entry.module_path = Some(intern(&value)),
+                "target" => entry.target = Some(intern(&value)),
+                "trace_id" => trace_id = Some(value),
+                "span_id" => span_id = Some(value),
+                "parent_span_id" => parent_span_id = Some(value),
+                key if pair.quoted => context.add_field(key, value),
+                key => context.add_field(key, infer_value(value)),
+            }
+        }
+
+        entry.timestamp = timestamp.ok_or("missing timestamp")?;
+        self.apply_level(&mut entry, &level.ok_or("missing level")?)?;
+        entry.message = message.ok_or("missing message")?;
+        if let (Some(trace_id), Some(span_id)) = (trace_id, span_id) {
+            let tracing = TracingContext::new(trace_id, span_id);
+            entry.tracing = Some(match parent_span_id {
+                Some(parent) => tracing.with_parent(parent),
+                None => tracing,
+            });
+        }
+        if !context.is_empty() {
+            entry.context = Some(context);
+        }
+        Ok(entry)
+    }
+
+    /// Set the level of `entry` from a built-in or registered custom name
+    fn apply_level(&self, entry: &mut LogEntry, name: &str) -> std::result::Result<(), String> {
+        match name.parse::<LogLevel>() {
+            Ok(level) => entry.level = level,
+            Err(e) => {
+                let custom = self.levels.get(name).ok_or(e)?;
+                entry.level = custom.level();
+                entry.custom_level = Some(custom.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse a timestamp in the configured format, falling back to RFC 3339
+    fn parse_timestamp(&self, value: &str) -> std::result::Result<DateTime<Utc>, String> {
+        self.timestamp_format
+            .parse(value)
+            .or_else(|| TimestampFormat::Rfc3339.parse(value))
+            .ok_or_else(|| format!("invalid timestamp '{}'", value))
+    }
+}
+
+impl<R: BufRead> Iterator for LogReader<R> {
+    type Item = Result<LogEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buffer.clear();
+            match self.reader.read_until(b'\n', &mut self.buffer) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(e) => {
+                    let line = format!("line {}", self.line_number + 1);
+                    return Some(Err(LoggerError::io_operation("reading log file", line, e)));
+                }
+            }
+            self.line_number += 1;
+
+            let parsed = match std::str::from_utf8(&self.buffer) {
+                Ok(line) => {
+                    let line = line.trim_end_matches(['\n', '\r']);
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let format = self.format.detect(line);
+                    self.parse_line(line, format).map_err(|reason| (format, reason))
+                }
+                Err(_) => Err((self.format, "invalid UTF-8".to_string())),
+            };
+            match parsed {
+                Ok(entry) => return Some(Ok(entry)),
+                Err((format, reason)) if self.strict => {
+                    let message = format!("line {}: {}", self.line_number, reason);
+                    return Some(Err(LoggerError::formatter(format.as_str(), message)));
+                }
+                Err(_) => self.skipped += 1,
+            }
+        }
+    }
+}
+
+/// Create an entry without thread or location information
+fn new_entry(timestamp: DateTime<Utc>, level: LogLevel, message: String) -> LogEntry {
+    LogEntry {
+        level,
+        message,
+        timestamp,
+        file: None,
+        line: None,
+        module_path: None,
+        target: None,
+        thread_id: Arc::from(""),
+        thread_name: None,
+        context: None,
+        tracing: None,
+        custom_level: None,
+    }
+}
+
+/// A `key=value` pair of a logfmt line
+struct LogfmtPair<'a> {
+    key: &'a str,
+    value: String,
+    quoted: bool,
+}
+
+/// Split a logfmt line into its pairs, unescaping quoted values
+fn split_logfmt(line: &str) -> std::result::Result<Vec<LogfmtPair<'_>>, String> {
+    let mut pairs = Vec::new();
+    let mut rest = line.trim_start();
+    while !rest.is_empty() {
+        let (key, after_key) = rest.split_once('=').ok_or("expected key=value")?;
+        if key.is_empty() || key.contains(' ') {
+            return Err(format!("invalid key '{}'", key));
+        }
+
+        let (value, quoted, after_value) = match after_key.strip_prefix('"') {
+            Some(quoted_value) => {
+                let mut value = String::new();
+                let mut chars = quoted_value.char_indices();
+                let end = loop {
+                    match chars.next() {
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, c)) => value.push(c),
+                            None => return Err("unterminated quote".to_string()),
+                        },
+                        Some((index, '"')) => break index + 1,
+                        Some((_, c)) => value.push(c),
+                        None => return Err("unterminated quote".to_string()),
+                    }
+                };
+                (value, true, &quoted_value[end..])
+            }
+            None => {
+                let end = after_key.find(' ').unwrap_or(after_key.len());
+                (after_key[..end].to_string(), false, &after_key[end..])
+            }
+        };
+        if !after_value.is_empty() && !after_value.starts_with(' ') {
+            return Err(format!("missing space after value of '{}'", key));
+        }
+
+        pairs.push(LogfmtPair { key, value, quoted });
+        rest = after_value.trim_start();
+    }
+    Ok(pairs)
+}
+
+/// Type an unquoted logfmt value; strings are always written quoted
+fn infer_value(value: String) -> FieldValue {
+    match value.as_str() {
+        "null" => FieldValue::Null,
+        "true" => FieldValue::Bool(true),
+        "false" => FieldValue::Bool(false),
+        _ => {
+            if let Ok(int) = value.parse::<i64>() {
+                FieldValue::Int(int)
+            } else if let Ok(uint) = value.parse::<u64>() {
+                FieldValue::U64(uint)
+            } else if let Ok(float) = value.parse::<f64>() {
+                FieldValue::Float(float)
+            } else {
+                FieldValue::String(value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{CustomLevel, FormatterConfig, OutputFormat};
+
+    fn sample_entry() -> LogEntry {
+        let context = LogContext::new()
+            .with_field("user", "alice smith")
+            .with_field("attempt", 3)
+            .with_field("ratio", 0.5)
+            .with_field("cached", false)
+            .with_field("quote", "say \"hi\" \\ bye");
+        LogEntry::new(LogLevel::Warn, "payment failed".to_string())
+            .with_location("src/pay.rs", 42, "app::pay")
+            .with_context(context)
+            .with_tracing(
+                TracingContext::new("trace-1".to_string(), "span-2".to_string())
+                    .with_parent("span-0".to_string()),
+            )
+    }
+
+    fn write_lines(format: &OutputFormat, config: &FormatterConfig, entry: &LogEntry) -> String {
+        let line = format.format_with_config(entry, config);
+        format!("{}\ngarbage that is no entry\n\n{}\n", line, line)
+    }
+
+    fn assert_same_fields(read: &LogEntry, written: &LogEntry) {
+        let read = read.context.as_ref().unwrap().fields();
+        let written = written.context.as_ref().unwrap().fields();
+        assert_eq!(read.len(), written.len());
+        for (key, value) in written {
+            assert_eq!(read.get(key), Some(value), "{}", key);
+        }
+    }
+
+    fn read_all(log: &str, format: InputFormat) -> (Vec<LogEntry>, u64) {
+        let mut reader = LogReader::new(log.as_bytes(), format);
+        let entries = reader.by_ref().collect::<Result<Vec<_>>>().unwrap();
+        (entries, reader.skipped())
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let entry = sample_entry();
+        let config = FormatterConfig::default();
+        let (entries, skipped) =
+            read_all(&write_lines(&OutputFormat::Json, &config, &entry), InputFormat::Json);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(skipped, 1);
+        let read = &entries[0];
+        assert_eq!(read.timestamp.timestamp_millis(), entry.timestamp.timestamp_millis());
+        assert_eq!(read.level, entry.level);
+        assert_eq!(read.message, entry.message);
+        assert_eq!(read.thread_id, entry.thread_id);
+        assert_eq!(read.file, entry.file);
+        assert_eq!(read.line, Some(42));
+        assert_eq!(read.module_path, entry.module_path);
+        assert_eq!(read.tracing, entry.tracing);
+        assert_same_fields(read, &entry);
+    }
+
+    #[test]
+    fn test_logfmt_round_trip() {
+        let entry = sample_entry();
+        let config = FormatterConfig::default();
+        let (entries, skipped) =
+            read_all(&write_lines(&OutputFormat::Logfmt, &config, &entry), InputFormat::Logfmt);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(skipped, 1);
+        let read = &entries[0];
+        assert_eq!(read.timestamp.timestamp_millis(), entry.timestamp.timestamp_millis());
+        assert_eq!(read.message, entry.message);
+        assert_eq!(read.line, Some(42));
+        assert_eq!(read.tracing, entry.tracing);
+        assert_same_fields(read, &entry);
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let entry = sample_entry();
+        let config = FormatterConfig::default()
+            .with_include_file_location(true)
+            .with_include_module_path(true);
+        let (entries, skipped) =
+            read_all(&write_lines(&OutputFormat::Text, &config, &entry), InputFormat::Text);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(skipped, 1);
+        let read = &entries[0];
+        assert_eq!(read.level, LogLevel::Warn);
+        assert!(read.message.starts_with("payment failed user=alice smith attempt=3"));
+        assert_eq!(read.file.as_deref(), Some("src/pay.rs"));
+        assert_eq!(read.line, Some(42));
+        assert_eq!(read.module_path.as_deref(), Some("app::pay"));
+
+        let entry = sample_entry().with_target("billing");
+        let (entries, _) =
+            read_all(&write_lines(&OutputFormat::Text, &config, &entry), InputFormat::Text);
+        assert_eq!(entries[0].module_path.as_deref(), Some("app::pay"));
+        assert_eq!(entries[0].target.as_deref(), Some("billing"));
+        assert_eq!(entries[0].line, Some(42));
+    }
+
+    #[test]
+    fn test_auto_detection_and_strict_mode() {
+        let log = "\
+[2025-01-08T10:30:45.123Z] [INFO ] main - from text
+{\"timestamp\":1736332245123,\"level\":\"ERROR\",\"message\":\"from structured\",\"code\":7}
+timestamp=2025-01-08T10:30:45.123Z level=DEBUG message=\"from logfmt\"
+level=INFO message=\"no timestamp\"
+";
+        let (entries, skipped) = read_all(log, InputFormat::Auto);
+        let messages: Vec<&str> = entries.iter().map(|entry| entry.message.as_str()).collect();
+        assert_eq!(messages, ["from text", "from structured", "from logfmt"]);
+        assert_eq!(entries[1].timestamp, entries[0].timestamp);
+        assert_eq!(skipped, 1);
+
+        let results: Vec<_> = LogReader::new(log.as_bytes(), InputFormat::Auto)
+            .strict()
+            .structured()
+            .collect();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[1].as_ref().unwrap().context.fields().get("code"), Some(&7.into()));
+        let error = results[3].as_ref().unwrap_err().to_string();
+        assert!(error.contains("line 4: missing timestamp"), "{}", error);
+    }
+
+    #[test]
+    fn test_custom_level_names() {
+        let notice = CustomLevel::new("NOTICE", 25);
+        let entry = LogEntry::custom(&notice, "renewing".to_string());
+        let log = write_lines(&OutputFormat::Logfmt, &FormatterConfig::default(), &entry);
+
+        let (entries, skipped) = read_all(&log, InputFormat::Logfmt);
+        assert!(entries.is_empty());
+        assert_eq!(skipped, 3);
+
+        let mut levels = LevelRegistry::new();
+        levels.register(notice.clone()).unwrap();
+        let reader = LogReader::new(log.as_bytes(), InputFormat::Logfmt).with_levels(levels);
+        let entries: Vec<LogEntry> = reader.map(|entry| entry.unwrap()).collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].level, LogLevel::Info);
+        assert_eq!(entries[0].custom_level, Some(notice));
+    }
+}
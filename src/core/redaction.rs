@@ -0,0 +1,351 @@
+//! Field redaction and PII masking
+//!
+//! A [`Redactor`] masks sensitive data before entries reach the appenders.
+//! Fields whose key matches a key pattern (e.g. `password`, `*_token`) have
+//! their value replaced entirely; string values and the message are scanned
+//! with value patterns (e.g. email addresses, card numbers) and every match
+//! is replaced.
+//!
+//! Redaction runs where entries are delivered to appenders: on the worker
+//! thread in async mode, so the cost stays off the calling thread.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//!
+//! let logger = Logger::builder()
+//!     .redaction(
+//!         RedactionConfig::new()
+//!             .with_key_pattern("password")
+//!             .with_key_pattern("*_token")
+//!             .with_emails()
+//!             .with_credit_cards(),
+//!     )
+//!     .build();
+//!
+//! // Logged as: user [REDACTED] signed up password=[REDACTED]
+//! logger.info_builder()
+//!     .message("user alice@example.com signed up")
+//!     .field("password", "hunter2")
+//!     .log();
+//! ```
+
+use super::error::{LoggerError, Result};
+use super::log_context::FieldValue;
+use super::log_entry::LogEntry;
+use regex::Regex;
+use std::borrow::Cow;
+
+/// Default replacement for redacted values
+pub const DEFAULT_REDACTION: &str = "[REDACTED]";
+
+/// Pattern matching email addresses
+const EMAIL_PATTERN: &str = r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}";
+
+/// Pattern matching 13-19 digit card numbers, optionally grouped by spaces or dashes
+const CREDIT_CARD_PATTERN: &str = r"\b(?:\d[ -]?){12,18}\d\b";
+
+/// Configuration for field redaction
+///
+/// Key patterns are matched case-insensitively against field names and
+/// support `*` as a wildcard. Value patterns are regular expressions.
+#[derive(Debug, Clone)]
+pub struct RedactionConfig {
+    /// Field name patterns whose values are replaced entirely
+    pub key_patterns: Vec<String>,
+
+    /// Regular expressions whose matches are masked in string values
+    pub value_patterns: Vec<Regex>,
+
+    /// Replacement text for redacted values
+    pub replacement: String,
+
+    /// Whether value patterns are also applied to the log message
+    pub redact_message: bool,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            key_patterns: Vec::new(),
+            value_patterns: Vec::new(),
+            replacement: DEFAULT_REDACTION.to_string(),
+            redact_message: true,
+        }
+    }
+}
+
+impl RedactionConfig {
+    /// Create an empty configuration (nothing is redacted)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Preset covering common credentials, emails and card numbers
+    ///
+    /// Key patterns: `password`, `passwd`, `secret`, `*secret*`, `token`,
+    /// `*_token`, `api_key`, `apikey`, `authorization`, `cookie`.
+    pub fn pii() -> Self {
+        [
+            "password",
+            "passwd",
+            "secret",
+            "*secret*",
+            "token",
+            "*_token",
+            "api_key",
+            "apikey",
+            "authorization",
+            "cookie",
+        ]
+        .into_iter()
+        .fold(Self::new(), Self::with_key_pattern)
+        .with_emails()
+        .with_credit_cards()
+    }
+
+    /// Redact fields whose name matches `pattern` (`*` is a wildcard)
+    #[must_use]
+    pub fn with_key_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.key_patterns.push(pattern.into().to_lowercase());
+        self
+    }
+
+    /// Mask substrings matching a compiled regular expression
+    #[must_use]
+    pub fn with_value_pattern(mut self, pattern: Regex) -> Self {
+        self.value_patterns.push(pattern);
+        self
+    }
+
+    /// Mask substrings matching a regular expression
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pattern` is not a valid regular expression.
+    pub fn with_value_regex(self, pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| LoggerError::config("redaction", format!("invalid pattern: {}", e)))?;
+        Ok(self.with_value_pattern(regex))
+    }
+
+    /// Mask email addresses
+    #[must_use]
+    pub fn with_emails(self) -> Self {
+        self.with_value_pattern(Regex::new(EMAIL_PATTERN).expect("valid email pattern"))
+    }
+
+    /// Mask credit card numbers
+    #[must_use]
+    pub fn with_credit_cards(self) -> Self {
+        self.with_value_pattern(Regex::new(CREDIT_CARD_PATTERN).expect("valid card pattern"))
+    }
+
+    /// Set the replacement text (default: `[REDACTED]`)
+    #[must_use]
+    pub fn with_replacement(mut self, replacement: impl Into<String>) -> Self {
+        self.replacement = replacement.into();
+        self
+    }
+
+    /// Enable or disable masking of the log message
+    #[must_use]
+    pub fn redact_message(mut self, enabled: bool) -> Self {
+        self.redact_message = enabled;
+        self
+    }
+}
+
+/// Applies a [`RedactionConfig`] to log entries
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    config: RedactionConfig,
+}
+
+impl Redactor {
+    /// Create a redactor with the given configuration
+    pub fn new(config: RedactionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get a reference to the redaction configuration
+    pub fn config(&self) -> &RedactionConfig {
+        &self.config
+    }
+
+    /// Check whether a field name matches any key pattern
+    pub fn is_sensitive_key(&self, key: &str) -> bool {
+        let key = key.to_lowercase();
+        self.config
+            .key_patterns
+            .iter()
+            .any(|pattern| glob_match(pattern, &key))
+    }
+
+    /// Mask all value pattern matches in a string
+    pub fn redact_str<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut result = Cow::Borrowed(text);
+        for pattern in &self.config.value_patterns {
+            if let Cow::Owned(replaced) =
+                pattern.replace_all(&result, self.config.replacement.as_str())
+            {
+                result = Cow::Owned(replaced);
+            }
+        }
+        result
+    }
+
+    /// Redact an entry's message and context fields in place
+    pub fn redact(&self, entry: &mut LogEntry) {
+        if self.config.redact_message {
+            if let Cow::Owned(message) = self.redact_str(&entry.message) {
+                entry.message = message;
+            }
+        }
+
+        if let Some(ref mut context) = entry.context {
+            for (key, value) in context.fields_mut().iter_mut() {
+                self.redact_field(key, value);
+            }
+        }
+    }
+
+    fn redact_field(&self, key: &str, value: &mut FieldValue) {
+        if self.is_sensitive_key(key) {
+            *value = FieldValue::String(self.config.replacement.clone());
+            return;
+        }
+
+        match value {
+            FieldValue::String(s) => {
+                if let Cow::Owned(redacted) = self.redact_str(s) {
+                    *s = redacted;
+                }
+            }
+            FieldValue::Array(items) => {
+                for item in items {
+                    self.redact_field("", item);
+                }
+            }
+            FieldValue::Map(map) => {
+                for (nested_key, item) in map.iter_mut() {
+                    self.redact_field(nested_key, item);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Case-sensitive glob match supporting `*` wildcards
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard: exact match
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{LogContext, LogLevel};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("password", "password"));
+        assert!(!glob_match("password", "password_hint"));
+        assert!(glob_match("*_token", "access_token"));
+        assert!(!glob_match("*_token", "token"));
+        assert!(glob_match("*secret*", "client_secret_id"));
+        assert!(glob_match("api*key", "api_key"));
+        assert!(glob_match("*", "anything"));
+        assert!(!glob_match("a*b*c", "acb"));
+    }
+
+    #[test]
+    fn test_key_patterns_are_case_insensitive() {
+        let redactor = Redactor::new(RedactionConfig::new().with_key_pattern("*_TOKEN"));
+        assert!(redactor.is_sensitive_key("Refresh_Token"));
+        assert!(!redactor.is_sensitive_key("user_id"));
+    }
+
+    #[test]
+    fn test_redact_fields_and_message() {
+        let redactor = Redactor::new(RedactionConfig::pii());
+        let mut entry = LogEntry::new(
+            LogLevel::Info,
+            "charge 4111 1111 1111 1111 for bob@example.com".to_string(),
+        )
+        .with_context(
+            LogContext::new()
+                .with_field("password", "hunter2")
+                .with_field("access_token", "abc")
+                .with_field("note", "contact ops@example.org")
+                .with_field("user_id", 42),
+        );
+
+        redactor.redact(&mut entry);
+
+        assert_eq!(entry.message, "charge [REDACTED] for [REDACTED]");
+        let fields = entry.context.as_ref().unwrap().fields();
+        assert_eq!(fields["password"], "[REDACTED]".into());
+        assert_eq!(fields["access_token"], "[REDACTED]".into());
+        assert_eq!(fields["note"], "contact [REDACTED]".into());
+        assert_eq!(fields["user_id"], 42.into());
+    }
+
+    #[test]
+    fn test_redact_nested_values() {
+        let redactor = Redactor::new(
+            RedactionConfig::new()
+                .with_key_pattern("secret")
+                .with_emails()
+                .with_replacement("***"),
+        );
+        let mut creds = BTreeMap::new();
+        creds.insert("user", FieldValue::from("carol"));
+        creds.insert("secret", FieldValue::from("s3cr3t"));
+
+        let mut entry = LogEntry::new(LogLevel::Info, "login".to_string()).with_context(
+            LogContext::new()
+                .with_field("creds", creds)
+                .with_field("cc", vec!["a@b.io", "plain"]),
+        );
+        redactor.redact(&mut entry);
+
+        let fields = entry.context.as_ref().unwrap().fields();
+        assert_eq!(fields["creds"].to_string(), r#"{"secret":"***","user":"carol"}"#);
+        assert_eq!(fields["cc"], vec!["***", "plain"].into());
+    }
+
+    #[test]
+    fn test_message_redaction_can_be_disabled() {
+        let redactor = Redactor::new(RedactionConfig::new().with_emails().redact_message(false));
+        let mut entry = LogEntry::new(LogLevel::Info, "from a@b.io".to_string());
+        redactor.redact(&mut entry);
+        assert_eq!(entry.message, "from a@b.io");
+    }
+
+    #[test]
+    fn test_invalid_value_regex() {
+        assert!(RedactionConfig::new().with_value_regex("(unclosed").is_err());
+        assert!(RedactionConfig::new().with_value_regex(r"\d{3}-\d{2}-\d{4}").is_ok());
+    }
+}
@@ -0,0 +1,115 @@
+//! Settings that can be changed on a running logger
+//!
+//! [`Logger::reconfigure`](super::Logger::reconfigure) passes the current
+//! [`RuntimeConfig`] to a closure and applies the result as one change,
+//! without rebuilding the logger or touching queued entries.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//!
+//! let logger = Logger::builder()
+//!     .appender(ConsoleAppender::new())
+//!     .async_mode(1000)
+//!     .build();
+//!
+//! logger.reconfigure(|config| {
+//!     config.min_level = LogLevel::Debug;
+//!     config.sampling.rate = 0.5;
+//!     config.overflow_policy = OverflowPolicy::DropNewest;
+//!     config.flush_policy = FlushPolicy::EveryEntry;
+//!     config.formatter = Some(FormatterConfig::new().with_include_thread_id(false));
+//! })?;
+//! # Ok::<(), LoggerError>(())
+//! ```
+
+use super::error::{LoggerError, Result};
+use super::flush_policy::FlushPolicy;
+use super::log_level::LogLevel;
+use super::overflow_policy::OverflowPolicy;
+use super::sampling::SamplingConfig;
+use super::timestamp::FormatterConfig;
+use std::time::Duration;
+
+/// Snapshot of the settings [`Logger::reconfigure`] can change
+///
+/// [`Logger::reconfigure`]: super::Logger::reconfigure
+#[derive(Debug, Clone)]
+pub struct RuntimeConfig {
+    /// Minimum level
+    pub min_level: LogLevel,
+
+    /// Sampling settings; the default samples everything
+    pub sampling: SamplingConfig,
+
+    /// Queue overflow handling; has no effect in synchronous mode
+    pub overflow_policy: OverflowPolicy,
+
+    /// When appenders are flushed
+    pub flush_policy: FlushPolicy,
+
+    /// Formatting options to give every appender that supports them
+    ///
+    /// Appenders keep their own options, so this is `None` in the snapshot;
+    /// set it to replace them. See
+    /// [`Appender::set_formatter_config`](super::Appender::set_formatter_config).
+    pub formatter: Option<FormatterConfig>,
+}
+
+impl RuntimeConfig {
+    /// Check the settings for values the logger rejects
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoggerError::InvalidConfiguration`] for sample rates
+    /// outside `0.0..=1.0`, adaptive sampling with a zero threshold, or a
+    /// zero timeout for [`OverflowPolicy::BlockWithTimeout`].
+    pub fn validate(&self) -> Result<()> {
+        check_sampling(&self.sampling)?;
+        check_overflow_policy(&self.overflow_policy)
+    }
+}
+
+/// Reject sampling settings outside their valid ranges
+pub(crate) fn check_sampling(sampling: &SamplingConfig) -> Result<()> {
+    let in_range = |rate: f64| (0.0..=1.0).contains(&rate);
+    if !in_range(sampling.rate) {
+        return Err(LoggerError::config(
+            "sampling",
+            format!("rate must be between 0.0 and 1.0, got {}", sampling.rate),
+        ));
+    }
+    if let Some((category, rate)) =
+        sampling.category_rates.iter().find(|(_, rate)| !in_range(**rate))
+    {
+        return Err(LoggerError::config(
+            "sampling",
+            format!(
+                "rate for category '{}' must be between 0.0 and 1.0, got {}",
+                category, rate
+            ),
+        ));
+    }
+    if sampling.adaptive
+        && (sampling.adaptive_threshold == 0 || !in_range(sampling.adaptive_min_rate))
+    {
+        return Err(LoggerError::config(
+            "sampling",
+            "adaptive sampling needs a non-zero threshold and a minimum rate \
+             between 0.0 and 1.0",
+        ));
+    }
+    Ok(())
+}
+
+/// Reject an overflow policy that can never wait
+pub(crate) fn check_overflow_policy(policy: &OverflowPolicy) -> Result<()> {
+    if *policy == OverflowPolicy::BlockWithTimeout(Duration::ZERO) {
+        return Err(LoggerError::config(
+            "overflow_policy",
+            "BlockWithTimeout needs a non-zero timeout; use DropNewest to never wait",
+        ));
+    }
+    Ok(())
+}
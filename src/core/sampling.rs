@@ -9,6 +9,8 @@
 //! - **Level Bypass**: Critical levels (Error, Fatal) are never sampled
 //! - **Category-based Sampling**: Different rates for different log categories
 //! - **Adaptive Sampling**: Automatically adjusts rate based on throughput
+//! - **Consistent Sampling**: Hash-based decisions keyed on a context field,
+//!   so all entries for the same request are kept or dropped together
 //!
 //! # Example
 //!
@@ -24,14 +26,20 @@
 //!         adaptive: false,
 //!         adaptive_threshold: 10000,
 //!         adaptive_min_rate: 0.01,
+//!         key_field: None,
+//!         mode: SamplingMode::Random,
 //!     })
 //!     .build();
 //! ```
 
+use super::clock::{self, Clock};
+use super::log_context::{FieldValue, LogContext};
 use super::log_level::LogLevel;
+use parking_lot::RwLock;
 use rand::Rng;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Instant;
 
 /// Configuration for log sampling
@@ -52,9 +60,11 @@ use std::time::Instant;
 ///     adaptive: false,
 ///     adaptive_threshold: 10000,
 ///     adaptive_min_rate: 0.01,
+///     key_field: None,
+///     mode: SamplingMode::Random,
 /// };
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SamplingConfig {
     /// Sample rate between 0.0 and 1.0
     ///
@@ -93,6 +103,71 @@ pub struct SamplingConfig {
     /// The sampling rate will never go below this value, even under
     /// extreme load.
     pub adaptive_min_rate: f64,
+
+    /// Context field used for consistent (hash-based) sampling
+    ///
+    /// When set, entries carrying this field are sampled by hashing the
+    /// field value instead of drawing a random number, so all entries with
+    /// the same value (e.g. the same `request_id`) are kept or dropped
+    /// together. Entries without the field fall back to `mode`.
+    pub key_field: Option<String>,
+
+    /// How entries without a key field are picked
+    pub mode: SamplingMode,
+}
+
+/// How the sampler picks the entries it keeps
+///
+/// Entries with a [`key_field`](SamplingConfig::key_field) value are always
+/// sampled by hashing that value; the mode applies to all others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SamplingMode {
+    /// Draw from the thread's random number generator
+    #[default]
+    Random,
+
+    /// Draw from a generator seeded with the given value
+    ///
+    /// The sequence of decisions is reproducible: samplers with the same
+    /// seed, given entries in the same order, keep the same entries.
+    Seeded(u64),
+
+    /// Keep entries at even intervals, counting them
+    ///
+    /// At a rate of 0.1 the 10th, 20th, 30th, ... entry is kept; at 0.25
+    /// every 4th. Needs no randomness, so it is exact over any window.
+    Counter,
+}
+
+impl std::fmt::Display for SamplingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SamplingMode::Random => f.write_str("random"),
+            SamplingMode::Seeded(seed) => write!(f, "seeded:{}", seed),
+            SamplingMode::Counter => f.write_str("counter"),
+        }
+    }
+}
+
+impl std::str::FromStr for SamplingMode {
+    type Err = String;
+
+    /// Parse `random`, `counter` or `seeded:<seed>`
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        if let Some(seed) = s.strip_prefix("seeded:") {
+            return seed
+                .trim()
+                .parse()
+                .map(SamplingMode::Seeded)
+                .map_err(|_| format!("invalid sampling seed '{}'", seed));
+        }
+        match s.as_str() {
+            "random" => Ok(SamplingMode::Random),
+            "counter" => Ok(SamplingMode::Counter),
+            other => Err(format!("unknown sampling mode '{}'", other)),
+        }
+    }
 }
 
 impl Default for SamplingConfig {
@@ -104,6 +179,8 @@ impl Default for SamplingConfig {
             adaptive: false,
             adaptive_threshold: 10000,
             adaptive_min_rate: 0.01,
+            key_field: None,
+            mode: SamplingMode::Random,
         }
     }
 }
@@ -156,6 +233,39 @@ impl SamplingConfig {
         self.adaptive_min_rate = min_rate.clamp(0.0, 1.0);
         self
     }
+
+    /// Enable consistent sampling keyed on a context field
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::SamplingConfig;
+    ///
+    /// // Keep 10% of requests, with every line of a kept request logged
+    /// let config = SamplingConfig::new(0.1).with_key_field("request_id");
+    /// ```
+    #[must_use]
+    pub fn with_key_field(mut self, field: impl Into<String>) -> Self {
+        self.key_field = Some(field.into());
+        self
+    }
+
+    /// Set how entries are picked, e.g. reproducibly for tests
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::{LogLevel, LogSampler, SamplingConfig, SamplingMode};
+    ///
+    /// let sampler = LogSampler::new(SamplingConfig::new(0.25).with_mode(SamplingMode::Counter));
+    /// let kept: Vec<bool> = (0..8).map(|_| sampler.should_sample(LogLevel::Info, None)).collect();
+    /// assert_eq!(kept, [false, false, false, true, false, false, false, true]);
+    /// ```
+    #[must_use]
+    pub fn with_mode(mut self, mode: SamplingMode) -> Self {
+        self.mode = mode;
+        self
+    }
 }
 
 /// Metrics for sampling observability
@@ -270,6 +380,8 @@ impl Clone for SamplerMetrics {
 /// message rate (messages per second).
 #[derive(Debug)]
 struct RateTracker {
+    clock: Arc<dyn Clock>,
+
     /// Start time of the current measurement window
     window_start: Instant,
 
@@ -281,10 +393,11 @@ struct RateTracker {
 }
 
 impl RateTracker {
-    /// Create a new rate tracker
-    fn new() -> Self {
+    /// Create a rate tracker measuring time with `clock`
+    fn new(clock: Arc<dyn Clock>) -> Self {
         Self {
-            window_start: Instant::now(),
+            window_start: clock.now(),
+            clock,
             window_count: AtomicUsize::new(0),
             last_rate: AtomicU64::new(0),
         }
@@ -294,21 +407,19 @@ impl RateTracker {
     fn record_and_get_rate(&self) -> f64 {
         self.window_count.fetch_add(1, Ordering::Relaxed);
 
-        let elapsed = self.window_start.elapsed().as_secs_f64();
-
-        if elapsed > 0.0 {
-            let count = self.window_count.load(Ordering::Relaxed);
-            let rate = count as f64 / elapsed;
+        // Measure over at least one second, so a burst right after startup
+        // does not count as a high sustained rate
+        let elapsed = self.clock.now().saturating_duration_since(self.window_start);
+        let elapsed = elapsed.as_secs_f64().max(1.0);
+        let count = self.window_count.load(Ordering::Relaxed);
+        let rate = count as f64 / elapsed;
 
-            // Cache the rate for quick access
-            self.last_rate.store(rate.to_bits(), Ordering::Relaxed);
+        // Cache the rate for quick access
+        self.last_rate.store(rate.to_bits(), Ordering::Relaxed);
 
-            // If window is complete, we could reset here, but for simplicity
-            // we just keep accumulating. The rate calculation remains accurate.
-            rate
-        } else {
-            0.0
-        }
+        // If window is complete, we could reset here, but for simplicity
+        // we just keep accumulating. The rate calculation remains accurate.
+        rate
     }
 
     /// Get the last calculated rate without recording
@@ -326,6 +437,9 @@ impl RateTracker {
 ///
 /// The sampler is thread-safe and uses atomic operations for all counters.
 /// The random number generator is created per-call to avoid contention.
+/// The configuration sits behind a read-write lock so rates can be adjusted
+/// at runtime with [`set_rate`](Self::set_rate) and
+/// [`set_category_rate`](Self::set_category_rate).
 ///
 /// # Example
 ///
@@ -341,18 +455,27 @@ impl RateTracker {
 /// assert!(sampler.should_sample(LogLevel::Error, None));
 /// ```
 pub struct LogSampler {
-    config: SamplingConfig,
+    config: RwLock<SamplingConfig>,
     metrics: SamplerMetrics,
     rate_tracker: RateTracker,
+    /// Entries decided by [`SamplingMode::Counter`] or the state of the
+    /// [`SamplingMode::Seeded`] generator
+    sequence: AtomicU64,
 }
 
 impl LogSampler {
     /// Create a new sampler with the given configuration
     pub fn new(config: SamplingConfig) -> Self {
+        Self::with_clock(config, clock::system_clock())
+    }
+
+    /// Create a sampler measuring the rate for adaptive sampling with `clock`
+    pub fn with_clock(config: SamplingConfig, clock: Arc<dyn Clock>) -> Self {
         Self {
-            config,
+            config: RwLock::new(config),
             metrics: SamplerMetrics::new(),
-            rate_tracker: RateTracker::new(),
+            rate_tracker: RateTracker::new(clock),
+            sequence: AtomicU64::new(0),
         }
     }
 
@@ -367,14 +490,62 @@ impl LogSampler {
     ///
     /// `true` if the log should be recorded, `false` if it should be dropped.
     pub fn should_sample(&self, level: LogLevel, category: Option<&str>) -> bool {
+        self.decide(level, category, None)
+    }
+
+    /// Determine if a log entry should be sampled, using its context
+    ///
+    /// The category is read from the `"category"` field, and when
+    /// [`SamplingConfig::key_field`] is set, the key field's value drives a
+    /// consistent hash-based decision.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::{LogContext, LogLevel, LogSampler, SamplingConfig};
+    ///
+    /// let sampler = LogSampler::new(SamplingConfig::new(0.5).with_key_field("request_id"));
+    /// let ctx = LogContext::new().with_field("request_id", "req-42");
+    ///
+    /// // The same request always gets the same decision
+    /// let first = sampler.should_sample_context(LogLevel::Info, Some(&ctx));
+    /// for _ in 0..10 {
+    ///     assert_eq!(sampler.should_sample_context(LogLevel::Info, Some(&ctx)), first);
+    /// }
+    /// ```
+    pub fn should_sample_context(&self, level: LogLevel, context: Option<&LogContext>) -> bool {
+        let fields = context.map(|ctx| ctx.field_map());
+
+        let category = fields.and_then(|f| match f.get("category") {
+            Some(FieldValue::String(s)) => Some(s.as_str()),
+            _ => None,
+        });
+
+        // Resolve the key before `decide` takes the config lock again
+        let key = {
+            let config = self.config.read();
+            match (&config.key_field, fields) {
+                (Some(field), Some(f)) => f.get(field).map(|v| v.to_string()),
+                _ => None,
+            }
+        };
+
+        self.decide(level, category, key.as_deref())
+    }
+
+    fn decide(&self, level: LogLevel, category: Option<&str>, key: Option<&str>) -> bool {
+        let config = self.config.read();
+
         // Always sample configured levels (typically Error, Fatal)
-        if self.config.always_sample.contains(&level) {
+        if config.always_sample.contains(&level) {
             self.metrics.record_sampled();
             return true;
         }
 
         // Get effective rate
-        let rate = self.get_effective_rate(category);
+        let rate = self.get_effective_rate(&config, category);
+        let mode = config.mode;
+        drop(config);
 
         // Fast path: if rate is 1.0, always sample
         if rate >= 1.0 {
@@ -388,8 +559,23 @@ impl LogSampler {
             return false;
         }
 
-        // Random sampling
-        let sample = rand::thread_rng().gen::<f64>() < rate;
+        // Consistent sampling when a key is available, by mode otherwise
+        let sample = match (key, mode) {
+            (Some(key), _) => key_fraction(key) < rate,
+            (None, SamplingMode::Random) => rand::thread_rng().gen::<f64>() < rate,
+            (None, SamplingMode::Seeded(seed)) => {
+                // SplitMix64: each call takes the next state of the sequence
+                let step = self.sequence.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+                let bits = splitmix64(seed.wrapping_add(step.wrapping_mul(SPLITMIX_GAMMA)));
+                ((bits >> 11) as f64 / (1u64 << 53) as f64) < rate
+            }
+            (None, SamplingMode::Counter) => {
+                // Keep an entry each time the count times the rate reaches
+                // the next whole number
+                let n = self.sequence.fetch_add(1, Ordering::Relaxed) as f64;
+                ((n + 1.0) * rate).floor() > (n * rate).floor()
+            }
+        };
 
         if sample {
             self.metrics.record_sampled();
@@ -401,26 +587,26 @@ impl LogSampler {
     }
 
     /// Get the effective sampling rate, considering adaptive sampling
-    fn get_effective_rate(&self, category: Option<&str>) -> f64 {
+    fn get_effective_rate(&self, config: &SamplingConfig, category: Option<&str>) -> f64 {
         // Check category-specific rate first
         if let Some(cat) = category {
-            if let Some(&rate) = self.config.category_rates.get(cat) {
+            if let Some(&rate) = config.category_rates.get(cat) {
                 return rate;
             }
         }
 
         // Apply adaptive sampling if enabled
-        if self.config.adaptive {
+        if config.adaptive {
             let current_rate = self.rate_tracker.record_and_get_rate();
 
-            if current_rate > self.config.adaptive_threshold as f64 {
+            if current_rate > config.adaptive_threshold as f64 {
                 // Reduce sampling rate proportionally to load
-                let scale = self.config.adaptive_threshold as f64 / current_rate;
-                return (self.config.rate * scale).max(self.config.adaptive_min_rate);
+                let scale = config.adaptive_threshold as f64 / current_rate;
+                return (config.rate * scale).max(config.adaptive_min_rate);
             }
         }
 
-        self.config.rate
+        config.rate
     }
 
     /// Get the sampler metrics
@@ -440,16 +626,83 @@ impl LogSampler {
         self.rate_tracker.current_rate()
     }
 
-    /// Get a reference to the sampling configuration
-    pub fn config(&self) -> &SamplingConfig {
-        &self.config
+    /// Get a snapshot of the current sampling configuration
+    pub fn config(&self) -> SamplingConfig {
+        self.config.read().clone()
+    }
+
+    /// Update the base sample rate
+    ///
+    /// The rate is clamped to `0.0..=1.0` and takes effect for the next
+    /// sampling decision.
+    pub fn set_rate(&self, rate: f64) {
+        self.config.write().rate = rate.clamp(0.0, 1.0);
+    }
+
+    /// Update (or add) the sample rate for a category
+    ///
+    /// The rate is clamped to `0.0..=1.0`.
+    pub fn set_category_rate(&self, category: impl Into<String>, rate: f64) {
+        self.config
+            .write()
+            .category_rates
+            .insert(category.into(), rate.clamp(0.0, 1.0));
+    }
+
+    /// Replace the whole sampling configuration
+    ///
+    /// Sampler metrics and the adaptive rate tracker are kept.
+    pub fn set_config(&self, config: SamplingConfig) {
+        *self.config.write() = config;
+    }
+
+    /// Remove a category-specific rate, falling back to the base rate
+    ///
+    /// Returns the removed rate, if one was configured.
+    pub fn clear_category_rate(&self, category: &str) -> Option<f64> {
+        self.config.write().category_rates.remove(category)
     }
 }
 
+/// Map a sampling key to a stable value in `[0.0, 1.0)`
+///
+/// Uses 64-bit FNV-1a followed by the MurmurHash3 finalizer, so the result
+/// is identical across processes and builds. This lets separate services
+/// make the same decision for a shared request id.
+fn key_fraction(key: &str) -> f64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = key.bytes().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+
+    // Avalanche the bits so similar keys spread evenly
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^= hash >> 33;
+
+    // Use the top 53 bits for a uniformly distributed f64
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Increment of the SplitMix64 state
+const SPLITMIX_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Output function of the SplitMix64 generator for `state`
+fn splitmix64(state: u64) -> u64 {
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
 impl std::fmt::Debug for LogSampler {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("LogSampler")
-            .field("config", &self.config)
+            .field("config", &*self.config.read())
             .field("metrics", &self.metrics)
             .finish()
     }
@@ -458,6 +711,7 @@ impl std::fmt::Debug for LogSampler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
     #[test]
     fn test_sampling_config_default() {
@@ -604,7 +858,7 @@ mod tests {
 
     #[test]
     fn test_rate_tracker() {
-        let tracker = RateTracker::new();
+        let tracker = RateTracker::new(clock::system_clock());
 
         // Record some messages
         for _ in 0..100 {
@@ -615,6 +869,114 @@ mod tests {
         assert!(rate > 0.0, "Rate should be positive");
     }
 
+    #[test]
+    fn test_adaptive_rate_on_virtual_time() {
+        let clock = Arc::new(crate::core::ManualClock::new());
+        let config = SamplingConfig::new(1.0).with_adaptive(100, 0.01);
+        let sampler = LogSampler::with_clock(config, clock.clone());
+
+        for _ in 0..1000 {
+            sampler.should_sample(LogLevel::Info, None);
+        }
+        assert_eq!(sampler.current_message_rate(), 1000.0);
+
+        // The same messages spread over 100 seconds are below the threshold
+        clock.advance(Duration::from_secs(100));
+        assert!(sampler.should_sample(LogLevel::Info, None));
+        assert_eq!(sampler.current_message_rate(), 10.01);
+    }
+
+    #[test]
+    fn test_deterministic_modes() {
+        let decisions = |mode: SamplingMode| -> Vec<bool> {
+            let sampler = LogSampler::new(SamplingConfig::new(0.3).with_mode(mode));
+            (0..1000).map(|_| sampler.should_sample(LogLevel::Info, None)).collect()
+        };
+
+        let seeded = decisions(SamplingMode::Seeded(42));
+        assert_eq!(seeded, decisions(SamplingMode::Seeded(42)));
+        assert_ne!(seeded, decisions(SamplingMode::Seeded(43)));
+        let kept = seeded.iter().filter(|&&kept| kept).count();
+        assert!((200..400).contains(&kept), "kept {}", kept);
+
+        let counted = decisions(SamplingMode::Counter);
+        assert_eq!(counted.iter().filter(|&&kept| kept).count(), 300);
+        let first: Vec<usize> = (0..10).filter(|&i| counted[i]).collect();
+        assert_eq!(first, [3, 6, 9]);
+    }
+
+    #[test]
+    fn test_sampling_mode_parse() {
+        assert_eq!("counter".parse(), Ok(SamplingMode::Counter));
+        assert_eq!(" Seeded:7 ".parse(), Ok(SamplingMode::Seeded(7)));
+        assert!("seeded:x".parse::<SamplingMode>().is_err());
+        assert!("sometimes".parse::<SamplingMode>().is_err());
+        let mode = SamplingMode::Seeded(7);
+        assert_eq!(mode.to_string().parse(), Ok(mode));
+    }
+
+    #[test]
+    fn test_key_fraction_is_stable() {
+        assert_eq!(key_fraction("req-1"), key_fraction("req-1"));
+        assert!((0.0..1.0).contains(&key_fraction("")));
+        assert_ne!(key_fraction("req-1"), key_fraction("req-2"));
+    }
+
+    #[test]
+    fn test_consistent_sampling_by_key() {
+        let sampler = LogSampler::new(SamplingConfig::new(0.5).with_key_field("request_id"));
+
+        let mut kept = 0;
+        for i in 0..1000 {
+            let ctx = LogContext::new().with_field("request_id", format!("req-{}", i));
+            let first = sampler.should_sample_context(LogLevel::Info, Some(&ctx));
+            for _ in 0..5 {
+                assert_eq!(sampler.should_sample_context(LogLevel::Debug, Some(&ctx)), first);
+            }
+            if first {
+                kept += 1;
+            }
+        }
+
+        // Roughly half of the requests should be kept
+        assert!((400..=600).contains(&kept), "kept {} of 1000 requests", kept);
+    }
+
+    #[test]
+    fn test_context_category_rate() {
+        let sampler = LogSampler::new(SamplingConfig::new(1.0).with_category_rate("noisy", 0.0));
+        let noisy = LogContext::new().with_field("category", "noisy");
+
+        assert!(!sampler.should_sample_context(LogLevel::Info, Some(&noisy)));
+        assert!(sampler.should_sample_context(LogLevel::Info, None));
+    }
+
+    #[test]
+    fn test_runtime_rate_adjustment() {
+        let sampler = LogSampler::new(SamplingConfig::new(1.0));
+        assert!(sampler.should_sample(LogLevel::Info, None));
+
+        sampler.set_rate(0.0);
+        assert!(!sampler.should_sample(LogLevel::Info, None));
+        assert!(sampler.should_sample(LogLevel::Error, None));
+
+        sampler.set_rate(7.5);
+        assert_eq!(sampler.config().rate, 1.0);
+    }
+
+    #[test]
+    fn test_runtime_category_rate_adjustment() {
+        let sampler = LogSampler::new(SamplingConfig::new(1.0));
+
+        sampler.set_category_rate("db", 0.0);
+        assert!(!sampler.should_sample(LogLevel::Info, Some("db")));
+        assert!(sampler.should_sample(LogLevel::Info, Some("http")));
+
+        assert_eq!(sampler.clear_category_rate("db"), Some(0.0));
+        assert!(sampler.should_sample(LogLevel::Info, Some("db")));
+        assert_eq!(sampler.clear_category_rate("db"), None);
+    }
+
     #[test]
     fn test_sampler_debug() {
         let sampler = LogSampler::new(SamplingConfig::new(0.5));
@@ -0,0 +1,296 @@
+//! Versioned layout of structured log records
+//!
+//! JSON output and serialized [`StructuredLogEntry`](super::StructuredLogEntry)
+//! values carry a `schema_version` field, so consumers can tell which fields
+//! to expect. Adding a field bumps the minor version; renaming or removing
+//! one bumps the major version. A reader understands every record whose
+//! major version matches its own: fields added by a newer minor version
+//! are ignored (or kept as context fields by
+//! [`LogReader`](super::LogReader)), and fields missing from an older one
+//! are optional. Records without a `schema_version` predate it and follow
+//! version 1.0.
+//!
+//! [`Schema::current`] describes the fields of the version this crate
+//! writes, for tools that validate or document the format.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::core::{Schema, SchemaVersion, StructuredLogEntry};
+//! use rust_logger_system::prelude::*;
+//!
+//! let json = StructuredLogEntry::new(LogLevel::Info, "started").to_json().unwrap();
+//! assert!(json.contains(r#""schema_version":"1.1""#));
+//! assert!(StructuredLogEntry::from_json_checked(&json).is_ok());
+//!
+//! let future = json.replace(r#""1.1""#, r#""2.0""#);
+//! assert!(StructuredLogEntry::from_json_checked(&future).is_err());
+//!
+//! let schema = Schema::current();
+//! assert_eq!(schema.version, SchemaVersion::CURRENT);
+//! assert!(schema.field("message").is_some_and(|field| field.required));
+//! ```
+
+use super::error::{LoggerError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Name of the field holding the schema version
+pub const SCHEMA_VERSION_FIELD: &str = "schema_version";
+
+/// Version of the structured record layout, written as `"major.minor"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SchemaVersion {
+    /// Incremented when fields are renamed or removed
+    pub major: u16,
+    /// Incremented when fields are added
+    pub minor: u16,
+}
+
+impl SchemaVersion {
+    /// Version written by this crate
+    pub const CURRENT: SchemaVersion = SchemaVersion::new(1, 1);
+
+    /// Version assumed for records without a `schema_version` field
+    pub const LEGACY: SchemaVersion = SchemaVersion::new(1, 0);
+
+    /// Create a version
+    pub const fn new(major: u16, minor: u16) -> Self {
+        Self { major, minor }
+    }
+
+    /// Check whether records of this version can be read by this crate
+    pub fn is_compatible(&self) -> bool {
+        self.major == Self::CURRENT.major
+    }
+
+    /// Return an error unless records of this version can be read
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoggerError::FormatterError`] if the major version differs
+    /// from [`SchemaVersion::CURRENT`].
+    pub fn check(&self) -> Result<()> {
+        if self.is_compatible() {
+            Ok(())
+        } else {
+            Err(LoggerError::formatter(
+                "json",
+                format!(
+                    "unsupported schema version {} (supported: {}.x)",
+                    self,
+                    Self::CURRENT.major
+                ),
+            ))
+        }
+    }
+}
+
+impl Default for SchemaVersion {
+    fn default() -> Self {
+        Self::CURRENT
+    }
+}
+
+impl fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl std::str::FromStr for SchemaVersion {
+    type Err = String;
+
+    /// Parse `major.minor`, or a bare `major` meaning `major.0`
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("invalid schema version '{}'", s);
+        let (major, minor) = s.trim().split_once('.').unwrap_or((s.trim(), "0"));
+        Ok(Self::new(
+            major.parse().map_err(|_| invalid())?,
+            minor.parse().map_err(|_| invalid())?,
+        ))
+    }
+}
+
+impl TryFrom<String> for SchemaVersion {
+    type Error = String;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<SchemaVersion> for String {
+    fn from(version: SchemaVersion) -> Self {
+        version.to_string()
+    }
+}
+
+/// JSON type of a schema field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    /// A JSON string
+    String,
+    /// A JSON integer
+    Integer,
+    /// An RFC 3339 string or a number of seconds, milliseconds or
+    /// microseconds since the epoch, depending on the timestamp format
+    Timestamp,
+    /// A JSON object
+    Object,
+}
+
+/// Description of one field of a structured record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SchemaField {
+    /// Field name in JSON output
+    pub name: &'static str,
+    /// JSON type of the value
+    pub kind: FieldKind,
+    /// Whether every record has the field
+    pub required: bool,
+    /// Version that introduced the field
+    pub since: SchemaVersion,
+    /// What the field holds
+    pub description: &'static str,
+}
+
+impl SchemaField {
+    const fn new(
+        name: &'static str,
+        kind: FieldKind,
+        required: bool,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            kind,
+            required,
+            since: SchemaVersion::new(1, 0),
+            description,
+        }
+    }
+
+    const fn since(mut self, major: u16, minor: u16) -> Self {
+        self.since = SchemaVersion::new(major, minor);
+        self
+    }
+}
+
+/// Descriptor of the structured record layout
+///
+/// JSON output writes context fields next to these fields under their own
+/// names; a serialized [`StructuredLogEntry`](super::StructuredLogEntry)
+/// nests them under `fields`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Schema {
+    /// Version described
+    pub version: SchemaVersion,
+    /// Fields defined by this version
+    pub fields: &'static [SchemaField],
+}
+
+const FIELDS: &[SchemaField] = &[
+    SchemaField::new(
+        SCHEMA_VERSION_FIELD,
+        FieldKind::String,
+        false,
+        "Layout version as \"major.minor\"; absent in records older than 1.0",
+    ),
+    SchemaField::new("timestamp", FieldKind::Timestamp, true, "Time the entry was logged"),
+    SchemaField::new("level", FieldKind::String, true, "Level name, e.g. \"INFO\""),
+    SchemaField::new("message", FieldKind::String, true, "Log message"),
+    SchemaField::new("thread_id", FieldKind::String, false, "Logging thread"),
+    SchemaField::new("thread_name", FieldKind::String, false, "Name of the logging thread"),
+    SchemaField::new("file", FieldKind::String, false, "Source file of the call site"),
+    SchemaField::new("line", FieldKind::Integer, false, "Source line of the call site"),
+    SchemaField::new("module_path", FieldKind::String, false, "Module of the call site"),
+    SchemaField::new(
+        "target",
+        FieldKind::String,
+        false,
+        "Component the entry is about; the module path unless set explicitly",
+    )
+    .since(1, 1),
+    SchemaField::new("trace_id", FieldKind::String, false, "Distributed trace ID"),
+    SchemaField::new("span_id", FieldKind::String, false, "Span ID within the trace"),
+    SchemaField::new("parent_span_id", FieldKind::String, false, "ID of the parent span"),
+    SchemaField::new(
+        "tracing",
+        FieldKind::Object,
+        false,
+        "Trace, span and parent span IDs of a serialized StructuredLogEntry",
+    ),
+    SchemaField::new(
+        "fields",
+        FieldKind::Object,
+        false,
+        "Context fields of a serialized StructuredLogEntry",
+    ),
+];
+
+const CURRENT: Schema = Schema {
+    version: SchemaVersion::CURRENT,
+    fields: FIELDS,
+};
+
+impl Schema {
+    /// Get the schema written by this crate
+    pub fn current() -> &'static Schema {
+        &CURRENT
+    }
+
+    /// Look up a field by name
+    pub fn field(&self, name: &str) -> Option<&SchemaField> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Check whether `name` is a field of the schema rather than a context
+    /// field
+    pub fn is_reserved(&self, name: &str) -> bool {
+        self.field(name).is_some()
+    }
+
+    /// Serialize the descriptor as JSON, for publishing the format
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_parse_and_compatibility() {
+        assert_eq!("1.3".parse::<SchemaVersion>(), Ok(SchemaVersion::new(1, 3)));
+        assert_eq!("2".parse::<SchemaVersion>(), Ok(SchemaVersion::new(2, 0)));
+        assert!("1.x".parse::<SchemaVersion>().is_err());
+        assert_eq!(SchemaVersion::new(1, 3).to_string(), "1.3");
+
+        assert!(SchemaVersion::new(1, 7).check().is_ok());
+        let error = SchemaVersion::new(2, 0).check().unwrap_err().to_string();
+        assert!(error.contains("unsupported schema version 2.0"), "{}", error);
+
+        let json = serde_json::to_string(&SchemaVersion::LEGACY).unwrap();
+        assert_eq!(json, "\"1.0\"");
+        assert!(serde_json::from_str::<SchemaVersion>("\"one\"").is_err());
+    }
+
+    #[test]
+    fn test_descriptor_lists_required_fields() {
+        let schema = Schema::current();
+        let required: Vec<&str> = schema
+            .fields
+            .iter()
+            .filter(|field| field.required)
+            .map(|field| field.name)
+            .collect();
+        assert_eq!(required, ["timestamp", "level", "message"]);
+        assert!(schema.is_reserved("span_id"));
+        assert!(!schema.is_reserved("user_id"));
+        assert!(schema.to_json().contains("\"kind\": \"timestamp\""));
+    }
+}
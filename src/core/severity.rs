@@ -0,0 +1,266 @@
+//! Numeric severities and user-defined log levels
+//!
+//! Every entry has a [`Severity`], a number that orders it against all
+//! other entries. The built-in levels sit ten apart, from
+//! [`Severity::TRACE`] (0) to [`Severity::FATAL`] (50), leaving room for
+//! [`CustomLevel`]s such as `NOTICE` between `INFO` and `WARN` or `AUDIT`
+//! above `WARN`.
+//!
+//! An entry logged at a custom level keeps the nearest built-in level at or
+//! below its severity in [`LogEntry::level`](super::LogEntry::level), so
+//! code that only knows the built-in levels still sees a sensible value.
+//! The logger's minimum level, the level filters, overflow priorities and
+//! the formatters use the severity and name of the custom level instead,
+//! and thresholds can be severities between two built-in levels (see
+//! [`LoggerBuilder::min_severity`](super::LoggerBuilder::min_severity)).
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::core::{CustomLevel, Severity};
+//! use rust_logger_system::prelude::*;
+//!
+//! let notice = CustomLevel::new("NOTICE", 25);
+//! let logger = Logger::builder()
+//!     .min_level(LogLevel::Info)
+//!     .custom_level(notice.clone())
+//!     .build();
+//!
+//! assert_eq!(notice.level(), LogLevel::Info);
+//! assert!(logger.severity_enabled(notice.severity()));
+//! assert_eq!(logger.custom_level("notice"), Some(&notice));
+//! logger.log_custom(&notice, "certificate expires in 30 days");
+//! ```
+
+use super::error::{LoggerError, Result};
+use super::log_level::LogLevel;
+use super::overflow_policy::LogPriority;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+/// Numeric severity of an entry; higher is more severe
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Severity(pub u8);
+
+impl Severity {
+    /// Severity of [`LogLevel::Trace`]
+    pub const TRACE: Severity = Severity(0);
+    /// Severity of [`LogLevel::Debug`]
+    pub const DEBUG: Severity = Severity(10);
+    /// Severity of [`LogLevel::Info`]
+    pub const INFO: Severity = Severity(20);
+    /// Severity of [`LogLevel::Warn`]
+    pub const WARN: Severity = Severity(30);
+    /// Severity of [`LogLevel::Error`]
+    pub const ERROR: Severity = Severity(40);
+    /// Severity of [`LogLevel::Fatal`]
+    pub const FATAL: Severity = Severity(50);
+
+    /// Create a severity
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    /// Get the numeric value
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+
+    /// Get the nearest built-in level at or below this severity
+    pub const fn level(self) -> LogLevel {
+        match self.0 {
+            0..=9 => LogLevel::Trace,
+            10..=19 => LogLevel::Debug,
+            20..=29 => LogLevel::Info,
+            30..=39 => LogLevel::Warn,
+            40..=49 => LogLevel::Error,
+            _ => LogLevel::Fatal,
+        }
+    }
+
+    /// Get the OpenTelemetry `SeverityNumber` (1-24)
+    ///
+    /// Each built-in level maps to the first number of its OpenTelemetry
+    /// range, and severities between two levels to the numbers after it.
+    pub fn otel_number(self) -> u8 {
+        let base = self.level() as u8 * 4 + 1;
+        let above = u16::from(self.0 - self.level().severity().0);
+        base + (above * 4 / 10).min(3) as u8
+    }
+}
+
+impl From<LogLevel> for Severity {
+    fn from(level: LogLevel) -> Self {
+        level.severity()
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A user-defined level with its own name and severity
+///
+/// Register custom levels with
+/// [`LoggerBuilder::custom_level`](super::LoggerBuilder::custom_level) so
+/// they can be looked up by name, and log at them with
+/// [`Logger::log_custom`](super::Logger::log_custom).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomLevel {
+    name: Arc<str>,
+    severity: Severity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<LogPriority>,
+}
+
+impl CustomLevel {
+    /// Create a level named `name` with the given severity
+    ///
+    /// The name is written as given; lookups ignore case.
+    pub fn new(name: impl AsRef<str>, severity: u8) -> Self {
+        Self {
+            name: Arc::from(name.as_ref()),
+            severity: Severity(severity),
+            priority: None,
+        }
+    }
+
+    /// Set the overflow priority, instead of the one of [`level`](Self::level)
+    #[must_use = "builder methods return a new value"]
+    pub fn with_priority(mut self, priority: LogPriority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Get the name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the severity
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Get the nearest built-in level at or below the severity
+    pub fn level(&self) -> LogLevel {
+        self.severity.level()
+    }
+
+    /// Get the priority for overflow handling
+    pub fn priority(&self) -> LogPriority {
+        self.priority.unwrap_or_else(|| self.level().priority())
+    }
+}
+
+impl From<&CustomLevel> for Severity {
+    fn from(level: &CustomLevel) -> Self {
+        level.severity
+    }
+}
+
+impl fmt::Display for CustomLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Custom levels of a logger, looked up by name
+#[derive(Debug, Clone, Default)]
+pub struct LevelRegistry {
+    levels: Vec<CustomLevel>,
+}
+
+impl LevelRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a custom level
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoggerError::InvalidConfiguration`] if the name is empty,
+    /// is the name of a built-in level, or is already registered.
+    pub fn register(&mut self, level: CustomLevel) -> Result<()> {
+        let invalid = |message: String| Err(LoggerError::config("custom_level", message));
+        if level.name.trim().is_empty() {
+            return invalid("name must not be empty".to_string());
+        }
+        if level.name.parse::<LogLevel>().is_ok() {
+            return invalid(format!("'{}' is a built-in level", level.name));
+        }
+        if self.get(&level.name).is_some() {
+            return invalid(format!("'{}' is registered more than once", level.name));
+        }
+        self.levels.push(level);
+        Ok(())
+    }
+
+    /// Look up a custom level by name, ignoring case
+    pub fn get(&self, name: &str) -> Option<&CustomLevel> {
+        self.levels.iter().find(|level| level.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Get the severity of a built-in or custom level name
+    pub fn severity_of(&self, name: &str) -> Option<Severity> {
+        match name.parse::<LogLevel>() {
+            Ok(level) => Some(level.severity()),
+            Err(_) => self.get(name).map(CustomLevel::severity),
+        }
+    }
+
+    /// Iterate over the custom levels in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &CustomLevel> {
+        self.levels.iter()
+    }
+
+    /// Check whether no custom levels are registered
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_maps_to_levels() {
+        assert_eq!(Severity::from(LogLevel::Warn), Severity::WARN);
+        assert_eq!(Severity(25).level(), LogLevel::Info);
+        assert_eq!(Severity(200).level(), LogLevel::Fatal);
+
+        assert_eq!(Severity::TRACE.otel_number(), 1);
+        assert_eq!(Severity::INFO.otel_number(), 9);
+        assert_eq!(Severity(25).otel_number(), 11);
+        assert_eq!(Severity::FATAL.otel_number(), 21);
+        assert_eq!(Severity(255).otel_number(), 24);
+
+        let audit = CustomLevel::new("AUDIT", 35);
+        assert_eq!(audit.priority(), LogPriority::High);
+        assert_eq!(audit.with_priority(LogPriority::Critical).priority(), LogPriority::Critical);
+    }
+
+    #[test]
+    fn test_registry_rejects_conflicting_names() {
+        let mut registry = LevelRegistry::new();
+        registry.register(CustomLevel::new("Notice", 25)).unwrap();
+
+        assert!(registry.register(CustomLevel::new("NOTICE", 26)).is_err());
+        assert!(registry.register(CustomLevel::new("warning", 30)).is_err());
+        assert!(registry.register(CustomLevel::new(" ", 30)).is_err());
+
+        assert_eq!(registry.get("notice").map(CustomLevel::name), Some("Notice"));
+        assert_eq!(registry.severity_of("NOTICE"), Some(Severity(25)));
+        assert_eq!(registry.severity_of("error"), Some(Severity::ERROR));
+        assert_eq!(registry.severity_of("audit"), None);
+    }
+}
@@ -0,0 +1,238 @@
+//! Lightweight timed spans
+//!
+//! [`Logger::span`] returns a [`Span`] guard that logs when an operation
+//! starts and finishes, including the elapsed time. While the guard is
+//! alive, every entry logged on the same thread carries the span's
+//! [`TracingContext`], so output formats include `trace_id` and `span_id`.
+//!
+//! Spans nest: a span opened inside another one shares its trace ID and
+//! records the outer span as its parent. With the `otel` feature, a span
+//! opened inside an active OpenTelemetry span joins that trace.
+//!
+//! The active span is tracked per thread. Entries logged from other threads
+//! (including async tasks resumed on another worker thread) do not see it.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//!
+//! let logger = Logger::builder()
+//!     .appender(ConsoleAppender::new())
+//!     .build();
+//!
+//! {
+//!     let _span = logger.span("checkout");
+//!     logger.info("charging card"); // carries the span's trace_id/span_id
+//! } // logs "checkout finished" with the elapsed time
+//! ```
+
+use super::log_context::LogContext;
+use super::log_entry::LogEntry;
+use super::log_level::LogLevel;
+use super::logger::Logger;
+use super::structured_entry::TracingContext;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static SPAN_STACK: RefCell<Vec<TracingContext>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Tracing context of the innermost span active on the current thread
+pub fn current_span() -> Option<TracingContext> {
+    SPAN_STACK.with(|stack| stack.borrow().last().cloned())
+}
+
+/// Tracing context attached to entries that carry none of their own
+///
+/// The innermost [`Span`] wins; with the `otel` feature enabled, the active
+/// OpenTelemetry span is used otherwise.
+pub(crate) fn ambient_context() -> Option<TracingContext> {
+    let current = current_span();
+    #[cfg(feature = "otel")]
+    let current = current.or_else(crate::bridge::current_otel_context);
+    current
+}
+
+/// Guard for a timed span
+///
+/// Created by [`Logger::span`] or [`Logger::span_at`]. Logs a `<name>
+/// started` entry when created and a `<name> finished` entry with an
+/// `elapsed` field when dropped. Both entries carry a `span` field with the
+/// span name.
+///
+/// The guard must be dropped on the thread that created it.
+#[must_use = "the span ends as soon as the guard is dropped"]
+pub struct Span<'a> {
+    logger: &'a Logger,
+    level: LogLevel,
+    name: String,
+    context: TracingContext,
+    start: Instant,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl<'a> Span<'a> {
+    pub(crate) fn enter(logger: &'a Logger, level: LogLevel, name: String) -> Self {
+        let context = match ambient_context() {
+            Some(parent) => TracingContext::new(parent.trace_id, new_span_id())
+                .with_parent(parent.span_id),
+            None => TracingContext::new(new_trace_id(), new_span_id()),
+        };
+        SPAN_STACK.with(|stack| stack.borrow_mut().push(context.clone()));
+
+        let span = Self {
+            logger,
+            level,
+            name,
+            context,
+            start: Instant::now(),
+            _not_send: PhantomData,
+        };
+        span.emit(format!("{} started", span.name), LogContext::new());
+        span
+    }
+
+    /// Get the span name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the span ID
+    pub fn id(&self) -> &str {
+        &self.context.span_id
+    }
+
+    /// Get the trace ID shared by this span and all nested spans
+    pub fn trace_id(&self) -> &str {
+        &self.context.trace_id
+    }
+
+    /// Get the full tracing context of this span
+    pub fn context(&self) -> &TracingContext {
+        &self.context
+    }
+
+    /// Time elapsed since the span was entered
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn emit(&self, message: String, context: LogContext) {
+        let entry = LogEntry::new(self.level, message)
+            .with_context(context.with_field("span", self.name.as_str()))
+            .with_tracing(self.context.clone());
+        self.logger.log_entry(entry);
+    }
+}
+
+impl Drop for Span<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.elapsed();
+        SPAN_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            if let Some(pos) = stack
+                .iter()
+                .rposition(|ctx| ctx.span_id == self.context.span_id)
+            {
+                stack.remove(pos);
+            }
+        });
+        self.emit(
+            format!("{} finished", self.name),
+            LogContext::new().with_field("elapsed", elapsed),
+        );
+    }
+}
+
+impl std::fmt::Debug for Span<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Span")
+            .field("name", &self.name)
+            .field("level", &self.level)
+            .field("context", &self.context)
+            .field("elapsed", &self.elapsed())
+            .finish()
+    }
+}
+
+fn new_trace_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+fn new_span_id() -> String {
+    format!("{:016x}", rand::random::<u64>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::FieldValue;
+    use crate::testing::CaptureAppender;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    fn capture_logger() -> (Logger, Arc<Mutex<Vec<LogEntry>>>) {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .min_level(LogLevel::Trace)
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .build();
+        (logger, entries)
+    }
+
+    #[test]
+    fn test_span_logs_entry_and_exit() {
+        let (logger, entries) = capture_logger();
+        {
+            let span = logger.span("load");
+            assert_eq!(span.trace_id().len(), 32);
+            assert_eq!(span.id().len(), 16);
+            logger.info("inside");
+        }
+        logger.info("outside");
+
+        let entries = entries.lock();
+        let messages: Vec<_> = entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["load started", "inside", "load finished", "outside"]);
+
+        let span_ctx = entries[0].tracing.clone().expect("span context");
+        assert_eq!(entries[1].tracing.as_ref(), Some(&span_ctx));
+        assert_eq!(entries[2].tracing.as_ref(), Some(&span_ctx));
+        assert!(entries[3].tracing.is_none());
+
+        let fields = entries[2].context.as_ref().unwrap().fields();
+        assert_eq!(fields["span"], "load".into());
+        assert!(matches!(fields["elapsed"], FieldValue::Duration(_)));
+    }
+
+    #[test]
+    fn test_nested_spans_share_trace() {
+        let (logger, entries) = capture_logger();
+        {
+            let outer = logger.span("request");
+            let inner = logger.span_at(LogLevel::Debug, "query");
+            assert_eq!(inner.trace_id(), outer.trace_id());
+            assert_eq!(inner.context().parent_span_id.as_deref(), Some(outer.id()));
+            assert_eq!(current_span().as_ref(), Some(inner.context()));
+            drop(inner);
+            assert_eq!(current_span().as_ref(), Some(outer.context()));
+        }
+        assert!(current_span().is_none());
+        assert_eq!(entries.lock()[1].level, LogLevel::Debug);
+    }
+
+    #[test]
+    fn test_span_is_thread_local() {
+        let (logger, entries) = capture_logger();
+        let _span = logger.span("main");
+        std::thread::scope(|s| {
+            s.spawn(|| logger.info("other thread"));
+        });
+
+        let entries = entries.lock();
+        assert!(entries[1].tracing.is_none());
+    }
+}
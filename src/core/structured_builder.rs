@@ -3,8 +3,10 @@
 //! Provides a builder pattern for creating log entries with structured fields.
 
 use super::log_context::{FieldValue, LogContext};
+use super::log_entry::{intern, LogEntry};
 use super::log_level::LogLevel;
 use super::logger::Logger;
+use std::sync::Arc;
 
 /// Builder for structured log entries
 ///
@@ -32,6 +34,7 @@ pub struct StructuredLogBuilder<'a> {
     file: Option<&'static str>,
     line: Option<u32>,
     module_path: Option<&'static str>,
+    target: Option<Arc<str>>,
 }
 
 impl<'a> StructuredLogBuilder<'a> {
@@ -45,6 +48,7 @@ impl<'a> StructuredLogBuilder<'a> {
             file: None,
             line: None,
             module_path: None,
+            target: None,
         }
     }
 
@@ -69,12 +73,39 @@ impl<'a> StructuredLogBuilder<'a> {
     /// Add multiple fields from a LogContext
     #[must_use]
     pub fn fields(mut self, context: LogContext) -> Self {
-        for (key, value) in context.fields().iter() {
+        for (key, value) in context.field_map().iter() {
             self.context.add_field(key.clone(), value.clone());
         }
         self
     }
 
+    /// Record an error and its `source()` chain as structured fields
+    ///
+    /// See [`LogContext::with_error`] for the fields that are added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_logger_system::prelude::*;
+    ///
+    /// let logger = Logger::new();
+    ///
+    /// if let Err(e) = std::fs::read("/nonexistent/config.toml") {
+    ///     logger.error_builder()
+    ///         .message("Failed to load config")
+    ///         .error(&e)
+    ///         .log();
+    /// }
+    /// ```
+    #[must_use]
+    pub fn error<E>(mut self, error: &E) -> Self
+    where
+        E: std::error::Error + ?Sized,
+    {
+        self.context.add_error(error);
+        self
+    }
+
     /// Set source location information
     #[must_use]
     pub fn location(mut self, file: &'static str, line: u32, module_path: &'static str) -> Self {
@@ -84,11 +115,29 @@ impl<'a> StructuredLogBuilder<'a> {
         self
     }
 
+    /// Set the target, which defaults to the module path of the
+    /// [`location`](Self::location)
+    #[must_use]
+    pub fn target(mut self, target: &str) -> Self {
+        self.target = Some(intern(target));
+        self
+    }
+
     /// Build and send the log entry
     ///
     /// This consumes the builder and logs the entry.
     pub fn log(self) {
-        self.logger.log_with_context(self.level, self.message, self.context);
+        let mut entry = LogEntry::new(self.level, self.message).with_context(self.context);
+        entry.target = self.target;
+        if let Some(file) = self.file {
+            entry.file = Some(intern(file));
+            entry.line = self.line;
+            entry.module_path = self.module_path.map(intern);
+            if entry.target.is_none() {
+                entry.target = entry.module_path.clone();
+            }
+        }
+        self.logger.log_entry(entry);
     }
 }
 
@@ -273,4 +322,23 @@ mod tests {
             .field("key", "value")
             .log();
     }
+
+    #[test]
+    fn test_structured_builder_error() {
+        let logger = Logger::builder()
+            .min_level(LogLevel::Trace)
+            .build();
+
+        let err = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let dyn_err: &dyn std::error::Error = &err;
+
+        logger.error_builder()
+            .message("Lookup failed")
+            .error(&err)
+            .log();
+        logger.error_builder()
+            .message("Lookup failed")
+            .error(dyn_err)
+            .log();
+    }
 }
@@ -1,11 +1,13 @@
 //! Structured log entry with distributed tracing support
 
 use super::log_context::LogContext;
+use super::log_entry::LogEntry;
 use super::log_level::LogLevel;
+use super::schema::{SchemaVersion, SCHEMA_VERSION_FIELD};
 use serde::{Deserialize, Serialize};
 
 /// Tracing context for distributed tracing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TracingContext {
     /// Trace ID for request correlation
     pub trace_id: String,
@@ -33,11 +35,33 @@ impl TracingContext {
         self.parent_span_id = Some(parent_span_id);
         self
     }
+
+    /// Insert `trace_id`, `span_id` and `parent_span_id` into a JSON object
+    pub(crate) fn insert_json(&self, json_obj: &mut serde_json::Map<String, serde_json::Value>) {
+        json_obj.insert(
+            "trace_id".to_string(),
+            serde_json::Value::String(self.trace_id.clone()),
+        );
+        json_obj.insert(
+            "span_id".to_string(),
+            serde_json::Value::String(self.span_id.clone()),
+        );
+        if let Some(ref parent) = self.parent_span_id {
+            json_obj.insert(
+                "parent_span_id".to_string(),
+                serde_json::Value::String(parent.clone()),
+            );
+        }
+    }
 }
 
 /// Structured log entry with distributed tracing support
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StructuredLogEntry {
+    /// Layout version of the record; see [`Schema`](super::Schema)
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: SchemaVersion,
+
     /// Log timestamp in milliseconds since epoch
     pub timestamp: i64,
 
@@ -60,6 +84,7 @@ impl StructuredLogEntry {
     /// Create a new structured log entry
     pub fn new(level: LogLevel, message: impl Into<String>) -> Self {
         Self {
+            schema_version: SchemaVersion::CURRENT,
             timestamp: chrono::Utc::now().timestamp_millis(),
             level,
             message: message.into(),
@@ -71,6 +96,7 @@ impl StructuredLogEntry {
     /// Create from existing LogContext
     pub fn from_context(level: LogLevel, message: impl Into<String>, context: LogContext) -> Self {
         Self {
+            schema_version: SchemaVersion::CURRENT,
             timestamp: chrono::Utc::now().timestamp_millis(),
             level,
             message: message.into(),
@@ -99,6 +125,45 @@ impl StructuredLogEntry {
     pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
         serde_json::from_str(json)
     }
+
+    /// Parse from JSON string, rejecting records of an incompatible schema
+    /// version
+    ///
+    /// The version is checked before the fields, so a record whose layout
+    /// changed is reported as such rather than as a missing field.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid, the schema version is not
+    /// supported (see [`SchemaVersion::check`]) or a required field is
+    /// missing.
+    pub fn from_json_checked(json: &str) -> super::error::Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        if let Some(version) = value.get(SCHEMA_VERSION_FIELD) {
+            serde_json::from_value::<SchemaVersion>(version.clone())?.check()?;
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+/// Version of records written before the field existed
+fn legacy_schema_version() -> SchemaVersion {
+    SchemaVersion::LEGACY
+}
+
+impl From<&LogEntry> for StructuredLogEntry {
+    /// Convert a log entry, keeping its timestamp, level, message, context
+    /// fields and tracing context
+    fn from(entry: &LogEntry) -> Self {
+        Self {
+            schema_version: SchemaVersion::CURRENT,
+            timestamp: entry.timestamp.timestamp_millis(),
+            level: entry.level,
+            message: entry.message.clone(),
+            context: entry.context.clone().unwrap_or_default(),
+            tracing: entry.tracing.clone(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -150,6 +215,27 @@ mod tests {
         assert_eq!(deserialized.message, "Warning message");
     }
 
+    #[test]
+    fn test_schema_version_check() {
+        let entry = StructuredLogEntry::new(LogLevel::Info, "versioned");
+        let json = entry.to_json().unwrap();
+        assert!(json.contains("\"schema_version\":\"1.1\""));
+
+        let mut newer_minor: serde_json::Value = serde_json::from_str(&json).unwrap();
+        newer_minor["schema_version"] = "1.4".into();
+        newer_minor["added"] = 1.into();
+        let parsed = StructuredLogEntry::from_json_checked(&newer_minor.to_string()).unwrap();
+        assert_eq!(parsed.schema_version, SchemaVersion::new(1, 4));
+
+        let legacy = r#"{"timestamp":0,"level":"Info","message":"old"}"#;
+        let parsed = StructuredLogEntry::from_json_checked(legacy).unwrap();
+        assert_eq!(parsed.schema_version, SchemaVersion::LEGACY);
+
+        let renamed = r#"{"schema_version":"2.0","ts":0,"severity":"Info","msg":"new"}"#;
+        let error = StructuredLogEntry::from_json_checked(renamed).unwrap_err().to_string();
+        assert!(error.contains("unsupported schema version 2.0"), "{}", error);
+    }
+
     #[test]
     fn test_tracing_context() {
         let tracing = TracingContext::new("trace-abc".to_string(), "span-123".to_string())
@@ -0,0 +1,523 @@
+//! Burst suppression for repetitive log messages
+//!
+//! Limits how often the same call site (or message template) can log within
+//! a time window. The first `max_per_window` occurrences are logged as usual;
+//! further occurrences are suppressed and counted. When the window closes, a
+//! single summary entry such as `suppressed 4,312 similar messages` is
+//! emitted: before the next entry of the same key, or on the next entry of
+//! any key, flush or shutdown if the burst has stopped.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//! use std::time::Duration;
+//!
+//! let logger = Logger::builder()
+//!     .burst_suppression(SuppressionConfig::new(5, Duration::from_secs(10)))
+//!     .build();
+//!
+//! for i in 0..1000 {
+//!     // Only the first 5 are written in each 10 second window
+//!     logger.error(format!("connection refused (attempt {})", i));
+//! }
+//! ```
+
+use super::clock::{self, Clock};
+use super::log_context::LogContext;
+use super::log_entry::LogEntry;
+use super::log_level::LogLevel;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Upper bound on tracked keys; the oldest window is closed beyond it
+const MAX_TRACKED_KEYS: usize = 10_000;
+
+/// How log entries are grouped for burst suppression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SuppressionKey {
+    /// Group by source location (`file:line`)
+    ///
+    /// Entries without location information fall back to `Template`.
+    #[default]
+    CallSite,
+
+    /// Group by message template
+    ///
+    /// Digits are masked so that messages differing only in numbers
+    /// (ids, counters, ports) are treated as the same template.
+    Template,
+}
+
+/// Configuration for burst suppression
+#[derive(Debug, Clone)]
+pub struct SuppressionConfig {
+    /// Number of occurrences logged per key within each window
+    pub max_per_window: u64,
+
+    /// Length of the suppression window
+    pub window: Duration,
+
+    /// How entries are grouped
+    pub key: SuppressionKey,
+}
+
+impl Default for SuppressionConfig {
+    fn default() -> Self {
+        Self {
+            max_per_window: 10,
+            window: Duration::from_secs(60),
+            key: SuppressionKey::default(),
+        }
+    }
+}
+
+impl SuppressionConfig {
+    /// Create a config allowing `max_per_window` occurrences per `window`
+    pub fn new(max_per_window: u64, window: Duration) -> Self {
+        Self {
+            max_per_window,
+            window,
+            ..Default::default()
+        }
+    }
+
+    /// Set how entries are grouped
+    #[must_use]
+    pub fn with_key(mut self, key: SuppressionKey) -> Self {
+        self.key = key;
+        self
+    }
+}
+
+/// Per-key window state
+#[derive(Debug)]
+struct WindowState {
+    start: Instant,
+    count: u64,
+    suppressed: u64,
+    /// Level and location the summary is reported with
+    origin: Origin,
+}
+
+/// Level and location of the entry that opened a window
+#[derive(Debug)]
+struct Origin {
+    level: LogLevel,
+    file: Option<Arc<str>>,
+    line: Option<u32>,
+    module_path: Option<Arc<str>>,
+}
+
+impl Origin {
+    fn of(entry: &LogEntry) -> Self {
+        Self {
+            level: entry.level,
+            file: entry.file.clone(),
+            line: entry.line,
+            module_path: entry.module_path.clone(),
+        }
+    }
+}
+
+/// Open windows, with their keys in the order the windows opened
+#[derive(Debug, Default)]
+struct Windows {
+    states: HashMap<String, WindowState>,
+    /// Start and key of each window, oldest first; a window that reopened
+    /// leaves a stale item, recognized by its start
+    opened: VecDeque<(Instant, String)>,
+    /// Summaries of closed windows, not yet taken
+    summaries: Vec<LogEntry>,
+}
+
+/// Outcome of checking an entry against the suppressor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuppressionDecision {
+    /// Log the entry
+    Allow,
+
+    /// Log a summary for the previous window, then the entry
+    ///
+    /// The value is the number of entries suppressed in the previous window.
+    AllowWithSummary(u64),
+
+    /// Drop the entry
+    Suppress,
+}
+
+/// Tracks per-key occurrence counts and decides which entries to suppress
+///
+/// Thread-safe: the window table is protected by a mutex and the total
+/// counter is atomic.
+pub struct BurstSuppressor {
+    config: SuppressionConfig,
+    windows: Mutex<Windows>,
+    /// Whether `windows` holds summaries, checked without the lock
+    has_summaries: AtomicBool,
+    suppressed_total: AtomicU64,
+    clock: Arc<dyn Clock>,
+}
+
+impl BurstSuppressor {
+    /// Create a new suppressor with the given configuration
+    pub fn new(config: SuppressionConfig) -> Self {
+        Self::with_clock(config, clock::system_clock())
+    }
+
+    /// Create a suppressor measuring its windows with `clock`
+    pub fn with_clock(config: SuppressionConfig, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            config,
+            windows: Mutex::new(Windows::default()),
+            has_summaries: AtomicBool::new(false),
+            suppressed_total: AtomicU64::new(0),
+            clock,
+        }
+    }
+
+    /// Decide whether an entry should be logged
+    ///
+    /// Windows of other keys that have closed meanwhile leave their
+    /// summaries for [`take_summaries`](Self::take_summaries).
+    pub fn check(&self, entry: &LogEntry) -> SuppressionDecision {
+        let key = self.key_for(entry);
+        let now = self.clock.now();
+        let mut windows = self.windows.lock();
+
+        let decision = match windows.states.get_mut(&key) {
+            Some(state) if now.duration_since(state.start) >= self.config.window => {
+                let suppressed = state.suppressed;
+                state.start = now;
+                state.count = 1;
+                state.suppressed = 0;
+                state.origin = Origin::of(entry);
+                windows.opened.push_back((now, key));
+                if suppressed > 0 {
+                    SuppressionDecision::AllowWithSummary(suppressed)
+                } else {
+                    SuppressionDecision::Allow
+                }
+            }
+            Some(state) if state.count < self.config.max_per_window => {
+                state.count += 1;
+                SuppressionDecision::Allow
+            }
+            Some(state) => {
+                state.suppressed += 1;
+                self.suppressed_total.fetch_add(1, Ordering::Relaxed);
+                SuppressionDecision::Suppress
+            }
+            None => {
+                while windows.states.len() >= MAX_TRACKED_KEYS {
+                    self.close_oldest(&mut windows);
+                }
+                let state = WindowState {
+                    start: now,
+                    count: 1,
+                    suppressed: 0,
+                    origin: Origin::of(entry),
+                };
+                windows.states.insert(key.clone(), state);
+                windows.opened.push_back((now, key));
+                SuppressionDecision::Allow
+            }
+        };
+
+        self.close_expired(&mut windows, now);
+        decision
+    }
+
+    /// Take the summaries of windows closed since the last call
+    ///
+    /// Also closes the windows that have expired by now.
+    pub fn take_summaries(&self) -> Vec<LogEntry> {
+        let mut windows = self.windows.lock();
+        self.close_expired(&mut windows, self.clock.now());
+        self.has_summaries.store(false, Ordering::Relaxed);
+        std::mem::take(&mut windows.summaries)
+    }
+
+    /// Whether [`take_summaries`](Self::take_summaries) has summaries to
+    /// return, without taking the lock
+    ///
+    /// Only covers windows closed by [`check`](Self::check); expired
+    /// windows not seen since are not counted.
+    pub fn has_summaries(&self) -> bool {
+        self.has_summaries.load(Ordering::Relaxed)
+    }
+
+    /// Close every window, returning the summaries of all that suppressed
+    /// entries
+    ///
+    /// For shutdown, when no later entry would report them.
+    pub fn close_all(&self) -> Vec<LogEntry> {
+        let mut windows = self.windows.lock();
+        while !windows.opened.is_empty() {
+            self.close_oldest(&mut windows);
+        }
+        self.has_summaries.store(false, Ordering::Relaxed);
+        std::mem::take(&mut windows.summaries)
+    }
+
+    /// Close the windows that have expired at `now`
+    fn close_expired(&self, windows: &mut Windows, now: Instant) {
+        while windows
+            .opened
+            .front()
+            .is_some_and(|(start, _)| now.duration_since(*start) >= self.config.window)
+        {
+            self.close_oldest(windows);
+        }
+    }
+
+    /// Close the oldest window, keeping its summary if it suppressed entries
+    fn close_oldest(&self, windows: &mut Windows) {
+        let Some((start, key)) = windows.opened.pop_front() else {
+            return;
+        };
+        if windows.states.get(&key).is_none_or(|state| state.start != start) {
+            return;
+        }
+        if let Some(state) = windows.states.remove(&key) {
+            if state.suppressed > 0 {
+                let summary = self.summary(&key, &state.origin, state.suppressed);
+                windows.summaries.push(summary);
+                self.has_summaries.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Build the summary entry emitted when a window with suppressed entries closes
+    pub fn summary_entry(&self, entry: &LogEntry, suppressed: u64) -> LogEntry {
+        self.summary(&self.key_for(entry), &Origin::of(entry), suppressed)
+    }
+
+    fn summary(&self, key: &str, origin: &Origin, suppressed: u64) -> LogEntry {
+        let mut summary = LogEntry::new(
+            origin.level,
+            format!(
+                "suppressed {} similar messages",
+                format_thousands(suppressed)
+            ),
+        )
+        .with_context(
+            LogContext::new()
+                .with_field("suppressed_count", suppressed as i64)
+                .with_field("suppressed_key", key),
+        );
+        summary.file = origin.file.clone();
+        summary.line = origin.line;
+        summary.module_path = origin.module_path.clone();
+        summary
+    }
+
+    /// Total number of entries suppressed since creation
+    pub fn suppressed_total(&self) -> u64 {
+        self.suppressed_total.load(Ordering::Relaxed)
+    }
+
+    /// Get a reference to the suppression configuration
+    pub fn config(&self) -> &SuppressionConfig {
+        &self.config
+    }
+
+    fn key_for(&self, entry: &LogEntry) -> String {
+        match (self.config.key, &entry.file, entry.line) {
+            (SuppressionKey::CallSite, Some(file), Some(line)) => format!("{}:{}", file, line),
+            _ => template_of(&entry.message),
+        }
+    }
+}
+
+impl std::fmt::Debug for BurstSuppressor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BurstSuppressor")
+            .field("config", &self.config)
+            .field("suppressed_total", &self.suppressed_total())
+            .finish()
+    }
+}
+
+/// Mask runs of digits so that similar messages share a template
+fn template_of(message: &str) -> String {
+    let mut template = String::with_capacity(message.len());
+    let mut in_digits = false;
+    for c in message.chars() {
+        if c.is_ascii_digit() {
+            if !in_digits {
+                template.push('#');
+            }
+            in_digits = true;
+        } else {
+            template.push(c);
+            in_digits = false;
+        }
+    }
+    template
+}
+
+/// Format a count with thousands separators (e.g. `4,312`)
+fn format_thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ManualClock;
+
+    fn entry(message: &str) -> LogEntry {
+        LogEntry::new(LogLevel::Error, message.to_string())
+    }
+
+    #[test]
+    fn test_template_masks_digits() {
+        assert_eq!(template_of("retry 3 of 10"), "retry # of #");
+        assert_eq!(template_of("port 8080 closed"), template_of("port 9090 closed"));
+    }
+
+    #[test]
+    fn test_format_thousands() {
+        assert_eq!(format_thousands(0), "0");
+        assert_eq!(format_thousands(999), "999");
+        assert_eq!(format_thousands(4312), "4,312");
+        assert_eq!(format_thousands(1234567), "1,234,567");
+    }
+
+    #[test]
+    fn test_suppresses_after_limit() {
+        let suppressor = BurstSuppressor::new(SuppressionConfig::new(3, Duration::from_secs(60)));
+
+        for i in 0..3 {
+            assert_eq!(
+                suppressor.check(&entry(&format!("failure {}", i))),
+                SuppressionDecision::Allow
+            );
+        }
+        for i in 3..10 {
+            assert_eq!(
+                suppressor.check(&entry(&format!("failure {}", i))),
+                SuppressionDecision::Suppress
+            );
+        }
+        assert_eq!(suppressor.suppressed_total(), 7);
+
+        // Different template is tracked independently
+        assert_eq!(suppressor.check(&entry("other")), SuppressionDecision::Allow);
+    }
+
+    #[test]
+    fn test_summary_after_window_rollover() {
+        let clock = Arc::new(ManualClock::new());
+        let config = SuppressionConfig::new(1, Duration::from_secs(20));
+        let suppressor = BurstSuppressor::with_clock(config, clock.clone());
+
+        assert_eq!(suppressor.check(&entry("tick 1")), SuppressionDecision::Allow);
+        assert_eq!(suppressor.check(&entry("tick 2")), SuppressionDecision::Suppress);
+        assert_eq!(suppressor.check(&entry("tick 3")), SuppressionDecision::Suppress);
+
+        clock.advance(Duration::from_secs(20));
+
+        assert_eq!(
+            suppressor.check(&entry("tick 4")),
+            SuppressionDecision::AllowWithSummary(2)
+        );
+        assert_eq!(suppressor.check(&entry("tick 5")), SuppressionDecision::Suppress);
+        assert!(suppressor.take_summaries().is_empty());
+    }
+
+    #[test]
+    fn test_summary_when_burst_stops() {
+        let clock = Arc::new(ManualClock::new());
+        let config = SuppressionConfig::new(1, Duration::from_secs(20));
+        let suppressor = BurstSuppressor::with_clock(config, clock.clone());
+
+        suppressor.check(&entry("disk full"));
+        suppressor.check(&entry("disk full"));
+        suppressor.check(&entry("queue 1 stalled"));
+        suppressor.check(&entry("queue 2 stalled"));
+        suppressor.check(&entry("queue 3 stalled"));
+        assert!(!suppressor.has_summaries());
+
+        // Another key closes the expired windows
+        clock.advance(Duration::from_secs(20));
+        assert_eq!(suppressor.check(&entry("other")), SuppressionDecision::Allow);
+        assert!(suppressor.has_summaries());
+        let summaries: Vec<_> = suppressor
+            .take_summaries()
+            .into_iter()
+            .map(|summary| summary.message)
+            .collect();
+        assert_eq!(
+            summaries,
+            ["suppressed 1 similar messages", "suppressed 2 similar messages"]
+        );
+        assert!(!suppressor.has_summaries());
+
+        // Without further entries, taking summaries closes expired windows
+        suppressor.check(&entry("other"));
+        clock.advance(Duration::from_secs(20));
+        assert_eq!(suppressor.take_summaries().len(), 1);
+
+        // Shutdown closes open windows
+        suppressor.check(&entry("late"));
+        suppressor.check(&entry("late"));
+        assert!(suppressor.take_summaries().is_empty());
+        assert_eq!(suppressor.close_all()[0].message, "suppressed 1 similar messages");
+    }
+
+    #[test]
+    fn test_tracked_keys_are_bounded() {
+        let suppressor = BurstSuppressor::new(SuppressionConfig::new(1, Duration::from_secs(60)));
+        let first = entry("request a failed");
+        suppressor.check(&first);
+        suppressor.check(&first);
+
+        // Keys without digits, so templates stay distinct
+        for i in 0..MAX_TRACKED_KEYS {
+            let id: String = format!("{:04}", i)
+                .bytes()
+                .map(|digit| (digit - b'0' + b'b') as char)
+                .collect();
+            suppressor.check(&entry(&format!("request {} failed", id)));
+        }
+        let windows = suppressor.windows.lock();
+        assert_eq!(windows.states.len(), MAX_TRACKED_KEYS);
+        assert!(!windows.states.contains_key("request a failed"));
+        assert_eq!(windows.summaries.len(), 1);
+    }
+
+    #[test]
+    fn test_call_site_key() {
+        let suppressor = BurstSuppressor::new(SuppressionConfig::new(1, Duration::from_secs(60)));
+        let a = entry("same").with_location("a.rs", 1, "a");
+        let b = entry("same").with_location("a.rs", 2, "a");
+
+        assert_eq!(suppressor.check(&a), SuppressionDecision::Allow);
+        assert_eq!(suppressor.check(&b), SuppressionDecision::Allow);
+        assert_eq!(suppressor.check(&a), SuppressionDecision::Suppress);
+    }
+
+    #[test]
+    fn test_summary_entry() {
+        let suppressor = BurstSuppressor::new(SuppressionConfig::default());
+        let summary = suppressor.summary_entry(&entry("disk full"), 4312);
+
+        assert_eq!(summary.level, LogLevel::Error);
+        assert_eq!(summary.message, "suppressed 4,312 similar messages");
+        let fields = summary.context.as_ref().unwrap().fields();
+        assert_eq!(fields.get("suppressed_count"), Some(&4312_i64.into()));
+    }
+}
@@ -0,0 +1,342 @@
+//! Synchronization primitives of the logger and its async queue
+//!
+//! [`Logger`](super::Logger) and the queue to its worker take their atomics,
+//! locks, channel and threads from here rather than from `std`,
+//! `parking_lot` and `crossbeam_channel` directly. Built with
+//! `RUSTFLAGS="--cfg loom"`, they are replaced by [loom]'s model-checked
+//! versions, so the tests in `tests/loom.rs` can explore every interleaving
+//! of logging threads, the worker, overflow handling and shutdown:
+//!
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --release --test loom
+//! ```
+//!
+//! Loom cannot model timeouts. Under loom, waits with a timeout give up
+//! as soon as they would block, sleeps only yield, and the lock-free ring of
+//! [`AsyncQueue::LockFree`](super::AsyncQueue::LockFree) is replaced by the
+//! channel.
+//!
+//! [loom]: https://docs.rs/loom
+
+use std::time::Duration;
+
+#[cfg(not(loom))]
+pub(crate) use parking_lot::{Mutex, RwLock};
+#[cfg(not(loom))]
+pub(crate) use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+
+#[cfg(loom)]
+pub(crate) use loom::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+#[cfg(loom)]
+pub(crate) use loom_lock::{Mutex, RwLock};
+
+/// Threads of the async worker
+pub(crate) mod thread {
+    use std::time::Duration;
+
+    #[cfg(not(loom))]
+    pub(crate) use std::thread::{spawn, JoinHandle};
+
+    #[cfg(loom)]
+    pub(crate) use loom::thread::{spawn, JoinHandle};
+
+    /// Pause the current thread; under loom, let other threads run instead
+    pub(crate) fn sleep(duration: Duration) {
+        #[cfg(not(loom))]
+        std::thread::sleep(duration);
+        #[cfg(loom)]
+        {
+            let _ = duration;
+            loom::thread::yield_now();
+        }
+    }
+}
+
+/// Wait up to `timeout` for the thread of `handle` to finish and join it
+///
+/// Returns the result of the thread, or the handle if it is still running.
+/// Under loom the thread is always joined.
+pub(crate) fn join_timeout(
+    handle: thread::JoinHandle<()>,
+    timeout: Duration,
+) -> Result<std::thread::Result<()>, thread::JoinHandle<()>> {
+    #[cfg(not(loom))]
+    {
+        let start = std::time::Instant::now();
+        while !handle.is_finished() {
+            if start.elapsed() >= timeout {
+                return Err(handle);
+            }
+            // Small sleep to avoid busy-waiting
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+    #[cfg(loom)]
+    let _ = timeout;
+    Ok(handle.join())
+}
+
+/// Count of entries sent to the worker that it has not finished writing
+///
+/// Threads waiting for the worker to catch up sleep on a condition variable
+/// until the count drops to zero, rather than polling it.
+pub(crate) struct InFlight {
+    count: AtomicUsize,
+    #[cfg(not(loom))]
+    idle_lock: parking_lot::Mutex<()>,
+    #[cfg(not(loom))]
+    idle: parking_lot::Condvar,
+    #[cfg(loom)]
+    idle_lock: loom::sync::Mutex<()>,
+    #[cfg(loom)]
+    idle: loom::sync::Condvar,
+}
+
+impl InFlight {
+    pub(crate) fn new() -> Self {
+        Self {
+            count: AtomicUsize::new(0),
+            #[cfg(not(loom))]
+            idle_lock: parking_lot::Mutex::new(()),
+            #[cfg(not(loom))]
+            idle: parking_lot::Condvar::new(),
+            #[cfg(loom)]
+            idle_lock: loom::sync::Mutex::new(()),
+            #[cfg(loom)]
+            idle: loom::sync::Condvar::new(),
+        }
+    }
+
+    /// Number of entries not yet written
+    pub(crate) fn get(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Count `n` more entries
+    pub(crate) fn add(&self, n: usize) {
+        self.count.fetch_add(n, Ordering::AcqRel);
+    }
+
+    /// Release `n` entries, waking waiters if none are left
+    pub(crate) fn sub(&self, n: usize) {
+        if n > 0 && self.count.fetch_sub(n, Ordering::AcqRel) == n {
+            // Taking the lock orders the wake-up after a waiter's last check
+            // of the count, so it cannot be missed
+            #[cfg(not(loom))]
+            drop(self.idle_lock.lock());
+            #[cfg(loom)]
+            drop(self.idle_lock.lock().unwrap_or_else(|e| e.into_inner()));
+            self.idle.notify_all();
+        }
+    }
+
+    /// Wait up to `timeout` for the count to drop to zero
+    ///
+    /// Returns `false` if entries were still in flight after `timeout`.
+    /// Under loom the wait has no timeout.
+    pub(crate) fn wait_idle(&self, timeout: Duration) -> bool {
+        #[cfg(not(loom))]
+        {
+            let deadline = std::time::Instant::now() + timeout;
+            let mut guard = self.idle_lock.lock();
+            while self.get() > 0 {
+                if self.idle.wait_until(&mut guard, deadline).timed_out() {
+                    return self.get() == 0;
+                }
+            }
+        }
+        #[cfg(loom)]
+        {
+            let _ = timeout;
+            let mut guard = self.idle_lock.lock().unwrap_or_else(|e| e.into_inner());
+            while self.get() > 0 {
+                guard = self.idle.wait(guard).unwrap_or_else(|e| e.into_inner());
+            }
+        }
+        true
+    }
+}
+
+/// Bounded channel carrying entries to the worker
+#[cfg(not(loom))]
+pub(crate) mod channel {
+    pub(crate) use crossbeam_channel::{bounded, Receiver, Sender};
+}
+
+/// Bounded channel carrying entries to the worker, with the interface of
+/// `crossbeam_channel`
+#[cfg(loom)]
+pub(crate) mod channel {
+    use crossbeam_channel::{
+        RecvError, RecvTimeoutError, SendError, SendTimeoutError, TryRecvError, TrySendError,
+    };
+    use loom::sync::{Arc, Condvar, Mutex};
+    use std::collections::VecDeque;
+    use std::time::Duration;
+
+    struct State<T> {
+        queue: VecDeque<T>,
+        capacity: usize,
+        sender_alive: bool,
+        receiver_alive: bool,
+    }
+
+    struct Shared<T> {
+        state: Mutex<State<T>>,
+        changed: Condvar,
+    }
+
+    pub(crate) fn bounded<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                capacity: capacity.max(1),
+                sender_alive: true,
+                receiver_alive: true,
+            }),
+            changed: Condvar::new(),
+        });
+        (
+            Sender {
+                shared: Arc::clone(&shared),
+            },
+            Receiver { shared },
+        )
+    }
+
+    pub(crate) struct Sender<T> {
+        shared: Arc<Shared<T>>,
+    }
+
+    impl<T> Sender<T> {
+        pub(crate) fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+            let mut state = self.shared.state.lock().unwrap();
+            if !state.receiver_alive {
+                return Err(TrySendError::Disconnected(value));
+            }
+            if state.queue.len() >= state.capacity {
+                return Err(TrySendError::Full(value));
+            }
+            state.queue.push_back(value);
+            self.shared.changed.notify_all();
+            Ok(())
+        }
+
+        pub(crate) fn send(&self, value: T) -> Result<(), SendError<T>> {
+            let mut state = self.shared.state.lock().unwrap();
+            loop {
+                if !state.receiver_alive {
+                    return Err(SendError(value));
+                }
+                if state.queue.len() < state.capacity {
+                    state.queue.push_back(value);
+                    self.shared.changed.notify_all();
+                    return Ok(());
+                }
+                state = self.shared.changed.wait(state).unwrap();
+            }
+        }
+
+        /// Send without waiting; a full channel times out at once
+        pub(crate) fn send_timeout(
+            &self,
+            value: T,
+            _timeout: Duration,
+        ) -> Result<(), SendTimeoutError<T>> {
+            self.try_send(value).map_err(|e| match e {
+                TrySendError::Full(value) => SendTimeoutError::Timeout(value),
+                TrySendError::Disconnected(value) => SendTimeoutError::Disconnected(value),
+            })
+        }
+    }
+
+    impl<T> Drop for Sender<T> {
+        fn drop(&mut self) {
+            self.shared.state.lock().unwrap().sender_alive = false;
+            self.shared.changed.notify_all();
+        }
+    }
+
+    pub(crate) struct Receiver<T> {
+        shared: Arc<Shared<T>>,
+    }
+
+    impl<T> Receiver<T> {
+        pub(crate) fn try_recv(&self) -> Result<T, TryRecvError> {
+            let mut state = self.shared.state.lock().unwrap();
+            match state.queue.pop_front() {
+                Some(value) => {
+                    self.shared.changed.notify_all();
+                    Ok(value)
+                }
+                None if state.sender_alive => Err(TryRecvError::Empty),
+                None => Err(TryRecvError::Disconnected),
+            }
+        }
+
+        pub(crate) fn recv(&self) -> Result<T, RecvError> {
+            let mut state = self.shared.state.lock().unwrap();
+            loop {
+                if let Some(value) = state.queue.pop_front() {
+                    self.shared.changed.notify_all();
+                    return Ok(value);
+                }
+                if !state.sender_alive {
+                    return Err(RecvError);
+                }
+                state = self.shared.changed.wait(state).unwrap();
+            }
+        }
+
+        /// Receive without waiting; an empty channel times out at once
+        pub(crate) fn recv_timeout(&self, _timeout: Duration) -> Result<T, RecvTimeoutError> {
+            self.try_recv().map_err(|e| match e {
+                TryRecvError::Empty => RecvTimeoutError::Timeout,
+                TryRecvError::Disconnected => RecvTimeoutError::Disconnected,
+            })
+        }
+    }
+
+    impl<T> Drop for Receiver<T> {
+        fn drop(&mut self) {
+            self.shared.state.lock().unwrap().receiver_alive = false;
+            self.shared.changed.notify_all();
+        }
+    }
+}
+
+/// Loom locks with the interface of `parking_lot`
+#[cfg(loom)]
+mod loom_lock {
+    use loom::sync::{MutexGuard, RwLockReadGuard, RwLockWriteGuard};
+
+    #[derive(Debug)]
+    pub(crate) struct Mutex<T>(loom::sync::Mutex<T>);
+
+    impl<T> Mutex<T> {
+        pub(crate) fn new(value: T) -> Self {
+            Self(loom::sync::Mutex::new(value))
+        }
+
+        pub(crate) fn lock(&self) -> MutexGuard<'_, T> {
+            self.0.lock().unwrap_or_else(|e| e.into_inner())
+        }
+    }
+
+    #[derive(Debug)]
+    pub(crate) struct RwLock<T>(loom::sync::RwLock<T>);
+
+    impl<T> RwLock<T> {
+        pub(crate) fn new(value: T) -> Self {
+            Self(loom::sync::RwLock::new(value))
+        }
+
+        pub(crate) fn read(&self) -> RwLockReadGuard<'_, T> {
+            self.0.read().unwrap_or_else(|e| e.into_inner())
+        }
+
+        pub(crate) fn write(&self) -> RwLockWriteGuard<'_, T> {
+            self.0.write().unwrap_or_else(|e| e.into_inner())
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! Console color themes
+//!
+//! A [`ConsoleTheme`] chooses how the console appender styles each part of
+//! a line when colors are enabled: the level (per level), the timestamp,
+//! the thread name and context field keys.
+//!
+//! # Example
+//!
+//! ```
+//! use colored::Color;
+//! use rust_logger_system::appenders::ConsoleAppender;
+//! use rust_logger_system::core::{ConsoleTheme, LogLevel, TextStyle};
+//!
+//! let theme = ConsoleTheme::light()
+//!     .with_level(LogLevel::Info, TextStyle::new().color(Color::Cyan).bold())
+//!     .with_thread(TextStyle::new().dimmed());
+//!
+//! let appender = ConsoleAppender::new().with_theme(theme);
+//! ```
+
+use super::ansi;
+use super::log_level::LogLevel;
+use colored::Color;
+
+/// Color and emphasis for one part of a console line
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextStyle {
+    /// Foreground color; `None` keeps the terminal default
+    pub color: Option<Color>,
+    /// Render in bold
+    pub bold: bool,
+    /// Render dimmed
+    pub dimmed: bool,
+}
+
+impl TextStyle {
+    /// Unstyled text
+    pub const fn new() -> Self {
+        Self {
+            color: None,
+            bold: false,
+            dimmed: false,
+        }
+    }
+
+    /// Set the foreground color
+    #[must_use]
+    pub const fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Render in bold
+    #[must_use]
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Render dimmed
+    #[must_use]
+    pub const fn dimmed(mut self) -> Self {
+        self.dimmed = true;
+        self
+    }
+
+    /// Wrap `text` in the escape sequences for this style
+    ///
+    /// Returns `text` unchanged for an unstyled `TextStyle`.
+    pub fn paint(&self, text: &str) -> String {
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push(ansi::BOLD.to_string());
+        }
+        if self.dimmed {
+            codes.push(ansi::DIM.to_string());
+        }
+        if let Some(color) = self.color {
+            codes.push(ansi::fg(color));
+        }
+
+        if codes.is_empty() {
+            text.to_string()
+        } else {
+            ansi::paint(text, &codes.join(";"))
+        }
+    }
+}
+
+/// Styles used by the console appender when colors are enabled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsoleTheme {
+    /// Styles for Trace, Debug, Info, Warn, Error and Fatal, in that order
+    levels: [TextStyle; 6],
+    timestamp: TextStyle,
+    thread: TextStyle,
+    context_key: TextStyle,
+}
+
+impl Default for ConsoleTheme {
+    /// Theme for dark terminals, using [`LogLevel::color_code`] for levels
+    fn default() -> Self {
+        let level = |level: LogLevel| TextStyle::new().color(level.color_code());
+        Self {
+            levels: [
+                level(LogLevel::Trace),
+                level(LogLevel::Debug),
+                level(LogLevel::Info),
+                level(LogLevel::Warn),
+                level(LogLevel::Error),
+                level(LogLevel::Fatal).bold(),
+            ],
+            timestamp: TextStyle::new().dimmed(),
+            thread: TextStyle::new(),
+            context_key: TextStyle::new().bold(),
+        }
+    }
+}
+
+impl ConsoleTheme {
+    /// Theme for dark terminals (same as `default()`)
+    pub fn dark() -> Self {
+        Self::default()
+    }
+
+    /// Theme for light terminals, avoiding bright colors that wash out on
+    /// white backgrounds
+    pub fn light() -> Self {
+        Self {
+            levels: [
+                TextStyle::new().color(Color::Black).dimmed(),
+                TextStyle::new().color(Color::Blue),
+                TextStyle::new().color(Color::Green).bold(),
+                TextStyle::new().color(Color::Magenta).bold(),
+                TextStyle::new().color(Color::Red).bold(),
+                TextStyle::new().color(Color::Red).bold(),
+            ],
+            timestamp: TextStyle::new().color(Color::Black).dimmed(),
+            thread: TextStyle::new(),
+            context_key: TextStyle::new().bold(),
+        }
+    }
+
+    /// Theme without any styling
+    pub fn plain() -> Self {
+        Self {
+            levels: [TextStyle::new(); 6],
+            timestamp: TextStyle::new(),
+            thread: TextStyle::new(),
+            context_key: TextStyle::new(),
+        }
+    }
+
+    /// Set the style for one level
+    #[must_use]
+    pub fn with_level(mut self, level: LogLevel, style: TextStyle) -> Self {
+        self.levels[level as usize] = style;
+        self
+    }
+
+    /// Set the timestamp style
+    #[must_use]
+    pub fn with_timestamp(mut self, style: TextStyle) -> Self {
+        self.timestamp = style;
+        self
+    }
+
+    /// Set the thread name style
+    #[must_use]
+    pub fn with_thread(mut self, style: TextStyle) -> Self {
+        self.thread = style;
+        self
+    }
+
+    /// Set the context field key style
+    #[must_use]
+    pub fn with_context_key(mut self, style: TextStyle) -> Self {
+        self.context_key = style;
+        self
+    }
+
+    /// Get the style for a level
+    pub fn level(&self, level: LogLevel) -> TextStyle {
+        self.levels[level as usize]
+    }
+
+    /// Get the timestamp style
+    pub fn timestamp(&self) -> TextStyle {
+        self.timestamp
+    }
+
+    /// Get the thread name style
+    pub fn thread(&self) -> TextStyle {
+        self.thread
+    }
+
+    /// Get the context field key style
+    pub fn context_key(&self) -> TextStyle {
+        self.context_key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_style_paint() {
+        assert_eq!(TextStyle::new().paint("plain"), "plain");
+        assert_eq!(
+            TextStyle::new().color(Color::Red).bold().paint("ERROR"),
+            "\x1b[1;31mERROR\x1b[0m"
+        );
+        assert_eq!(TextStyle::new().dimmed().paint("ts"), "\x1b[2mts\x1b[0m");
+    }
+
+    #[test]
+    fn test_theme_overrides() {
+        let style = TextStyle::new().color(Color::Cyan);
+        let theme = ConsoleTheme::default().with_level(LogLevel::Warn, style);
+
+        assert_eq!(theme.level(LogLevel::Warn), style);
+        assert_eq!(theme.level(LogLevel::Info).color, Some(Color::Green));
+        assert_eq!(ConsoleTheme::plain().level(LogLevel::Fatal), TextStyle::new());
+    }
+}
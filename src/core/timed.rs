@@ -0,0 +1,182 @@
+//! Operation timing guards
+//!
+//! [`Logger::timed`] returns a [`TimedGuard`] that logs one entry when the
+//! operation completes (the guard is dropped or [`TimedGuard::finish`] is
+//! called), with the elapsed time as a `duration_ms` field. Unlike
+//! [`Span`](super::Span), it logs nothing at the start and does not tag
+//! other entries.
+//!
+//! The [`timed!`](crate::timed) and [`measure!`](crate::measure) macros
+//! create guards that also record the call site.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//! use std::time::Duration;
+//!
+//! let logger = Logger::new();
+//!
+//! {
+//!     let _timed = logger
+//!         .timed_at(LogLevel::Debug, "load config")
+//!         .warn_after(Duration::from_millis(200));
+//!     // ...
+//! } // logs "load config completed" with duration_ms
+//! ```
+
+use super::log_context::LogContext;
+use super::log_entry::LogEntry;
+use super::log_level::LogLevel;
+use super::logger::Logger;
+use std::time::{Duration, Instant};
+
+/// Guard that logs the duration of an operation when dropped
+///
+/// The entry has the message `<name> completed` and the fields `operation`
+/// and `duration_ms`. If a threshold set with [`warn_after`] is exceeded,
+/// the entry is logged at Warn (or the guard's level, if higher) and also
+/// carries `threshold_ms`.
+///
+/// [`warn_after`]: TimedGuard::warn_after
+#[must_use = "the operation is timed until the guard is dropped"]
+pub struct TimedGuard<'a> {
+    logger: &'a Logger,
+    level: LogLevel,
+    name: String,
+    start: Instant,
+    threshold: Option<Duration>,
+    location: Option<(&'static str, u32, &'static str)>,
+}
+
+impl<'a> TimedGuard<'a> {
+    pub(crate) fn new(logger: &'a Logger, level: LogLevel, name: String) -> Self {
+        Self {
+            logger,
+            level,
+            name,
+            start: Instant::now(),
+            threshold: None,
+            location: None,
+        }
+    }
+
+    /// Log at Warn when the operation takes longer than `threshold`
+    pub fn warn_after(mut self, threshold: Duration) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Set the source location recorded on the entry
+    pub fn location(mut self, file: &'static str, line: u32, module_path: &'static str) -> Self {
+        self.location = Some((file, line, module_path));
+        self
+    }
+
+    /// Get the operation name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Time elapsed since the guard was created
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Stop timing and log the entry now
+    pub fn finish(self) {}
+}
+
+impl Drop for TimedGuard<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.elapsed();
+        let mut level = self.level;
+        let mut context = LogContext::new()
+            .with_field("operation", self.name.as_str())
+            .with_field("duration_ms", elapsed.as_secs_f64() * 1000.0);
+
+        if let Some(threshold) = self.threshold.filter(|threshold| elapsed > *threshold) {
+            level = level.max(LogLevel::Warn);
+            context.add_field("threshold_ms", threshold.as_secs_f64() * 1000.0);
+        }
+
+        let mut entry =
+            LogEntry::new(level, format!("{} completed", self.name)).with_context(context);
+        if let Some((file, line, module_path)) = self.location {
+            entry = entry.with_location(file, line, module_path);
+        }
+        self.logger.log_entry(entry);
+    }
+}
+
+impl std::fmt::Debug for TimedGuard<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimedGuard")
+            .field("name", &self.name)
+            .field("level", &self.level)
+            .field("threshold", &self.threshold)
+            .field("elapsed", &self.elapsed())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::FieldValue;
+    use crate::testing::CaptureAppender;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    fn capture_logger() -> (Logger, Arc<Mutex<Vec<LogEntry>>>) {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let mut logger = Logger::new();
+        logger.set_min_level(LogLevel::Debug);
+        logger.add_appender(Box::new(CaptureAppender::new(Arc::clone(&entries))));
+        (logger, entries)
+    }
+
+    #[test]
+    fn test_logs_duration_on_drop() {
+        let (logger, entries) = capture_logger();
+
+        {
+            let _timed = logger.timed_at(LogLevel::Debug, "load");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        logger.timed("noop").finish();
+
+        let entries = entries.lock();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].level, LogLevel::Debug);
+        assert_eq!(entries[0].message, "load completed");
+        let fields = entries[0].context.as_ref().unwrap().fields();
+        assert_eq!(fields["operation"], FieldValue::from("load"));
+        match fields["duration_ms"] {
+            FieldValue::Float(ms) => assert!(ms >= 5.0),
+            ref other => panic!("unexpected duration_ms {:?}", other),
+        }
+        assert!(!fields.contains_key("threshold_ms"));
+        assert_eq!(entries[1].level, LogLevel::Info);
+    }
+
+    #[test]
+    fn test_warns_over_threshold() {
+        let (logger, entries) = capture_logger();
+
+        logger
+            .timed_at(LogLevel::Debug, "slow")
+            .warn_after(Duration::ZERO)
+            .finish();
+        logger
+            .timed_at(LogLevel::Debug, "fast")
+            .warn_after(Duration::from_secs(60))
+            .finish();
+
+        let entries = entries.lock();
+        assert_eq!(entries[0].level, LogLevel::Warn);
+        let fields = entries[0].context.as_ref().unwrap().fields();
+        assert_eq!(fields["threshold_ms"], FieldValue::from(0.0));
+        assert_eq!(entries[1].level, LogLevel::Debug);
+    }
+}
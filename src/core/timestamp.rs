@@ -3,8 +3,12 @@
 //! Provides standardized, configurable timestamp formats for log output.
 //! Supports ISO 8601, RFC 3339, Unix timestamps, and custom formats.
 
-use chrono::{DateTime, Utc};
+use super::log_entry::LogEntry;
+use super::log_level::LogLevel;
+use super::output_format::JsonProfile;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt::Write;
 use std::sync::Arc;
 use std::time::SystemTime;
 
@@ -100,6 +104,59 @@ impl TimestampFormat {
         }
     }
 
+    /// Append the formatted `datetime` to `output`
+    pub fn format_into(&self, datetime: &DateTime<Utc>, output: &mut String) {
+        // Writing to a String cannot fail
+        let _ = match self {
+            TimestampFormat::Iso8601 => {
+                write!(output, "{}", datetime.format("%Y-%m-%dT%H:%M:%S%.3fZ"))
+            }
+            TimestampFormat::Iso8601Micros => {
+                write!(output, "{}", datetime.format("%Y-%m-%dT%H:%M:%S%.6fZ"))
+            }
+            TimestampFormat::Rfc3339 => {
+                output.push_str(&datetime.to_rfc3339());
+                Ok(())
+            }
+            TimestampFormat::Unix => write!(output, "{}", datetime.timestamp()),
+            TimestampFormat::UnixMillis => write!(output, "{}", datetime.timestamp_millis()),
+            TimestampFormat::UnixMicros => write!(output, "{}", datetime.timestamp_micros()),
+            TimestampFormat::Custom(format_str) => {
+                write!(output, "{}", datetime.format(format_str))
+            }
+        };
+    }
+
+    /// Parse a timestamp written in this format
+    ///
+    /// Custom formats without a time zone are read as UTC, and formats
+    /// without a time of day as midnight. Returns `None` if `value` does not
+    /// match the format.
+    #[must_use]
+    pub fn parse(&self, value: &str) -> Option<DateTime<Utc>> {
+        match self {
+            TimestampFormat::Iso8601
+            | TimestampFormat::Iso8601Micros
+            | TimestampFormat::Rfc3339 => DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|time| time.with_timezone(&Utc)),
+            TimestampFormat::Unix => DateTime::from_timestamp(value.parse().ok()?, 0),
+            TimestampFormat::UnixMillis => DateTime::from_timestamp_millis(value.parse().ok()?),
+            TimestampFormat::UnixMicros => DateTime::from_timestamp_micros(value.parse().ok()?),
+            TimestampFormat::Custom(format_str) => {
+                if let Ok(time) = DateTime::parse_from_str(value, format_str) {
+                    return Some(time.with_timezone(&Utc));
+                }
+                if let Ok(time) = NaiveDateTime::parse_from_str(value, format_str) {
+                    return Some(time.and_utc());
+                }
+                NaiveDate::parse_from_str(value, format_str)
+                    .ok()
+                    .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+            }
+        }
+    }
+
     /// Format a `SystemTime` according to this format
     ///
     /// Convenience method that converts `SystemTime` to `DateTime<Utc>` first.
@@ -148,6 +205,41 @@ mod tests {
             + chrono::Duration::microseconds(123456)
     }
 
+    #[test]
+    fn test_format_into_matches_format() {
+        let datetime = fixed_datetime();
+        for format in [
+            TimestampFormat::Iso8601,
+            TimestampFormat::Iso8601Micros,
+            TimestampFormat::Rfc3339,
+            TimestampFormat::UnixMillis,
+            TimestampFormat::Custom("%d/%m/%Y".to_string()),
+        ] {
+            let mut output = "at ".to_string();
+            format.format_into(&datetime, &mut output);
+            assert_eq!(output, format!("at {}", format.format(&datetime)));
+        }
+    }
+
+    #[test]
+    fn test_parse_reverses_format() {
+        let time = DateTime::from_timestamp_micros(1_736_332_245_123_456).unwrap();
+        for (format, expected) in [
+            (TimestampFormat::Iso8601, 1_736_332_245_123_000),
+            (TimestampFormat::Iso8601Micros, 1_736_332_245_123_456),
+            (TimestampFormat::Rfc3339, 1_736_332_245_123_456),
+            (TimestampFormat::Unix, 1_736_332_245_000_000),
+            (TimestampFormat::UnixMillis, 1_736_332_245_123_000),
+            (TimestampFormat::UnixMicros, 1_736_332_245_123_456),
+            (TimestampFormat::Custom("%Y-%m-%d %H:%M:%S".to_string()), 1_736_332_245_000_000),
+            (TimestampFormat::Custom("%Y-%m-%d".to_string()), 1_736_294_400_000_000),
+        ] {
+            let parsed = format.parse(&format.format(&time));
+            assert_eq!(parsed.map(|time| time.timestamp_micros()), Some(expected), "{:?}", format);
+        }
+        assert_eq!(TimestampFormat::Iso8601.parse("yesterday"), None);
+    }
+
     #[test]
     fn test_iso8601_format() {
         let format = TimestampFormat::Iso8601;
@@ -293,8 +385,13 @@ pub struct FormatterConfig {
     pub include_thread_id: bool,
     /// Whether to include file location (file:line) in output
     pub include_file_location: bool,
+    /// Whether to prefix the file location with the module path, and the
+    /// target when it differs
+    pub include_module_path: bool,
     /// Whether to display log level in uppercase (ERROR vs error)
     pub level_uppercase: bool,
+    /// Field naming used for JSON output
+    pub json_profile: JsonProfile,
 }
 
 impl Default for FormatterConfig {
@@ -304,7 +401,9 @@ impl Default for FormatterConfig {
             include_level: true,
             include_thread_id: true,
             include_file_location: false,
+            include_module_path: false,
             level_uppercase: true,
+            json_profile: JsonProfile::default(),
         }
     }
 }
@@ -344,6 +443,16 @@ impl FormatterConfig {
         self
     }
 
+    /// Set whether to prefix the file location with the module path, and
+    /// the target when the entry sets another
+    ///
+    /// Only has an effect when the file location is included.
+    #[must_use]
+    pub fn with_include_module_path(mut self, include: bool) -> Self {
+        self.include_module_path = include;
+        self
+    }
+
     /// Set whether log level should be uppercase
     #[must_use]
     pub fn with_level_uppercase(mut self, uppercase: bool) -> Self {
@@ -351,6 +460,13 @@ impl FormatterConfig {
         self
     }
 
+    /// Set the field naming used for JSON output
+    #[must_use]
+    pub fn with_json_profile(mut self, profile: JsonProfile) -> Self {
+        self.json_profile = profile;
+        self
+    }
+
     /// Create a custom timestamp format
     ///
     /// # Arguments
@@ -362,6 +478,61 @@ impl FormatterConfig {
         self
     }
 
+    /// Get the display name of `level` with this config's casing
+    pub fn level_str(&self, level: LogLevel) -> String {
+        if self.level_uppercase {
+            level.to_str().to_string()
+        } else {
+            level.to_str().to_lowercase()
+        }
+    }
+
+    /// Get the level name of `entry`, custom or built-in, with this
+    /// config's casing
+    pub fn entry_level_str(&self, entry: &LogEntry) -> String {
+        if self.level_uppercase {
+            entry.level_name().to_string()
+        } else {
+            entry.level_name().to_lowercase()
+        }
+    }
+
+    /// Get the source location of `entry` as `file:line`
+    ///
+    /// Returns `None` unless `include_file_location` is set and the entry
+    /// has a file. With `include_module_path` the module path comes first,
+    /// as in `my_app::db src/db.rs:42`, followed by the target when it
+    /// differs, as in `my_app::db (sqlx) src/db.rs:42`.
+    pub fn location(&self, entry: &LogEntry) -> Option<String> {
+        let mut location = String::new();
+        self.push_location(entry, &mut location).then_some(location)
+    }
+
+    /// Append the source location of [`location`](Self::location) to
+    /// `output`, returning whether there was one
+    pub(crate) fn push_location(&self, entry: &LogEntry, output: &mut String) -> bool {
+        let Some(file) = entry.file.as_deref().filter(|_| self.include_file_location) else {
+            return false;
+        };
+        if self.include_module_path {
+            match (entry.module_path.as_deref(), entry.target.as_deref()) {
+                (Some(module), Some(target)) if target != module => {
+                    let _ = write!(output, "{} ({}) ", module, target);
+                }
+                (Some(module), _) | (None, Some(module)) => {
+                    output.push_str(module);
+                    output.push(' ');
+                }
+                (None, None) => {}
+            }
+        }
+        output.push_str(file);
+        if let Some(line) = entry.line {
+            let _ = write!(output, ":{}", line);
+        }
+        true
+    }
+
     /// Wrap this config in an Arc for sharing across appenders
     #[must_use]
     pub fn shared(self) -> Arc<Self> {
@@ -380,6 +551,7 @@ mod formatter_config_tests {
         assert!(config.include_level);
         assert!(config.include_thread_id);
         assert!(!config.include_file_location);
+        assert!(!config.include_module_path);
         assert!(config.level_uppercase);
     }
 
@@ -399,6 +571,46 @@ mod formatter_config_tests {
         assert!(!config.level_uppercase);
     }
 
+    #[test]
+    fn test_location() {
+        let entry = LogEntry::new(LogLevel::Info, "ready".to_string())
+            .with_location("src/db.rs", 42, "my_app::db");
+
+        assert_eq!(FormatterConfig::new().location(&entry), None);
+
+        let config = FormatterConfig::new().with_include_file_location(true);
+        assert_eq!(config.location(&entry).as_deref(), Some("src/db.rs:42"));
+        assert_eq!(
+            config.clone().with_include_module_path(true).location(&entry).as_deref(),
+            Some("my_app::db src/db.rs:42")
+        );
+        let unlocated = LogEntry::new(LogLevel::Info, "ready".to_string());
+        assert_eq!(config.location(&unlocated), None);
+    }
+
+    #[test]
+    fn test_location_with_target() {
+        let config = FormatterConfig::new()
+            .with_include_file_location(true)
+            .with_include_module_path(true);
+        let entry = LogEntry::new(LogLevel::Info, "query".to_string())
+            .with_target("sqlx")
+            .with_location("src/db.rs", 42, "my_app::db");
+        assert_eq!(config.location(&entry).as_deref(), Some("my_app::db (sqlx) src/db.rs:42"));
+
+        let same = LogEntry::new(LogLevel::Info, "query".to_string())
+            .with_location("src/db.rs", 42, "my_app::db")
+            .with_target("my_app::db");
+        assert_eq!(config.location(&same).as_deref(), Some("my_app::db src/db.rs:42"));
+    }
+
+    #[test]
+    fn test_level_str() {
+        assert_eq!(FormatterConfig::new().level_str(LogLevel::Warn), "WARN");
+        let config = FormatterConfig::new().with_level_uppercase(false);
+        assert_eq!(config.level_str(LogLevel::Error), "error");
+    }
+
     #[test]
     fn test_custom_timestamp() {
         let config = FormatterConfig::new().with_custom_timestamp("%Y/%m/%d");
@@ -0,0 +1,109 @@
+//! Entry transformation extension point
+//!
+//! Transformers rewrite or drop entries before the appenders see them:
+//! rewording messages, normalizing field names, or converting entries of a
+//! legacy layout while producers migrate to a new schema. Unlike a
+//! [`Filter`](super::Filter), a transformer receives the entry by value and
+//! returns the entry to log, which may be changed, or `None` to drop it.
+//!
+//! Transformers run in the order they were added, after the filters and
+//! before burst suppression, on the logging thread. What they return is
+//! what the enrichers, the async queue and every appender see.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//!
+//! let logger = Logger::builder()
+//!     .transformer(RenameFields::new().field("userId", "user_id"))
+//!     .transformer(|mut entry: LogEntry| {
+//!         if entry.message.starts_with("DEPRECATED:") {
+//!             return None;
+//!         }
+//!         entry.message = entry.message.replace("usr", "user");
+//!         Some(entry)
+//!     })
+//!     .build();
+//! ```
+
+use super::log_entry::LogEntry;
+
+/// Trait for rewriting or dropping log entries
+///
+/// Transformers are applied in the order they were added. An entry is only
+/// logged if every transformer returns it.
+pub trait Transformer: Send + Sync {
+    /// Return the entry to log, or `None` to drop it
+    fn transform(&self, entry: LogEntry) -> Option<LogEntry>;
+}
+
+impl<F> Transformer for F
+where
+    F: Fn(LogEntry) -> Option<LogEntry> + Send + Sync,
+{
+    fn transform(&self, entry: LogEntry) -> Option<LogEntry> {
+        self(entry)
+    }
+}
+
+/// Transformer renaming context fields
+///
+/// Renamed fields keep their position; a field already holding the new
+/// name is replaced.
+#[derive(Debug, Clone, Default)]
+pub struct RenameFields {
+    renames: Vec<(String, String)>,
+}
+
+impl RenameFields {
+    /// Create a transformer that renames nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rename the field `from` to `to`
+    #[must_use]
+    pub fn field(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.renames.push((from.into(), to.into()));
+        self
+    }
+}
+
+impl Transformer for RenameFields {
+    fn transform(&self, mut entry: LogEntry) -> Option<LogEntry> {
+        if let Some(context) = entry.context.as_mut() {
+            let fields = context.fields_mut();
+            for (from, to) in &self.renames {
+                fields.rename(from, to.as_str());
+            }
+        }
+        Some(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::log_context::{FieldValue, LogContext};
+    use crate::core::log_level::LogLevel;
+
+    #[test]
+    fn test_rename_fields_keeps_order() {
+        let context = LogContext::new()
+            .with_field("userId", 7)
+            .with_field("user_id", 1)
+            .with_field("path", "/");
+        let entry = LogEntry::new(LogLevel::Info, "request".to_string()).with_context(context);
+
+        let renamed = RenameFields::new()
+            .field("userId", "user_id")
+            .field("missing", "other")
+            .transform(entry)
+            .unwrap();
+        let fields = renamed.context.unwrap();
+        let fields: Vec<(&str, &FieldValue)> =
+            fields.fields().iter().map(|(key, value)| (key.as_str(), value)).collect();
+        assert_eq!(fields, [("user_id", &7.into()), ("path", &"/".into())]);
+    }
+}
@@ -0,0 +1,411 @@
+//! Write-ahead log for the async queue
+//!
+//! An async logger holds entries in memory until its worker writes them, so
+//! a crash loses whatever is queued. With [`LoggerBuilder::write_ahead_log`],
+//! every entry is also appended to a file before [`Logger::log`] returns,
+//! and released once the worker has written and flushed it. When the next
+//! logger is built with the same file, entries left by a process that died
+//! are written to its appenders before anything else.
+//!
+//! Delivery is at least once: entries written just before a crash may be
+//! written again on recovery. The file holds entries after redaction, which
+//! then runs on the logging thread. Context values of the byte, duration and
+//! timestamp kinds come back as strings or numbers (see [`FieldValue`]).
+//!
+//! By default the file is not synced, which survives a crash of the process
+//! but not of the machine; [`WalConfig::with_sync`] syncs every entry at a
+//! large cost in throughput.
+//!
+//! [`LoggerBuilder::write_ahead_log`]: super::LoggerBuilder::write_ahead_log
+//! [`Logger::log`]: super::Logger::log
+//! [`FieldValue`]: super::FieldValue
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rust_logger_system::core::WalConfig;
+//! use rust_logger_system::prelude::*;
+//!
+//! let logger = Logger::builder()
+//!     .appender(FileAppender::new("audit.log").unwrap())
+//!     .async_mode(10_000)
+//!     .write_ahead_log(WalConfig::new("audit.wal"))
+//!     .try_build()
+//!     .expect("Failed to open write-ahead log");
+//! ```
+
+use super::diagnostics::{self, DiagnosticEvent};
+use super::log_entry::LogEntry;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Marker of an entry record
+const ENTRY: &[u8; 4] = b"LWE1";
+
+/// Marker of a checkpoint record
+const CHECKPOINT: &[u8; 4] = b"LWC1";
+
+/// Marker, payload length and CRC-32
+const HEADER_LEN: usize = 12;
+
+/// Settings of a write-ahead log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalConfig {
+    /// File the entries are logged to
+    pub path: PathBuf,
+
+    /// Sync the file after every entry
+    pub sync: bool,
+
+    /// Size above which the file is rewritten without released entries
+    pub compact_bytes: u64,
+}
+
+impl WalConfig {
+    /// Log to `path` without syncing, compacting above 4 MiB
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            sync: false,
+            compact_bytes: 4 << 20,
+        }
+    }
+
+    /// Sync the file after every entry
+    #[must_use]
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Set the size above which the file is compacted
+    #[must_use]
+    pub fn with_compact_bytes(mut self, compact_bytes: u64) -> Self {
+        self.compact_bytes = compact_bytes;
+        self
+    }
+}
+
+/// Write-ahead log shared by the logging threads and the worker
+///
+/// Entries are recorded in the order they are queued, which is the order
+/// the worker finishes them, so the worker only reports how many it
+/// finished. Producers hold the file lock while sending, so the worker
+/// never waits for it: it counts finished entries and applies them when the
+/// lock is free, or the next producer does.
+#[derive(Debug)]
+pub(crate) struct WriteAheadLog {
+    writer: Mutex<WalWriter>,
+    /// Entries finished by the worker, including ones not yet applied
+    completed: AtomicU64,
+}
+
+#[derive(Debug)]
+struct WalWriter {
+    config: WalConfig,
+    file: File,
+    size: u64,
+    /// Start offset of each queued entry, `None` if recording it failed
+    queued: VecDeque<Option<u64>>,
+    /// Finished entries removed from `queued`
+    applied: u64,
+    /// Finished entry records in the file
+    released: u64,
+}
+
+impl WriteAheadLog {
+    /// Open the log, returning the entries a previous process left
+    ///
+    /// The file keeps those entries until [`clear`](Self::clear) is called
+    /// after they were written.
+    pub(crate) fn open(config: WalConfig) -> io::Result<(Self, Vec<LogEntry>)> {
+        if let Some(parent) = config.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&config.path)?;
+        let data = fs::read(&config.path)?;
+        let (entries, corrupted) = read_records(&data);
+        if !entries.is_empty() || corrupted > 0 {
+            diagnostics::report(DiagnosticEvent::WalRecovered {
+                path: config.path.clone(),
+                entries: entries.len() as u64,
+                corrupted_bytes: corrupted,
+            });
+        }
+        let writer = WalWriter {
+            config,
+            file,
+            size: data.len() as u64,
+            queued: VecDeque::new(),
+            applied: 0,
+            released: 0,
+        };
+        let wal = Self {
+            writer: Mutex::new(writer),
+            completed: AtomicU64::new(0),
+        };
+        Ok((wal, entries))
+    }
+
+    /// Empty the file once recovered entries have been written
+    pub(crate) fn clear(&self) {
+        let mut writer = self.writer.lock();
+        if let Err(e) = writer.truncate() {
+            writer.report(&e);
+        }
+    }
+
+    /// Send `entry` with `send` and record it if it was queued
+    pub(crate) fn record<E>(
+        &self,
+        entry: LogEntry,
+        send: impl FnOnce(LogEntry) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let payload = serde_json::to_vec(&entry).ok();
+        let mut writer = self.writer.lock();
+        send(entry)?;
+        let start = payload.and_then(|payload| match writer.append(ENTRY, &payload) {
+            Ok(start) => Some(start),
+            Err(e) => {
+                writer.report(&e);
+                None
+            }
+        });
+        writer.queued.push_back(start);
+        self.apply(&mut writer);
+        Ok(())
+    }
+
+    /// Release `count` entries the worker has written and flushed
+    pub(crate) fn complete(&self, count: usize) {
+        self.completed.fetch_add(count as u64, Ordering::AcqRel);
+        if let Some(mut writer) = self.writer.try_lock() {
+            self.apply(&mut writer);
+        }
+    }
+
+    /// Apply finished entries, waiting for the lock
+    pub(crate) fn settle(&self) {
+        self.apply(&mut self.writer.lock());
+    }
+
+    fn apply(&self, writer: &mut WalWriter) {
+        let completed = self.completed.load(Ordering::Acquire);
+        if completed == writer.applied {
+            return;
+        }
+        while writer.applied < completed {
+            let Some(start) = writer.queued.pop_front() else {
+                break;
+            };
+            writer.applied += 1;
+            writer.released += u64::from(start.is_some());
+        }
+        if let Err(e) = writer.checkpoint() {
+            writer.report(&e);
+        }
+    }
+}
+
+impl WalWriter {
+    /// Append a record, returning its offset
+    fn append(&mut self, marker: &[u8; 4], payload: &[u8]) -> io::Result<u64> {
+        let mut record = Vec::with_capacity(HEADER_LEN + payload.len());
+        encode_record(marker, payload, &mut record)?;
+        let start = self.size;
+        if let Err(e) = self.file.write_all(&record) {
+            // Drop a partial record so later ones stay readable
+            let _ = self.file.set_len(start);
+            return Err(e);
+        }
+        if self.config.sync {
+            self.file.sync_data()?;
+        }
+        self.size += record.len() as u64;
+        Ok(start)
+    }
+
+    /// Record the released entries, emptying or compacting the file
+    fn checkpoint(&mut self) -> io::Result<()> {
+        let Some(first) = self.queued.iter().copied().flatten().next() else {
+            return self.truncate();
+        };
+        if self.size > self.config.compact_bytes && first > self.size / 2 {
+            self.compact(first)?;
+        }
+        self.append(CHECKPOINT, &self.released.to_le_bytes()).map(drop)
+    }
+
+    fn truncate(&mut self) -> io::Result<()> {
+        self.file.set_len(0)?;
+        self.size = 0;
+        self.released = 0;
+        self.queued.iter_mut().for_each(|start| *start = None);
+        Ok(())
+    }
+
+    /// Rewrite the file from the record at `first`, dropping released ones
+    ///
+    /// Checkpoints left in the rest count entries of the old file, so the
+    /// new file ends with one releasing none; it holds if the process dies
+    /// before the caller appends the next.
+    fn compact(&mut self, first: u64) -> io::Result<()> {
+        let data = fs::read(&self.config.path)?;
+        let mut rest = data.get(first as usize..).unwrap_or_default().to_vec();
+        encode_record(CHECKPOINT, &0u64.to_le_bytes(), &mut rest)?;
+        let temp = self.config.path.with_extension("wal.tmp");
+        fs::write(&temp, &rest)?;
+        fs::rename(&temp, &self.config.path)?;
+        self.file = OpenOptions::new().append(true).open(&self.config.path)?;
+        self.size = rest.len() as u64;
+        self.released = 0;
+        for start in self.queued.iter_mut().flatten() {
+            *start -= first;
+        }
+        Ok(())
+    }
+
+    fn report(&self, error: &io::Error) {
+        diagnostics::report(DiagnosticEvent::WalFailed {
+            path: self.config.path.clone(),
+            error: error.to_string(),
+        });
+    }
+}
+
+/// Append a record of `payload` to `out`
+fn encode_record(marker: &[u8; 4], payload: &[u8], out: &mut Vec<u8>) -> io::Result<()> {
+    let payload_len = u32::try_from(payload.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "entry too large"))?;
+    out.extend_from_slice(marker);
+    out.extend_from_slice(&payload_len.to_le_bytes());
+    out.extend_from_slice(&crc32(payload).to_le_bytes());
+    out.extend_from_slice(payload);
+    Ok(())
+}
+
+/// Entries after the last checkpoint, and the bytes of a torn or damaged tail
+fn read_records(data: &[u8]) -> (Vec<LogEntry>, u64) {
+    let mut entries = Vec::new();
+    let mut released = 0;
+    let mut pos = 0;
+    while let Some((marker, payload)) = record_at(data, pos) {
+        pos += HEADER_LEN + payload.len();
+        if marker == ENTRY {
+            match serde_json::from_slice(payload) {
+                Ok(entry) => entries.push(Some(entry)),
+                // Counted so the checkpoint still lines up
+                Err(_) => entries.push(None),
+            }
+        } else if let Ok(count) = payload.try_into() {
+            released = u64::from_le_bytes(count) as usize;
+        }
+    }
+    let recovered = entries.into_iter().skip(released).flatten().collect();
+    (recovered, (data.len() - pos) as u64)
+}
+
+fn record_at(data: &[u8], at: usize) -> Option<(&[u8], &[u8])> {
+    let header = data.get(at..at + HEADER_LEN)?;
+    let marker = &header[..4];
+    if marker != ENTRY && marker != CHECKPOINT {
+        return None;
+    }
+    let len = u32::from_le_bytes(header[4..8].try_into().ok()?) as usize;
+    let crc = u32::from_le_bytes(header[8..12].try_into().ok()?);
+    let payload = data.get(at + HEADER_LEN..at + HEADER_LEN + len)?;
+    (crc32(payload) == crc).then_some((marker, payload))
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(bytes);
+    crc.sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::LogLevel;
+
+    fn queue(wal: &WriteAheadLog, message: &str) {
+        let entry = LogEntry::new(LogLevel::Info, message.to_string());
+        wal.record(entry, |_| Ok::<(), ()>(())).unwrap();
+    }
+
+    #[test]
+    fn test_recovers_unfinished_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = WalConfig::new(dir.path().join("queue.wal"));
+        let (wal, recovered) = WriteAheadLog::open(config.clone()).unwrap();
+        assert!(recovered.is_empty());
+        for message in ["one", "two", "three"] {
+            queue(&wal, message);
+        }
+        wal.complete(1);
+        drop(wal);
+
+        // A torn record at the end is ignored
+        let mut file = OpenOptions::new().append(true).open(&config.path).unwrap();
+        file.write_all(&ENTRY[..]).unwrap();
+        drop(file);
+
+        let (wal, recovered) = WriteAheadLog::open(config.clone()).unwrap();
+        let messages: Vec<_> = recovered.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["two", "three"]);
+        wal.clear();
+        assert_eq!(fs::metadata(&config.path).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_empties_and_compacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = WalConfig::new(dir.path().join("queue.wal")).with_compact_bytes(200);
+        let (wal, _) = WriteAheadLog::open(config.clone()).unwrap();
+
+        // A failed send leaves nothing to recover
+        let entry = LogEntry::new(LogLevel::Info, "dropped".to_string());
+        assert!(wal.record(entry, |_| Err(())).is_err());
+        queue(&wal, "done");
+        wal.complete(1);
+        assert_eq!(fs::metadata(&config.path).unwrap().len(), 0);
+
+        for i in 0..6 {
+            queue(&wal, &format!("entry {}", i));
+        }
+        let before = fs::metadata(&config.path).unwrap().len();
+        wal.complete(5);
+        assert!(fs::metadata(&config.path).unwrap().len() < before / 2);
+        let (_, recovered) = WriteAheadLog::open(config.clone()).unwrap();
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].message, "entry 5");
+    }
+
+    #[test]
+    fn test_compaction_survives_crash_before_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = WalConfig::new(dir.path().join("queue.wal")).with_compact_bytes(200);
+        let (wal, _) = WriteAheadLog::open(config.clone()).unwrap();
+        for i in 0..6 {
+            queue(&wal, &format!("entry {}", i));
+        }
+        // Leaves a checkpoint behind the last entry, copied by compaction
+        wal.complete(1);
+        wal.complete(4);
+        drop(wal);
+
+        // Lose the checkpoint appended after compacting
+        let len = fs::metadata(&config.path).unwrap().len();
+        let file = OpenOptions::new().write(true).open(&config.path).unwrap();
+        file.set_len(len - (HEADER_LEN + 8) as u64).unwrap();
+        drop(file);
+
+        let (_, recovered) = WriteAheadLog::open(config).unwrap();
+        let messages: Vec<_> = recovered.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["entry 5"]);
+    }
+}
@@ -0,0 +1,240 @@
+//! Draining loggers when the process exits
+//!
+//! A logger that is never dropped, such as the [global](crate::global) one,
+//! loses the entries still in its async queue when the process ends, and a
+//! container stopped with SIGTERM loses them even for loggers that would
+//! have been dropped. [`install_exit_hooks`] drains a logger when the
+//! process exits normally (`atexit`), on SIGTERM and SIGINT on Unix, and on
+//! Ctrl-C, Ctrl-Break and console close on Windows.
+
+use crate::core::{Logger, DEFAULT_SHUTDOWN_TIMEOUT};
+use parking_lot::Mutex;
+use std::sync::Once;
+
+/// Loggers drained by the exit hooks
+static LOGGERS: Mutex<Vec<&'static Logger>> = Mutex::new(Vec::new());
+
+static INSTALL: Once = Once::new();
+
+/// Drain `logger` when the process exits or is asked to terminate
+///
+/// On exit the logger is drained with
+/// [`Logger::drain_prioritized`] within [`DEFAULT_SHUTDOWN_TIMEOUT`], so
+/// that errors are written even if the queue cannot be emptied in time, and
+/// [`LoggerEvent::ShutdownStarted`](crate::core::LoggerEvent::ShutdownStarted)
+/// and `ShutdownFinished` are delivered. The logger stays usable afterwards.
+///
+/// On Unix, SIGTERM and SIGINT are handled on a dedicated thread: once the
+/// loggers are drained, the signal handlers installed before are restored
+/// and the signal is raised again, so the process terminates as it would
+/// have without the hooks (or the application's own handler runs). Signals
+/// that were ignored stay ignored. A second signal while draining is not
+/// delayed.
+///
+/// The hooks are installed by the first call; later calls add loggers.
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_logger_system::prelude::*;
+///
+/// rust_logger_system::init(
+///     Logger::builder()
+///         .appender(ConsoleAppender::new())
+///         .async_mode(10_000)
+///         .build(),
+/// );
+/// rust_logger_system::install_exit_hooks(rust_logger_system::global().unwrap());
+///
+/// // A logger that is not global can be leaked for the rest of the process
+/// let audit: &'static Logger = Box::leak(Box::new(Logger::with_async(1000)));
+/// rust_logger_system::install_exit_hooks(audit);
+/// ```
+pub fn install_exit_hooks(logger: &'static Logger) {
+    {
+        let mut loggers = LOGGERS.lock();
+        if !loggers.iter().any(|registered| std::ptr::eq(*registered, logger)) {
+            loggers.push(logger);
+        }
+    }
+    INSTALL.call_once(platform::install);
+}
+
+/// Drain every registered logger
+fn run_hooks() {
+    let loggers = LOGGERS.lock().clone();
+    for logger in loggers {
+        logger.shutdown_for_exit(DEFAULT_SHUTDOWN_TIMEOUT);
+    }
+}
+
+#[cfg(unix)]
+mod platform {
+    use super::run_hooks;
+    use std::io::ErrorKind;
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    const SIGNALS: [libc::c_int; 2] = [libc::SIGTERM, libc::SIGINT];
+
+    /// Write end of the pipe waking the exit thread
+    static SIGNAL_PIPE: AtomicI32 = AtomicI32::new(-1);
+
+    extern "C" fn on_signal(signal: libc::c_int) {
+        // Only async-signal-safe calls here: hand the signal to the exit thread
+        let fd = SIGNAL_PIPE.load(Ordering::Relaxed);
+        if fd >= 0 {
+            let byte = signal as u8;
+            unsafe { libc::write(fd, (&byte as *const u8).cast(), 1) };
+        }
+    }
+
+    extern "C" fn on_exit() {
+        run_hooks();
+    }
+
+    pub(super) fn install() {
+        install_with(|exit_thread| {
+            let builder = std::thread::Builder::new().name("logger-exit".to_string());
+            builder.spawn(exit_thread).map(drop)
+        });
+    }
+
+    /// Install the hooks, starting the thread that drains on a signal with
+    /// `spawn`
+    ///
+    /// If the thread cannot be started, the previous signal handlers are
+    /// restored, as nothing would act on the signals otherwise.
+    pub(super) fn install_with(
+        spawn: impl FnOnce(Box<dyn FnOnce() + Send>) -> std::io::Result<()>,
+    ) {
+        unsafe { libc::atexit(on_exit) };
+
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return;
+        }
+        for fd in fds {
+            unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) };
+        }
+        SIGNAL_PIPE.store(fds[1], Ordering::Relaxed);
+
+        let mut previous = Vec::new();
+        for signal in SIGNALS {
+            let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+            action.sa_sigaction = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            action.sa_flags = libc::SA_RESTART;
+            let mut old: libc::sigaction = unsafe { std::mem::zeroed() };
+            unsafe { libc::sigemptyset(&mut action.sa_mask) };
+            if unsafe { libc::sigaction(signal, &action, &mut old) } != 0 {
+                continue;
+            }
+            if old.sa_sigaction == libc::SIG_IGN {
+                // Keep ignoring signals the process was told to ignore
+                unsafe { libc::sigaction(signal, &old, std::ptr::null_mut()) };
+            } else {
+                previous.push((signal, old));
+            }
+        }
+
+        let read_fd = fds[0];
+        let restore = previous.clone();
+        let exit_thread = move || {
+            let mut byte = 0u8;
+            loop {
+                let read = unsafe { libc::read(read_fd, (&mut byte as *mut u8).cast(), 1) };
+                if read == 1 {
+                    break;
+                }
+                let interrupted = std::io::Error::last_os_error().kind() == ErrorKind::Interrupted;
+                if read < 0 && interrupted {
+                    continue;
+                }
+                return;
+            }
+
+            // A second signal takes the previous path right away
+            for (signal, old) in &previous {
+                unsafe { libc::sigaction(*signal, old, std::ptr::null_mut()) };
+            }
+            run_hooks();
+            unsafe { libc::raise(libc::c_int::from(byte)) };
+        };
+        if spawn(Box::new(exit_thread)).is_err() {
+            SIGNAL_PIPE.store(-1, Ordering::Relaxed);
+            for (signal, old) in &restore {
+                unsafe { libc::sigaction(*signal, old, std::ptr::null_mut()) };
+            }
+            for fd in fds {
+                unsafe { libc::close(fd) };
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::run_hooks;
+    use windows_sys::Win32::Foundation::{BOOL, FALSE, TRUE};
+    use windows_sys::Win32::System::Console::SetConsoleCtrlHandler;
+
+    extern "C" {
+        fn atexit(callback: extern "C" fn()) -> std::ffi::c_int;
+    }
+
+    /// Runs on a thread the system creates for the console event
+    unsafe extern "system" fn on_console_event(_event: u32) -> BOOL {
+        run_hooks();
+        // Let the next handler, by default process termination, run
+        FALSE
+    }
+
+    extern "C" fn on_exit() {
+        run_hooks();
+    }
+
+    pub(super) fn install() {
+        unsafe {
+            atexit(on_exit);
+            SetConsoleCtrlHandler(Some(on_console_event), TRUE);
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod platform {
+    pub(super) fn install() {}
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::Command;
+    use std::time::Duration;
+
+    /// Set in the child process that installs the hooks
+    const CHILD_VAR: &str = "EXIT_HOOKS_SPAWN_FAILS";
+
+    // Installing the hooks changes signal handling for the whole process, so
+    // the test runs itself again as a child process that does it.
+    #[test]
+    fn test_signals_restored_when_exit_thread_fails() {
+        if std::env::var_os(CHILD_VAR).is_some() {
+            platform::install_with(|_| Err(std::io::Error::other("no threads")));
+            unsafe { libc::raise(libc::SIGTERM) };
+            std::thread::sleep(Duration::from_secs(1));
+            std::process::exit(0);
+        }
+
+        let status = Command::new(std::env::current_exe().unwrap())
+            .args([
+                "exit_hooks::tests::test_signals_restored_when_exit_thread_fails",
+                "--exact",
+                "--test-threads=1",
+            ])
+            .env(CHILD_VAR, "1")
+            .status()
+            .expect("run child process");
+        assert_eq!(status.signal(), Some(libc::SIGTERM), "{:?}", status);
+    }
+}
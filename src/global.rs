@@ -0,0 +1,161 @@
+//! Process-global logger
+//!
+//! Libraries and deeply nested code can log without threading a `&Logger`
+//! through every call by installing one logger for the whole process.
+//! The `global_*!` macros, and the level macros called without a logger
+//! argument (`info!("...")`), write to it and do nothing until it is
+//! installed.
+//!
+//! The global logger lives for the rest of the process and is never dropped,
+//! so entries still queued by an async logger when the process exits are
+//! lost. Prefer synchronous mode for the global logger, or drain it at exit
+//! with [`install_exit_hooks`](crate::install_exit_hooks).
+//!
+//! [`set_scoped`] puts another logger in place of the global one for the
+//! current thread, which lets tests check what code logs through it.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//! use rust_logger_system::global_info;
+//!
+//! rust_logger_system::init(
+//!     Logger::builder()
+//!         .appender(ConsoleAppender::new())
+//!         .build(),
+//! );
+//!
+//! global_info!("Server listening on port {}", 8080);
+//! ```
+
+use crate::core::{Logger, LoggerError, Result};
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+
+static GLOBAL: OnceLock<Logger> = OnceLock::new();
+
+thread_local! {
+    /// Logger standing in for the global one on this thread
+    static SCOPED: Cell<Option<&'static Logger>> = const { Cell::new(None) };
+}
+
+/// Install the process-global logger
+///
+/// # Panics
+///
+/// Panics if a global logger has already been installed; use [`try_init`]
+/// to handle that case.
+pub fn init(logger: Logger) {
+    if try_init(logger).is_err() {
+        panic!("global logger already initialized");
+    }
+}
+
+/// Install the process-global logger if none is installed yet
+///
+/// # Errors
+///
+/// Returns an error if a global logger has already been installed. The
+/// given logger is dropped in that case.
+pub fn try_init(logger: Logger) -> Result<()> {
+    GLOBAL
+        .set(logger)
+        .map_err(|_| LoggerError::config("global", "global logger already initialized"))
+}
+
+/// Get the process-global logger, if one has been installed
+///
+/// On a thread with a [scoped](set_scoped) logger, that logger is returned
+/// instead.
+pub fn global() -> Option<&'static Logger> {
+    SCOPED.with(Cell::get).or_else(|| GLOBAL.get())
+}
+
+/// Use `logger` as the global logger on the current thread until the
+/// returned guard is dropped
+///
+/// Other threads keep using the process-global logger. Scopes nest: dropping
+/// the guard restores whatever logger was in place before.
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::prelude::*;
+///
+/// let logger: &'static Logger = Box::leak(Box::new(Logger::new()));
+/// {
+///     let _scope = rust_logger_system::global::set_scoped(logger);
+///     assert!(std::ptr::eq(rust_logger_system::global().unwrap(), logger));
+/// }
+/// ```
+pub fn set_scoped(logger: &'static Logger) -> ScopedGuard {
+    ScopedGuard {
+        previous: SCOPED.with(|scoped| scoped.replace(Some(logger))),
+        _not_send: PhantomData,
+    }
+}
+
+/// Restores the previous global logger of the thread when dropped
+///
+/// Returned by [`set_scoped`]; it cannot be sent to another thread.
+#[must_use = "the scoped logger is removed when the guard is dropped"]
+pub struct ScopedGuard {
+    previous: Option<&'static Logger>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ScopedGuard {
+    fn drop(&mut self) {
+        SCOPED.with(|scoped| scoped.set(self.previous));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{static_level_enabled, LogLevel};
+    use crate::testing::CaptureAppender;
+    use crate::{global_debug, global_info, global_log, global_trace};
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    // The global logger can only be installed once per process, so the
+    // whole lifecycle is covered by a single test.
+    #[test]
+    fn test_global_lifecycle() {
+        assert!(global().is_none());
+        global_info!("dropped before init {}", 1);
+        crate::info!("dropped before init {}", 2);
+
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .min_level(LogLevel::Debug)
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .build();
+        assert!(try_init(logger).is_ok());
+        assert!(try_init(Logger::new()).is_err());
+
+        global_debug!("user {} logged in", 42);
+        global_trace!("below min level");
+        global_log!(LogLevel::Error, "failed");
+        crate::warn!("port {}", 8080);
+        crate::trace!("still below min level");
+        crate::log!(LogLevel::Info, "ready"; attempt = 1);
+
+        let expected: Vec<_> = [
+            (LogLevel::Debug, "user 42 logged in"),
+            (LogLevel::Error, "failed"),
+            (LogLevel::Warn, "port 8080"),
+            (LogLevel::Info, "ready"),
+        ]
+        .into_iter()
+        .filter(|(level, _)| static_level_enabled(*level))
+        .map(|(_, message)| message)
+        .collect();
+        let messages: Vec<_> = entries.lock().iter().map(|e| e.message.clone()).collect();
+        assert_eq!(messages, expected);
+        assert!(std::ptr::eq(global().unwrap(), global().unwrap()));
+    }
+}
@@ -11,25 +11,50 @@
 //! - **Easy to Use**: Simple and intuitive API
 
 pub mod appenders;
+pub mod bridge;
 pub mod core;
+pub mod exit_hooks;
+pub mod global;
 pub mod macros;
+pub mod middleware;
+pub mod panic_hook;
+pub mod testing;
 
 pub mod prelude {
     pub use crate::appenders::{ConsoleAppender, FileAppender};
     pub use crate::core::{
-        Appender, ContextGuard, FieldValue, FormatterConfig, LogContext, LogEntry, LogLevel,
-        LogSampler, Logger, LoggerBuilder, LoggerContext, LoggerError, LoggerMetrics, LogPriority,
-        OutputFormat, OverflowCallback, OverflowPolicy, PriorityConfig, Result, SamplerMetrics,
-        SamplingConfig, StructuredLogBuilder, StructuredLogEntry, TimestampFormat, TracingContext,
-        DEFAULT_SHUTDOWN_TIMEOUT,
+        Appender, AppenderHealth, AppenderMetrics, AsyncQueue, BurstSuppressor, ChildLogger,
+        CircuitBreakerConfig, CircuitState, Clock, ConsoleTheme, ContextGuard, CustomLevel,
+        DiagnosticEvent, DiagnosticsHandler, Enricher, EnvConfig, EnvOverrides, FieldCondition,
+        FieldFilter, FieldMap, FieldValue, Filter, FlushPolicy, Formatter, FormatterConfig,
+        HealthStatus, HostMetadata, JsonProfile, LevelSchedule, LogContext, LogEntry, LogLevel,
+        LogSampler, Logger, LoggerBuilder, LoggerConfig, LoggerContext, LoggerError, LoggerEvent,
+        LoggerMetrics, LoggerRegistry, LogPriority, ManualClock, MetricsSink, ModuleLevelFilter,
+        OutputFormat, OverflowCallback, OverflowPolicy, PatternLayout, PriorityConfig,
+        RedactionConfig, Redactor, RenameFields, Result, RuntimeConfig, SamplerMetrics,
+        SamplingConfig, SamplingMode, Severity, Span, StructuredLogBuilder, StructuredLogEntry,
+        SuppressionConfig, SuppressionKey, SystemClock, TimedGuard, TimestampFormat, TracingContext,
+        Transformer, WalConfig, DEFAULT_SHUTDOWN_TIMEOUT,
     };
 }
 
 pub use appenders::{ConsoleAppender, FileAppender};
+pub use exit_hooks::install_exit_hooks;
+pub use global::{global, init, try_init};
+pub use panic_hook::install_panic_hook;
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+pub use core::BinaryFormat;
 pub use core::{
-    Appender, ContextGuard, FieldValue, FormatterConfig, LogContext, LogEntry, LogLevel,
-    LogSampler, Logger, LoggerBuilder, LoggerContext, LoggerError, LoggerMetrics, LogPriority,
-    OutputFormat, OverflowCallback, OverflowPolicy, PriorityConfig, Result, SamplerMetrics,
-    SamplingConfig, StructuredLogBuilder, StructuredLogEntry, TimestampFormat, TracingContext,
-    DEFAULT_SHUTDOWN_TIMEOUT,
+    Appender, AppenderHealth, AppenderMetrics, AsyncQueue, BurstSuppressor, ChildLogger,
+    CircuitBreakerConfig, CircuitState, Clock, ConsoleTheme, ContextGuard, CustomLevel,
+    DiagnosticEvent, DiagnosticsHandler, Enricher, EnvConfig, EnvOverrides, FieldCondition,
+    FieldFilter, FieldMap, FieldValue, Filter, FlushPolicy, Formatter, FormatterConfig,
+    HealthStatus, HostMetadata, JsonProfile, LevelSchedule, LogContext, LogEntry, LogLevel,
+    LogSampler, Logger, LoggerBuilder, LoggerConfig, LoggerContext, LoggerError, LoggerEvent,
+    LoggerMetrics, LoggerRegistry, LogPriority, ManualClock, MetricsSink, ModuleLevelFilter,
+    OutputFormat, OverflowCallback, OverflowPolicy, PatternLayout, PriorityConfig, RedactionConfig,
+    Redactor, RenameFields, Result, RuntimeConfig, SamplerMetrics, SamplingConfig, SamplingMode,
+    Severity, Span, StructuredLogBuilder, StructuredLogEntry, SuppressionConfig, SuppressionKey,
+    SystemClock, TimedGuard, TimestampFormat, TracingContext, Transformer, WalConfig,
+    DEFAULT_SHUTDOWN_TIMEOUT, STATIC_MAX_LEVEL,
 };
@@ -22,10 +22,69 @@
 //! let user_id = 42;
 //! let action = "login";
 //! info!(logger, "User {} performed action: {}", user_id, action);
+//!
+//! // Structured fields after a semicolon
+//! info!(logger, "User login"; user_id = user_id, action = action);
+//! ```
+//!
+//! # Global logger
+//!
+//! Without a logger argument, the macros write to the process-global logger
+//! (see [`init`](crate::init)) and do nothing, without evaluating their
+//! arguments, until one is installed:
+//!
+//! ```
+//! use rust_logger_system::info;
+//!
+//! info!("Server listening on port {}", 8080);
+//! ```
+//!
+//! # Compile-time filtering
+//!
+//! The `max_level_*` and `release_max_level_*` cargo features remove macro
+//! calls below a level at compile time, so disabled Trace and Debug calls
+//! cost nothing in release builds:
+//!
+//! ```toml
+//! [dependencies]
+//! rust_logger_system = { version = "0.1", features = ["release_max_level_info"] }
+//! ```
+//!
+//! See [`STATIC_MAX_LEVEL`](crate::core::STATIC_MAX_LEVEL).
+//!
+//! # Structured fields
+//!
+//! Fields follow the message after a `;` and are recorded through
+//! [`StructuredLogBuilder`](crate::StructuredLogBuilder). A value is
+//! converted with `Into<FieldValue>`; prefix it with `%` to record its
+//! `Display` output or with `?` for its `Debug` output:
+//!
+//! ```
+//! # use rust_logger_system::prelude::*;
+//! # let logger = Logger::new();
+//! use rust_logger_system::warn;
+//! let addr = std::net::Ipv4Addr::LOCALHOST;
+//! warn!(logger, "Slow request to {}", "/api";
+//!     latency_ms = 950, ip = %addr, "http.method" = "GET");
 //! ```
 
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
 /// Log a message with automatic formatting.
 ///
+/// The message and fields are only evaluated if the level is enabled (see
+/// [`Logger::enabled`](crate::Logger::enabled)). A message without fields
+/// is passed to [`Logger::log_args_at`](crate::Logger::log_args_at) and only
+/// formatted if sampling keeps the entry.
+///
+/// The entry records the calling file, line and module path. The module
+/// path is also its target, unless `target: "name"` follows the level, as
+/// with the `log` crate; see
+/// [`ModuleLevelFilter`](crate::ModuleLevelFilter) for per-target levels.
+/// Without a logger argument the message goes to the global logger, as with
+/// [`global_log!`](crate::global_log).
+///
 /// # Examples
 ///
 /// ```
@@ -34,11 +93,147 @@
 /// use rust_logger_system::log;
 /// log!(logger, LogLevel::Info, "Simple message");
 /// log!(logger, LogLevel::Error, "Error code: {}", 500);
+/// log!(logger, LogLevel::Info, target: "audit", "User {} logged in", 42);
+/// log!(LogLevel::Info, "To the global logger");
 /// ```
 #[macro_export]
 macro_rules! log {
-    ($logger:expr, $level:expr, $($arg:tt)+) => {
-        $logger.log($level, format!($($arg)+))
+    ($level:expr, $fmt:literal $($arg:tt)*) => {
+        $crate::global_log!($level, $fmt $($arg)*)
+    };
+    (
+        $logger:expr, $level:expr, target: $target:expr, $fmt:literal $(, $arg:expr)*
+        $(; $($fields:tt)+)?
+    ) => {{
+        let logger = &$logger;
+        let level = $level;
+        if $crate::core::static_level_enabled(level) && logger.enabled(level) {
+            $crate::__log_fields!(
+                $crate::StructuredLogBuilder::new(logger, level)
+                    .message($crate::core::entry_pool::format_message(format_args!(
+                        $fmt $(, $arg)*
+                    )))
+                    .target($target)
+                    .location(file!(), line!(), module_path!());
+                $($($fields)+)?
+            )
+            .log()
+        }
+    }};
+    ($logger:expr, $level:expr, err = $err:expr $(,)?) => {{
+        let logger = &$logger;
+        let level = $level;
+        if $crate::core::static_level_enabled(level) && logger.enabled(level) {
+            let err = &$err;
+            $crate::StructuredLogBuilder::new(logger, level)
+                .message(err.to_string())
+                .error(err)
+                .location(file!(), line!(), module_path!())
+                .log()
+        }
+    }};
+    (
+        $logger:expr, $level:expr, err = $err:expr, $fmt:literal $(, $arg:expr)*
+        $(; $($fields:tt)+)?
+    ) => {{
+        let logger = &$logger;
+        let level = $level;
+        if $crate::core::static_level_enabled(level) && logger.enabled(level) {
+            $crate::__log_fields!(
+                $crate::StructuredLogBuilder::new(logger, level)
+                    .message($crate::core::entry_pool::format_message(format_args!(
+                        $fmt $(, $arg)*
+                    )))
+                    .error(&$err)
+                    .location(file!(), line!(), module_path!());
+                $($($fields)+)?
+            )
+            .log()
+        }
+    }};
+    ($logger:expr, $level:expr, $fmt:literal $(, $arg:expr)* ; $($fields:tt)+) => {{
+        let logger = &$logger;
+        let level = $level;
+        if $crate::core::static_level_enabled(level) && logger.enabled(level) {
+            $crate::__log_fields!(
+                $crate::StructuredLogBuilder::new(logger, level)
+                    .message($crate::core::entry_pool::format_message(format_args!(
+                        $fmt $(, $arg)*
+                    )))
+                    .location(file!(), line!(), module_path!());
+                $($fields)+
+            )
+            .log()
+        }
+    }};
+    ($logger:expr, $level:expr, $($arg:tt)+) => {{
+        let logger = &$logger;
+        let level = $level;
+        if $crate::core::static_level_enabled(level) && logger.enabled(level) {
+            logger.log_args_at(level, format_args!($($arg)+), file!(), line!(), module_path!())
+        }
+    }};
+}
+
+/// Add `key = value` fields to a structured log builder
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_fields {
+    ($builder:expr;) => {
+        $builder
+    };
+    ($builder:expr; $key:ident = % $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__log_fields!(
+            $builder.field(stringify!($key), format!("{}", $value)); $($($rest)*)?
+        )
+    };
+    ($builder:expr; $key:ident = ? $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__log_fields!(
+            $builder.field(stringify!($key), format!("{:?}", $value)); $($($rest)*)?
+        )
+    };
+    ($builder:expr; $key:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__log_fields!($builder.field(stringify!($key), $value); $($($rest)*)?)
+    };
+    ($builder:expr; $key:literal = % $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__log_fields!($builder.field($key, format!("{}", $value)); $($($rest)*)?)
+    };
+    ($builder:expr; $key:literal = ? $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__log_fields!($builder.field($key, format!("{:?}", $value)); $($($rest)*)?)
+    };
+    ($builder:expr; $key:literal = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__log_fields!($builder.field($key, $value); $($($rest)*)?)
+    };
+}
+
+/// Check whether a level is enabled, to guard expensive work
+///
+/// Takes a level name (`Trace`, `Debug`, ...). Without a logger argument it
+/// checks the global logger and is false until one is installed. Levels
+/// compiled out by the `max_level_*` features (see
+/// [`STATIC_MAX_LEVEL`](crate::core::STATIC_MAX_LEVEL)) are never enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use rust_logger_system::prelude::*;
+/// # let logger = Logger::new();
+/// use rust_logger_system::{debug, log_enabled};
+/// if log_enabled!(logger, Debug) {
+///     let snapshot = format!("{:?}", vec![1, 2, 3]);
+///     debug!(logger, "State: {}", snapshot);
+/// }
+/// assert!(!log_enabled!(Trace));
+/// ```
+#[macro_export]
+macro_rules! log_enabled {
+    ($level:ident) => {
+        $crate::core::static_level_enabled($crate::LogLevel::$level)
+            && $crate::global().is_some_and(|logger| logger.enabled($crate::LogLevel::$level))
+    };
+    ($logger:expr, $level:ident) => {
+        $crate::core::static_level_enabled($crate::LogLevel::$level)
+            && $logger.enabled($crate::LogLevel::$level)
     };
 }
 
@@ -56,6 +251,12 @@ macro_rules! log {
 /// ```
 #[macro_export]
 macro_rules! trace {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::global_log!($crate::LogLevel::Trace, target: $target, $($arg)+)
+    };
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::global_log!($crate::LogLevel::Trace, $fmt $($arg)*)
+    };
     ($logger:expr, $($arg:tt)+) => {
         $crate::log!($logger, $crate::LogLevel::Trace, $($arg)+)
     };
@@ -74,6 +275,12 @@ macro_rules! trace {
 /// ```
 #[macro_export]
 macro_rules! debug {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::global_log!($crate::LogLevel::Debug, target: $target, $($arg)+)
+    };
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::global_log!($crate::LogLevel::Debug, $fmt $($arg)*)
+    };
     ($logger:expr, $($arg:tt)+) => {
         $crate::log!($logger, $crate::LogLevel::Debug, $($arg)+)
     };
@@ -89,9 +296,17 @@ macro_rules! debug {
 /// use rust_logger_system::info;
 /// info!(logger, "Application started");
 /// info!(logger, "Processing {} items", 100);
+/// info!(logger, target: "http", "GET {} took {}ms", "/health", 3);
+/// info!(target: "http", "To the global logger");
 /// ```
 #[macro_export]
 macro_rules! info {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::global_log!($crate::LogLevel::Info, target: $target, $($arg)+)
+    };
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::global_log!($crate::LogLevel::Info, $fmt $($arg)*)
+    };
     ($logger:expr, $($arg:tt)+) => {
         $crate::log!($logger, $crate::LogLevel::Info, $($arg)+)
     };
@@ -110,6 +325,12 @@ macro_rules! info {
 /// ```
 #[macro_export]
 macro_rules! warn {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::global_log!($crate::LogLevel::Warn, target: $target, $($arg)+)
+    };
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::global_log!($crate::LogLevel::Warn, $fmt $($arg)*)
+    };
     ($logger:expr, $($arg:tt)+) => {
         $crate::log!($logger, $crate::LogLevel::Warn, $($arg)+)
     };
@@ -117,6 +338,12 @@ macro_rules! warn {
 
 /// Log an error-level message.
 ///
+/// With `err = <error>` first, the error's message, type and `source()`
+/// chain are recorded as `error.message`, `error.type` and `error.chain`
+/// fields (see [`LogContext::with_error`](crate::LogContext::with_error)).
+/// The message defaults to the error's `Display` output. For a boxed
+/// `dyn Error`, pass `err = *e`.
+///
 /// # Examples
 ///
 /// ```
@@ -125,9 +352,19 @@ macro_rules! warn {
 /// use rust_logger_system::error;
 /// error!(logger, "Failed to connect to database");
 /// error!(logger, "Error code: {}, message: {}", 500, "Internal error");
+///
+/// let e = "abc".parse::<u16>().unwrap_err();
+/// error!(logger, err = e, "Invalid port in {}", "config.toml");
+/// error!(logger, err = e);
 /// ```
 #[macro_export]
 macro_rules! error {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::global_log!($crate::LogLevel::Error, target: $target, $($arg)+)
+    };
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::global_log!($crate::LogLevel::Error, $fmt $($arg)*)
+    };
     ($logger:expr, $($arg:tt)+) => {
         $crate::log!($logger, $crate::LogLevel::Error, $($arg)+)
     };
@@ -146,14 +383,397 @@ macro_rules! error {
 /// ```
 #[macro_export]
 macro_rules! fatal {
+    (target: $target:expr, $($arg:tt)+) => {
+        $crate::global_log!($crate::LogLevel::Fatal, target: $target, $($arg)+)
+    };
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::global_log!($crate::LogLevel::Fatal, $fmt $($arg)*)
+    };
     ($logger:expr, $($arg:tt)+) => {
         $crate::log!($logger, $crate::LogLevel::Fatal, $($arg)+)
     };
 }
 
+/// Log a Fatal message, flush everything and exit the process.
+///
+/// Queued entries are drained, the message is written on the calling thread
+/// even in async mode, and all appenders are flushed before
+/// [`std::process::exit`] is called with `code` (1 if omitted). See
+/// [`Logger::log_entry_and_exit`](crate::Logger::log_entry_and_exit).
+///
+/// Unlike the other macros, the message is never compiled out or filtered
+/// by level; the process always exits.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use rust_logger_system::prelude::*;
+/// # let logger = Logger::with_async(1000);
+/// use rust_logger_system::fatal_abort;
+///
+/// fatal_abort!(logger, code = 3, "Cannot open database {}", "users.db");
+/// ```
+#[macro_export]
+macro_rules! fatal_abort {
+    ($logger:expr, code = $code:expr, $($arg:tt)+) => {
+        $logger.log_entry_and_exit(
+            $crate::LogEntry::new($crate::LogLevel::Fatal, format!($($arg)+))
+                .with_location(file!(), line!(), module_path!()),
+            $code,
+        )
+    };
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::fatal_abort!($logger, code = 1, $($arg)+)
+    };
+}
+
+/// Log a message only the first time this call site runs.
+///
+/// Takes a level name (`Trace`, `Debug`, ...). Calls made while the level is
+/// disabled are not counted.
+///
+/// # Examples
+///
+/// ```
+/// # use rust_logger_system::prelude::*;
+/// # let logger = Logger::new();
+/// use rust_logger_system::once;
+/// for _ in 0..3 {
+///     once!(logger, Warn, "Config key {} is deprecated", "timeout");
+/// }
+/// ```
+#[macro_export]
+macro_rules! once {
+    ($logger:expr, $level:ident, $($arg:tt)+) => {{
+        static DONE: ::std::sync::atomic::AtomicBool = ::std::sync::atomic::AtomicBool::new(false);
+        let logger = &$logger;
+        if $crate::log_enabled!(logger, $level)
+            && !DONE.swap(true, ::std::sync::atomic::Ordering::Relaxed)
+        {
+            $crate::log!(logger, $crate::LogLevel::$level, $($arg)+)
+        }
+    }};
+}
+
+/// Log a message on the 1st, (n+1)th, (2n+1)th, ... run of this call site.
+///
+/// Takes a level name and `n`. Calls made while the level is disabled are
+/// not counted.
+///
+/// # Examples
+///
+/// ```
+/// # use rust_logger_system::prelude::*;
+/// # let logger = Logger::new();
+/// use rust_logger_system::every_n;
+/// for i in 0..1000 {
+///     every_n!(logger, Info, 100, "Processed {} items", i + 1);
+/// }
+/// ```
+#[macro_export]
+macro_rules! every_n {
+    ($logger:expr, $level:ident, $n:expr, $($arg:tt)+) => {{
+        static COUNT: ::std::sync::atomic::AtomicU64 = ::std::sync::atomic::AtomicU64::new(0);
+        let logger = &$logger;
+        if $crate::log_enabled!(logger, $level)
+            && COUNT.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed) % ($n as u64).max(1) == 0
+        {
+            $crate::log!(logger, $crate::LogLevel::$level, $($arg)+)
+        }
+    }};
+}
+
+/// Log a message from this call site at most once per interval.
+///
+/// Takes a level name and a [`Duration`](std::time::Duration). Calls within
+/// the interval after a logged message are dropped. The time is read from
+/// the logger's [`clock`](crate::core::LoggerBuilder::clock).
+///
+/// # Examples
+///
+/// ```
+/// # use rust_logger_system::prelude::*;
+/// # let logger = Logger::new();
+/// use rust_logger_system::throttle;
+/// use std::time::Duration;
+/// for attempt in 0..100 {
+///     throttle!(logger, Warn, Duration::from_secs(5), "Upstream down (attempt {})", attempt);
+/// }
+/// ```
+#[macro_export]
+macro_rules! throttle {
+    ($logger:expr, $level:ident, $interval:expr, $($arg:tt)+) => {{
+        static GATE: $crate::macros::CallSiteThrottle = $crate::macros::CallSiteThrottle::new();
+        let logger = &$logger;
+        if $crate::log_enabled!(logger, $level) && GATE.ready(logger.clock().now(), $interval) {
+            $crate::log!(logger, $crate::LogLevel::$level, $($arg)+)
+        }
+    }};
+}
+
+/// Time the rest of the scope, logging its duration when the guard drops.
+///
+/// Returns a [`TimedGuard`](crate::TimedGuard) that records the call site.
+/// The level defaults to Info; the entry is logged at Warn instead if the
+/// optional `warn_after` threshold is exceeded.
+///
+/// # Examples
+///
+/// ```
+/// # use rust_logger_system::prelude::*;
+/// # let logger = Logger::new();
+/// use rust_logger_system::timed;
+/// use std::time::Duration;
+///
+/// fn rebuild(logger: &Logger) {
+///     let _timed = timed!(logger, Debug, "rebuild index", warn_after = Duration::from_secs(1));
+///     // ...
+/// } // logs "rebuild index completed" with duration_ms
+/// # rebuild(&logger);
+/// ```
+#[macro_export]
+macro_rules! timed {
+    ($logger:expr, $level:ident, $name:expr, warn_after = $threshold:expr $(,)?) => {
+        $crate::timed!($logger, $level, $name).warn_after($threshold)
+    };
+    ($logger:expr, $level:ident, $name:expr $(,)?) => {
+        $logger
+            .timed_at($crate::LogLevel::$level, $name)
+            .location(file!(), line!(), module_path!())
+    };
+    ($logger:expr, $name:expr, warn_after = $threshold:expr $(,)?) => {
+        $crate::timed!($logger, Info, $name, warn_after = $threshold)
+    };
+    ($logger:expr, $name:expr $(,)?) => {
+        $crate::timed!($logger, Info, $name)
+    };
+}
+
+/// Evaluate an expression and log how long it took.
+///
+/// Returns the expression's value. Takes an optional level name (Info by
+/// default), like [`timed!`](crate::timed).
+///
+/// # Examples
+///
+/// ```
+/// # use rust_logger_system::prelude::*;
+/// # let logger = Logger::new();
+/// use rust_logger_system::measure;
+/// let total: u64 = measure!(logger, "sum", (1..=1000u64).sum());
+/// let sorted = measure!(logger, Debug, "sort", {
+///     let mut values = vec![3, 1, 2];
+///     values.sort();
+///     values
+/// });
+/// assert_eq!(total, 500500);
+/// ```
+#[macro_export]
+macro_rules! measure {
+    ($logger:expr, $level:ident, $name:expr, $body:expr $(,)?) => {{
+        let _timed = $crate::timed!($logger, $level, $name);
+        $body
+    }};
+    ($logger:expr, $name:expr, $body:expr $(,)?) => {{
+        let _timed = $crate::timed!($logger, $name);
+        $body
+    }};
+}
+
+/// Log an expression and its value at Debug, then return the value.
+///
+/// Like [`std::dbg!`], but the entry goes through the logger, recording the
+/// call site, instead of being printed to stderr. The value is moved in and
+/// returned; pass a reference to keep ownership. Several expressions return
+/// a tuple.
+///
+/// # Examples
+///
+/// ```
+/// # use rust_logger_system::prelude::*;
+/// # let logger = Logger::new();
+/// use rust_logger_system::log_dbg;
+/// let retries = 3;
+/// let delay = log_dbg!(logger, retries * 250); // logs "retries * 250 = 750"
+/// let (a, b) = log_dbg!(logger, delay / 2, &retries);
+/// ```
+#[macro_export]
+macro_rules! log_dbg {
+    ($logger:expr, $value:expr $(,)?) => {
+        match $value {
+            value => {
+                $crate::log!(
+                    $logger,
+                    $crate::LogLevel::Debug,
+                    "{} = {:?}",
+                    stringify!($value),
+                    &value
+                );
+                value
+            }
+        }
+    };
+    ($logger:expr, $($value:expr),+ $(,)?) => {
+        ($($crate::log_dbg!($logger, $value)),+,)
+    };
+}
+
+/// Time of the last message from one [`throttle!`] call site
+#[doc(hidden)]
+pub struct CallSiteThrottle(Mutex<Option<Instant>>);
+
+impl CallSiteThrottle {
+    pub const fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    /// Check whether `interval` has passed between the last message and
+    /// `now`, and if so, record a new one
+    pub fn ready(&self, now: Instant, interval: Duration) -> bool {
+        let mut last = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        match *last {
+            Some(previous) if now.duration_since(previous) < interval => false,
+            _ => {
+                *last = Some(now);
+                true
+            }
+        }
+    }
+}
+
+impl Default for CallSiteThrottle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Log a message to the global logger with automatic formatting.
+///
+/// Does nothing if no global logger has been installed with
+/// [`init`](crate::init) or [`try_init`](crate::try_init).
+///
+/// # Examples
+///
+/// ```
+/// # use rust_logger_system::prelude::*;
+/// use rust_logger_system::global_log;
+/// global_log!(LogLevel::Info, "Simple message");
+/// global_log!(LogLevel::Error, "Error code: {}", 500);
+/// ```
+#[macro_export]
+macro_rules! global_log {
+    ($level:expr, $($arg:tt)+) => {
+        if let Some(logger) = $crate::global() {
+            $crate::log!(logger, $level, $($arg)+)
+        }
+    };
+}
+
+/// Log a trace-level message to the global logger.
+///
+/// # Examples
+///
+/// ```
+/// use rust_logger_system::global_trace;
+/// global_trace!("Entering function: calculate()");
+/// global_trace!("Variable value: {}", 42);
+/// ```
+#[macro_export]
+macro_rules! global_trace {
+    ($($arg:tt)+) => {
+        $crate::global_log!($crate::LogLevel::Trace, $($arg)+)
+    };
+}
+
+/// Log a debug-level message to the global logger.
+///
+/// # Examples
+///
+/// ```
+/// use rust_logger_system::global_debug;
+/// global_debug!("Cache miss");
+/// global_debug!("Lookup took {}ms", 3);
+/// ```
+#[macro_export]
+macro_rules! global_debug {
+    ($($arg:tt)+) => {
+        $crate::global_log!($crate::LogLevel::Debug, $($arg)+)
+    };
+}
+
+/// Log an info-level message to the global logger.
+///
+/// # Examples
+///
+/// ```
+/// use rust_logger_system::global_info;
+/// global_info!("Server started");
+/// global_info!("Listening on port {}", 8080);
+/// ```
+#[macro_export]
+macro_rules! global_info {
+    ($($arg:tt)+) => {
+        $crate::global_log!($crate::LogLevel::Info, $($arg)+)
+    };
+}
+
+/// Log a warning-level message to the global logger.
+///
+/// # Examples
+///
+/// ```
+/// use rust_logger_system::global_warn;
+/// global_warn!("Disk usage high");
+/// global_warn!("Retry {} of {}", 1, 3);
+/// ```
+#[macro_export]
+macro_rules! global_warn {
+    ($($arg:tt)+) => {
+        $crate::global_log!($crate::LogLevel::Warn, $($arg)+)
+    };
+}
+
+/// Log an error-level message to the global logger.
+///
+/// # Examples
+///
+/// ```
+/// use rust_logger_system::global_error;
+/// global_error!("Connection lost");
+/// global_error!("Error code: {}", 500);
+/// ```
+#[macro_export]
+macro_rules! global_error {
+    ($($arg:tt)+) => {
+        $crate::global_log!($crate::LogLevel::Error, $($arg)+)
+    };
+}
+
+/// Log a fatal-level message to the global logger.
+///
+/// # Examples
+///
+/// ```
+/// use rust_logger_system::global_fatal;
+/// global_fatal!("Unrecoverable state");
+/// global_fatal!("Critical failure: {}", "system");
+/// ```
+#[macro_export]
+macro_rules! global_fatal {
+    ($($arg:tt)+) => {
+        $crate::global_log!($crate::LogLevel::Fatal, $($arg)+)
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::{Logger, LogLevel};
+    use crate::testing::CapturingLogger;
+
+    /// Logger at the default level, capturing its entries
+    fn capture() -> CapturingLogger {
+        CapturingLogger::from_builder(Logger::builder())
+    }
 
     #[test]
     fn test_log_macro() {
@@ -162,6 +782,220 @@ mod tests {
         log!(logger, LogLevel::Info, "Formatted: {}", 42);
     }
 
+    #[test]
+    fn test_macro_records_location() {
+
+        let logger = capture();
+
+        let line = line!() + 1;
+        info!(logger, "located");
+        warn!(logger, target: "audit", "targeted {}", 1; user = "alice");
+
+        let entries = logger.entries();
+        assert_eq!(entries[0].file.as_deref(), Some(file!()));
+        assert_eq!(entries[0].line, Some(line));
+        assert_eq!(entries[0].module_path.as_deref(), Some(module_path!()));
+        assert_eq!(entries[0].target.as_deref(), Some(module_path!()));
+        assert_eq!(entries[1].target.as_deref(), Some("audit"));
+        assert_eq!(entries[1].module_path.as_deref(), Some(module_path!()));
+        assert_eq!(entries[1].message, "targeted 1");
+    }
+
+    #[test]
+    fn test_macro_fields() {
+        use crate::core::FieldValue;
+
+        let logger = capture();
+
+        let addr = std::net::Ipv4Addr::new(10, 0, 0, 1);
+        let line = line!() + 1;
+        info!(logger, "user {} login", "alice"; user_id = 42, ip = %addr, tags = ?["a"],);
+        warn!(logger, "retry"; "http.status" = 503);
+
+        let entries = logger.entries();
+        assert_eq!(entries[0].message, "user alice login");
+        assert_eq!(entries[0].line, Some(line));
+        let fields = entries[0].context.as_ref().unwrap().fields();
+        assert_eq!(fields["user_id"], FieldValue::from(42));
+        assert_eq!(fields["ip"], FieldValue::from("10.0.0.1"));
+        assert_eq!(fields["tags"], FieldValue::from("[\"a\"]"));
+
+        let fields = entries[1].context.as_ref().unwrap().fields();
+        assert_eq!(entries[1].level, LogLevel::Warn);
+        assert_eq!(fields["http.status"], FieldValue::from(503));
+    }
+
+    #[test]
+    fn test_disabled_level_skips_formatting() {
+        use std::cell::Cell;
+        use std::fmt;
+
+        struct Counted<'a>(&'a Cell<u32>);
+
+        impl fmt::Display for Counted<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.set(self.0.get() + 1);
+                write!(f, "counted")
+            }
+        }
+
+        let calls = Cell::new(0);
+        let logger = Logger::new();
+        assert!(!log_enabled!(logger, Debug));
+        assert!(log_enabled!(logger, Info));
+
+        debug!(logger, "{}", Counted(&calls));
+        trace!(logger, "skipped"; value = %Counted(&calls));
+        assert_eq!(calls.get(), 0);
+
+        info!(logger, "{}", Counted(&calls));
+        assert_eq!(calls.get(), 1);
+
+        logger.set_enabled(false);
+        assert!(!log_enabled!(logger, Fatal));
+    }
+
+    #[test]
+    fn test_rate_limited_macros() {
+        use std::time::Duration;
+
+        let logger = capture();
+
+        for i in 0..7 {
+            once!(logger, Debug, "disabled {}", i);
+            once!(logger, Warn, "once {}", i);
+            every_n!(logger, Info, 3, "every {}", i);
+            throttle!(logger, Info, Duration::from_secs(3600), "throttled {}", i);
+            throttle!(logger, Info, Duration::ZERO, "unthrottled {}", i);
+        }
+
+        let messages = logger.messages();
+        let count = |prefix: &str| messages.iter().filter(|m| m.starts_with(prefix)).count();
+        assert_eq!(count("disabled"), 0);
+        assert_eq!(count("once"), 1);
+        assert_eq!(count("every"), 3);
+        assert!(messages.contains(&"every 6".to_string()));
+        assert_eq!(count("throttled"), 1);
+        assert_eq!(count("unthrottled"), 7);
+    }
+
+    #[test]
+    fn test_throttle_reads_logger_clock() {
+        use crate::core::ManualClock;
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let clock = Arc::new(ManualClock::new());
+        let logger = CapturingLogger::from_builder(Logger::builder().clock(clock.clone()));
+
+        for step in [0, 59, 1, 30, 30] {
+            clock.advance(Duration::from_secs(step));
+            throttle!(logger, Info, Duration::from_secs(60), "after {}", step);
+        }
+        assert_eq!(logger.messages(), ["after 0", "after 1", "after 30"]);
+    }
+
+    #[test]
+    fn test_error_macro_records_chain() {
+        use crate::core::FieldValue;
+
+        let logger = capture();
+
+        let io = std::io::Error::other("disk full");
+        let wrapped = crate::core::LoggerError::IoOperation {
+            operation: "saving".to_string(),
+            message: "disk full".to_string(),
+            source: io,
+        };
+        error!(logger, err = wrapped, "Saving {} failed", "report"; attempt = 2);
+        let boxed: Box<dyn std::error::Error> = "x".parse::<u8>().unwrap_err().into();
+        error!(logger, err = *boxed);
+
+        let entries = logger.entries();
+        assert_eq!(entries[0].message, "Saving report failed");
+        let fields = entries[0].context.as_ref().unwrap().fields();
+        assert_eq!(fields["attempt"], FieldValue::from(2));
+        assert!(fields["error.type"].to_string().contains("LoggerError"));
+        assert!(fields.contains_key("error.message"));
+        assert!(fields["error.chain"].to_string().contains("disk full"));
+
+        assert_eq!(entries[1].message, "invalid digit found in string");
+        assert_eq!(entries[1].level, LogLevel::Error);
+    }
+
+    #[test]
+    fn test_static_max_level() {
+        use crate::core::{static_level_enabled, STATIC_MAX_LEVEL};
+
+        for level in [LogLevel::Trace, LogLevel::Info, LogLevel::Fatal] {
+            let expected = STATIC_MAX_LEVEL.is_some_and(|max| level >= max);
+            assert_eq!(static_level_enabled(level), expected);
+        }
+        if cfg!(not(any(
+            feature = "max_level_off",
+            feature = "max_level_error",
+            feature = "max_level_warn",
+            feature = "max_level_info",
+            feature = "max_level_debug",
+            feature = "release_max_level_off",
+            feature = "release_max_level_error",
+            feature = "release_max_level_warn",
+            feature = "release_max_level_info",
+            feature = "release_max_level_debug",
+        ))) {
+            assert_eq!(STATIC_MAX_LEVEL, Some(LogLevel::Trace));
+        }
+    }
+
+    #[test]
+    fn test_timing_macros() {
+        use std::time::Duration;
+
+        let logger = capture();
+
+        let value = measure!(logger, "compute", 6 * 7);
+        assert_eq!(value, 42);
+        {
+            let line = line!() + 1;
+            let timed = timed!(logger, Debug, "slow", warn_after = Duration::ZERO);
+            std::thread::sleep(Duration::from_millis(1));
+            timed.finish();
+            assert_eq!(logger.entries()[1].line, Some(line));
+        }
+        measure!(logger, Debug, "hidden", ());
+
+        let entries = logger.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message, "compute completed");
+        assert_eq!(entries[0].file.as_deref(), Some(file!()));
+        assert_eq!(entries[1].level, LogLevel::Warn);
+        assert!(entries[1].context.as_ref().unwrap().fields().contains_key("duration_ms"));
+    }
+
+    #[test]
+    fn test_log_dbg_macro() {
+        let logger = capture();
+
+        let name = String::from("db");
+        assert_eq!(log_dbg!(logger, 6 * 7), 42);
+        logger.store_min_level(LogLevel::Debug);
+        let line = line!() + 1;
+        let doubled = log_dbg!(logger, 6 * 7) * 2;
+        let (owned, len) = log_dbg!(logger, name, 2,);
+
+        assert_eq!((doubled, owned.as_str(), len), (84, "db", 2));
+        let entries = logger.entries();
+        if !crate::core::static_level_enabled(LogLevel::Debug) {
+            // Compiled out, but the values are still passed through
+            assert!(entries.is_empty());
+            return;
+        }
+        let messages: Vec<_> = entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["6 * 7 = 42", "name = \"db\"", "2 = 2"]);
+        assert_eq!(entries[0].level, LogLevel::Debug);
+        assert_eq!(entries[0].line, Some(line));
+    }
+
     #[test]
     fn test_trace_macro() {
         let mut logger = Logger::new();
@@ -0,0 +1,15 @@
+//! Request logging middleware for HTTP frameworks
+//!
+//! Middleware in this module emits one structured entry per request and
+//! scopes a `request_id` field to the handler (see
+//! [`local_context`](crate::core::local_context)), so handler logs can be
+//! correlated with the request entry.
+//!
+//! - `tower` feature: `RequestLogLayer`, usable with axum, hyper and any
+//!   other `tower`-based stack
+
+#[cfg(feature = "tower")]
+mod tower;
+
+#[cfg(feature = "tower")]
+pub use self::tower::{RequestLogLayer, RequestLogService, REQUEST_ID_HEADER};
@@ -0,0 +1,223 @@
+//! `tower` request logging layer
+
+use crate::core::local_context::{with_local_fields, WithLocalFields};
+use crate::core::{LogContext, LogLevel, Logger};
+use http::{Request, Response};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Header carrying the request ID
+///
+/// If an incoming request has this header its value is used as the
+/// `request_id` field; otherwise a random ID is generated.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// [`tower_layer::Layer`] that logs every request through a [`Logger`]
+///
+/// Each request produces one entry when the response is ready:
+///
+/// - message: `<method> <path> <status>`
+/// - fields: `method`, `path`, `status`, `latency_ms` and `request_id`
+/// - level: `Error` for 5xx responses and service errors, `Warn` for 4xx,
+///   `Info` otherwise
+///
+/// While the inner service handles the request, `request_id` is attached
+/// as a local field to every entry logged from the handler.
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::middleware::RequestLogLayer;
+/// use rust_logger_system::prelude::*;
+/// use std::sync::Arc;
+///
+/// let logger = Arc::new(
+///     Logger::builder()
+///         .appender(ConsoleAppender::new())
+///         .build(),
+/// );
+///
+/// // With axum: Router::new().route(...).layer(RequestLogLayer::new(logger))
+/// let layer = RequestLogLayer::new(logger);
+/// ```
+#[derive(Clone)]
+pub struct RequestLogLayer {
+    logger: Arc<Logger>,
+}
+
+impl RequestLogLayer {
+    /// Create a layer logging to the given logger
+    pub fn new(logger: impl Into<Arc<Logger>>) -> Self {
+        Self {
+            logger: logger.into(),
+        }
+    }
+}
+
+impl<S> Layer<S> for RequestLogLayer {
+    type Service = RequestLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestLogService {
+            inner,
+            logger: Arc::clone(&self.logger),
+        }
+    }
+}
+
+/// Service created by [`RequestLogLayer`]
+#[derive(Clone)]
+pub struct RequestLogService<S> {
+    inner: S,
+    logger: Arc<Logger>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for RequestLogService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: fmt::Display,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future =
+        Pin<Box<dyn Future<Output = std::result::Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let start = Instant::now();
+        let method = request.method().to_string();
+        let path = request.uri().path().to_string();
+        let request_id = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map_or_else(|| format!("{:016x}", rand::random::<u64>()), str::to_string);
+
+        let fields = LogContext::new().with_field("request_id", request_id.as_str());
+        let inner = &mut self.inner;
+        let future = with_local_fields(fields.clone(), || inner.call(request));
+        let future = WithLocalFields::new(fields, future);
+        let logger = Arc::clone(&self.logger);
+
+        Box::pin(async move {
+            let result = future.await;
+            let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+            let mut context = LogContext::new()
+                .with_field("method", method.as_str())
+                .with_field("path", path.as_str());
+            let (level, message) = match &result {
+                Ok(response) => {
+                    let status = response.status();
+                    context.add_field("status", i64::from(status.as_u16()));
+                    let level = if status.is_server_error() {
+                        LogLevel::Error
+                    } else if status.is_client_error() {
+                        LogLevel::Warn
+                    } else {
+                        LogLevel::Info
+                    };
+                    (level, format!("{} {} {}", method, path, status.as_u16()))
+                }
+                Err(error) => {
+                    context.add_field("error", error.to_string());
+                    (LogLevel::Error, format!("{} {} failed", method, path))
+                }
+            };
+            context.add_field("latency_ms", latency_ms);
+            context.add_field("request_id", request_id);
+
+            logger.log_with_context(level, message, context);
+            result
+        })
+    }
+}
+
+impl fmt::Debug for RequestLogLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestLogLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for RequestLogService<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestLogService")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::FieldValue;
+    use crate::testing::CaptureAppender;
+    use parking_lot::Mutex;
+    use std::convert::Infallible;
+    use std::future::{ready, Ready};
+
+    /// Handler that logs once and answers with the status from the path
+    struct Handler(Arc<Logger>);
+
+    impl Service<Request<()>> for Handler {
+        type Response = Response<()>;
+        type Error = Infallible;
+        type Future = Ready<std::result::Result<Response<()>, Infallible>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<std::result::Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: Request<()>) -> Self::Future {
+            self.0.info("handler");
+            let status: u16 = request.uri().path().trim_start_matches('/').parse().unwrap();
+            ready(Ok(Response::builder().status(status).body(()).unwrap()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_entry_and_handler_context() {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let logger = Arc::new(
+            Logger::builder()
+                .appender(CaptureAppender::new(Arc::clone(&entries)))
+                .build(),
+        );
+        let mut service = RequestLogLayer::new(Arc::clone(&logger)).layer(Handler(logger));
+
+        let request = Request::get("/503")
+            .header(REQUEST_ID_HEADER, "req-1")
+            .body(())
+            .unwrap();
+        service.call(request).await.unwrap();
+        service.call(Request::get("/200").body(()).unwrap()).await.unwrap();
+
+        let entries = entries.lock();
+        assert_eq!(entries.len(), 4);
+
+        let handler = entries[0].context.as_ref().unwrap().fields();
+        assert_eq!(handler["request_id"], "req-1".into());
+
+        assert_eq!(entries[1].level, LogLevel::Error);
+        assert_eq!(entries[1].message, "GET /503 503");
+        let fields = entries[1].context.as_ref().unwrap().fields();
+        assert_eq!(fields["method"], "GET".into());
+        assert_eq!(fields["status"], 503.into());
+        assert_eq!(fields["request_id"], "req-1".into());
+        assert!(matches!(fields["latency_ms"], FieldValue::Float(_)));
+
+        assert_eq!(entries[3].level, LogLevel::Info);
+        let generated = &entries[3].context.as_ref().unwrap().fields()["request_id"];
+        assert_eq!(entries[2].context.as_ref().unwrap().fields()["request_id"], *generated);
+    }
+}
@@ -0,0 +1,104 @@
+//! Panic logging
+//!
+//! [`install_panic_hook`] routes panics through a [`Logger`] so that they
+//! end up in the same appenders as the rest of the application's logs
+//! instead of only on stderr.
+
+use crate::core::{intern, LogContext, LogEntry, LogLevel, Logger};
+use std::backtrace::Backtrace;
+use std::cell::Cell;
+use std::panic::PanicHookInfo;
+use std::sync::Arc;
+
+thread_local! {
+    /// Set while this thread is logging a panic, to ignore nested panics
+    static IN_HOOK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Install a panic hook that logs panics as `Fatal` entries
+///
+/// Each panic produces an entry with the message `panic: <payload>`, the
+/// panic location as source location, and a `backtrace` field. The entry
+/// is written and the appenders are flushed before the previously
+/// installed hook runs; with [`PriorityConfig::preserve_critical`]
+/// enabled (the default), an async logger writes it on the panicking
+/// thread instead of queueing it.
+///
+/// A panic raised while the hook itself is logging is not logged again.
+///
+/// [`PriorityConfig::preserve_critical`]: crate::core::PriorityConfig::preserve_critical
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::prelude::*;
+///
+/// let logger = Logger::builder()
+///     .appender(ConsoleAppender::new())
+///     .build();
+///
+/// rust_logger_system::install_panic_hook(logger);
+/// ```
+pub fn install_panic_hook(logger: impl Into<Arc<Logger>>) {
+    let logger = logger.into();
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if !IN_HOOK.with(|flag| flag.replace(true)) {
+            logger.log_entry_and_flush(panic_entry(info));
+            IN_HOOK.with(|flag| flag.set(false));
+        }
+        previous(info);
+    }));
+}
+
+/// Build the log entry describing a panic
+fn panic_entry(info: &PanicHookInfo<'_>) -> LogEntry {
+    let payload = info
+        .payload()
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| info.payload().downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+
+    let context = LogContext::new().with_field("backtrace", Backtrace::force_capture().to_string());
+    let mut entry =
+        LogEntry::new(LogLevel::Fatal, format!("panic: {}", payload)).with_context(context);
+    if let Some(location) = info.location() {
+        entry.file = Some(intern(location.file()));
+        entry.line = Some(location.line());
+    }
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CaptureAppender;
+    use parking_lot::Mutex;
+
+    #[test]
+    fn test_panic_is_logged_before_previous_hook() {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let logger = Logger::builder()
+            .async_mode(16)
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .build();
+        install_panic_hook(logger);
+
+        let line = line!() + 1;
+        let result = std::panic::catch_unwind(|| panic!("disk {} is full", "sda1"));
+        // Restore the default hook; other tests may panic concurrently
+        let _ = std::panic::take_hook();
+        assert!(result.is_err());
+
+        let entries = entries.lock();
+        let entry = entries
+            .iter()
+            .find(|entry| entry.message == "panic: disk sda1 is full")
+            .expect("panic entry written synchronously");
+        assert_eq!(entry.level, LogLevel::Fatal);
+        assert_eq!(entry.file.as_deref(), Some(file!()));
+        assert_eq!(entry.line, Some(line));
+        assert!(entry.context.as_ref().unwrap().fields().contains_key("backtrace"));
+    }
+}
@@ -0,0 +1,854 @@
+//! Checking log output in tests
+//!
+//! [`CapturingLogger`] is a synchronous logger that keeps every entry in
+//! memory and offers assertions over them. It dereferences to [`Logger`], so
+//! it can be passed wherever code expects one, and
+//! [`install_global`](CapturingLogger::install_global) puts it in place of
+//! the global logger for the current test.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_logger_system::prelude::*;
+//! use rust_logger_system::testing::CapturingLogger;
+//! use rust_logger_system::{global_warn, info};
+//!
+//! let logs = CapturingLogger::new();
+//! info!(logs, "user {} logged in", 42; user_id = 42);
+//! {
+//!     let _global = logs.install_global();
+//!     global_warn!("disk almost full");
+//! }
+//!
+//! logs.assert_logged(LogLevel::Info, "logged in");
+//! logs.assert_logged(LogLevel::Warn, "disk");
+//! assert_eq!(logs.entries_matching("user_id", 42).len(), 1);
+//! logs.assert_no_errors();
+//! ```
+//!
+//! [`Snapshot`] renders entries with a fixed time and thread into the exact
+//! text a format produces, and [`assert_golden`] compares it with a file
+//! checked into the repository, so a format change that would break log
+//! parsers shows up as a failing test.
+//!
+//! [`FailingAppender`], [`PanickingAppender`] and [`SlowAppender`] inject
+//! faults, for testing failover groups, circuit breakers, quarantine and
+//! backpressure. Each has a [`FaultHandle`] that keeps counting calls after
+//! the appender has been moved into a logger.
+
+use crate::core::{
+    Appender, Clock, FieldValue, FormatterConfig, LogEntry, LogLevel, Logger, LoggerBuilder,
+    LoggerError, ManualClock, OutputFormat, Result,
+};
+use crate::global::{self, ScopedGuard};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use std::fmt::Write as _;
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Appender keeping entries in memory
+pub(crate) struct CaptureAppender {
+    entries: Arc<Mutex<Vec<LogEntry>>>,
+}
+
+impl CaptureAppender {
+    /// Appender pushing every entry it receives onto `entries`
+    pub(crate) fn new(entries: Arc<Mutex<Vec<LogEntry>>>) -> Self {
+        Self { entries }
+    }
+}
+
+impl Appender for CaptureAppender {
+    fn append(&mut self, entry: &LogEntry) -> Result<()> {
+        self.entries.lock().push(entry.clone());
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "capture"
+    }
+}
+
+/// Logger recording its entries in memory, for assertions in tests
+pub struct CapturingLogger {
+    logger: Arc<Logger>,
+    entries: Arc<Mutex<Vec<LogEntry>>>,
+}
+
+impl CapturingLogger {
+    /// Create a synchronous logger capturing entries of every level
+    pub fn new() -> Self {
+        Self::from_builder(Logger::builder().min_level(LogLevel::Trace))
+    }
+
+    /// Capture the entries of a logger configured by `builder`
+    ///
+    /// Filters, sampling and other settings of the builder apply, so a test
+    /// can check what a configuration lets through. The capture is added to
+    /// the builder's appenders. In async mode, entries are only captured
+    /// once written; call [`Logger::drain`] before asserting.
+    pub fn from_builder(builder: LoggerBuilder) -> Self {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let logger = builder
+            .appender(CaptureAppender::new(Arc::clone(&entries)))
+            .build();
+        Self {
+            logger: Arc::new(logger),
+            entries,
+        }
+    }
+
+    /// Use this logger as the global logger on the current thread until the
+    /// returned guard is dropped
+    ///
+    /// The global logging macros and [`crate::global`] then log here. The
+    /// global logger lives for the rest of the process, so this logger is
+    /// never dropped once installed. See [`global::set_scoped`].
+    pub fn install_global(&self) -> ScopedGuard {
+        let logger: &'static Arc<Logger> = Box::leak(Box::new(Arc::clone(&self.logger)));
+        global::set_scoped(logger)
+    }
+
+    /// Get a copy of the captured entries, oldest first
+    pub fn entries(&self) -> Vec<LogEntry> {
+        self.entries.lock().clone()
+    }
+
+    /// Get the messages of the captured entries, oldest first
+    pub fn messages(&self) -> Vec<String> {
+        self.entries.lock().iter().map(|entry| entry.message.clone()).collect()
+    }
+
+    /// Get the captured entries whose context field `field` equals `value`
+    pub fn entries_matching(&self, field: &str, value: impl Into<FieldValue>) -> Vec<LogEntry> {
+        let value = value.into();
+        self.entries
+            .lock()
+            .iter()
+            .filter(|entry| {
+                entry
+                    .context
+                    .as_ref()
+                    .and_then(|context| context.field_map().get(field))
+                    .is_some_and(|field_value| *field_value == value)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Forget the entries captured so far
+    pub fn clear(&self) {
+        self.entries.lock().clear();
+    }
+
+    /// Panic unless an entry at `level` has a message containing `substring`
+    #[track_caller]
+    pub fn assert_logged(&self, level: LogLevel, substring: &str) {
+        let entries = self.entries.lock();
+        let found = entries
+            .iter()
+            .any(|entry| entry.level == level && entry.message.contains(substring));
+        if !found {
+            panic!(
+                "no {} entry containing {:?} was logged; captured:{}",
+                level.to_str(),
+                substring,
+                Self::describe(&entries)
+            );
+        }
+    }
+
+    /// Panic if an entry at [`LogLevel::Error`] or above was captured
+    #[track_caller]
+    pub fn assert_no_errors(&self) {
+        let entries = self.entries.lock();
+        let errors: Vec<LogEntry> = entries
+            .iter()
+            .filter(|entry| entry.level >= LogLevel::Error)
+            .cloned()
+            .collect();
+        if !errors.is_empty() {
+            panic!("expected no errors, but captured:{}", Self::describe(&errors));
+        }
+    }
+
+    /// List entries one per line, for assertion messages
+    fn describe(entries: &[LogEntry]) -> String {
+        if entries.is_empty() {
+            return " nothing".to_string();
+        }
+        let mut description = String::new();
+        for entry in entries {
+            let _ = write!(description, "\n  [{}] {}", entry.level_name(), entry.message);
+        }
+        description
+    }
+}
+
+impl Default for CapturingLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for CapturingLogger {
+    type Target = Logger;
+
+    fn deref(&self) -> &Logger {
+        &self.logger
+    }
+}
+
+/// Time of snapshot entries: `2025-01-08T10:30:45.123Z`
+pub const SNAPSHOT_TIME: Duration = Duration::from_millis(1_736_332_245_123);
+
+/// Thread ID of snapshot entries
+pub const SNAPSHOT_THREAD_ID: &str = "ThreadId(1)";
+
+/// Thread name of snapshot entries
+pub const SNAPSHOT_THREAD_NAME: &str = "main";
+
+/// Environment variable that makes [`assert_golden`] rewrite golden files
+pub const UPDATE_GOLDEN_ENV: &str = "UPDATE_GOLDEN";
+
+/// Renders entries into canonical strings for snapshot tests
+///
+/// Every rendered entry gets the time of the clock and the same thread, so
+/// the output only changes when the format does. By default the clock
+/// stands at [`SNAPSHOT_TIME`] and the thread is [`SNAPSHOT_THREAD_NAME`].
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::core::{LogContext, OutputFormat};
+/// use rust_logger_system::prelude::*;
+/// use rust_logger_system::testing::Snapshot;
+///
+/// let snapshot = Snapshot::new(OutputFormat::Logfmt);
+/// let entry = snapshot
+///     .entry(LogLevel::Warn, "disk almost full")
+///     .with_context(LogContext::new().with_field("free_mb", 512));
+///
+/// assert_eq!(
+///     snapshot.render(&entry),
+///     "timestamp=2025-01-08T10:30:45.123Z level=WARN message=\"disk almost full\" \
+///      thread_id=ThreadId(1) thread_name=main free_mb=512"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    format: OutputFormat,
+    config: FormatterConfig,
+    clock: Arc<dyn Clock>,
+    thread_id: Arc<str>,
+    thread_name: Option<Arc<str>>,
+}
+
+impl Snapshot {
+    /// Render with `format` and the default [`FormatterConfig`]
+    pub fn new(format: OutputFormat) -> Self {
+        let clock = ManualClock::starting_at(SystemTime::UNIX_EPOCH + SNAPSHOT_TIME);
+        Self {
+            format,
+            config: FormatterConfig::default(),
+            clock: Arc::new(clock),
+            thread_id: Arc::from(SNAPSHOT_THREAD_ID),
+            thread_name: Some(Arc::from(SNAPSHOT_THREAD_NAME)),
+        }
+    }
+
+    /// Render with `config`, e.g. to include file locations or change the
+    /// timestamp format
+    #[must_use = "builder methods return a new value"]
+    pub fn with_config(mut self, config: FormatterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Take the time of rendered entries from `clock`
+    #[must_use = "builder methods return a new value"]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Render entries as logged by the thread `thread_id` named
+    /// `thread_name`
+    #[must_use = "builder methods return a new value"]
+    pub fn with_thread(mut self, thread_id: &str, thread_name: Option<&str>) -> Self {
+        self.thread_id = Arc::from(thread_id);
+        self.thread_name = thread_name.map(Arc::from);
+        self
+    }
+
+    /// Create an entry with the snapshot time and thread
+    pub fn entry(&self, level: LogLevel, message: impl Into<String>) -> LogEntry {
+        self.canonical(LogEntry::new(level, message.into()))
+    }
+
+    /// Render `entry` with the snapshot time and thread
+    pub fn render(&self, entry: &LogEntry) -> String {
+        let entry = self.canonical(entry.clone());
+        self.format.format_with_config(&entry, &self.config)
+    }
+
+    /// Render `entries`, one per line
+    pub fn render_all<'a>(&self, entries: impl IntoIterator<Item = &'a LogEntry>) -> String {
+        let mut output = String::new();
+        for entry in entries {
+            output.push_str(&self.render(entry));
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Replace the time and thread of `entry`
+    fn canonical(&self, mut entry: LogEntry) -> LogEntry {
+        entry.timestamp = DateTime::<Utc>::from(self.clock.system_time());
+        entry.thread_id = Arc::clone(&self.thread_id);
+        entry.thread_name = self.thread_name.clone();
+        entry
+    }
+}
+
+/// Panic unless `actual` equals the content of the golden file at `path`
+///
+/// A missing golden file is created from `actual`. With the
+/// [`UPDATE_GOLDEN_ENV`] environment variable set, the file is rewritten
+/// instead of compared, for accepting intended format changes.
+#[track_caller]
+pub fn assert_golden(path: impl AsRef<Path>, actual: &str) {
+    let path = path.as_ref();
+    let update = std::env::var_os(UPDATE_GOLDEN_ENV).is_some();
+    if update || !path.exists() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("create golden file directory");
+        }
+        std::fs::write(path, actual).expect("write golden file");
+        return;
+    }
+
+    let expected = std::fs::read_to_string(path).expect("read golden file");
+    if expected == actual {
+        return;
+    }
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    let line_count = expected_lines.len().max(actual_lines.len());
+    // Without a differing line, only the line endings differ
+    let line = (0..line_count)
+        .find(|&line| expected_lines.get(line) != actual_lines.get(line))
+        .unwrap_or(line_count);
+    let show = |lines: &[&str]| {
+        lines
+            .get(line)
+            .map_or_else(|| "<end of file>".to_string(), |text| format!("{:?}", text))
+    };
+    panic!(
+        "output differs from golden file {} at line {}:\n  expected: {}\n    actual: {}\n\
+         set {}=1 to accept the new output",
+        path.display(),
+        line + 1,
+        show(&expected_lines),
+        show(&actual_lines),
+        UPDATE_GOLDEN_ENV
+    );
+}
+
+/// When a fault-injecting appender faults
+#[derive(Debug, Clone, Copy)]
+enum Schedule {
+    Always,
+    Every(usize),
+    After(usize),
+    Triggered,
+}
+
+#[derive(Debug, Default)]
+struct FaultState {
+    calls: AtomicUsize,
+    faults: AtomicUsize,
+    triggered: AtomicBool,
+}
+
+impl FaultState {
+    /// Count a call and decide whether it faults
+    fn fault(&self, schedule: Schedule) -> bool {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        let fault = self.triggered.load(Ordering::SeqCst)
+            || match schedule {
+                Schedule::Always => true,
+                Schedule::Every(n) => call.is_multiple_of(n),
+                Schedule::After(n) => call > n,
+                Schedule::Triggered => false,
+            };
+        if fault {
+            self.faults.fetch_add(1, Ordering::SeqCst);
+        }
+        fault
+    }
+}
+
+/// Shared view of a fault-injecting appender
+///
+/// Counts the appends, and switches faults on and off for appenders created
+/// with `on_trigger`; a triggered fault applies to every schedule.
+#[derive(Debug, Clone)]
+pub struct FaultHandle {
+    state: Arc<FaultState>,
+}
+
+impl FaultHandle {
+    /// Make every following append fault
+    pub fn trigger(&self) {
+        self.state.triggered.store(true, Ordering::SeqCst);
+    }
+
+    /// Stop faulting because of [`trigger`](Self::trigger)
+    pub fn recover(&self) {
+        self.state.triggered.store(false, Ordering::SeqCst);
+    }
+
+    /// Get the number of appends the appender was called for
+    pub fn calls(&self) -> usize {
+        self.state.calls.load(Ordering::SeqCst)
+    }
+
+    /// Get the number of appends that failed or panicked
+    pub fn faults(&self) -> usize {
+        self.state.faults.load(Ordering::SeqCst)
+    }
+}
+
+/// Appender returning errors on a schedule
+///
+/// Successful appends discard the entry. Flushing succeeds unless
+/// [`with_failing_flush`](Self::with_failing_flush) is set.
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::core::CircuitBreakerConfig;
+/// use rust_logger_system::prelude::*;
+/// use rust_logger_system::testing::FailingAppender;
+/// use std::time::Duration;
+///
+/// let appender = FailingAppender::on_trigger();
+/// let faults = appender.handle();
+/// let logger = Logger::builder()
+///     .appender(appender)
+///     .circuit_breaker(CircuitBreakerConfig::new(2, Duration::from_secs(60)))
+///     .build();
+///
+/// faults.trigger();
+/// for _ in 0..5 {
+///     logger.info("lost");
+/// }
+/// // The open circuit stops calling the appender
+/// assert_eq!(faults.calls(), 2);
+/// ```
+#[derive(Debug)]
+pub struct FailingAppender {
+    name: String,
+    schedule: Schedule,
+    fail_flush: bool,
+    state: Arc<FaultState>,
+}
+
+impl FailingAppender {
+    fn with_schedule(schedule: Schedule) -> Self {
+        Self {
+            name: "failing".to_string(),
+            schedule,
+            fail_flush: false,
+            state: Arc::default(),
+        }
+    }
+
+    /// Fail every append
+    pub fn always() -> Self {
+        Self::with_schedule(Schedule::Always)
+    }
+
+    /// Fail every `n`th append (the `n`th, the `2n`th, ...)
+    pub fn every(n: usize) -> Self {
+        Self::with_schedule(Schedule::Every(n.max(1)))
+    }
+
+    /// Succeed for the first `n` appends, then fail
+    pub fn after(n: usize) -> Self {
+        Self::with_schedule(Schedule::After(n))
+    }
+
+    /// Succeed until [`FaultHandle::trigger`] is called
+    pub fn on_trigger() -> Self {
+        Self::with_schedule(Schedule::Triggered)
+    }
+
+    /// Use `name` instead of `"failing"`
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Fail every flush as well
+    #[must_use]
+    pub fn with_failing_flush(mut self, fail_flush: bool) -> Self {
+        self.fail_flush = fail_flush;
+        self
+    }
+
+    /// Get a handle counting the appends and controlling the trigger
+    pub fn handle(&self) -> FaultHandle {
+        FaultHandle {
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl Appender for FailingAppender {
+    fn append(&mut self, _entry: &LogEntry) -> Result<()> {
+        if self.state.fault(self.schedule) {
+            return Err(LoggerError::writer(format!("injected failure in '{}'", self.name)));
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if self.fail_flush {
+            return Err(LoggerError::writer(format!("injected flush failure in '{}'", self.name)));
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Appender panicking on a schedule
+///
+/// Successful appends discard the entry.
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::prelude::*;
+/// use rust_logger_system::testing::PanickingAppender;
+///
+/// let appender = PanickingAppender::always();
+/// let faults = appender.handle();
+/// let logger = Logger::builder().appender(appender).quarantine_after(2).build();
+///
+/// for _ in 0..5 {
+///     logger.info("survives");
+/// }
+/// assert_eq!(faults.calls(), 2);
+/// assert_eq!(logger.quarantined_appenders(), ["panicking"]);
+/// ```
+#[derive(Debug)]
+pub struct PanickingAppender {
+    name: String,
+    schedule: Schedule,
+    state: Arc<FaultState>,
+}
+
+impl PanickingAppender {
+    fn with_schedule(schedule: Schedule) -> Self {
+        Self {
+            name: "panicking".to_string(),
+            schedule,
+            state: Arc::default(),
+        }
+    }
+
+    /// Panic on every append
+    pub fn always() -> Self {
+        Self::with_schedule(Schedule::Always)
+    }
+
+    /// Panic on every `n`th append (the `n`th, the `2n`th, ...)
+    pub fn every(n: usize) -> Self {
+        Self::with_schedule(Schedule::Every(n.max(1)))
+    }
+
+    /// Succeed for the first `n` appends, then panic
+    pub fn after(n: usize) -> Self {
+        Self::with_schedule(Schedule::After(n))
+    }
+
+    /// Succeed until [`FaultHandle::trigger`] is called
+    pub fn on_trigger() -> Self {
+        Self::with_schedule(Schedule::Triggered)
+    }
+
+    /// Use `name` instead of `"panicking"`
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Get a handle counting the appends and controlling the trigger
+    pub fn handle(&self) -> FaultHandle {
+        FaultHandle {
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl Appender for PanickingAppender {
+    fn append(&mut self, _entry: &LogEntry) -> Result<()> {
+        if self.state.fault(self.schedule) {
+            panic!("injected panic in '{}'", self.name);
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Appender taking `delay` for every append, for testing backpressure
+///
+/// On its own it discards entries; [`wrap`](Self::wrap) slows down another
+/// appender instead. Flushing is not delayed.
+///
+/// # Example
+///
+/// ```
+/// use rust_logger_system::prelude::*;
+/// use rust_logger_system::testing::SlowAppender;
+/// use std::time::Duration;
+///
+/// let logger = Logger::builder()
+///     .async_mode(4)
+///     .overflow_policy(OverflowPolicy::DropNewest)
+///     .appender(SlowAppender::new(Duration::from_millis(20)))
+///     .build();
+/// for _ in 0..50 {
+///     logger.info("flood");
+/// }
+/// assert!(logger.dropped_count() > 0);
+/// ```
+pub struct SlowAppender {
+    delay: Duration,
+    inner: Option<Box<dyn Appender>>,
+    state: Arc<FaultState>,
+}
+
+impl SlowAppender {
+    /// Sleep for `delay` on every append and discard the entry
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            inner: None,
+            state: Arc::default(),
+        }
+    }
+
+    /// Sleep for `delay` before every append to `appender`
+    pub fn wrap(appender: impl Appender + 'static, delay: Duration) -> Self {
+        Self {
+            delay,
+            inner: Some(Box::new(appender)),
+            state: Arc::default(),
+        }
+    }
+
+    /// Get a handle counting the appends
+    pub fn handle(&self) -> FaultHandle {
+        FaultHandle {
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl Appender for SlowAppender {
+    fn append(&mut self, entry: &LogEntry) -> Result<()> {
+        self.state.calls.fetch_add(1, Ordering::SeqCst);
+        std::thread::sleep(self.delay);
+        match self.inner {
+            Some(ref mut inner) => inner.append(entry),
+            None => Ok(()),
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match self.inner {
+            Some(ref mut inner) => inner.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self.inner {
+            Some(ref inner) => inner.name(),
+            None => "slow",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{LogContext, TracingContext};
+    use crate::{global_error, info};
+    use std::panic::AssertUnwindSafe;
+
+    #[test]
+    fn test_captures_and_matches_fields() {
+        let logs = CapturingLogger::new();
+        logs.debug("starting");
+        info!(logs, "request served"; status = 200, path = "/health");
+        info!(logs, "request served"; status = 503, path = "/ready");
+
+        assert_eq!(logs.messages(), ["starting", "request served", "request served"]);
+        logs.assert_logged(LogLevel::Debug, "start");
+        let unavailable = logs.entries_matching("status", 503);
+        assert_eq!(unavailable.len(), 1);
+        assert_eq!(
+            unavailable[0].context.as_ref().unwrap().fields().get("path"),
+            Some(&FieldValue::from("/ready"))
+        );
+        logs.assert_no_errors();
+
+        logs.clear();
+        assert!(logs.entries().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "no WARN entry containing \"timeout\"")]
+    fn test_assert_logged_lists_entries() {
+        let logs = CapturingLogger::new();
+        logs.warn("retrying");
+        logs.assert_logged(LogLevel::Warn, "timeout");
+    }
+
+    #[test]
+    fn test_formats_match_golden_files() {
+        let context = LogContext::new()
+            .with_field("user_id", 42)
+            .with_field("path", "/api/orders")
+            .with_field("tags", vec!["retry", "slow"]);
+        let tracing = TracingContext::new("4bf92f3577b34da6".to_string(), "00f067aa".to_string());
+        let base = Snapshot::new(OutputFormat::Text);
+        let entries = [
+            base.entry(LogLevel::Info, "request served"),
+            base.entry(LogLevel::Warn, "payment \"declined\"")
+                .with_location("src/pay.rs", 42, "shop::pay")
+                .with_context(context)
+                .with_tracing(tracing),
+        ];
+
+        let config = FormatterConfig::new().with_include_file_location(true);
+        for (name, format) in [
+            ("text", OutputFormat::Text),
+            ("json", OutputFormat::Json),
+            ("logfmt", OutputFormat::Logfmt),
+            ("otel", OutputFormat::OtelJson),
+            ("pretty", OutputFormat::Pretty),
+        ] {
+            let snapshot = Snapshot::new(format).with_config(config.clone());
+            let path = format!("{}/tests/golden/{}.log", env!("CARGO_MANIFEST_DIR"), name);
+            assert_golden(path, &snapshot.render_all(&entries));
+        }
+    }
+
+    #[test]
+    fn test_snapshot_is_independent_of_time_and_thread() {
+        let snapshot = Snapshot::new(OutputFormat::Text);
+        let entry = LogEntry::new(LogLevel::Error, "failed".to_string());
+        assert_eq!(
+            snapshot.render(&entry),
+            "[2025-01-08T10:30:45.123Z] [ERROR] main - failed"
+        );
+
+        let clock = Arc::new(ManualClock::starting_at(SystemTime::UNIX_EPOCH));
+        let snapshot = snapshot.with_clock(clock).with_thread("ThreadId(7)", None);
+        assert_eq!(
+            snapshot.render(&entry),
+            "[1970-01-01T00:00:00.000Z] [ERROR] ThreadId(7) - failed"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "at line 2:\n  expected: \"b\"\n    actual: \"c\"")]
+    fn test_assert_golden_reports_first_difference() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("golden.log");
+        std::fs::write(&path, "a\nb\n").unwrap();
+        assert_golden(&path, "a\nb\n");
+        assert_golden(&path, "a\nc\n");
+    }
+
+    #[test]
+    fn test_fault_schedules() {
+        let entry = LogEntry::new(LogLevel::Info, "entry".to_string());
+        let outcomes = |mut appender: FailingAppender, calls: usize| -> Vec<bool> {
+            (0..calls).map(|_| appender.append(&entry).is_ok()).collect()
+        };
+        assert_eq!(outcomes(FailingAppender::every(3), 6), [true, true, false, true, true, false]);
+        assert_eq!(outcomes(FailingAppender::after(2), 4), [true, true, false, false]);
+        assert_eq!(outcomes(FailingAppender::always(), 2), [false, false]);
+
+        let mut appender = FailingAppender::on_trigger().with_failing_flush(true);
+        let faults = appender.handle();
+        assert!(appender.append(&entry).is_ok());
+        faults.trigger();
+        assert!(appender.append(&entry).is_err());
+        faults.recover();
+        assert!(appender.append(&entry).is_ok());
+        assert!(appender.flush().is_err());
+        assert_eq!((faults.calls(), faults.faults()), (3, 1));
+    }
+
+    #[test]
+    fn test_panicking_and_slow_appenders_in_a_logger() {
+        let panicking = PanickingAppender::every(2).with_name("flaky");
+        let panics = panicking.handle();
+        let capture = CapturingLogger::new();
+        let slow = SlowAppender::wrap(
+            CaptureAppender::new(Arc::clone(&capture.entries)),
+            Duration::from_millis(1),
+        );
+        let writes = slow.handle();
+        let logger = Logger::builder().appender(panicking).appender(slow).build();
+
+        for i in 0..4 {
+            logger.info(format!("entry {}", i));
+        }
+        assert_eq!((panics.calls(), panics.faults()), (4, 2));
+        assert_eq!(writes.calls(), 4);
+        assert_eq!(capture.messages().len(), 4);
+    }
+
+    #[test]
+    fn test_install_global_is_scoped() {
+        let logs = CapturingLogger::new();
+        {
+            let _global = logs.install_global();
+            global_error!("failed");
+        }
+        let global = crate::global();
+        assert!(global.is_none_or(|global| !std::ptr::eq(global, &*logs)));
+
+        assert_eq!(logs.messages(), ["failed"]);
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| logs.assert_no_errors()));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,75 @@
+//! Exit hook tests
+//!
+//! Installing the hooks registers an `atexit` callback and signal handlers
+//! for the rest of the process, so each test runs itself again as a child
+//! process that installs them, logs and exits. The parent checks that the
+//! queued entry reached the log file.
+
+use rust_logger_system::appenders::file::FileAppender;
+use rust_logger_system::core::logger::Logger;
+use rust_logger_system::install_exit_hooks;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+use tempfile::TempDir;
+
+/// Set in the child process to the log file it writes
+const LOG_PATH_VAR: &str = "EXIT_HOOKS_LOG_PATH";
+
+/// Leak an async logger writing to `path` and install the exit hooks for it
+fn install_logger(path: &str) -> &'static Logger {
+    let logger: &'static Logger = Box::leak(Box::new(
+        Logger::builder()
+            .appender(FileAppender::new(path).expect("open log file"))
+            .async_mode(100)
+            .build(),
+    ));
+    install_exit_hooks(logger);
+    install_exit_hooks(logger);
+    logger
+}
+
+/// Run `test` in a child process, returning its exit status and log file
+fn run_child(test: &str, dir: &Path) -> (ExitStatus, String) {
+    let path = dir.join("exit.log");
+    let status = Command::new(env::current_exe().unwrap())
+        .args([test, "--exact", "--test-threads=1", "--nocapture"])
+        .env(LOG_PATH_VAR, &path)
+        .status()
+        .expect("run child process");
+    (status, fs::read_to_string(&path).unwrap_or_default())
+}
+
+#[test]
+fn test_entries_drained_on_exit() {
+    if let Ok(path) = env::var(LOG_PATH_VAR) {
+        install_logger(&path).info("queued at exit");
+        std::process::exit(0);
+    }
+
+    let dir = TempDir::new().unwrap();
+    let (status, log) = run_child("test_entries_drained_on_exit", dir.path());
+    assert!(status.success(), "{:?}", status);
+    assert_eq!(log.matches("queued at exit").count(), 1, "{}", log);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_entries_drained_on_sigterm() {
+    use std::os::unix::process::ExitStatusExt;
+
+    if let Ok(path) = env::var(LOG_PATH_VAR) {
+        install_logger(&path).info("queued at sigterm");
+        unsafe { libc::raise(libc::SIGTERM) };
+        // The hooks drain the logger on their own thread, then raise again
+        loop {
+            std::thread::park();
+        }
+    }
+
+    let dir = TempDir::new().unwrap();
+    let (status, log) = run_child("test_entries_drained_on_sigterm", dir.path());
+    assert_eq!(status.signal(), Some(libc::SIGTERM), "{:?}", status);
+    assert_eq!(log.matches("queued at sigterm").count(), 1, "{}", log);
+}
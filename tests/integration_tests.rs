@@ -10,11 +10,11 @@
 
 use rust_logger_system::appenders::file::FileAppender;
 use rust_logger_system::appenders::json::JsonAppender;
-use rust_logger_system::appenders::Appender;
 use rust_logger_system::core::log_context::LogContext;
 use rust_logger_system::core::log_level::LogLevel;
 use rust_logger_system::core::logger::Logger;
 use rust_logger_system::core::timestamp::TimestampFormat;
+use rust_logger_system::testing::FailingAppender;
 use std::fs;
 use std::sync::Arc;
 use std::time::Duration;
@@ -66,10 +66,8 @@ fn test_async_logging() {
         logger.info(format!("Message {}", i));
     }
 
-    // Give async worker time to process
-    std::thread::sleep(Duration::from_millis(200));
-
-    logger.flush().expect("Failed to flush");
+    // Wait for the async worker to write everything
+    assert!(logger.drain(Duration::from_secs(5)));
 
     // Verify all messages were logged
     let content = fs::read_to_string(&log_file).expect("Failed to read log file");
@@ -100,10 +98,8 @@ fn test_async_backpressure() {
         logger.info(format!("Message {}", i));
     }
 
-    // Give time to process
-    std::thread::sleep(Duration::from_millis(300));
-
-    logger.flush().expect("Failed to flush");
+    // Wait for the async worker to write everything
+    assert!(logger.drain(Duration::from_secs(5)));
 
     // Verify all messages were logged (Block policy ensures no drops)
     let content = fs::read_to_string(&log_file).expect("Failed to read log file");
@@ -114,29 +110,8 @@ fn test_async_backpressure() {
 #[test]
 fn test_error_tracking() {
     // Test that failed log writes are tracked
-    struct FailingAppender {
-        fail_count: std::sync::atomic::AtomicUsize,
-    }
-
-    impl Appender for FailingAppender {
-        fn append(&mut self, _entry: &rust_logger_system::core::log_entry::LogEntry) -> rust_logger_system::core::error::Result<()> {
-            self.fail_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-            Err(rust_logger_system::core::error::LoggerError::other("Simulated failure"))
-        }
-
-        fn flush(&mut self) -> rust_logger_system::core::error::Result<()> {
-            Ok(())
-        }
-
-        fn name(&self) -> &str {
-            "FailingAppender"
-        }
-    }
-
     let mut logger = Logger::new();
-    logger.add_appender(Box::new(FailingAppender {
-        fail_count: std::sync::atomic::AtomicUsize::new(0),
-    }));
+    logger.add_appender(Box::new(FailingAppender::always()));
 
     // Log some messages
     for _ in 0..5 {
@@ -206,10 +181,8 @@ fn test_concurrent_logging() {
         handle.join().expect("Thread panicked");
     }
 
-    // Give async worker time to process
-    std::thread::sleep(Duration::from_millis(300));
-
-    logger.flush().expect("Failed to flush");
+    // Wait for the async worker to write everything
+    assert!(logger.drain(Duration::from_secs(5)));
 
     // Verify all messages were logged
     let content = fs::read_to_string(&log_file).expect("Failed to read log file");
@@ -320,9 +293,6 @@ fn test_graceful_shutdown() {
         // Logger drops here - should flush and shutdown gracefully
     }
 
-    // Give time for cleanup
-    std::thread::sleep(Duration::from_millis(200));
-
     // Verify all messages were written
     let content = fs::read_to_string(&log_file).expect("Failed to read log file");
     let lines: Vec<&str> = content.lines().collect();
@@ -697,9 +667,8 @@ fn test_sampling_with_async_logging() {
         logger.info(format!("Async message {}", i));
     }
 
-    // Give async worker time to process
-    std::thread::sleep(Duration::from_millis(300));
-    logger.flush().expect("Failed to flush");
+    // Wait for the async worker to write everything
+    assert!(logger.drain(Duration::from_secs(5)));
 
     // Check sampling metrics
     let sampler = logger.sampler().expect("Sampler should be configured");
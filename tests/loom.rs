@@ -0,0 +1,114 @@
+//! Model-checked tests of the async logger's races
+//!
+//! Only built with loom, which explores every interleaving of the logging
+//! threads and the async worker:
+//!
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --release --test loom
+//! ```
+
+#![cfg(loom)]
+
+use loom::thread;
+use rust_logger_system::core::{Appender, LogEntry, LogLevel, Logger, Result};
+use rust_logger_system::{OverflowPolicy, PriorityConfig};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+struct CountingAppender {
+    written: Arc<AtomicUsize>,
+}
+
+impl Appender for CountingAppender {
+    fn append(&mut self, _entry: &LogEntry) -> Result<()> {
+        self.written.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "counting"
+    }
+}
+
+/// Check all interleavings, bounding preemptions to keep the runs short
+fn model(test: impl Fn() + Sync + Send + 'static) {
+    let mut builder = loom::model::Builder::new();
+    builder.preemption_bound = Some(2);
+    builder.check(test);
+}
+
+/// Build an async logger with a one-entry queue, counting written entries
+fn logger(policy: OverflowPolicy, priority: PriorityConfig) -> (Arc<Logger>, Arc<AtomicUsize>) {
+    let written = Arc::new(AtomicUsize::new(0));
+    let logger = Logger::builder()
+        .async_mode(1)
+        .overflow_policy(policy)
+        .priority_config(priority)
+        .appender(CountingAppender {
+            written: Arc::clone(&written),
+        })
+        .build();
+    (Arc::new(logger), written)
+}
+
+/// Log `level` from a second thread and the current one
+fn log_twice(logger: &Arc<Logger>, level: LogLevel) {
+    let other = Arc::clone(logger);
+    let handle = thread::spawn(move || other.log(level, "from thread"));
+    logger.log(level, "from main");
+    handle.join().unwrap();
+}
+
+/// Take the logger back from the threads and stop it
+fn stop(logger: Arc<Logger>) -> Logger {
+    let mut logger = Arc::into_inner(logger).expect("threads joined");
+    assert!(logger.shutdown(Duration::from_secs(1)));
+    logger
+}
+
+#[test]
+fn overflow_writes_or_counts_every_entry() {
+    model(|| {
+        let (logger, written) = logger(OverflowPolicy::DropNewest, PriorityConfig::default());
+        log_twice(&logger, LogLevel::Info);
+
+        let logger = stop(logger);
+        let written = written.load(Ordering::SeqCst) as u64;
+        assert_eq!(written + logger.dropped_count(), 2);
+    });
+}
+
+#[test]
+fn sync_fallback_keeps_critical_entries() {
+    model(|| {
+        let priority = PriorityConfig {
+            preserve_critical: true,
+            ..PriorityConfig::default()
+        };
+        let (logger, written) = logger(OverflowPolicy::DropNewest, priority);
+        log_twice(&logger, LogLevel::Error);
+
+        let logger = stop(logger);
+        assert_eq!(written.load(Ordering::SeqCst), 2);
+        assert_eq!(logger.dropped_count(), 0);
+    });
+}
+
+#[test]
+fn dropping_the_last_handle_writes_queued_entries() {
+    model(|| {
+        let (logger, written) = logger(OverflowPolicy::Block, PriorityConfig::default());
+        let other = Arc::clone(&logger);
+        let handle = thread::spawn(move || other.info("from thread"));
+        logger.info("from main");
+        drop(logger);
+        handle.join().unwrap();
+
+        assert_eq!(written.load(Ordering::SeqCst), 2);
+    });
+}
@@ -174,9 +174,9 @@ proptest! {
         let entry = LogEntry::new(LogLevel::Info, message)
             .with_location(&file, line, &module);
 
-        assert_eq!(entry.file, Some(file));
+        assert_eq!(entry.file.as_deref(), Some(file.as_str()));
         assert_eq!(entry.line, Some(line));
-        assert_eq!(entry.module_path, Some(module));
+        assert_eq!(entry.module_path.as_deref(), Some(module.as_str()));
     }
 
     /// Test that LogEntry always has a timestamp
@@ -212,6 +212,19 @@ proptest! {
         assert_eq!(original.timestamp, cloned.timestamp);
         assert_eq!(original.thread_id, cloned.thread_id);
     }
+
+    /// Test that entries from one location and thread share their strings
+    #[test]
+    fn test_log_entry_shares_strings(file in "[a-z]+\\.rs", module in "[a-z_:]+") {
+        use std::sync::Arc;
+
+        let first = LogEntry::new(LogLevel::Info, String::new()).with_location(&file, 1, &module);
+        let second = LogEntry::new(LogLevel::Info, String::new()).with_location(&file, 2, &module);
+
+        assert!(Arc::ptr_eq(&first.thread_id, &second.thread_id));
+        assert!(Arc::ptr_eq(&first.file.unwrap(), &second.file.unwrap()));
+        assert!(Arc::ptr_eq(&first.module_path.unwrap(), &second.module_path.unwrap()));
+    }
 }
 
 // ============================================================================
@@ -53,7 +53,7 @@ fn test_critical_logs_never_dropped() {
     }
 
     // Wait for processing
-    std::thread::sleep(Duration::from_millis(500));
+    assert!(logger.drain(Duration::from_secs(5)));
     drop(logger);
 
     // Verify all critical logs are present
@@ -103,7 +103,7 @@ fn test_high_priority_retry_mechanism() {
     }
 
     // Wait for processing
-    std::thread::sleep(Duration::from_millis(500));
+    assert!(logger.drain(Duration::from_secs(5)));
     drop(logger);
 
     // Verify at least some warnings are preserved (retry should help)
@@ -143,7 +143,7 @@ fn test_priority_config_preserve_critical_disabled() {
         logger.error(format!("Error {}", i));
     }
 
-    std::thread::sleep(Duration::from_millis(300));
+    assert!(logger.drain(Duration::from_secs(5)));
     drop(logger);
 
     // With preserve_critical disabled and DropNewest policy,
@@ -204,7 +204,7 @@ fn test_concurrent_priority_logging() {
     }
 
     // Give time for async processing
-    std::thread::sleep(Duration::from_millis(500));
+    assert!(logger.drain(Duration::from_secs(5)));
     drop(logger);
 
     // Verify critical logs are preserved
@@ -247,7 +247,7 @@ fn test_block_on_critical_setting() {
         logger.fatal(format!("Fatal {}", i));
     }
 
-    std::thread::sleep(Duration::from_millis(300));
+    assert!(logger.drain(Duration::from_secs(5)));
     drop(logger);
 
     // All fatal logs should be written
@@ -280,7 +280,7 @@ fn test_priority_metrics_tracking() {
         logger.error("Critical log");
     }
 
-    std::thread::sleep(Duration::from_millis(200));
+    assert!(logger.drain(Duration::from_secs(5)));
 
     let metrics = logger.metrics();
 
@@ -322,7 +322,7 @@ fn test_rapid_burst_logging() {
         logger.fatal(format!("Burst {} complete", burst));
     }
 
-    std::thread::sleep(Duration::from_millis(500));
+    assert!(logger.drain(Duration::from_secs(5)));
     drop(logger);
 
     // Verify all burst completion markers are present